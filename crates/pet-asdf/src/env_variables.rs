@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::os_environment::Environment;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+// NOTE: Do not implement Default trait, as we do not want to ever forget to set the values.
+// Lets be explicit, this way we never miss a value (in Windows or Unix).
+pub struct EnvVariables {
+    pub home: Option<PathBuf>,
+    pub path: Option<String>,
+    /// Directory where asdf is installed, defaults to `~/.asdf`.
+    pub asdf_dir: Option<String>,
+    /// Directory where asdf installs plugins, shims & tool versions, defaults to `~/.asdf`.
+    pub asdf_data_dir: Option<String>,
+}
+
+impl EnvVariables {
+    pub fn from(env: &dyn Environment) -> Self {
+        EnvVariables {
+            home: env.get_user_home(),
+            path: env.get_env_var("PATH".to_string()),
+            asdf_dir: env.get_env_var("ASDF_DIR".to_string()),
+            asdf_data_dir: env.get_env_var("ASDF_DATA_DIR".to_string()),
+        }
+    }
+}
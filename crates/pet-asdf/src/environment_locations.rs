@@ -0,0 +1,85 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// See https://asdf-vm.com/manage/configuration.html#environment-variables
+pub fn get_asdf_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(asdf_dir) = &env_vars.asdf_dir {
+        return Some(norm_case(PathBuf::from(asdf_dir)));
+    }
+    env_vars.home.as_ref().map(|home| home.join(".asdf"))
+}
+
+/// Tool versions are installed in `ASDF_DATA_DIR`, older versions of asdf used `ASDF_DIR`.
+pub fn get_asdf_data_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(asdf_data_dir) = &env_vars.asdf_data_dir {
+        return Some(norm_case(PathBuf::from(asdf_data_dir)));
+    }
+    get_asdf_dir(env_vars)
+}
+
+/// Directory containing the Python versions installed by asdf, e.g. `~/.asdf/installs/python/3.12.4`.
+pub fn get_python_installs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    get_asdf_data_dir(env_vars).map(|data_dir| data_dir.join("installs").join("python"))
+}
+
+/// Python versions selected in the `.tool-versions` file of a project, e.g. `python 3.12.4 3.11.9`.
+/// The first version is the one that is active, the others are fallbacks.
+pub fn get_tool_versions(project_dir: &Path) -> Vec<String> {
+    let contents = match fs::read_to_string(project_dir.join(".tool-versions")) {
+        Ok(contents) => contents,
+        Err(_) => return vec![],
+    };
+    for line in contents.lines() {
+        // Comments can be at the end of a line as well.
+        let line = line.split('#').next().unwrap_or_default();
+        let mut parts = line.split_whitespace();
+        if parts.next() == Some("python") {
+            return parts.map(|version| version.to_string()).collect();
+        }
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_dir_defaults_to_asdf_dir() {
+        let env_vars = EnvVariables {
+            home: Some(PathBuf::from("/home/user")),
+            path: None,
+            asdf_dir: None,
+            asdf_data_dir: None,
+        };
+        assert_eq!(
+            get_python_installs_dir(&env_vars),
+            Some(PathBuf::from("/home/user/.asdf/installs/python"))
+        );
+
+        let env_vars = EnvVariables {
+            asdf_dir: Some("/opt/asdf".to_string()),
+            ..env_vars
+        };
+        assert_eq!(
+            get_python_installs_dir(&env_vars),
+            Some(PathBuf::from("/opt/asdf/installs/python"))
+        );
+
+        let env_vars = EnvVariables {
+            asdf_data_dir: Some("/data/asdf".to_string()),
+            ..env_vars
+        };
+        assert_eq!(
+            get_python_installs_dir(&env_vars),
+            Some(PathBuf::from("/data/asdf/installs/python"))
+        );
+    }
+}
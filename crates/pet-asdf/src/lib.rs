@@ -0,0 +1,158 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::{get_python_installs_dir, get_tool_versions};
+use log::trace;
+use manager::{find_asdf_executable, get_asdf_version};
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables};
+use pet_python_utils::version;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+/// Python versions are installed in directories named after the version, e.g. `3.12.4`.
+/// Other implementations (e.g. `pypy3.10-7.3.15`) use the names from pyenv, for those rely on the header files.
+fn get_version(prefix: &Path) -> Option<String> {
+    let name = prefix.file_name()?.to_str()?;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(name.to_string())
+    } else {
+        version::from_header_files(prefix)
+    }
+}
+
+pub struct Asdf {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+    manager: Arc<Mutex<Option<Option<EnvManager>>>>,
+    /// Time after which asdf is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Asdf {
+    pub fn from(environment: &dyn Environment) -> Asdf {
+        Asdf {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+            manager: Arc::new(Mutex::new(None)),
+            timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let mut manager = self.manager.lock().unwrap();
+        if manager.is_none() {
+            let asdf = find_asdf_executable(&self.env_vars).map(|executable| {
+                let version = get_asdf_version(&executable, *self.timeout.lock().unwrap());
+                let mut asdf = EnvManager::new(executable, EnvManagerType::Asdf, version);
+                asdf.install_path = get_python_installs_dir(&self.env_vars);
+                asdf
+            });
+            manager.replace(asdf);
+        }
+        manager.clone().flatten()
+    }
+    /// The first project that selects this version in its `.tool-versions` file.
+    fn get_project(&self, version: &str) -> Option<PathBuf> {
+        self.workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| get_tool_versions(dir).first().map(|v| v.as_str()) == Some(version))
+            .cloned()
+    }
+    /// Python installed by asdf, e.g. `~/.asdf/installs/python/3.12.4`.
+    fn get_environment(
+        &self,
+        prefix: &Path,
+        executable: Option<PathBuf>,
+        manager: &Option<EnvManager>,
+    ) -> Option<PythonEnvironment> {
+        let executable = executable.or_else(|| find_executable(prefix))?;
+        let project = prefix
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| self.get_project(name));
+        Some(
+            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Asdf))
+                .source_locator(Some(LocatorKind::Asdf.name()))
+                .executable(Some(executable))
+                .version(get_version(prefix).and_then(|v| v.parse().ok()))
+                .prefix(Some(prefix.to_path_buf()))
+                .project(project)
+                .manager(manager.clone())
+                .symlinks(Some(find_executables(prefix.join("bin"))))
+                .build(),
+        )
+    }
+}
+
+impl Locator for Asdf {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Asdf
+    }
+    fn description(&self) -> &'static str {
+        "Finds Python versions installed by asdf, reads ASDF_DATA_DIR and ASDF_DIR (defaults to ~/.asdf)"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("asdf") | Some("asdf.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Asdf]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let installs_dir = get_python_installs_dir(&self.env_vars)?;
+        let path = env.executable.strip_prefix(&installs_dir).ok()?;
+        // The version is the first directory under `$ASDF_DATA_DIR/installs/python`.
+        let name = path.components().next()?;
+        let prefix = installs_dir.join(name);
+        self.get_environment(&prefix, Some(env.executable.clone()), &self.get_manager())
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        self.manager.lock().unwrap().take();
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+
+        if let Some(installs_dir) = get_python_installs_dir(&self.env_vars) {
+            trace!("Searching for asdf Python versions in {:?}", installs_dir);
+            for prefix in list_dirs(&installs_dir) {
+                if let Some(env) = self.get_environment(&prefix, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{env_variables::EnvVariables, environment_locations::get_asdf_dir};
+use lazy_static::lazy_static;
+use log::{trace, warn};
+use pet_process::run;
+use regex::Regex;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+lazy_static! {
+    /// `asdf version` prints `v0.14.1-f00f759` in older versions & `asdf version 0.16.0` in newer versions.
+    static ref ASDF_VERSION: Regex =
+        Regex::new(r"(\d+\.\d+\.\d+)").expect("error parsing asdf version regex");
+}
+
+fn get_asdf_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["asdf.exe", "asdf"]
+    } else {
+        vec!["asdf"]
+    }
+}
+
+/// Looks for the asdf executable in the bin directory of `ASDF_DIR` & the PATH variable.
+pub fn find_asdf_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(asdf_dir) = get_asdf_dir(env_vars) {
+        search_paths.push(asdf_dir.join("bin"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    for dir in search_paths {
+        for name in get_asdf_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("asdf exe not found");
+    None
+}
+
+pub fn parse_asdf_version(output: &str) -> Option<String> {
+    ASDF_VERSION
+        .captures(output)
+        .and_then(|captures| captures.get(1))
+        .map(|version| version.as_str().to_string())
+}
+
+/// Gets the version by spawning `asdf version`.
+pub fn get_asdf_version(asdf: &Path, timeout: Option<Duration>) -> Option<String> {
+    match run(Command::new(asdf).arg("version"), timeout) {
+        Ok(output) if output.status.success() => {
+            parse_asdf_version(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            warn!(
+                "Failed to get the version of asdf {:?}: {:?}",
+                asdf,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            None
+        }
+        Err(err) => {
+            warn!("Failed to get the version of asdf {:?}: {:?}", asdf, err);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            parse_asdf_version("v0.14.1-f00f759\n"),
+            Some("0.14.1".to_string())
+        );
+        assert_eq!(
+            parse_asdf_version("asdf version 0.16.0\n"),
+            Some("0.16.0".to_string())
+        );
+        assert_eq!(parse_asdf_version("unknown"), None);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_asdf_python_versions() {
+    use pet_asdf::Asdf;
+    use pet_core::{
+        manager::EnvManagerType, python_environment::PythonEnvironmentKind, Configuration, Locator,
+    };
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let asdf_dir = resolve_test_path!(&["unix", "asdf"]);
+    let asdf_data_dir = resolve_test_path!(&["unix", "asdf_data"]);
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let other_project = resolve_test_path!(&["unix", "other_app"]);
+    let environment = create_test_environment(
+        HashMap::from([
+            (
+                "ASDF_DIR".to_string(),
+                asdf_dir.to_string_lossy().to_string(),
+            ),
+            (
+                "ASDF_DATA_DIR".to_string(),
+                asdf_data_dir.to_string_lossy().to_string(),
+            ),
+        ]),
+        None,
+    );
+    let locator = Asdf::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![other_project, project.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    assert_eq!(result.managers.len(), 1);
+    let manager = &result.managers[0];
+    assert_eq!(manager.tool, EnvManagerType::Asdf);
+    assert_eq!(manager.executable, asdf_dir.join("bin").join("asdf"));
+    assert_eq!(manager.version, Some("0.14.1".to_string()));
+
+    let installs_dir = asdf_data_dir.join("installs").join("python");
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Asdf));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            // Active version in the `.tool-versions` of the project.
+            (
+                installs_dir.join("3.11.9"),
+                "3.11.9".to_string(),
+                Some(project)
+            ),
+            (installs_dir.join("3.12.4"), "3.12.4".to_string(), None),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_asdf_python_executable() {
+    use pet_asdf::Asdf;
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let asdf_data_dir = resolve_test_path!(&["unix", "asdf_data"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "ASDF_DATA_DIR".to_string(),
+            asdf_data_dir.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Asdf::from(&environment);
+    let prefix = asdf_data_dir.join("installs").join("python").join("3.12.4");
+
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python3.12"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Asdf));
+    assert_eq!(env.prefix, Some(prefix.clone()));
+    assert_eq!(env.version, "3.12.4".parse().ok());
+    assert_eq!(
+        env.symlinks,
+        Some(vec![
+            prefix.join("bin").join("python3"),
+            prefix.join("bin").join("python3.12")
+        ])
+    );
+
+    // Python not installed by asdf.
+    let other = resolve_test_path!(&["unix", "my_app", "bin", "python3"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(other, None, None))
+        .is_none());
+}
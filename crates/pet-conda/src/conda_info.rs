@@ -3,7 +3,8 @@
 
 use log::{error, trace, warn};
 use pet_fs::path::resolve_symlink;
-use std::path::PathBuf;
+use pet_process::run;
+use std::{path::PathBuf, process::Command, time::Duration};
 
 #[derive(Debug, serde::Deserialize)]
 pub struct CondaInfo {
@@ -35,7 +36,8 @@ pub struct CondaInfoJson {
 }
 
 impl CondaInfo {
-    pub fn from(executable: Option<PathBuf>) -> Option<CondaInfo> {
+    /// Conda is killed if it does not complete within the given time.
+    pub fn from(executable: Option<PathBuf>, timeout: Option<Duration>) -> Option<CondaInfo> {
         // let using_default = executable.is_none() || executable == Some("conda".into());
         // Possible we got a symlink to the conda exe, first try to resolve that.
         let executable = if cfg!(windows) {
@@ -45,10 +47,7 @@ impl CondaInfo {
             resolve_symlink(&executable).unwrap_or(executable)
         };
 
-        let result = std::process::Command::new(&executable)
-            .arg("info")
-            .arg("--json")
-            .output();
+        let result = run(Command::new(&executable).arg("info").arg("--json"), timeout);
         trace!("Executing Conda: {:?} info --json -a", executable);
         match result {
             Ok(output) => {
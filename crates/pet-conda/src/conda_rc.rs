@@ -300,7 +300,7 @@ envs_path:
 "#;
 
         assert_eq!(
-            parse_conda_rc_contents(&cfg).unwrap().env_dirs,
+            parse_conda_rc_contents(cfg).unwrap().env_dirs,
             [
                 PathBuf::from("/Users/username/dev/envs"),
                 PathBuf::from("/opt/conda/envs"),
@@ -320,7 +320,7 @@ envs_dirs:
 "#;
 
         assert_eq!(
-            parse_conda_rc_contents(&cfg).unwrap().env_dirs,
+            parse_conda_rc_contents(cfg).unwrap().env_dirs,
             ["/Users/username/dev/envs", "/opt/conda/envs",].map(PathBuf::from)
         );
 
@@ -335,7 +335,7 @@ envs_path:
 "#;
 
         assert_eq!(
-            parse_conda_rc_contents(&cfg).unwrap().env_dirs,
+            parse_conda_rc_contents(cfg).unwrap().env_dirs,
             [
                 PathBuf::from("/opt/somep lace/envs"),
                 expand_path(PathBuf::from("~/dev/envs2"))
@@ -349,7 +349,7 @@ channels:
 channel_priority: strict
 "#;
 
-        assert!(parse_conda_rc_contents(&cfg).unwrap().env_dirs.is_empty(),);
-        assert!(parse_conda_rc_contents(&cfg).unwrap().files.is_empty(),);
+        assert!(parse_conda_rc_contents(cfg).unwrap().env_dirs.is_empty(),);
+        assert!(parse_conda_rc_contents(cfg).unwrap().files.is_empty(),);
     }
 }
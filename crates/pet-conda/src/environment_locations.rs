@@ -7,7 +7,7 @@ use crate::{
     utils::{is_conda_env, is_conda_install},
 };
 use log::trace;
-use pet_fs::path::{expand_path, norm_case};
+use pet_fs::path::{expand_path, is_absolute_on_any_platform, norm_case};
 use pet_python_utils::platform_dirs::Platformdirs;
 use std::{
     env, fs,
@@ -247,6 +247,38 @@ pub fn get_conda_envs_from_environment_txt(env_vars: &EnvVariables) -> Vec<PathB
     envs
 }
 
+/// Parses the output of `conda env list`, returning the name (if any) and path of each environment.
+/// Environments created using `--prefix` do not have a name, and the active environment is marked with `*`.
+/// ```text
+/// # conda environments:
+/// #
+/// base                  *  /home/user/anaconda3
+/// myenv                    /home/user/anaconda3/envs/myenv
+///                          /home/user/custom-prefix
+/// ```
+pub fn parse_conda_env_list_output(output: &str) -> Vec<(Option<String>, PathBuf)> {
+    let mut envs = vec![];
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.trim_start_matches('*').trim_start();
+        if is_absolute_on_any_platform(line) {
+            envs.push((None, PathBuf::from(line)));
+            continue;
+        }
+        // Names cannot contain spaces, however paths can.
+        if let Some((name, path)) = line.split_once(char::is_whitespace) {
+            let path = path.trim_start().trim_start_matches('*').trim();
+            if !path.is_empty() {
+                envs.push((Some(name.to_string()), PathBuf::from(path)));
+            }
+        }
+    }
+    envs
+}
+
 #[cfg(windows)]
 pub fn get_known_conda_install_locations(
     env_vars: &EnvVariables,
@@ -11,6 +11,7 @@ use pet_core::{
     arch::Architecture,
     manager::EnvManager,
     python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    LocatorKind,
 };
 use pet_fs::path::{norm_case, resolve_symlink};
 use pet_python_utils::executable::{find_executable, find_executables};
@@ -55,8 +56,9 @@ impl CondaEnvironment {
         }
         // This is a root env.
         let builder = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+            .source_locator(Some(LocatorKind::Conda.name()))
             .executable(self.executable.clone())
-            .version(self.version.clone())
+            .version(self.version.clone().and_then(|v| v.parse().ok()))
             .prefix(Some(self.prefix.clone()))
             .arch(self.arch.clone())
             .symlinks(Some(find_executables(&self.prefix)))
@@ -186,9 +188,7 @@ fn get_conda_dir_from_cmd(cmd_line: String) -> Option<PathBuf> {
     let conda_exe = resolve_symlink(&conda_exe).unwrap_or(conda_exe);
     if let Some(cmd_line) = conda_exe.parent() {
         if let Some(conda_dir) = cmd_line.file_name() {
-            if conda_dir.to_ascii_lowercase() == "bin"
-                || conda_dir.to_ascii_lowercase() == "scripts"
-            {
+            if conda_dir.eq_ignore_ascii_case("bin") || conda_dir.eq_ignore_ascii_case("scripts") {
                 if let Some(conda_dir) = cmd_line.parent() {
                     // Ensure the casing of the paths are correct.
                     // Its possible the actual path is in a different case.
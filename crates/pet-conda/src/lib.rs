@@ -9,21 +9,23 @@ use environment_locations::{
 };
 use environments::{get_conda_environment_info, CondaEnvironment};
 use log::error;
-use manager::CondaManager;
+use manager::{find_conda_binary, CondaManager};
 use pet_core::{
     env::PythonEnv,
     os_environment::Environment,
     python_environment::{PythonEnvironment, PythonEnvironmentKind},
     reporter::Reporter,
-    Locator, LocatorKind,
+    Locator, LocatorKind, LocatorResult,
 };
 use pet_fs::path::norm_case;
+use pet_reporter::collect;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
     thread,
+    time::Duration,
 };
 use telemetry::{get_conda_rcs_and_env_dirs, report_missing_envs};
 use utils::{is_conda_env, is_conda_install};
@@ -65,6 +67,8 @@ pub struct Conda {
     pub managers: Arc<Mutex<HashMap<PathBuf, CondaManager>>>,
     pub env_vars: EnvVariables,
     conda_executable: Arc<Mutex<Option<PathBuf>>>,
+    /// Time after which conda is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
 }
 
 impl Conda {
@@ -74,8 +78,23 @@ impl Conda {
             managers: Arc::new(Mutex::new(HashMap::new())),
             env_vars: EnvVariables::from(env),
             conda_executable: Arc::new(Mutex::new(None)),
+            timeout: Arc::new(Mutex::new(None)),
         }
     }
+    /// Creates a Conda locator that uses the given conda executable instead of looking for one on PATH.
+    pub fn with_executable(exe: PathBuf, env: &dyn Environment) -> Conda {
+        let conda = Conda::from(env);
+        conda.conda_executable.lock().unwrap().replace(exe);
+        conda
+    }
+    /// Returns the conda executable provided by the user (if any),
+    /// else falls back to the first conda executable found on PATH.
+    pub fn get_conda_executable(&self) -> Option<PathBuf> {
+        if let Some(exe) = self.conda_executable.lock().unwrap().clone() {
+            return Some(exe);
+        }
+        find_conda_binary(&self.env_vars)
+    }
     fn clear(&self) {
         self.environments.lock().unwrap().clear();
         self.managers.lock().unwrap().clear();
@@ -90,7 +109,7 @@ impl CondaLocator for Conda {
     ) -> Option<()> {
         // Look for environments that we couldn't find without spawning conda.
         let user_provided_conda_exe = conda_executable.is_some();
-        let conda_info = CondaInfo::from(conda_executable)?;
+        let conda_info = CondaInfo::from(conda_executable, *self.timeout.lock().unwrap())?;
         let environments = self.environments.lock().unwrap().clone();
         let new_envs = conda_info
             .envs
@@ -118,7 +137,8 @@ impl CondaLocator for Conda {
     }
 
     fn get_info_for_telemetry(&self, conda_executable: Option<PathBuf>) -> CondaTelemetryInfo {
-        let can_spawn_conda = CondaInfo::from(conda_executable).is_some();
+        let can_spawn_conda =
+            CondaInfo::from(conda_executable, *self.timeout.lock().unwrap()).is_some();
         let environments = self.environments.lock().unwrap().clone();
         let environments = environments
             .into_values()
@@ -206,15 +226,69 @@ impl Locator for Conda {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::Conda
     }
+    fn description(&self) -> &'static str {
+        "Finds conda environments & installs, reads .condarc, environments.txt, CONDA_PREFIX and CONDA_ENVS_PATH"
+    }
+    fn priority(&self) -> u8 {
+        10
+    }
     fn configure(&self, config: &pet_core::Configuration) {
         if let Some(ref conda_exe) = config.conda_executable {
             let mut conda_executable = self.conda_executable.lock().unwrap();
             conda_executable.replace(conda_exe.clone());
         }
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("conda")
+                | Some("conda.exe")
+                | Some("mamba")
+                | Some("mamba.exe")
+                | Some("micromamba")
+                | Some("micromamba.exe")
+        )
     }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::Conda]
     }
+    fn validate(&self, env: &PythonEnvironment) -> bool {
+        // Conda envs need not contain Python, hence only the prefix is checked.
+        env.prefix
+            .as_ref()
+            .is_some_and(|prefix| is_conda_env(prefix))
+    }
+    fn describe(&self) -> String {
+        let executable = match self.get_conda_executable() {
+            Some(executable) => executable,
+            None => {
+                return format!(
+                    "{} ({:?}), executable: not found",
+                    self.get_kind().name(),
+                    PythonEnvironmentKind::Conda
+                )
+            }
+        };
+        // Prefer the managers already found, else look for the version in the install folder (without spawning conda).
+        let manager = self
+            .managers
+            .lock()
+            .unwrap()
+            .values()
+            .find(|m| m.executable == executable)
+            .cloned()
+            .or_else(|| CondaManager::from(executable.parent()?.parent()?));
+        format!(
+            "{} ({:?}), executable: {}, version: {}",
+            self.get_kind().name(),
+            PythonEnvironmentKind::Conda,
+            executable.display(),
+            manager
+                .and_then(|m| m.version)
+                .unwrap_or_else(|| "unknown".to_string())
+        )
+    }
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
         // Possible we do not have the prefix, but this exe is in the bin directory and its a conda env or root conda install.
         let mut prefix = env.prefix.clone();
@@ -277,12 +351,19 @@ impl Locator for Conda {
         None
     }
 
+    fn refresh(&self) -> LocatorResult {
+        // Finding the environments clears the cached state.
+        let reporter = collect::create_reporter();
+        self.find(&reporter);
+        reporter.to_locator_result()
+    }
+
     fn find(&self, reporter: &dyn Reporter) {
         // if we're calling this again, then clear what ever cache we have.
         self.clear();
 
         let env_vars = self.env_vars.clone();
-        let executable = self.conda_executable.lock().unwrap().clone();
+        let executable = self.get_conda_executable();
         thread::scope(|s| {
             // 1. Get a list of all know conda environments file paths
             let possible_conda_envs = get_conda_environment_paths(&env_vars, &executable);
@@ -70,6 +70,7 @@ impl CondaManager {
             tool: EnvManagerType::Conda,
             executable: self.executable.clone(),
             version: self.version.clone(),
+            install_path: self.conda_dir.clone(),
         }
     }
     pub fn from(path: &Path) -> Option<CondaManager> {
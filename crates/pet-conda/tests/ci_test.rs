@@ -57,7 +57,7 @@ fn detect_conda_root() {
     assert_eq!(env.name, Some("base".into()));
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, Some(conda_dir.join("bin").join("python")));
-    assert_eq!(env.version, Some(get_version(&info.python_version)));
+    assert_eq!(env.version, get_version(&info.python_version).parse().ok());
 
     assert_eq!(env.manager, Some(manager.clone()));
 }
@@ -84,7 +84,7 @@ fn detect_conda_root_from_path() {
     let python_env = PythonEnv::new(exe, Some(conda_dir.clone()), None);
     let env = conda.try_from(&python_env).unwrap();
 
-    assert_eq!(env.manager.is_some(), true);
+    assert!(env.manager.is_some());
 
     let manager = env.manager.unwrap();
     assert_eq!(manager.executable, conda_dir.join("bin").join("conda"));
@@ -95,7 +95,7 @@ fn detect_conda_root_from_path() {
     assert_eq!(env.name, Some("base".into()));
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, Some(conda_dir.join("bin").join("python")));
-    assert_eq!(env.version, Some(get_version(&info.python_version)));
+    assert_eq!(env.version, get_version(&info.python_version).parse().ok());
 }
 
 #[cfg(unix)]
@@ -132,13 +132,12 @@ fn detect_new_conda_env() {
     let env = environments
         .iter()
         .find(|x| x.name == Some(env_name.into()))
-        .expect(
-            format!(
+        .unwrap_or_else(|| {
+            panic!(
                 "New Environment not created, detected envs {:?}",
                 environments
             )
-            .as_str(),
-        );
+        });
 
     let prefix = conda_dir.clone().join("envs").join(env_name);
     assert_eq!(env.prefix, prefix.clone().into());
@@ -146,7 +145,9 @@ fn detect_new_conda_env() {
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, prefix.join("bin").join("python").into());
     assert!(
-        env.version.clone().unwrap_or_default().starts_with("3.10"),
+        env.version
+            .as_ref()
+            .is_some_and(|v| (v.major, v.minor) == (3, 10)),
         "Expected 3.10, but got Version: {:?}",
         env.version
     );
@@ -182,7 +183,7 @@ fn detect_conda_env_from_path() {
     let python_env = PythonEnv::new(exe.clone(), Some(prefix.clone()), None);
     let env = conda.try_from(&python_env).unwrap();
 
-    assert_eq!(env.manager.is_some(), true);
+    assert!(env.manager.is_some());
 
     let manager = env.manager.unwrap();
     assert_eq!(manager.executable, conda_dir.join("bin").join("conda"));
@@ -194,7 +195,9 @@ fn detect_conda_env_from_path() {
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, exe.clone().into());
     assert!(
-        env.version.clone().unwrap_or_default().starts_with("3.10"),
+        env.version
+            .as_ref()
+            .is_some_and(|v| (v.major, v.minor) == (3, 10)),
         "Expected 3.10, but got Version: {:?}",
         env.version
     );
@@ -231,20 +234,19 @@ fn detect_new_conda_env_without_python() {
     let env = environments
         .iter()
         .find(|x| x.name == Some(env_name.into()))
-        .expect(
-            format!(
+        .unwrap_or_else(|| {
+            panic!(
                 "New Environment not created, detected envs {:?}",
                 environments
             )
-            .as_str(),
-        );
+        });
 
     let prefix = conda_dir.clone().join("envs").join(env_name);
     assert_eq!(env.prefix, prefix.clone().into());
     assert_eq!(env.name, Some(env_name.into()));
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
-    assert_eq!(env.executable.is_none(), true);
-    assert_eq!(env.version.is_none(), true);
+    assert!(env.executable.is_none());
+    assert!(env.version.is_none());
 
     assert_eq!(env.manager, Some(manager.clone()));
 }
@@ -281,19 +283,18 @@ fn detect_new_conda_env_created_with_p_flag_without_python() {
     let env = environments
         .iter()
         .find(|x| x.prefix == Some(prefix.clone()))
-        .expect(
-            format!(
+        .unwrap_or_else(|| {
+            panic!(
                 "New Environment ({:?}) not created, detected envs {:?}",
                 prefix, environments
             )
-            .as_str(),
-        );
+        });
 
     assert_eq!(env.prefix, prefix.clone().into());
     assert_eq!(env.name, None);
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
-    assert_eq!(env.executable.is_none(), true);
-    assert_eq!(env.version.is_none(), true);
+    assert!(env.executable.is_none());
+    assert!(env.version.is_none());
 
     assert_eq!(env.manager, Some(manager.clone()));
 }
@@ -334,20 +335,21 @@ fn detect_new_conda_env_created_with_p_flag_with_python() {
     let env = environments
         .iter()
         .find(|x| x.prefix == Some(prefix.clone()))
-        .expect(
-            format!(
+        .unwrap_or_else(|| {
+            panic!(
                 "New Environment not created, detected envs {:?}",
                 environments
             )
-            .as_str(),
-        );
+        });
 
     assert_eq!(env.prefix, prefix.clone().into());
     assert_eq!(env.name, None);
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, exe.into());
     assert!(
-        env.version.clone().unwrap_or_default().starts_with("3.10"),
+        env.version
+            .as_ref()
+            .is_some_and(|v| (v.major, v.minor) == (3, 10)),
         "Expected 3.10, but got Version: {:?}",
         env.version
     );
@@ -409,7 +411,7 @@ fn create_conda_env(mode: &CondaCreateEnvNameOrPath, python_version: Option<Stri
         .expect("Failed to execute command");
 }
 
-fn get_version(value: &String) -> String {
+fn get_version(value: &str) -> String {
     // Regex to extract just the d.d.d version from the full version string
     let re = regex::Regex::new(r"\d+\.\d+\.\d+").unwrap();
     let captures = re.captures(value).unwrap();
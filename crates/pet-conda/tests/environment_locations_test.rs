@@ -9,8 +9,8 @@ fn non_existent_envrionments_txt() {
     use common::{create_env_variables, resolve_test_path};
     use pet_conda::environment_locations::get_conda_envs_from_environment_txt;
 
-    let root = resolve_test_path(&["unix", "root_empty"]).into();
-    let home = resolve_test_path(&["unix", "bogus directory"]).into();
+    let root = resolve_test_path(&["unix", "root_empty"]);
+    let home = resolve_test_path(&["unix", "bogus directory"]);
     let env = create_env_variables(home, root);
 
     let environments = get_conda_envs_from_environment_txt(&env);
@@ -39,3 +39,49 @@ fn list_conda_envs_in_install_location() {
         ]
     );
 }
+
+#[test]
+fn parse_conda_env_list_output() {
+    use pet_conda::environment_locations::parse_conda_env_list_output;
+    use std::path::PathBuf;
+
+    let output = r#"
+# conda environments:
+#
+base                  *  /home/user/anaconda3
+myenv                    /home/user/anaconda3/envs/myenv
+                         /home/user/custom-prefix
+                      *  /home/user/active prefix
+"#;
+    assert_eq!(
+        parse_conda_env_list_output(output),
+        vec![
+            (
+                Some("base".to_string()),
+                PathBuf::from("/home/user/anaconda3")
+            ),
+            (
+                Some("myenv".to_string()),
+                PathBuf::from("/home/user/anaconda3/envs/myenv")
+            ),
+            (None, PathBuf::from("/home/user/custom-prefix")),
+            (None, PathBuf::from("/home/user/active prefix")),
+        ]
+    );
+
+    let output = "# conda environments:\r\n#\r\nbase                     C:\\Users\\user\\miniconda3\r\nml                    *  C:\\Users\\user\\miniconda3\\envs\\ml\r\n                         D:\\envs\\custom\r\n";
+    assert_eq!(
+        parse_conda_env_list_output(output),
+        vec![
+            (
+                Some("base".to_string()),
+                PathBuf::from("C:\\Users\\user\\miniconda3")
+            ),
+            (
+                Some("ml".to_string()),
+                PathBuf::from("C:\\Users\\user\\miniconda3\\envs\\ml")
+            ),
+            (None, PathBuf::from("D:\\envs\\custom")),
+        ]
+    );
+}
@@ -20,8 +20,8 @@ fn find_conda_env_without_manager() {
 
     let env = locator
         .try_from(&PythonEnv::new(
-            path.join("bin").join("python").into(),
-            Some(path.clone().into()),
+            path.join("bin").join("python"),
+            Some(path.clone()),
             None,
         ))
         .unwrap();
@@ -30,7 +30,7 @@ fn find_conda_env_without_manager() {
     assert_eq!(env.arch, Architecture::X64.into());
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, path.join("bin").join("python").into());
-    assert_eq!(env.version, "3.12.2".to_string().into());
+    assert_eq!(env.version, "3.12.2".parse().ok());
     assert_eq!(env.manager, None);
     assert_eq!(env.name, "env_python_3".to_string().into());
 }
@@ -71,8 +71,8 @@ fn find_conda_env_without_manager_but_detect_manager_from_history() {
 
     let env = locator
         .try_from(&PythonEnv::new(
-            path.join("bin").join("python").into(),
-            Some(path.clone().into()),
+            path.join("bin").join("python"),
+            Some(path.clone()),
             None,
         ))
         .unwrap();
@@ -81,7 +81,7 @@ fn find_conda_env_without_manager_but_detect_manager_from_history() {
     assert_eq!(env.arch, Architecture::X64.into());
     assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
     assert_eq!(env.executable, path.join("bin").join("python").into());
-    assert_eq!(env.version, "3.12.2".to_string().into());
+    assert_eq!(env.version, "3.12.2".parse().ok());
     assert_eq!(
         env.manager.clone().unwrap().executable,
         conda_dir.join("bin").join("conda")
@@ -92,3 +92,195 @@ fn find_conda_env_without_manager_but_detect_manager_from_history() {
     );
     assert_eq!(env.name, None);
 }
+
+#[test]
+fn manages_conda_executables() {
+    use common::create_test_environment;
+    use pet_conda::Conda;
+    use pet_core::Locator;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let environment = create_test_environment(HashMap::new(), None, vec![], None);
+    let locator = Conda::from(&environment);
+
+    assert!(locator.manages_executable(&PathBuf::from("/opt/conda/bin/conda")));
+    assert!(locator.manages_executable(&PathBuf::from("/opt/conda/bin/mamba")));
+    assert!(locator.manages_executable(&PathBuf::from("/usr/local/bin/micromamba")));
+    assert!(locator.manages_executable(&PathBuf::from("conda.exe")));
+    assert!(!locator.manages_executable(&PathBuf::from("/opt/conda/bin/python")));
+    assert!(!locator.manages_executable(&PathBuf::from("/usr/local/bin/poetry")));
+}
+
+#[cfg(unix)]
+#[test]
+fn provided_conda_executable_skips_path_search() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_conda::Conda;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let conda_dir = resolve_test_path(&["unix", "anaconda3-2023.03"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "PATH".to_string(),
+            conda_dir
+                .join("bin")
+                .to_str()
+                .unwrap_or_default()
+                .to_string(),
+        )]),
+        None,
+        vec![],
+        None,
+    );
+
+    let locator = Conda::from(&environment);
+    assert_eq!(
+        locator.get_conda_executable(),
+        Some(conda_dir.join("bin").join("conda"))
+    );
+
+    let mock_exe = PathBuf::from("/mock/conda/bin/conda");
+    let locator = Conda::with_executable(mock_exe.clone(), &environment);
+    assert_eq!(locator.get_conda_executable(), Some(mock_exe));
+}
+
+#[cfg(unix)]
+#[test]
+fn describe_includes_conda_executable_and_version() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_conda::Conda;
+    use pet_core::Locator;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let conda_dir = resolve_test_path(&["unix", "anaconda3-2023.03"]);
+    let environment = create_test_environment(HashMap::new(), None, vec![], None);
+
+    let locator = Conda::with_executable(conda_dir.join("bin").join("conda"), &environment);
+    assert_eq!(
+        locator.describe(),
+        format!(
+            "Conda (Conda), executable: {}, version: 23.1.0",
+            conda_dir.join("bin").join("conda").display()
+        )
+    );
+
+    let locator = Conda::with_executable(PathBuf::from("/mock/conda/bin/conda"), &environment);
+    assert_eq!(
+        locator.describe(),
+        "Conda (Conda), executable: /mock/conda/bin/conda, version: unknown"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn validate_conda_env_without_spawning_conda() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_conda::Conda;
+    use pet_core::{
+        python_environment::{PythonEnvironmentBuilder, PythonEnvironmentKind},
+        Locator,
+    };
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(HashMap::new(), None, vec![], None);
+    let locator = Conda::from(&environment);
+    let path = resolve_test_path(&["unix", "conda_env_without_manager", "env_python_3"]);
+    let env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+        .executable(Some(path.join("bin").join("python")))
+        .prefix(Some(path.clone()))
+        .build();
+    let deleted_env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+        .executable(Some(path.join("deleted").join("bin").join("python")))
+        .prefix(Some(path.join("deleted")))
+        .build();
+    let env_without_prefix = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+        .executable(Some(path.join("bin").join("python")))
+        .build();
+
+    assert!(locator.validate(&env));
+    assert!(!locator.validate(&deleted_env));
+    assert!(!locator.validate(&env_without_prefix));
+}
+
+#[cfg(unix)]
+#[test]
+fn get_environments_in_user_home() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_conda::Conda;
+    use pet_core::Locator;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "conda_envs", "user_home"]);
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()), vec![], None);
+    let locator = Conda::from(&environment);
+
+    let mut prefixes = locator
+        .get_environments()
+        .iter()
+        .map(|env| env.prefix.clone().unwrap())
+        .collect::<Vec<_>>();
+    prefixes.sort();
+
+    let conda_dir = home.join("miniconda3");
+    assert_eq!(
+        prefixes,
+        vec![
+            conda_dir.clone(),
+            conda_dir.join("envs").join("env_python_3"),
+            conda_dir.join("envs").join("myenv"),
+            conda_dir.join("envs").join("without_python"),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn refresh_finds_new_environments() {
+    use common::create_test_environment;
+    use pet_conda::Conda;
+    use pet_core::Locator;
+    use std::{collections::HashMap, fs, path::Path};
+
+    fn create_env(prefix: &Path) {
+        fs::create_dir_all(prefix.join("conda-meta")).unwrap();
+    }
+
+    let home = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join(format!("pet_conda_refresh_test_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    let conda_dir = home.join("miniconda3");
+    create_env(&conda_dir);
+    create_env(&conda_dir.join("envs").join("env1"));
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()), vec![], None);
+    let locator = Conda::from(&environment);
+    let prefixes_found = || {
+        let mut prefixes = locator
+            .refresh()
+            .environments
+            .iter()
+            .filter_map(|e| e.prefix.clone())
+            .filter(|prefix| prefix.starts_with(&home))
+            .collect::<Vec<_>>();
+        prefixes.sort();
+        prefixes
+    };
+
+    let before = prefixes_found();
+    create_env(&conda_dir.join("envs").join("env2"));
+    let after = prefixes_found();
+    let _ = fs::remove_dir_all(&home);
+
+    assert_eq!(
+        before,
+        vec![conda_dir.clone(), conda_dir.join("envs").join("env1")]
+    );
+    assert_eq!(
+        after,
+        vec![
+            conda_dir.clone(),
+            conda_dir.join("envs").join("env1"),
+            conda_dir.join("envs").join("env2"),
+        ]
+    );
+}
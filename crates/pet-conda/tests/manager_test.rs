@@ -15,6 +15,7 @@ fn finds_manager_from_root_env() {
 
     assert_eq!(manager.executable, path.join("bin").join("conda"));
     assert_eq!(manager.version, Some("23.1.0".into()));
+    assert_eq!(manager.to_manager().install_path, Some(path));
 }
 
 #[cfg(unix)]
@@ -30,6 +31,7 @@ fn finds_manager_from_root_within_an_env() {
 
     assert_eq!(manager.executable, conda_dir.join("bin").join("conda"));
     assert_eq!(manager.version, Some("23.1.0".into()));
+    assert_eq!(manager.to_manager().install_path, Some(conda_dir.clone()));
 
     // Try a conda env without Python
     let path = resolve_test_path(&["unix", "anaconda3-2023.03", "envs", "without_python"]);
@@ -48,5 +50,5 @@ fn does_not_find_conda_env_for_bogus_dirs() {
 
     let path = resolve_test_path(&["unix", "bogus_directory"]);
 
-    assert_eq!(CondaManager::from(&path).is_none(), true);
+    assert!(CondaManager::from(&path).is_none());
 }
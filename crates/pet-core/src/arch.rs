@@ -8,6 +8,9 @@ use serde::{Deserialize, Serialize};
 pub enum Architecture {
     X64,
     X86,
+    Arm64,
+    Wasm32,
+    Unknown,
 }
 
 impl Ord for Architecture {
@@ -26,10 +29,12 @@ impl std::fmt::Display for Architecture {
         write!(
             f,
             "{}",
-            if *self == Architecture::X64 {
-                "x64"
-            } else {
-                "x86"
+            match self {
+                Architecture::X64 => "x64",
+                Architecture::X86 => "x86",
+                Architecture::Arm64 => "arm64",
+                Architecture::Wasm32 => "wasm32",
+                Architecture::Unknown => "unknown",
             }
         )
         .unwrap_or_default();
@@ -0,0 +1,198 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::cancellation::CancellationToken;
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+struct TaskState<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future that completes when the blocking work (run in a separate thread) completes or is cancelled.
+/// Does not depend on any particular async runtime.
+pub struct BlockingTask<T> {
+    state: Arc<Mutex<TaskState<T>>>,
+    cancellation: Option<CancellationToken>,
+    /// Waker registered with the cancellation token, unregistered once completed or dropped.
+    registered: Option<Waker>,
+}
+
+/// Runs the blocking work in a separate thread, the result is `None` if cancelled before the work completes.
+/// Panics in the work are propagated to the caller awaiting the future, else the future would never complete.
+pub fn spawn_blocking<T, F>(work: F, cancellation: Option<CancellationToken>) -> BlockingTask<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let state = Arc::new(Mutex::new(TaskState {
+        result: None,
+        waker: None,
+    }));
+    let thread_state = state.clone();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(work));
+        let mut state = thread_state.lock().unwrap();
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    });
+    BlockingTask {
+        state,
+        cancellation,
+        registered: None,
+    }
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        // Register before checking, to ensure we do not miss a cancellation in between.
+        this.register(cx.waker());
+        if this.is_cancelled() {
+            this.unregister();
+            return Poll::Ready(None);
+        }
+        let mut state = this.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => {
+                drop(state);
+                this.unregister();
+                match result {
+                    Ok(result) => Poll::Ready(Some(result)),
+                    Err(err) => panic::resume_unwind(err),
+                }
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> BlockingTask<T> {
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|cancellation| cancellation.is_cancelled())
+    }
+
+    fn register(&mut self, waker: &Waker) {
+        let Some(cancellation) = &self.cancellation else {
+            return;
+        };
+        if let Some(registered) = &self.registered {
+            if registered.will_wake(waker) {
+                return;
+            }
+            cancellation.unregister_waker(registered);
+        }
+        cancellation.register_waker(waker);
+        self.registered = Some(waker.clone());
+    }
+
+    fn unregister(&mut self) {
+        if let (Some(cancellation), Some(waker)) = (&self.cancellation, self.registered.take()) {
+            cancellation.unregister_waker(&waker);
+        }
+    }
+}
+
+impl<T> Drop for BlockingTask<T> {
+    fn drop(&mut self) {
+        // Long lived tokens must not hold on to the wakers of futures that will never be polled again.
+        self.unregister();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc,
+        },
+        task::Wake,
+        time::Duration,
+    };
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn poll<T>(task: &mut BlockingTask<T>, waker: &Waker) -> Poll<Option<T>> {
+        Pin::new(task).poll(&mut Context::from_waker(waker))
+    }
+
+    #[test]
+    fn completes_with_the_result_of_the_work() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let (sender, receiver) = mpsc::channel::<()>();
+        let mut task = spawn_blocking(
+            move || {
+                receiver.recv().unwrap();
+                42
+            },
+            None,
+        );
+
+        assert_eq!(poll(&mut task, &waker), Poll::Pending);
+        sender.send(()).unwrap();
+        while counter.0.load(Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(poll(&mut task, &waker), Poll::Ready(Some(42)));
+    }
+
+    #[test]
+    fn completes_without_a_result_when_cancelled() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let cancellation = CancellationToken::new();
+        let (_sender, receiver) = mpsc::channel::<()>();
+        let mut task = spawn_blocking(move || receiver.recv().is_ok(), Some(cancellation.clone()));
+
+        assert_eq!(poll(&mut task, &waker), Poll::Pending);
+        cancellation.cancel();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(poll(&mut task, &waker), Poll::Ready(None));
+    }
+
+    #[test]
+    fn waker_is_unregistered_when_dropped_after_the_work_completes() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let cancellation = CancellationToken::new();
+        let (sender, receiver) = mpsc::channel::<()>();
+        let mut task = spawn_blocking(move || receiver.recv().is_ok(), Some(cancellation.clone()));
+
+        assert_eq!(poll(&mut task, &waker), Poll::Pending);
+        sender.send(()).unwrap();
+        while counter.0.load(Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        // Dropped without being polled again.
+        drop(task);
+
+        // The token no longer holds on to the waker, hence it is not woken again.
+        cancellation.cancel();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    }
+}
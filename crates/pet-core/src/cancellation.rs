@@ -0,0 +1,100 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::Waker,
+};
+
+/// Used to cancel long running operations such as the discovery of environments.
+/// Clones share the same state, i.e. cancelling one cancels all of them.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    /// Wakers of the pending futures that need to be woken when cancelled.
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        for waker in self.inner.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+    /// Wakes the future when cancelled, check `is_cancelled` after registering to not miss a cancellation.
+    /// Futures must call `unregister_waker` once they complete (or are dropped).
+    pub fn register_waker(&self, waker: &Waker) {
+        let mut wakers = self.inner.wakers.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+    pub fn unregister_waker(&self, waker: &Waker) {
+        self.inner
+            .wakers
+            .lock()
+            .unwrap()
+            .retain(|w| !w.will_wake(waker));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::atomic::AtomicUsize,
+        task::{Wake, Waker},
+    };
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn wakers_are_woken_once_when_cancelled() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let token = CancellationToken::new();
+        token.register_waker(&waker);
+        token.register_waker(&waker);
+        assert_eq!(token.inner.wakers.lock().unwrap().len(), 1);
+
+        token.clone().cancel();
+
+        assert!(token.is_cancelled());
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert!(token.inner.wakers.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unregistered_wakers_are_not_kept() {
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let token = CancellationToken::new();
+        token.register_waker(&waker);
+        token.unregister_waker(&waker);
+        assert!(token.inner.wakers.lock().unwrap().is_empty());
+
+        token.cancel();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+    }
+}
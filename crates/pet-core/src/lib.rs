@@ -1,54 +1,460 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
 
+use clap::ValueEnum;
 use env::PythonEnv;
+use log::warn;
 use manager::EnvManager;
-use python_environment::{PythonEnvironment, PythonEnvironmentKind};
-use reporter::Reporter;
+use os_environment::Environment;
+use python_environment::{get_environment_key, PythonEnvironment, PythonEnvironmentKind};
+use reporter::{Reporter, VecReporter};
+use serde::{Deserialize, Serialize};
 
 pub mod arch;
+pub mod blocking;
+pub mod cancellation;
 pub mod env;
 pub mod manager;
 pub mod os_environment;
 pub mod python_environment;
+pub mod python_version;
 pub mod pyvenv_cfg;
 pub mod reporter;
 pub mod telemetry;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct LocatorResult {
     pub managers: Vec<EnvManager>,
     pub environments: Vec<PythonEnvironment>,
 }
 
-#[derive(Debug, Default, Clone)]
+impl LocatorResult {
+    /// Whether nothing (neither managers nor environments) was found.
+    pub fn is_empty(&self) -> bool {
+        self.managers.is_empty() && self.environments.is_empty()
+    }
+    /// Combines the results of two locators.
+    /// Managers with the same executable & environments with the same key (see `get_environment_key`) are only included once,
+    /// with the entries in `self` taking precedence.
+    pub fn merge(mut self, other: LocatorResult) -> LocatorResult {
+        for manager in other.managers {
+            if !self
+                .managers
+                .iter()
+                .any(|m| m.executable == manager.executable)
+            {
+                self.managers.push(manager);
+            }
+        }
+        for env in other.environments {
+            let key = get_environment_key(&env);
+            if key.is_none()
+                || !self
+                    .environments
+                    .iter()
+                    .any(|e| get_environment_key(e) == key)
+            {
+                self.environments.push(env);
+            }
+        }
+        self
+    }
+    /// Environments found in `self` that were not found in `other` (compared by `get_environment_key`).
+    pub fn difference(&self, other: &LocatorResult) -> LocatorResult {
+        let keys = other
+            .environments
+            .iter()
+            .filter_map(get_environment_key)
+            .collect::<HashSet<_>>();
+        LocatorResult {
+            environments: self
+                .environments
+                .iter()
+                .filter(|e| get_environment_key(e).is_none_or(|key| !keys.contains(&key)))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        }
+    }
+    /// Removes environments with the same key (see `get_environment_key`), e.g. when reported by multiple locators.
+    /// The entry with the most information is kept and the symlinks of all duplicates are merged into it.
+    pub fn dedup_by_key(&mut self) {
+        let mut environments: Vec<PythonEnvironment> = vec![];
+        // Position of each key in `environments`.
+        let mut positions: HashMap<PathBuf, usize> = HashMap::new();
+        for env in self.environments.drain(..) {
+            let existing = match get_environment_key(&env) {
+                Some(key) => match positions.get(&key) {
+                    Some(position) => Some(&mut environments[*position]),
+                    None => {
+                        positions.insert(key, environments.len());
+                        None
+                    }
+                },
+                None => None,
+            };
+            match existing {
+                Some(existing) => {
+                    let mut symlinks = existing.symlinks.clone().unwrap_or_default();
+                    symlinks.extend(env.symlinks.clone().unwrap_or_default());
+                    symlinks.sort();
+                    symlinks.dedup();
+                    if count_known_fields(&env) > count_known_fields(existing) {
+                        *existing = env;
+                    }
+                    if !symlinks.is_empty() {
+                        existing.symlinks = Some(symlinks);
+                    }
+                }
+                None => environments.push(env),
+            }
+        }
+        self.environments = environments;
+    }
+}
+
+fn count_known_fields(env: &PythonEnvironment) -> usize {
+    [
+        env.display_name.is_some(),
+        env.name.is_some(),
+        env.executable.is_some(),
+        env.kind.is_some(),
+        env.version.is_some(),
+        env.prefix.is_some(),
+        env.manager.is_some(),
+        env.project.is_some(),
+        env.arch.is_some(),
+        env.packages.is_some(),
+    ]
+    .iter()
+    .filter(|known| **known)
+    .count()
+}
+
+/// Default number of levels of sub directories searched for environments in the workspace directories.
+pub const DEFAULT_MAX_SEARCH_DEPTH: u32 = 2;
+/// Largest `max_search_depth` accepted by `Configuration::validate`.
+pub const MAX_SEARCH_DEPTH_LIMIT: u32 = 10;
+/// Largest `timeout_ms` accepted by `Configuration::validate` (10 minutes).
+pub const TIMEOUT_MS_LIMIT: u64 = 10 * 60 * 1000;
+/// Largest `max_threads` accepted by `Configuration::validate`.
+pub const MAX_THREADS_LIMIT: usize = 1024;
+
+/// The JSON representation (see `Configuration::from_file`) uses camelCase names,
+/// same as the JSON-RPC `configure` request, and all fields are optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Configuration {
     /// These are paths like workspace folders, where we can look for environments.
     pub workspace_directories: Option<Vec<PathBuf>>,
     pub executables: Option<Vec<PathBuf>>,
     pub conda_executable: Option<PathBuf>,
     pub poetry_executable: Option<PathBuf>,
+    pub hatch_executable: Option<PathBuf>,
+    pub mamba_executable: Option<PathBuf>,
+    pub micromamba_executable: Option<PathBuf>,
     /// Custom locations where environments can be found.
     /// These are different from search_paths, as these are specific directories where environments are expected.
     /// environment_directories on the other hand can be any directory such as a workspace folder, where envs might never exist.
     pub environment_directories: Option<Vec<PathBuf>>,
     /// Directory to cache the Python environment details.
     pub cache_directory: Option<PathBuf>,
+    /// Look for all Poetry environments in the Poetry virtualenvs directory,
+    /// instead of only those associated with the workspace directories.
+    /// Useful in CI, as this does not require spawning Poetry.
+    /// Defaults to `false`.
+    pub poetry_scan_prefix_dirs: Option<bool>,
+    /// Whether symlinked directories are followed when scanning the workspace directories (defaults to `false`).
+    /// Leave this disabled when workspace directories contain symlinks to large shared storage.
+    pub follow_symlinks: Option<bool>,
+    /// Directories (and their sub directories) that are never searched for environments
+    /// when scanning the workspace directories, e.g. large data directories.
+    pub exclude_paths: Option<Vec<PathBuf>>,
+    /// Number of levels of sub directories searched for environments in the workspace directories
+    /// (defaults to `DEFAULT_MAX_SEARCH_DEPTH`), `0` searches just the workspace directories.
+    pub max_search_depth: Option<u32>,
+    /// Time (in milliseconds) after which processes spawned by the locators (e.g. conda, Poetry) are killed.
+    /// Defaults to 30 seconds (`pet_process::DEFAULT_TIMEOUT`).
+    pub timeout_ms: Option<u64>,
+    /// Max number of threads used to search for environments at the same time (unlimited by default).
+    pub max_threads: Option<usize>,
+    /// Only the locators that support at least one of these kinds are used to search for environments.
+    pub kind_filter: Option<Vec<PythonEnvironmentKind>>,
+    /// Whether the packages installed in each environment are listed (by reading the site-packages directory).
+    /// Defaults to `false`, as this can be expensive.
+    pub scan_packages: Option<bool>,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            workspace_directories: None,
+            executables: None,
+            conda_executable: None,
+            poetry_executable: None,
+            hatch_executable: None,
+            mamba_executable: None,
+            micromamba_executable: None,
+            environment_directories: None,
+            cache_directory: None,
+            poetry_scan_prefix_dirs: None,
+            follow_symlinks: None,
+            exclude_paths: None,
+            max_search_depth: Some(DEFAULT_MAX_SEARCH_DEPTH),
+            timeout_ms: None,
+            max_threads: None,
+            kind_filter: None,
+            scan_packages: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationError {
+    /// Name of the invalid field in the configuration.
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IoError(io::Error),
+    ParseError(serde_json::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::IoError(err) => write!(f, "Failed to read the configuration: {err}"),
+            ConfigError::ParseError(err) => write!(f, "Invalid configuration: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Configuration {
+    /// Reads the configuration from a JSON file, e.g. `{ "workspaceDirectories": ["/projects/demo"] }`.
+    pub fn from_file(path: &Path) -> Result<Configuration, ConfigError> {
+        let contents = fs::read_to_string(path).map_err(ConfigError::IoError)?;
+        serde_json::from_str(&contents).map_err(ConfigError::ParseError)
+    }
+
+    /// Reads the configuration from the `PET_*` environment variables.
+    /// Lists of paths are separated by the platform specific delimiter (`:` on Unix & `;` on Windows),
+    /// and kinds (`PET_KIND_FILTER`) are separated by `,`.
+    /// Fields without a corresponding environment variable are not set (including `max_search_depth`),
+    /// so that this can be merged with other configurations.
+    pub fn from_env(env: &dyn Environment) -> Configuration {
+        let var = |key: &str| {
+            env.get_env_var(key.to_string())
+                .filter(|value| !value.trim().is_empty())
+        };
+        let paths = |key: &str| var(key).map(|value| std::env::split_paths(&value).collect());
+        Configuration {
+            workspace_directories: paths("PET_PROJECT_DIRS"),
+            environment_directories: paths("PET_SEARCH_PATHS"),
+            cache_directory: var("PET_CACHE_DIR").map(PathBuf::from),
+            max_search_depth: parse_number("PET_MAX_DEPTH", var("PET_MAX_DEPTH")),
+            timeout_ms: parse_number("PET_TIMEOUT_MS", var("PET_TIMEOUT_MS")),
+            exclude_paths: paths("PET_EXCLUDE_PATHS"),
+            kind_filter: var("PET_KIND_FILTER").map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|kind| !kind.is_empty())
+                    .filter_map(|kind| match PythonEnvironmentKind::from_str(kind, true) {
+                        Ok(kind) => Some(kind),
+                        Err(_) => {
+                            warn!("Ignoring unknown kind {kind:?} in PET_KIND_FILTER");
+                            None
+                        }
+                    })
+                    .collect()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Values in `self` take precedence, the values in `other` are used for the fields that are not set in `self`.
+    pub fn merge(self, other: Configuration) -> Configuration {
+        Configuration {
+            workspace_directories: self.workspace_directories.or(other.workspace_directories),
+            executables: self.executables.or(other.executables),
+            conda_executable: self.conda_executable.or(other.conda_executable),
+            poetry_executable: self.poetry_executable.or(other.poetry_executable),
+            hatch_executable: self.hatch_executable.or(other.hatch_executable),
+            mamba_executable: self.mamba_executable.or(other.mamba_executable),
+            micromamba_executable: self.micromamba_executable.or(other.micromamba_executable),
+            environment_directories: self
+                .environment_directories
+                .or(other.environment_directories),
+            cache_directory: self.cache_directory.or(other.cache_directory),
+            poetry_scan_prefix_dirs: self
+                .poetry_scan_prefix_dirs
+                .or(other.poetry_scan_prefix_dirs),
+            follow_symlinks: self.follow_symlinks.or(other.follow_symlinks),
+            exclude_paths: self.exclude_paths.or(other.exclude_paths),
+            max_search_depth: self.max_search_depth.or(other.max_search_depth),
+            timeout_ms: self.timeout_ms.or(other.timeout_ms),
+            max_threads: self.max_threads.or(other.max_threads),
+            kind_filter: self.kind_filter.or(other.kind_filter),
+            scan_packages: self.scan_packages.or(other.scan_packages),
+        }
+    }
+
+    /// Validates the configuration and returns all of the invalid fields.
+    /// Its upto the caller to decide whether these are fatal or just warnings.
+    pub fn validate(&self) -> Vec<ConfigurationError> {
+        let mut errors = vec![];
+        for exe in self.executables.iter().flatten() {
+            validate_file("executables", exe, &mut errors);
+        }
+        if let Some(exe) = &self.conda_executable {
+            validate_file("conda_executable", exe, &mut errors);
+        }
+        if let Some(exe) = &self.poetry_executable {
+            validate_file("poetry_executable", exe, &mut errors);
+        }
+        if let Some(exe) = &self.hatch_executable {
+            validate_file("hatch_executable", exe, &mut errors);
+        }
+        if let Some(exe) = &self.mamba_executable {
+            validate_file("mamba_executable", exe, &mut errors);
+        }
+        if let Some(exe) = &self.micromamba_executable {
+            validate_file("micromamba_executable", exe, &mut errors);
+        }
+        for dir in self.workspace_directories.iter().flatten() {
+            validate_directory("workspace_directories", dir, &mut errors);
+        }
+        for dir in self.environment_directories.iter().flatten() {
+            validate_directory("environment_directories", dir, &mut errors);
+        }
+        // The cache directory is created if it does not exist.
+        if let Some(dir) = &self.cache_directory {
+            if dir.exists() && !dir.is_dir() {
+                errors.push(ConfigurationError {
+                    field: "cache_directory",
+                    message: format!("{:?} is not a directory", dir),
+                });
+            }
+        }
+        // `0` is valid, as it searches just the workspace directories.
+        if let Some(depth) = self.max_search_depth {
+            validate_range(
+                "max_search_depth",
+                depth,
+                0,
+                MAX_SEARCH_DEPTH_LIMIT,
+                &mut errors,
+            );
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            validate_range("timeout_ms", timeout_ms, 1, TIMEOUT_MS_LIMIT, &mut errors);
+        }
+        if let Some(max_threads) = self.max_threads {
+            validate_range(
+                "max_threads",
+                max_threads,
+                1,
+                MAX_THREADS_LIMIT,
+                &mut errors,
+            );
+        }
+        errors
+    }
+}
+
+fn parse_number<T: std::str::FromStr<Err = std::num::ParseIntError>>(
+    key: &str,
+    value: Option<String>,
+) -> Option<T> {
+    match value?.trim().parse() {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring {key}, as it is not a valid number: {err}");
+            None
+        }
+    }
+}
+
+fn validate_file(field: &'static str, file: &Path, errors: &mut Vec<ConfigurationError>) {
+    if !file.exists() {
+        errors.push(ConfigurationError {
+            field,
+            message: format!("{:?} does not exist", file),
+        });
+    } else if !file.is_file() {
+        errors.push(ConfigurationError {
+            field,
+            message: format!("{:?} is not a file", file),
+        });
+    }
+}
+
+fn validate_range<T: PartialOrd + fmt::Display>(
+    field: &'static str,
+    value: T,
+    min: T,
+    max: T,
+    errors: &mut Vec<ConfigurationError>,
+) {
+    if value < min || value > max {
+        errors.push(ConfigurationError {
+            field,
+            message: format!("{value} is not between {min} and {max}"),
+        });
+    }
+}
+
+fn validate_directory(field: &'static str, dir: &Path, errors: &mut Vec<ConfigurationError>) {
+    if !dir.exists() {
+        errors.push(ConfigurationError {
+            field,
+            message: format!("{:?} does not exist", dir),
+        });
+    } else if !dir.is_dir() {
+        errors.push(ConfigurationError {
+            field,
+            message: format!("{:?} is not a directory", dir),
+        });
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LocatorKind {
+    Asdf,
     Conda,
+    Hatch,
     Homebrew,
     LinuxGlobal,
     MacCommandLineTools,
     MacPythonOrg,
     MacXCode,
+    Mamba,
+    Mise,
+    Nox,
+    Pdm,
     PipEnv,
+    Pixi,
     Poetry,
     PyEnv,
+    Rye,
+    Tox,
+    Uv,
     Venv,
     VirtualEnv,
     VirtualEnvWrapper,
@@ -56,17 +462,85 @@ pub enum LocatorKind {
     WindowsStore,
 }
 
+impl LocatorKind {
+    /// Name of the locator, e.g. reported as the `source_locator` of an environment.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LocatorKind::Asdf => "Asdf",
+            LocatorKind::Conda => "Conda",
+            LocatorKind::Hatch => "Hatch",
+            LocatorKind::Homebrew => "Homebrew",
+            LocatorKind::LinuxGlobal => "LinuxGlobal",
+            LocatorKind::MacCommandLineTools => "MacCommandLineTools",
+            LocatorKind::MacPythonOrg => "MacPythonOrg",
+            LocatorKind::MacXCode => "MacXCode",
+            LocatorKind::Mamba => "Mamba",
+            LocatorKind::Mise => "Mise",
+            LocatorKind::Nox => "Nox",
+            LocatorKind::Pdm => "Pdm",
+            LocatorKind::PipEnv => "PipEnv",
+            LocatorKind::Pixi => "Pixi",
+            LocatorKind::Poetry => "Poetry",
+            LocatorKind::PyEnv => "PyEnv",
+            LocatorKind::Rye => "Rye",
+            LocatorKind::Tox => "Tox",
+            LocatorKind::Uv => "Uv",
+            LocatorKind::Venv => "Venv",
+            LocatorKind::VirtualEnv => "VirtualEnv",
+            LocatorKind::VirtualEnvWrapper => "VirtualEnvWrapper",
+            LocatorKind::WindowsRegistry => "WindowsRegistry",
+            LocatorKind::WindowsStore => "WindowsStore",
+        }
+    }
+}
+
 pub trait Locator: Send + Sync {
     /// Returns the name of the locator.
     fn get_kind(&self) -> LocatorKind;
+    /// Human readable description of what this locator finds and where it looks.
+    /// E.g. `Finds environments created by Poetry, reads POETRY_VIRTUALENVS_PATH`.
+    fn description(&self) -> &'static str;
+    /// Locators with a lower priority are asked to identify an environment (`try_from`) first.
+    /// When more than one locator can identify an environment (e.g. a conda env is also a virtual env),
+    /// the most specialized locator must have the lowest priority.
+    ///
+    /// - `5`: Locators of tools that install Python (pyenv, Windows Store, etc) or create conda like environments (pixi, mamba).
+    /// - `10`: Conda.
+    /// - `128` (default): Locators of tools that create virtual envs (Poetry, Pipenv, uv, etc).
+    /// - `200`: Generic virtual envs (venv & virtualenv).
+    /// - `250`: Global installs of Python (Homebrew, `/usr/bin`, etc).
+    ///
+    /// Locators with the same priority are evaluated in the order they were created.
+    fn priority(&self) -> u8 {
+        128
+    }
     /// Configures the locator with the given configuration.
     /// Override this method if you need to have some custom configuration.
     /// E.g. storing some of the configuration information in the locator.
     fn configure(&self, _config: &Configuration) {
         //
     }
+    /// Returns true if the given executable is a manager (tool) that belongs to this locator.
+    /// E.g. `/home/user/.local/bin/poetry` belongs to the Poetry locator.
+    /// Override this method if the locator has a corresponding manager.
+    fn manages_executable(&self, _exe: &Path) -> bool {
+        false
+    }
     /// Returns a list of supported categories for this locator.
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind>;
+    /// Human readable description of this locator, used for diagnostics.
+    /// Override this method to include details such as the manager executable & version.
+    fn describe(&self) -> String {
+        format!(
+            "{} ({})",
+            self.get_kind().name(),
+            self.supported_categories()
+                .iter()
+                .map(|c| format!("{:?}", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
     /// Given a Python executable, and some optional data like prefix,
     /// this method will attempt to convert it to a PythonEnvironment that can be supported by this particular locator.
     /// If an environment is not supported by this locator, then None is returned.
@@ -74,6 +548,517 @@ pub trait Locator: Send + Sync {
     /// Note: The returned environment could have some missing information.
     /// This is because the `from` will do a best effort to get the environment information without spawning Python.
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment>;
+    /// Returns true if the environment previously found by this locator is still valid (e.g. it has not been deleted).
+    /// Override this method if there is a cheaper check than identifying the environment all over again.
+    fn validate(&self, env: &PythonEnvironment) -> bool {
+        env.executable.as_ref().is_some_and(|executable| {
+            let env = PythonEnv::new(
+                executable.clone(),
+                env.prefix.clone(),
+                env.version.as_ref().map(|v| v.to_string()),
+            );
+            self.try_from(&env).is_some()
+        })
+    }
     /// Finds all environments specific to this locator.
     fn find(&self, reporter: &dyn Reporter);
+    /// Same as `find`, however the environments found are returned instead of being reported.
+    /// Override this method if the environments can be returned without going through a `Reporter`.
+    fn get_environments(&self) -> Vec<PythonEnvironment> {
+        let reporter = VecReporter::new();
+        self.find(&reporter);
+        reporter.into_environments()
+    }
+    /// Clears any state cached by this locator and re-runs the discovery, returning what was found.
+    /// Override this method if the locator caches the environments or managers it has found.
+    fn refresh(&self) -> LocatorResult {
+        LocatorResult::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn existing_file() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")
+    }
+
+    fn existing_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")
+    }
+
+    fn missing_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("does_not_exist")
+    }
+
+    struct MockEnvironment(std::collections::HashMap<String, String>);
+
+    impl Environment for MockEnvironment {
+        fn get_user_home(&self) -> Option<PathBuf> {
+            None
+        }
+        fn get_root(&self) -> Option<PathBuf> {
+            None
+        }
+        fn get_env_var(&self, key: String) -> Option<String> {
+            self.0.get(&key).cloned()
+        }
+        fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+            vec![]
+        }
+    }
+
+    fn config_from_env(vars: &[(&str, &str)]) -> Configuration {
+        Configuration::from_env(&MockEnvironment(
+            vars.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        ))
+    }
+
+    fn join_paths(paths: &[&str]) -> String {
+        std::env::join_paths(paths)
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    struct MockLocator;
+
+    impl Locator for MockLocator {
+        fn get_kind(&self) -> LocatorKind {
+            LocatorKind::PyEnv
+        }
+        fn description(&self) -> &'static str {
+            "MockLocator"
+        }
+        fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+            vec![
+                PythonEnvironmentKind::Pyenv,
+                PythonEnvironmentKind::PyenvVirtualEnv,
+            ]
+        }
+        fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+            None
+        }
+        fn find(&self, _reporter: &dyn Reporter) {
+            //
+        }
+    }
+
+    #[test]
+    fn describe_includes_name_and_categories() {
+        assert_eq!(MockLocator.describe(), "PyEnv (Pyenv, PyenvVirtualEnv)");
+    }
+
+    #[test]
+    fn configuration_from_env() {
+        let config = config_from_env(&[]);
+        assert_eq!(config.workspace_directories, None);
+        assert_eq!(config.max_search_depth, None);
+        assert_eq!(config.kind_filter, None);
+
+        let config = config_from_env(&[("PET_SEARCH_PATHS", &join_paths(&["/envs", "/other"]))]);
+        assert_eq!(
+            config.environment_directories,
+            Some(vec![PathBuf::from("/envs"), PathBuf::from("/other")])
+        );
+        let config = config_from_env(&[("PET_PROJECT_DIRS", &join_paths(&["/a", "/b"]))]);
+        assert_eq!(
+            config.workspace_directories,
+            Some(vec![PathBuf::from("/a"), PathBuf::from("/b")])
+        );
+        let config = config_from_env(&[("PET_CACHE_DIR", "/cache")]);
+        assert_eq!(config.cache_directory, Some(PathBuf::from("/cache")));
+        let config = config_from_env(&[("PET_MAX_DEPTH", "4")]);
+        assert_eq!(config.max_search_depth, Some(4));
+        let config = config_from_env(&[("PET_MAX_DEPTH", "deep")]);
+        assert_eq!(config.max_search_depth, None);
+        let config = config_from_env(&[("PET_TIMEOUT_MS", "1500")]);
+        assert_eq!(config.timeout_ms, Some(1500));
+        let config = config_from_env(&[("PET_EXCLUDE_PATHS", &join_paths(&["/data"]))]);
+        assert_eq!(config.exclude_paths, Some(vec![PathBuf::from("/data")]));
+        let config = config_from_env(&[("PET_KIND_FILTER", "Poetry, conda,unknown")]);
+        assert_eq!(
+            config.kind_filter,
+            Some(vec![
+                PythonEnvironmentKind::Poetry,
+                PythonEnvironmentKind::Conda
+            ])
+        );
+    }
+
+    #[test]
+    fn merge_configurations() {
+        let config = Configuration {
+            workspace_directories: Some(vec![PathBuf::from("/cli")]),
+            max_search_depth: None,
+            timeout_ms: Some(100),
+            ..Default::default()
+        }
+        .merge(Configuration {
+            workspace_directories: Some(vec![PathBuf::from("/env")]),
+            cache_directory: Some(PathBuf::from("/cache")),
+            max_search_depth: Some(5),
+            timeout_ms: Some(200),
+            poetry_scan_prefix_dirs: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(
+            config.workspace_directories,
+            Some(vec![PathBuf::from("/cli")])
+        );
+        assert_eq!(config.cache_directory, Some(PathBuf::from("/cache")));
+        assert_eq!(config.max_search_depth, Some(5));
+        assert_eq!(config.timeout_ms, Some(100));
+        assert_eq!(config.poetry_scan_prefix_dirs, Some(true));
+        assert_eq!(config.kind_filter, None);
+    }
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = Configuration {
+            workspace_directories: Some(vec![PathBuf::from("/projects/demo")]),
+            conda_executable: Some(PathBuf::from("/conda/bin/conda")),
+            poetry_scan_prefix_dirs: Some(true),
+            follow_symlinks: Some(false),
+            exclude_paths: Some(vec![PathBuf::from("/projects/demo/data")]),
+            max_search_depth: Some(3),
+            timeout_ms: Some(5000),
+            kind_filter: Some(vec![PythonEnvironmentKind::Poetry]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Configuration>(&json).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn configuration_from_file() {
+        let dir = std::env::temp_dir().join("pet_core_configuration_from_file");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("pet.json");
+        fs::write(
+            &file,
+            r#"{ "workspaceDirectories": ["/projects/demo"], "timeoutMs": 1000 }"#,
+        )
+        .unwrap();
+        let invalid_file = dir.join("invalid.json");
+        fs::write(&invalid_file, r#"{ "timeoutMs": "soon" }"#).unwrap();
+
+        let config = Configuration::from_file(&file);
+        let invalid = Configuration::from_file(&invalid_file);
+        let missing = Configuration::from_file(&dir.join("missing.json"));
+        let _ = fs::remove_dir_all(&dir);
+
+        let config = config.unwrap();
+        assert_eq!(
+            config.workspace_directories,
+            Some(vec![PathBuf::from("/projects/demo")])
+        );
+        assert_eq!(config.timeout_ms, Some(1000));
+        // Fields that are not in the file have their default values.
+        assert_eq!(config.max_search_depth, Some(DEFAULT_MAX_SEARCH_DEPTH));
+        assert!(matches!(invalid, Err(ConfigError::ParseError(_))));
+        assert!(matches!(missing, Err(ConfigError::IoError(_))));
+    }
+
+    #[test]
+    fn valid_configuration() {
+        let config = Configuration {
+            workspace_directories: Some(vec![existing_dir()]),
+            executables: Some(vec![existing_file()]),
+            conda_executable: Some(existing_file()),
+            poetry_executable: Some(existing_file()),
+            hatch_executable: Some(existing_file()),
+            mamba_executable: Some(existing_file()),
+            micromamba_executable: Some(existing_file()),
+            environment_directories: Some(vec![existing_dir()]),
+            cache_directory: Some(missing_path()),
+            poetry_scan_prefix_dirs: Some(true),
+            follow_symlinks: Some(false),
+            exclude_paths: Some(vec![missing_path()]),
+            max_search_depth: Some(3),
+            timeout_ms: Some(10_000),
+            max_threads: Some(4),
+            kind_filter: Some(vec![PythonEnvironmentKind::Poetry]),
+            scan_packages: Some(true),
+        };
+        assert!(config.validate().is_empty());
+        assert!(Configuration::default().validate().is_empty());
+    }
+
+    #[test]
+    fn executables_must_be_existing_files() {
+        let config = Configuration {
+            executables: Some(vec![existing_file(), missing_path(), existing_dir()]),
+            conda_executable: Some(missing_path()),
+            poetry_executable: Some(existing_dir()),
+            hatch_executable: Some(missing_path()),
+            micromamba_executable: Some(existing_dir()),
+            ..Default::default()
+        };
+        let fields = config
+            .validate()
+            .iter()
+            .map(|e| e.field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                "executables",
+                "executables",
+                "conda_executable",
+                "poetry_executable",
+                "hatch_executable",
+                "micromamba_executable"
+            ]
+        );
+    }
+
+    #[test]
+    fn directories_must_exist() {
+        let config = Configuration {
+            workspace_directories: Some(vec![existing_dir(), missing_path()]),
+            environment_directories: Some(vec![existing_file()]),
+            cache_directory: Some(existing_file()),
+            ..Default::default()
+        };
+        let fields = config
+            .validate()
+            .iter()
+            .map(|e| e.field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                "workspace_directories",
+                "environment_directories",
+                "cache_directory"
+            ]
+        );
+    }
+
+    #[test]
+    fn numbers_must_be_within_range() {
+        let config = Configuration {
+            max_search_depth: Some(0),
+            timeout_ms: Some(1),
+            max_threads: Some(MAX_THREADS_LIMIT),
+            ..Default::default()
+        };
+        assert!(config.validate().is_empty());
+        let config = Configuration {
+            max_search_depth: Some(MAX_SEARCH_DEPTH_LIMIT),
+            ..Default::default()
+        };
+        assert!(config.validate().is_empty());
+        let config = Configuration {
+            max_search_depth: Some(MAX_SEARCH_DEPTH_LIMIT + 1),
+            ..Default::default()
+        };
+        assert_eq!(config.validate()[0].field, "max_search_depth");
+
+        let config = Configuration {
+            max_search_depth: Some(u32::MAX),
+            timeout_ms: Some(0),
+            max_threads: Some(0),
+            ..Default::default()
+        };
+        let fields = config
+            .validate()
+            .iter()
+            .map(|e| e.field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec!["max_search_depth", "timeout_ms", "max_threads"]
+        );
+
+        let config = Configuration {
+            timeout_ms: Some(u64::MAX),
+            max_threads: Some(usize::MAX),
+            ..Default::default()
+        };
+        let fields = config
+            .validate()
+            .iter()
+            .map(|e| e.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec!["timeout_ms", "max_threads"]);
+    }
+
+    #[test]
+    fn default_locator_result_is_empty() {
+        assert!(LocatorResult::default().is_empty());
+        assert!(!LocatorResult {
+            environments: vec![PythonEnvironment::default()],
+            ..Default::default()
+        }
+        .is_empty());
+    }
+
+    #[test]
+    fn dedup_locator_result_by_key() {
+        use manager::EnvManagerType;
+
+        let exe = PathBuf::from("/home/user/.venv/bin/python");
+        let mut result = LocatorResult {
+            environments: vec![
+                PythonEnvironment {
+                    executable: Some(exe.clone()),
+                    symlinks: Some(vec![exe.clone()]),
+                    ..Default::default()
+                },
+                PythonEnvironment {
+                    executable: Some(PathBuf::from("/usr/bin/python3")),
+                    ..Default::default()
+                },
+                PythonEnvironment {
+                    executable: Some(exe.clone()),
+                    kind: Some(PythonEnvironmentKind::Poetry),
+                    manager: Some(EnvManager::new(
+                        PathBuf::from("/home/user/.local/bin/poetry"),
+                        EnvManagerType::Poetry,
+                        None,
+                    )),
+                    symlinks: Some(vec![PathBuf::from("/home/user/.venv/bin/python3")]),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        result.dedup_by_key();
+
+        assert_eq!(result.environments.len(), 2);
+        assert_eq!(
+            result.environments[0].kind,
+            Some(PythonEnvironmentKind::Poetry)
+        );
+        assert!(result.environments[0].manager.is_some());
+        assert_eq!(
+            result.environments[0].symlinks,
+            Some(vec![
+                exe.clone(),
+                PathBuf::from("/home/user/.venv/bin/python3")
+            ])
+        );
+        assert_eq!(
+            result.environments[1].executable,
+            Some(PathBuf::from("/usr/bin/python3"))
+        );
+    }
+
+    fn env_with_executable(executable: &str) -> PythonEnvironment {
+        PythonEnvironment {
+            executable: Some(PathBuf::from(executable)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merge_locator_results() {
+        use manager::EnvManagerType;
+
+        let poetry = EnvManager::new(
+            PathBuf::from("/home/user/.local/bin/poetry"),
+            EnvManagerType::Poetry,
+            None,
+        );
+        let conda = EnvManager::new(
+            PathBuf::from("/opt/conda/bin/conda"),
+            EnvManagerType::Conda,
+            None,
+        );
+        let first = LocatorResult {
+            managers: vec![poetry.clone()],
+            environments: vec![
+                env_with_executable("/home/user/.venv/bin/python"),
+                env_with_executable("/usr/bin/python3"),
+            ],
+        };
+        let second = LocatorResult {
+            managers: vec![poetry.clone(), conda.clone()],
+            environments: vec![
+                PythonEnvironment {
+                    kind: Some(PythonEnvironmentKind::Poetry),
+                    ..env_with_executable("/home/user/.venv/bin/python")
+                },
+                env_with_executable("/opt/conda/bin/python"),
+            ],
+        };
+
+        let merged = first.clone().merge(second);
+
+        assert_eq!(merged.managers, vec![poetry, conda]);
+        assert_eq!(
+            merged.environments,
+            vec![
+                env_with_executable("/home/user/.venv/bin/python"),
+                env_with_executable("/usr/bin/python3"),
+                env_with_executable("/opt/conda/bin/python"),
+            ]
+        );
+        assert_eq!(first.clone().merge(LocatorResult::default()), first);
+        assert_eq!(LocatorResult::default().merge(first.clone()), first);
+    }
+
+    #[test]
+    fn difference_of_locator_results() {
+        let ours = LocatorResult {
+            environments: vec![
+                env_with_executable("/home/user/.venv/bin/python"),
+                env_with_executable("/usr/bin/python3"),
+            ],
+            ..Default::default()
+        };
+        let theirs = LocatorResult {
+            environments: vec![
+                env_with_executable("/usr/bin/python3"),
+                env_with_executable("/opt/conda/bin/python"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ours.difference(&theirs).environments,
+            vec![env_with_executable("/home/user/.venv/bin/python")]
+        );
+        assert_eq!(
+            theirs.difference(&ours).environments,
+            vec![env_with_executable("/opt/conda/bin/python")]
+        );
+        assert_eq!(ours.difference(&LocatorResult::default()), ours);
+        assert!(ours.difference(&ours).is_empty());
+    }
+
+    #[test]
+    fn python_implementation_from_executable_names() {
+        use python_environment::{PythonEnvironmentBuilder, PythonImplementation};
+
+        let pypy = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Venv))
+            .executable(Some(PathBuf::from("/home/user/.venv/bin/python")))
+            .symlinks(Some(vec![PathBuf::from("/home/user/.venv/bin/pypy3.10")]))
+            .build();
+        assert_eq!(pypy.python_implementation, Some(PythonImplementation::PyPy));
+
+        let unknown = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Venv))
+            .executable(Some(PathBuf::from("/home/user/.venv/bin/python3")))
+            .build();
+        assert_eq!(unknown.python_implementation, None);
+
+        assert_eq!(
+            PythonImplementation::from_name("CPython"),
+            PythonImplementation::CPython
+        );
+        assert_eq!(
+            PythonImplementation::from_name("MicroPython"),
+            PythonImplementation::Other("MicroPython".to_string())
+        );
+    }
 }
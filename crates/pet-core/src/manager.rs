@@ -6,9 +6,19 @@ use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum EnvManagerType {
+    Asdf,
     Conda,
+    Hatch,
+    Mamba,
+    MicroMamba,
+    Mise,
+    Nox,
+    Pdm,
+    Pixi,
     Poetry,
     Pyenv,
+    Rye,
+    Uv,
 }
 
 impl Ord for EnvManagerType {
@@ -29,6 +39,9 @@ pub struct EnvManager {
     pub executable: PathBuf,
     pub version: Option<String>,
     pub tool: EnvManagerType,
+    /// Directory where the manager stores its data.
+    /// E.g. the conda installation directory, or the directory containing Poetry virtual environments.
+    pub install_path: Option<PathBuf>,
 }
 impl Ord for EnvManager {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
@@ -40,7 +53,11 @@ impl Ord for EnvManager {
             std::cmp::Ordering::Equal => {}
             ord => return ord,
         }
-        self.tool.cmp(&other.tool)
+        match self.tool.cmp(&other.tool) {
+            std::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+        self.install_path.cmp(&other.install_path)
     }
 }
 
@@ -56,6 +73,7 @@ impl EnvManager {
             executable: executable_path,
             version,
             tool,
+            install_path: None,
         }
     }
 }
@@ -72,6 +90,14 @@ impl std::fmt::Display for EnvManager {
         if let Some(version) = &self.version {
             writeln!(f, "   Version     : {version}").unwrap_or_default();
         }
+        if let Some(install_path) = &self.install_path {
+            writeln!(
+                f,
+                "   Install Path: {}",
+                install_path.to_str().unwrap_or_default()
+            )
+            .unwrap_or_default();
+        }
         Ok(())
     }
 }
@@ -17,6 +17,13 @@ pub trait Environment: Send + Sync {
     fn get_root(&self) -> Option<PathBuf>;
     fn get_env_var(&self, key: String) -> Option<String>;
     fn get_know_global_search_locations(&self) -> Vec<PathBuf>;
+    /// Directories in the `PATH` environment variable, split using the platform specific delimiter.
+    fn get_path_dirs(&self) -> Vec<PathBuf> {
+        match self.get_env_var("PATH".to_string()) {
+            Some(path) => env::split_paths(&path).collect(),
+            None => vec![],
+        }
+    }
 }
 
 pub struct EnvironmentApi {
@@ -48,10 +55,11 @@ impl Environment for EnvironmentApi {
     }
     fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
         if self.global_search_locations.lock().unwrap().is_empty() {
-            let mut paths =
-                env::split_paths(&self.get_env_var("PATH".to_string()).unwrap_or_default())
-                    .filter(|p| p.exists())
-                    .collect::<Vec<PathBuf>>();
+            let mut paths = self
+                .get_path_dirs()
+                .into_iter()
+                .filter(|p| p.exists())
+                .collect::<Vec<PathBuf>>();
             trace!("Env PATH: {:?}", paths);
             self.global_search_locations
                 .lock()
@@ -75,9 +83,7 @@ impl Environment for EnvironmentApi {
     }
     fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
         if self.global_search_locations.lock().unwrap().is_empty() {
-            let mut paths =
-                env::split_paths(&self.get_env_var("PATH".to_string()).unwrap_or_default())
-                    .collect::<Vec<PathBuf>>();
+            let mut paths = self.get_path_dirs();
             trace!("Env PATH: {:?}", paths);
             vec![
                 PathBuf::from("/bin"),
@@ -142,8 +148,59 @@ fn get_user_home() -> Option<PathBuf> {
 }
 
 fn get_env_var(key: String) -> Option<String> {
-    match env::var(key) {
-        Ok(path) => Some(path),
-        Err(_) => None,
+    env::var(key).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockEnvironment {
+        path: Option<String>,
+    }
+
+    impl Environment for MockEnvironment {
+        fn get_user_home(&self) -> Option<PathBuf> {
+            None
+        }
+        fn get_root(&self) -> Option<PathBuf> {
+            None
+        }
+        fn get_env_var(&self, key: String) -> Option<String> {
+            if key == "PATH" {
+                self.path.clone()
+            } else {
+                None
+            }
+        }
+        fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn path_dirs_are_split_on_platform_delimiter() {
+        let dirs = vec![
+            PathBuf::from("one").join("bin"),
+            PathBuf::from("two"),
+            PathBuf::from("three").join("sbin"),
+        ];
+        let environment = MockEnvironment {
+            path: Some(
+                env::join_paths(dirs.clone())
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+        };
+
+        assert_eq!(environment.get_path_dirs(), dirs);
+    }
+
+    #[test]
+    fn no_path_dirs_without_path_variable() {
+        let environment = MockEnvironment { path: None };
+
+        assert!(environment.get_path_dirs().is_empty());
     }
 }
@@ -5,20 +5,30 @@ use clap::{Parser, ValueEnum};
 use log::error;
 use pet_fs::path::norm_case;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::{arch::Architecture, manager::EnvManager};
+use crate::{arch::Architecture, manager::EnvManager, python_version::PythonVersion};
 
 #[derive(Parser, ValueEnum, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Hash)]
 pub enum PythonEnvironmentKind {
     Conda,
+    Pixi,
     Homebrew,
     Pyenv,
     GlobalPaths,     // Python found in global locations like PATH, /usr/bin etc.
     PyenvVirtualEnv, // Pyenv virtualenvs.
     Pipenv,
     Poetry,
+    Hatch,
+    Pdm,
+    Rye,
+    Tox,
+    Nox,
+    Uv,
+    Asdf,
+    Mise,
     MacPythonOrg,
+    MacOsFramework, // Python in /System/Library/Frameworks, shipped with older versions of macOS.
     MacCommandLineTools,
     LinuxGlobal,
     MacXCode,
@@ -39,7 +49,48 @@ impl PartialOrd for PythonEnvironmentKind {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonImplementation {
+    CPython,
+    PyPy,
+    GraalPy,
+    Jython,
+    IronPython,
+    Other(String),
+}
+
+impl PythonImplementation {
+    /// Parses the implementation name, as found in `pyvenv.cfg` or `sys.implementation.name`.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "cpython" => PythonImplementation::CPython,
+            "pypy" => PythonImplementation::PyPy,
+            "graalpy" | "graalpython" => PythonImplementation::GraalPy,
+            "jython" => PythonImplementation::Jython,
+            "ironpython" => PythonImplementation::IronPython,
+            _ => PythonImplementation::Other(name.trim().to_string()),
+        }
+    }
+    /// Determines the implementation from the name of the executable, e.g. `pypy3.10` or `graalpy`.
+    /// `python` executables are not identified, as alternative implementations ship with them as well.
+    pub fn from_executable(executable: &Path) -> Option<Self> {
+        let name = executable.file_name()?.to_string_lossy().to_lowercase();
+        if name.starts_with("pypy") {
+            Some(PythonImplementation::PyPy)
+        } else if name.starts_with("graalpy") {
+            Some(PythonImplementation::GraalPy)
+        } else if name.starts_with("jython") {
+            Some(PythonImplementation::Jython)
+        } else if name.starts_with("ipy") {
+            Some(PythonImplementation::IronPython)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq)]
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Default)]
 // Python environment.
@@ -52,7 +103,7 @@ pub struct PythonEnvironment {
     // Python executable, can be empty in the case of conda envs that do not have Python installed in them.
     pub executable: Option<PathBuf>,
     pub kind: Option<PythonEnvironmentKind>,
-    pub version: Option<String>,
+    pub version: Option<PythonVersion>,
     // SysPrefix for the environment.
     pub prefix: Option<PathBuf>,
     pub manager: Option<EnvManager>,
@@ -67,6 +118,43 @@ pub struct PythonEnvironment {
     // Some of the known symlinks for the environment.
     // E.g. in the case of Homebrew there are a number of symlinks that are created.
     pub symlinks: Option<Vec<PathBuf>>,
+    // Packages installed in the environment.
+    // Only populated when explicitly requested, as scanning site-packages can be expensive.
+    pub packages: Option<Vec<InstalledPackage>>,
+    // Whether this is a free-threaded build of CPython (i.e. the GIL has been disabled, 3.13+).
+    // Only populated when this can be determined without spawning Python.
+    pub is_free_threaded: Option<bool>,
+    // Implementation of Python, e.g. CPython or PyPy.
+    pub python_implementation: Option<PythonImplementation>,
+    // Name of the locator that discovered this environment, purely for diagnostics.
+    // Not considered when comparing environments.
+    #[serde(rename = "source_locator", skip_deserializing)]
+    pub source_locator: Option<&'static str>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+}
+
+impl PartialEq for PythonEnvironment {
+    fn eq(&self, other: &Self) -> bool {
+        self.display_name == other.display_name
+            && self.name == other.name
+            && self.executable == other.executable
+            && self.kind == other.kind
+            && self.version == other.version
+            && self.prefix == other.prefix
+            && self.manager == other.manager
+            && self.project == other.project
+            && self.arch == other.arch
+            && self.symlinks == other.symlinks
+            && self.packages == other.packages
+            && self.is_free_threaded == other.is_free_threaded
+            && self.python_implementation == other.python_implementation
+    }
 }
 
 impl Ord for PythonEnvironment {
@@ -95,7 +183,7 @@ impl PythonEnvironment {
         kind: Option<PythonEnvironmentKind>,
         prefix: Option<PathBuf>,
         manager: Option<EnvManager>,
-        version: Option<String>,
+        version: Option<PythonVersion>,
     ) -> Self {
         Self {
             executable,
@@ -185,12 +273,17 @@ pub struct PythonEnvironmentBuilder {
     name: Option<String>,
     executable: Option<PathBuf>,
     kind: Option<PythonEnvironmentKind>,
-    version: Option<String>,
+    version: Option<PythonVersion>,
     prefix: Option<PathBuf>,
     manager: Option<EnvManager>,
     project: Option<PathBuf>,
     arch: Option<Architecture>,
     symlinks: Option<Vec<PathBuf>>,
+    packages: Option<Vec<InstalledPackage>>,
+    is_free_threaded: Option<bool>,
+    python_implementation: Option<PythonImplementation>,
+    #[serde(skip_deserializing)]
+    source_locator: Option<&'static str>,
 }
 
 impl PythonEnvironmentBuilder {
@@ -206,6 +299,10 @@ impl PythonEnvironmentBuilder {
             project: None,
             arch: None,
             symlinks: None,
+            packages: None,
+            is_free_threaded: None,
+            python_implementation: None,
+            source_locator: None,
         }
     }
     pub fn from_environment(env: PythonEnvironment) -> Self {
@@ -220,6 +317,10 @@ impl PythonEnvironmentBuilder {
             project: env.project,
             arch: env.arch,
             symlinks: env.symlinks,
+            packages: env.packages,
+            is_free_threaded: env.is_free_threaded,
+            python_implementation: env.python_implementation,
+            source_locator: env.source_locator,
         }
     }
 
@@ -246,7 +347,7 @@ impl PythonEnvironmentBuilder {
         self
     }
 
-    pub fn version(mut self, version: Option<String>) -> Self {
+    pub fn version(mut self, version: Option<PythonVersion>) -> Self {
         self.version = version;
         self
     }
@@ -277,6 +378,29 @@ impl PythonEnvironmentBuilder {
         self
     }
 
+    pub fn packages(mut self, packages: Option<Vec<InstalledPackage>>) -> Self {
+        self.packages = packages;
+        self
+    }
+
+    pub fn is_free_threaded(mut self, is_free_threaded: Option<bool>) -> Self {
+        self.is_free_threaded = is_free_threaded;
+        self
+    }
+
+    pub fn python_implementation(
+        mut self,
+        python_implementation: Option<PythonImplementation>,
+    ) -> Self {
+        self.python_implementation = python_implementation;
+        self
+    }
+
+    pub fn source_locator(mut self, source_locator: Option<&'static str>) -> Self {
+        self.source_locator = source_locator;
+        self
+    }
+
     pub fn symlinks(mut self, symlinks: Option<Vec<PathBuf>>) -> Self {
         self.update_symlinks_and_exe(symlinks);
         self
@@ -325,6 +449,10 @@ impl PythonEnvironmentBuilder {
         let executable = self.executable.map(|executable| {
             get_shortest_executable(&self.kind, &Some(all.clone())).unwrap_or(executable)
         });
+        let python_implementation = self.python_implementation.or_else(|| {
+            all.iter()
+                .find_map(|exe| PythonImplementation::from_executable(exe))
+        });
 
         PythonEnvironment {
             display_name: self.display_name,
@@ -337,6 +465,10 @@ impl PythonEnvironmentBuilder {
             project: self.project,
             arch: self.arch,
             symlinks,
+            packages: self.packages,
+            is_free_threaded: self.is_free_threaded,
+            python_implementation,
+            source_locator: self.source_locator,
         }
     }
 }
@@ -415,6 +547,7 @@ pub fn get_environment_key(env: &PythonEnvironment) -> Option<PathBuf> {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(windows)]
     use super::*;
 
     #[test]
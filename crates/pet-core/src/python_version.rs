@@ -0,0 +1,241 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+lazy_static! {
+    static ref PYTHON_VERSION: Regex = Regex::new(r"^(\d+)\.(\d+)(?:\.(\d+))?(.*)$")
+        .expect("error parsing Version regex for PythonVersion");
+    static ref PRE_RELEASE: Regex =
+        Regex::new(r"^(a|b|rc|c)(\d+)$").expect("error parsing pre-release regex");
+    static ref POST_RELEASE: Regex =
+        Regex::new(r"^(?:\.?post(\d+)|\+.*)$").expect("error parsing post-release regex");
+}
+
+/// Version of Python, e.g. `3.12.1`, `3.13.0a5` or `3.10.14+`.
+/// Serialized as the version string, i.e. `"3.12.1"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PythonVersion {
+    pub major: u8,
+    pub minor: u8,
+    /// Some sources only provide the partial version (e.g. `3.12` from the install directory),
+    /// in which case the micro version is not known (rather than assuming `0`).
+    pub micro: Option<u8>,
+    /// Everything after the numeric part of the version, e.g. `a5`, `rc1` or `+` (post-release).
+    pub release_level: Option<String>,
+}
+
+/// Whether two versions are considered the same in `PythonVersion::matches`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionTolerance {
+    /// Only the major & minor versions need to be the same, e.g. `3.12.1` matches `3.12`.
+    MajorMinor,
+    /// All parts of the version need to be the same.
+    Exact,
+}
+
+impl PythonVersion {
+    pub fn new(major: u8, minor: u8, micro: u8) -> Self {
+        PythonVersion {
+            major,
+            minor,
+            micro: Some(micro),
+            release_level: None,
+        }
+    }
+    pub fn matches(&self, other: &PythonVersion, tolerance: VersionTolerance) -> bool {
+        match tolerance {
+            VersionTolerance::MajorMinor => self.major == other.major && self.minor == other.minor,
+            VersionTolerance::Exact => self == other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePythonVersionError(String);
+
+impl fmt::Display for ParsePythonVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid Python version {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParsePythonVersionError {}
+
+impl FromStr for PythonVersion {
+    type Err = ParsePythonVersionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePythonVersionError(value.to_string());
+        let captures = PYTHON_VERSION.captures(value.trim()).ok_or_else(error)?;
+        let number = |index: usize| {
+            captures
+                .get(index)
+                .map(|m| m.as_str().parse::<u8>().map_err(|_| error()))
+                .transpose()
+        };
+        let release_level = captures
+            .get(4)
+            .map(|m| m.as_str().to_string())
+            .filter(|r| !r.is_empty());
+        Ok(PythonVersion {
+            major: number(1)?.ok_or_else(error)?,
+            minor: number(2)?.ok_or_else(error)?,
+            micro: number(3)?,
+            release_level,
+        })
+    }
+}
+
+impl fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(micro) = self.micro {
+            write!(f, ".{micro}")?;
+        }
+        if let Some(release_level) = &self.release_level {
+            write!(f, "{release_level}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Kinds of releases, in the order they are released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ReleaseKind {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+    Final,
+    /// E.g. `3.10.14+`, built from the sources after the `3.10.14` release (as shipped by some Linux distributions).
+    Post,
+}
+
+/// Parses the release level into its kind & number, e.g. `rc1` is `(ReleaseCandidate, 1)`.
+/// Unknown release levels are treated as final releases.
+fn parse_release_level(release_level: Option<&str>) -> (ReleaseKind, u32) {
+    let release_level = match release_level {
+        Some(release_level) => release_level,
+        None => return (ReleaseKind::Final, 0),
+    };
+    if let Some(captures) = PRE_RELEASE.captures(release_level) {
+        let kind = match &captures[1] {
+            "a" => ReleaseKind::Alpha,
+            "b" => ReleaseKind::Beta,
+            _ => ReleaseKind::ReleaseCandidate,
+        };
+        return (kind, captures[2].parse().unwrap_or_default());
+    }
+    if let Some(captures) = POST_RELEASE.captures(release_level) {
+        let number = captures
+            .get(1)
+            .and_then(|m| m.as_str().parse().ok())
+            .unwrap_or_default();
+        return (ReleaseKind::Post, number);
+    }
+    (ReleaseKind::Final, 0)
+}
+
+impl Ord for PythonVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.micro.cmp(&other.micro))
+            // Pre-releases (e.g. `3.13.0a5`) come before the final release (`3.13.0`), post-releases after.
+            .then_with(|| {
+                parse_release_level(self.release_level.as_deref())
+                    .cmp(&parse_release_level(other.release_level.as_deref()))
+            })
+            // Keep the order consistent with `Eq` for release levels that are not recognized.
+            .then_with(|| self.release_level.cmp(&other.release_level))
+    }
+}
+impl PartialOrd for PythonVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Serialize for PythonVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PythonVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_versions() {
+        for value in [
+            "3.12.1",
+            "3.12",
+            "3.13.0a5",
+            "3.10.14+",
+            "3.11.0rc1",
+            "2.7.18",
+        ] {
+            let version: PythonVersion = value.parse().unwrap();
+            assert_eq!(version.to_string(), value);
+        }
+        assert_eq!(
+            "3.13.0a5".parse::<PythonVersion>().unwrap(),
+            PythonVersion {
+                major: 3,
+                minor: 13,
+                micro: Some(0),
+                release_level: Some("a5".to_string()),
+            }
+        );
+        assert!("".parse::<PythonVersion>().is_err());
+        assert!("3".parse::<PythonVersion>().is_err());
+        assert!("python3.12".parse::<PythonVersion>().is_err());
+        assert!("3.1000.1".parse::<PythonVersion>().is_err());
+    }
+
+    #[test]
+    fn compare_versions() {
+        let parse = |value: &str| value.parse::<PythonVersion>().unwrap();
+        assert!(parse("3.12.1") > parse("3.12.0"));
+        assert!(parse("3.12.0") > parse("3.11.9"));
+        assert!(parse("3.10.0") > parse("3.9.18"));
+        assert!(parse("3.13.0") > parse("3.13.0rc1"));
+        assert!(parse("3.13.0rc1") > parse("3.13.0a5"));
+        assert!(parse("3.12.0") > parse("3.12"));
+        assert!(parse("3.13.0a10") > parse("3.13.0a9"));
+        assert!(parse("3.13.0b1") > parse("3.13.0a10"));
+        assert!(parse("3.13.0rc10") > parse("3.13.0rc9"));
+        assert!(parse("3.10.14+") > parse("3.10.14"));
+        assert!(parse("3.10.14+") < parse("3.10.15a1"));
+        assert!(parse("3.10.14.post2") > parse("3.10.14.post1"));
+        assert_eq!(parse("3.10.14+").cmp(&parse("3.10.14+")), Ordering::Equal);
+
+        assert!(parse("3.12.1").matches(&parse("3.12"), VersionTolerance::MajorMinor));
+        assert!(!parse("3.12.1").matches(&parse("3.11.1"), VersionTolerance::MajorMinor));
+        assert!(parse("3.12.1").matches(&parse("3.12.1"), VersionTolerance::Exact));
+        assert!(!parse("3.12.1").matches(&parse("3.12.2"), VersionTolerance::Exact));
+    }
+
+    #[test]
+    fn serialized_as_string() {
+        let version: PythonVersion = "3.12.1".parse().unwrap();
+        assert_eq!(serde_json::to_string(&version).unwrap(), "\"3.12.1\"");
+        assert_eq!(
+            serde_json::from_str::<PythonVersion>("\"3.12.1\"").unwrap(),
+            version
+        );
+        assert!(serde_json::from_str::<PythonVersion>("\"latest\"").is_err());
+    }
+}
@@ -13,6 +13,8 @@ lazy_static! {
         .expect("error parsing Version regex for pyenv_cfg");
     static ref VERSION_INFO: Regex = Regex::new(r"^version_info\s*=\s*(\d+\.\d+\.\d+.*)$")
         .expect("error parsing Version_info regex for pyenv_cfg");
+    static ref IMPLEMENTATION: Regex = Regex::new(r"^implementation\s*=\s*(\S+)\s*$")
+        .expect("error parsing Implementation regex for pyenv_cfg");
 }
 
 const PYVENV_CONFIG_FILE: &str = "pyvenv.cfg";
@@ -20,12 +22,11 @@ const PYVENV_CONFIG_FILE: &str = "pyvenv.cfg";
 #[derive(Debug)]
 pub struct PyVenvCfg {
     pub version: String,
+    /// The `implementation` key written by `virtualenv`, e.g. `CPython` or `PyPy`.
+    pub implementation: Option<String>,
 }
 
 impl PyVenvCfg {
-    fn new(version: String) -> Self {
-        Self { version }
-    }
     pub fn find(path: &Path) -> Option<Self> {
         if let Some(ref file) = find(path) {
             parse(file)
@@ -73,20 +74,25 @@ fn find(path: &Path) -> Option<PathBuf> {
 
 fn parse(file: &Path) -> Option<PyVenvCfg> {
     let contents = fs::read_to_string(file).ok()?;
+    let mut version = None;
+    let mut implementation = None;
     for line in contents.lines() {
-        if !line.contains("version") {
+        if implementation.is_none() {
+            if let Some(captures) = IMPLEMENTATION.captures(line) {
+                implementation = captures.get(1).map(|value| value.as_str().to_string());
+            }
+        }
+        if version.is_some() || !line.contains("version") {
             continue;
         }
         if let Some(captures) = VERSION.captures(line) {
-            if let Some(value) = captures.get(1) {
-                return Some(PyVenvCfg::new(value.as_str().to_string()));
-            }
-        }
-        if let Some(captures) = VERSION_INFO.captures(line) {
-            if let Some(value) = captures.get(1) {
-                return Some(PyVenvCfg::new(value.as_str().to_string()));
-            }
+            version = captures.get(1).map(|value| value.as_str().to_string());
+        } else if let Some(captures) = VERSION_INFO.captures(line) {
+            version = captures.get(1).map(|value| value.as_str().to_string());
         }
     }
-    None
+    Some(PyVenvCfg {
+        version: version?,
+        implementation,
+    })
 }
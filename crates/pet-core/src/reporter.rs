@@ -4,9 +4,59 @@
 use crate::{
     manager::EnvManager, python_environment::PythonEnvironment, telemetry::TelemetryEvent,
 };
+use std::sync::Mutex;
 
 pub trait Reporter: Send + Sync {
     fn report_manager(&self, manager: &EnvManager);
     fn report_environment(&self, env: &PythonEnvironment);
     fn report_telemetry(&self, event: &TelemetryEvent);
+    /// Reports a manager (e.g. Poetry, Conda) whose version has changed since it was last reported.
+    /// E.g. after `pip install --upgrade poetry`.
+    fn report_manager_updated(&self, _old: &EnvManager, _new: &EnvManager) {
+        //
+    }
+    /// Reports the progress of a search, e.g. the number of locators that have completed.
+    fn report_progress(&self, _completed: usize, _total: Option<usize>) {
+        //
+    }
+    /// Reports an environment reported in a previous search that no longer exists, e.g. deleted by the user.
+    fn report_removed_environment(&self, _env: &PythonEnvironment) {
+        //
+    }
+    /// Reports an error the caller should know about, e.g. failure to spawn a tool such as Poetry.
+    fn report_error(&self, _message: &str) {
+        //
+    }
+}
+
+/// Accumulates the reported environments (see `Locator::get_environments`), everything else is ignored.
+/// Also re-exported as `pet_reporter::vec::VecReporter`.
+#[derive(Default)]
+pub struct VecReporter {
+    environments: Mutex<Vec<PythonEnvironment>>,
+}
+
+impl VecReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the environments reported so far.
+    pub fn environments(&self) -> Vec<PythonEnvironment> {
+        self.environments.lock().unwrap().clone()
+    }
+    pub fn into_environments(self) -> Vec<PythonEnvironment> {
+        self.environments.into_inner().unwrap()
+    }
+}
+
+impl Reporter for VecReporter {
+    fn report_manager(&self, _manager: &EnvManager) {
+        //
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        self.environments.lock().unwrap().push(env.clone());
+    }
+    fn report_telemetry(&self, _event: &TelemetryEvent) {
+        //
+    }
 }
@@ -2,3 +2,4 @@
 // Licensed under the MIT License.
 
 pub mod path;
+pub mod times;
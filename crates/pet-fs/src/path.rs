@@ -69,6 +69,30 @@ pub fn resolve_symlink<T: AsRef<Path>>(exe: &T) -> Option<PathBuf> {
     }
 }
 
+/// Whether this is an absolute path on any platform (Unix, Windows drive or UNC path).
+/// Useful when parsing the output of tools that can run on another OS (e.g. conda or poetry in WSL).
+pub fn is_absolute_on_any_platform(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'\\' || bytes[2] == b'/'))
+}
+
+/// Returns the sub directories of the given directory, empty if the directory cannot be read.
+pub fn list_dirs(path: &Path) -> Vec<PathBuf> {
+    match std::fs::read_dir(path) {
+        Ok(reader) => reader
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
 pub fn expand_path(path: PathBuf) -> PathBuf {
     if path.starts_with("~") {
         if let Some(ref home) = get_user_home() {
@@ -0,0 +1,56 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Times are returned as nano seconds since the Unix epoch.
+
+/// Gets the modified and created times of a file.
+pub fn get_mtime_ctime(path: &Path) -> Option<(u128, u128)> {
+    let metadata = path.metadata().ok()?;
+    let modified = to_nanos(metadata.modified().ok()?)?;
+    let created = to_nanos(metadata.created().ok()?)?;
+    Some((modified, created))
+}
+
+/// Gets only the modified time of a file.
+/// Cheaper than `get_mtime_ctime` on file systems where the created time is emulated.
+pub fn get_mtime(path: &Path) -> Option<u128> {
+    to_nanos(path.metadata().ok()?.modified().ok()?)
+}
+
+pub fn to_nanos(time: SystemTime) -> Option<u128> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    #[test]
+    fn mtime_is_consistent_with_mtime_ctime() {
+        let file = env::temp_dir().join("pet_fs_times_test.txt");
+        fs::write(&file, "test").unwrap();
+
+        let mtime = get_mtime(&file).unwrap();
+        let (mtime_with_ctime, _) = get_mtime_ctime(&file).unwrap();
+        assert_eq!(mtime, mtime_with_ctime);
+        assert_eq!(
+            Some(mtime),
+            to_nanos(file.metadata().unwrap().modified().unwrap())
+        );
+
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn missing_file_has_no_times() {
+        let file = env::temp_dir().join("pet_fs_times_test_missing.txt");
+        assert_eq!(get_mtime(&file), None);
+        assert_eq!(get_mtime_ctime(&file), None);
+    }
+}
@@ -3,7 +3,10 @@
 
 use pet_conda::utils::is_conda_env;
 use pet_fs::path::{expand_path, norm_case};
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 fn get_global_virtualenv_dirs(
     work_on_home_env_var: Option<String>,
@@ -55,22 +58,18 @@ fn get_global_virtualenv_dirs(
     venv_dirs
 }
 
+/// Lists the directories in the global virtual env locations (possible virtual envs).
+/// `depth` is the number of levels to enumerate, e.g. with a depth of 2 nested layouts
+/// such as `~/.virtualenvs/<project>/<env>` are also found (1 enumerates just `~/.virtualenvs/<env>`).
 pub fn list_global_virtual_envs_paths(
     work_on_home_env_var: Option<String>,
     xdg_data_home: Option<String>,
     user_home: Option<PathBuf>,
+    depth: u32,
 ) -> Vec<PathBuf> {
     let mut python_envs: Vec<PathBuf> = vec![];
     for root_dir in &get_global_virtualenv_dirs(work_on_home_env_var, xdg_data_home, user_home) {
-        if let Ok(dirs) = fs::read_dir(root_dir) {
-            python_envs.append(
-                &mut dirs
-                    .filter_map(Result::ok)
-                    .map(|e| e.path())
-                    .filter(|p| !is_conda_env(p))
-                    .collect(),
-            )
-        }
+        list_possible_envs(root_dir, depth, &mut python_envs);
     }
 
     python_envs.sort();
@@ -78,3 +77,22 @@ pub fn list_global_virtual_envs_paths(
 
     python_envs
 }
+
+fn list_possible_envs(dir: &Path, depth: u32, python_envs: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    if let Ok(dirs) = fs::read_dir(dir) {
+        for path in dirs
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| !is_conda_env(p))
+        {
+            // No need to look inside virtual envs.
+            if depth > 1 && !path.join("bin").is_dir() && !path.join("Scripts").is_dir() {
+                list_possible_envs(&path, depth - 1, python_envs);
+            }
+            python_envs.push(path);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[test]
+fn list_nested_global_virtual_envs() {
+    use pet_global_virtualenvs::list_global_virtual_envs_paths;
+    use std::fs;
+
+    let work_on_home = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_list_nested_global_virtual_envs");
+    let _ = fs::remove_dir_all(&work_on_home);
+    let flat_env = work_on_home.join("flat_env");
+    let project = work_on_home.join("project");
+    let nested_env = project.join("py311");
+    fs::create_dir_all(flat_env.join("bin")).unwrap();
+    fs::create_dir_all(nested_env.join("bin")).unwrap();
+    // Never look inside virtual envs.
+    fs::create_dir_all(flat_env.join("lib")).unwrap();
+
+    let work_on_home_env_var = Some(work_on_home.to_str().unwrap().to_string());
+    assert_eq!(
+        list_global_virtual_envs_paths(work_on_home_env_var.clone(), None, None, 1),
+        vec![flat_env.clone(), project.clone()]
+    );
+    assert_eq!(
+        list_global_virtual_envs_paths(work_on_home_env_var, None, None, 2),
+        vec![flat_env.clone(), project.clone(), nested_env.clone()]
+    );
+
+    let _ = fs::remove_dir_all(&work_on_home);
+}
@@ -0,0 +1,29 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::path::PathBuf;
+
+/// Directory where Hatch stores environments.
+/// See https://hatch.pypa.io/latest/config/hatch/#environments
+pub fn get_env_store(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(dir) = &env_vars.hatch_env_store {
+        return Some(norm_case(PathBuf::from(dir)));
+    }
+    if cfg!(windows) {
+        if let Some(localappdata) = &env_vars.localappdata {
+            return Some(PathBuf::from(localappdata).join("hatch").join("env"));
+        }
+    }
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".local").join("share").join("hatch").join("env"))
+}
+
+/// Directory containing the virtual environments, grouped by project.
+/// E.g. `<env store>/virtual/<project name>/<project id>/<env name>`.
+pub fn get_virtual_envs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    get_env_store(env_vars).map(|store| store.join("virtual"))
+}
@@ -0,0 +1,213 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::{get_env_store, get_virtual_envs_dir};
+use log::trace;
+use manager::find_hatch_executable;
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables, get_prefix_from_executable};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+/// Matrix environments can be nested, e.g. `test/py3.10`.
+const MAX_ENV_DEPTH: usize = 2;
+
+/// Hatch normalizes project names as per PEP 503, e.g. `My_App` & `my-app` are the same.
+fn normalize_project_name(name: &str) -> String {
+    let mut normalized = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c == '-' || c == '_' || c == '.' {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// Name of the project as defined in `pyproject.toml`.
+fn get_project_name(project_dir: &Path) -> Option<String> {
+    let contents = fs::read_to_string(project_dir.join("pyproject.toml")).ok()?;
+    let pyproject = contents.parse::<toml::Value>().ok()?;
+    Some(pyproject.get("project")?.get("name")?.as_str()?.to_string())
+}
+
+/// Ignores directories such as `.pythons` where Hatch installs Python.
+fn list_env_dirs(path: &Path) -> Vec<PathBuf> {
+    list_dirs(path)
+        .into_iter()
+        .filter(|p| {
+            !p.file_name()
+                .map(|name| name.to_string_lossy().starts_with('.'))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Finds the virtual environments in the directory of a project.
+fn find_venvs(dir: &Path, depth: usize, venvs: &mut Vec<PathBuf>) {
+    for path in list_env_dirs(dir) {
+        if path.join("pyvenv.cfg").is_file() {
+            venvs.push(path);
+        } else if depth < MAX_ENV_DEPTH {
+            find_venvs(&path, depth + 1, venvs);
+        }
+    }
+}
+
+pub struct Hatch {
+    pub env_vars: EnvVariables,
+    hatch_executable: Arc<Mutex<Option<PathBuf>>>,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Hatch {
+    pub fn from(environment: &dyn Environment) -> Hatch {
+        Hatch {
+            env_vars: EnvVariables::from(environment),
+            hatch_executable: Arc::new(Mutex::new(None)),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = self.hatch_executable.lock().unwrap().clone();
+        let executable = find_hatch_executable(executable, &self.env_vars)?;
+        let mut manager = EnvManager::new(executable, EnvManagerType::Hatch, None);
+        manager.install_path = get_env_store(&self.env_vars);
+        Some(manager)
+    }
+    /// The workspace directory of the project, Hatch groups the environments by project name.
+    fn get_project(&self, project_name: &str) -> Option<PathBuf> {
+        let project_name = normalize_project_name(project_name);
+        self.workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| {
+                get_project_name(dir)
+                    .map(|name| normalize_project_name(&name) == project_name)
+                    .unwrap_or_default()
+            })
+            .cloned()
+    }
+    /// Environments are stored in `<virtual envs dir>/<project name>/<project id>/<env name>`.
+    fn get_environment(
+        &self,
+        virtual_envs_dir: &Path,
+        prefix: &Path,
+        executable: Option<PathBuf>,
+        manager: &Option<EnvManager>,
+    ) -> Option<PythonEnvironment> {
+        let components = prefix
+            .strip_prefix(virtual_envs_dir)
+            .ok()?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        if components.len() < 3 {
+            return None;
+        }
+        let executable = executable.or_else(|| find_executable(prefix))?;
+        Some(
+            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Hatch))
+                .source_locator(Some(LocatorKind::Hatch.name()))
+                .name(Some(components[2..].join("/")))
+                .executable(Some(executable))
+                .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+                .prefix(Some(prefix.to_path_buf()))
+                .project(self.get_project(&components[0]))
+                .manager(manager.clone())
+                .symlinks(Some(find_executables(prefix)))
+                .build(),
+        )
+    }
+}
+
+impl Locator for Hatch {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Hatch
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by Hatch, reads HATCH_ENV_STORE and the Hatch data directory"
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+        if let Some(exe) = &config.hatch_executable {
+            self.hatch_executable.lock().unwrap().replace(exe.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("hatch") | Some("hatch.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Hatch]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let virtual_envs_dir = get_virtual_envs_dir(&self.env_vars)?;
+        let prefix = env
+            .prefix
+            .clone()
+            .or_else(|| get_prefix_from_executable(&env.executable))?;
+        if !prefix.starts_with(&virtual_envs_dir) || !prefix.join("pyvenv.cfg").is_file() {
+            return None;
+        }
+        self.get_environment(
+            &virtual_envs_dir,
+            &prefix,
+            Some(env.executable.clone()),
+            &self.get_manager(),
+        )
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+        let virtual_envs_dir = match get_virtual_envs_dir(&self.env_vars) {
+            Some(dir) => dir,
+            None => return,
+        };
+        trace!("Searching for Hatch environments in {:?}", virtual_envs_dir);
+        for project_dir in list_env_dirs(&virtual_envs_dir) {
+            for project_id_dir in list_env_dirs(&project_dir) {
+                let mut venvs = vec![];
+                find_venvs(&project_id_dir, 1, &mut venvs);
+                for prefix in venvs {
+                    if let Some(env) =
+                        self.get_environment(&virtual_envs_dir, &prefix, None, &manager)
+                    {
+                        reporter.report_environment(&env);
+                    }
+                }
+            }
+        }
+    }
+}
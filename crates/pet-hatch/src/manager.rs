@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::trace;
+use std::{env, path::PathBuf};
+
+/// Looks for the hatch executable in the PATH variable & the default install locations.
+pub fn find_hatch_executable(
+    executable: Option<PathBuf>,
+    env_vars: &EnvVariables,
+) -> Option<PathBuf> {
+    if let Some(executable) = executable {
+        if executable.is_file() {
+            return Some(executable);
+        }
+    }
+    let mut search_paths = vec![];
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        // Installed using pipx.
+        search_paths.push(home.join(".local").join("bin"));
+    }
+    let names = if cfg!(windows) {
+        vec!["hatch.exe", "hatch"]
+    } else {
+        vec!["hatch"]
+    };
+    for dir in search_paths {
+        for name in names.iter() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("Hatch exe not found");
+    None
+}
@@ -0,0 +1,165 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_hatch_matrix_environments() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_hatch::Hatch;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path!(&["unix", "user_home"]);
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()));
+    let locator = Hatch::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    // Hatch is not installed.
+    assert!(result.managers.is_empty());
+    let project_id_dir = home
+        .join(".local")
+        .join("share")
+        .join("hatch")
+        .join("env")
+        .join("virtual")
+        .join("my-app")
+        .join("Vn7sHnXW");
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Hatch));
+            // Project name in pyproject.toml is `My_App`.
+            assert_eq!(env.project, Some(project.clone()));
+            (
+                env.name.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.prefix.clone().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            (
+                "default/py3.11".to_string(),
+                "3.11.9".to_string(),
+                project_id_dir.join("default").join("py3.11")
+            ),
+            (
+                "lint".to_string(),
+                "3.12.1".to_string(),
+                project_id_dir.join("lint")
+            ),
+            (
+                "test/py3.10".to_string(),
+                "3.10.14".to_string(),
+                project_id_dir.join("test").join("py3.10")
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn find_hatch_environments_in_custom_env_store() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_hatch::Hatch;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let env_store = resolve_test_path!(&["unix", "env_store"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "HATCH_ENV_STORE".to_string(),
+            env_store.to_str().unwrap().to_string(),
+        )]),
+        Some(resolve_test_path!(&["unix", "user_home"])),
+    );
+    let locator = Hatch::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let prefix = env_store
+        .join("virtual")
+        .join("other")
+        .join("AbCdEf12")
+        .join("default");
+    let environments = reporter.to_locator_result().environments;
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].name, Some("default".to_string()));
+    assert_eq!(environments[0].prefix, Some(prefix.clone()));
+    assert_eq!(environments[0].project, None);
+
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Hatch));
+    assert_eq!(env.name, Some("default".to_string()));
+    assert_eq!(env.version, "3.12.1".parse().ok());
+}
+
+#[cfg(unix)]
+#[test]
+fn environments_outside_env_store_are_not_hatch_environments() {
+    use pet_core::{env::PythonEnv, Locator};
+    use pet_hatch::Hatch;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(
+        HashMap::new(),
+        Some(resolve_test_path!(&["unix", "user_home"])),
+    );
+    let locator = Hatch::from(&environment);
+    let prefix = resolve_test_path!(&[
+        "unix",
+        "env_store",
+        "virtual",
+        "other",
+        "AbCdEf12",
+        "default",
+    ]);
+
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            Some(prefix),
+            None
+        ))
+        .is_none());
+}
+
+#[test]
+fn manages_hatch_executables() {
+    use pet_core::Locator;
+    use pet_hatch::Hatch;
+    use pet_python_utils::test_utils::create_test_environment;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Hatch::from(&environment);
+
+    assert!(locator.manages_executable(&PathBuf::from("/home/user/.local/bin/hatch")));
+    assert!(locator.manages_executable(&PathBuf::from("hatch.exe")));
+    assert!(!locator.manages_executable(&PathBuf::from("/home/user/.local/bin/python")));
+}
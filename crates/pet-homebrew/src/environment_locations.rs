@@ -3,8 +3,10 @@
 
 use crate::env_variables::EnvVariables;
 use lazy_static::lazy_static;
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::find_executables;
 use regex::Regex;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 lazy_static! {
     static ref PYTHON_VERSION: Regex =
@@ -21,30 +23,87 @@ lazy_static! {
 //     None
 // }
 
-pub fn get_homebrew_prefix_bin(env_vars: &EnvVariables) -> Vec<PathBuf> {
-    // Homebrew install folders documented here https://docs.brew.sh/Installation
-    // /opt/homebrew for Apple Silicon,
-    // /usr/local for macOS Intel
-    // /home/linuxbrew/.linuxbrew for Linux
+/// Homebrew install folders documented here https://docs.brew.sh/Installation
+/// - macOS ARM: /opt/homebrew
+/// - macOS Intel: /usr/local
+/// - Linux: /home/linuxbrew/.linuxbrew
+pub fn get_homebrew_prefixes(env_vars: &EnvVariables) -> Vec<PathBuf> {
     // If user has rosetta enabled, then its possible we have homebrew installed via rosetta as well as apple silicon
     // I.e. we can have multiple home brews on the same machine, hence search all,
-    let mut homebrew_prefixes = [
-        "/home/linuxbrew/.linuxbrew/bin",
-        "/opt/homebrew/bin",
-        "/usr/local/bin",
-    ]
-    .iter()
-    .map(PathBuf::from)
-    .filter(|p| p.exists())
-    .collect::<Vec<PathBuf>>();
+    let mut homebrew_prefixes = ["/home/linuxbrew/.linuxbrew", "/opt/homebrew", "/usr/local"]
+        .iter()
+        .map(PathBuf::from)
+        .collect::<Vec<PathBuf>>();
 
     // Check the environment variables
     if let Some(homebrew_prefix) = &env_vars.homebrew_prefix {
-        let homebrew_prefix_bin = PathBuf::from(homebrew_prefix).join("bin");
-        if homebrew_prefix_bin.exists() && !homebrew_prefixes.contains(&homebrew_prefix_bin) {
-            homebrew_prefixes.push(homebrew_prefix_bin);
+        let homebrew_prefix = PathBuf::from(homebrew_prefix);
+        if !homebrew_prefixes.contains(&homebrew_prefix) {
+            homebrew_prefixes.push(homebrew_prefix);
         }
     }
 
     homebrew_prefixes
 }
+
+pub fn get_homebrew_prefix_bin(env_vars: &EnvVariables) -> Vec<PathBuf> {
+    get_homebrew_prefixes(env_vars)
+        .iter()
+        .map(|prefix| prefix.join("bin"))
+        .filter(|p| p.exists())
+        .collect()
+}
+
+/// Python executables in the Cellar, i.e. `<prefix>/Cellar/python@X.Y/X.Y.Z/bin/pythonX.Y`.
+/// Kegs that are not linked into `<prefix>/bin` (e.g. older versions) can only be found here.
+pub fn get_cellar_python_executables(homebrew_prefix: &Path) -> Vec<PathBuf> {
+    let mut executables = vec![];
+    for formula in list_dirs(&homebrew_prefix.join("Cellar")) {
+        if !formula
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .starts_with("python@")
+        {
+            continue;
+        }
+        for version in list_dirs(&formula) {
+            executables.extend(find_executables(version.join("bin")));
+        }
+    }
+    executables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_env_variables(homebrew_prefix: &str) -> EnvVariables {
+        EnvVariables {
+            home: None,
+            root: None,
+            path: None,
+            homebrew_prefix: Some(homebrew_prefix.to_string()),
+            known_global_search_locations: vec![],
+        }
+    }
+
+    #[test]
+    fn search_default_and_custom_homebrew_prefixes() {
+        let prefixes = get_homebrew_prefixes(&create_env_variables("/custom/homebrew"));
+
+        assert_eq!(
+            prefixes,
+            vec![
+                PathBuf::from("/home/linuxbrew/.linuxbrew"),
+                PathBuf::from("/opt/homebrew"),
+                PathBuf::from("/usr/local"),
+                PathBuf::from("/custom/homebrew"),
+            ]
+        );
+        assert_eq!(
+            get_homebrew_prefixes(&create_env_variables("/opt/homebrew")).len(),
+            3
+        );
+    }
+}
@@ -4,8 +4,9 @@
 use crate::sym_links::get_known_symlinks;
 use lazy_static::lazy_static;
 use log::trace;
-use pet_core::python_environment::{
-    PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind,
+use pet_core::{
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    LocatorKind,
 };
 use pet_fs::path::resolve_symlink;
 use regex::Regex;
@@ -52,8 +53,9 @@ pub fn get_python_info(
     symlinks.dedup();
 
     let env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Homebrew))
+        .source_locator(Some(LocatorKind::Homebrew.name()))
         .executable(Some(python_exe_from_bin_dir.to_path_buf()))
-        .version(version)
+        .version(version.and_then(|v| v.parse().ok()))
         .prefix(get_prefix(resolved_exe))
         .symlinks(Some(symlinks))
         .build();
@@ -2,7 +2,9 @@
 // Licensed under the MIT License.
 
 use env_variables::EnvVariables;
-use environment_locations::get_homebrew_prefix_bin;
+use environment_locations::{
+    get_cellar_python_executables, get_homebrew_prefix_bin, get_homebrew_prefixes,
+};
 use environments::get_python_info;
 use pet_conda::utils::is_conda_env;
 use pet_core::{
@@ -15,7 +17,13 @@ use pet_core::{
 use pet_fs::path::resolve_symlink;
 use pet_python_utils::executable::find_executables;
 use pet_virtualenv::is_virtualenv;
-use std::{fs, path::PathBuf, thread};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+};
 use sym_links::is_homebrew_python;
 
 mod env_variables;
@@ -35,12 +43,25 @@ impl Homebrew {
     }
 }
 
+/// The executable in the Homebrew bin directory, if the keg has been linked.
+/// Kegs that are not linked (e.g. older versions) are only available in the Cellar.
+fn get_bin_executable(bin: &Path, exe_file_name: &std::ffi::OsStr, exe: &Path) -> PathBuf {
+    let bin_exe = bin.join(exe_file_name);
+    if bin_exe.exists() {
+        bin_exe
+    } else {
+        exe.to_path_buf()
+    }
+}
+
 /// Deafult prefix paths for Homebrew
 /// Below are from the docs `man brew`      Display Homebrew’s install path. Default:
 /// - macOS ARM: /opt/homebrew
 /// - macOS Intel: /usr/local
 /// - Linux: /home/linuxbrew/.linuxbrew
-fn from(env: &PythonEnv) -> Option<PythonEnvironment> {
+///
+/// Other prefixes (e.g. `HOMEBREW_PREFIX`) are only checked for Python in their Cellar.
+fn from(env: &PythonEnv, homebrew_prefixes: &[PathBuf]) -> Option<PythonEnvironment> {
     // Assume we create a virtual env from a homebrew python install,
     // Then the exe in the virtual env bin will be a symlink to the homebrew python install.
     // Hence the first part of the condition will be true, but the second part will be false.
@@ -75,7 +96,11 @@ fn from(env: &PythonEnv) -> Option<PythonEnvironment> {
     // Sometimes a symlink points to another symlink, and we need to resolve it to get the real exe.
     // And for some reason even though they are symlinks, they are not resolved by `resolve_symlink`.
     if let Some(resolved) = resolve_symlink(&exe).or(fs::canonicalize(&exe).ok()) {
-        if is_homebrew_python(&resolved) {
+        if is_homebrew_python(&resolved)
+            || homebrew_prefixes
+                .iter()
+                .any(|prefix| resolved.starts_with(prefix.join("Cellar")))
+        {
             resolved_file = resolved;
         }
     }
@@ -96,7 +121,7 @@ fn from(env: &PythonEnv) -> Option<PythonEnvironment> {
         // Symlink  - /opt/homebrew/Frameworks/Python.framework/Versions/Current/bin/python3.12
         // SysPrefix- /opt/homebrew/opt/python@3.12/Frameworks/Python.framework/Versions/3.12
         get_python_info(
-            &PathBuf::from("/opt/homebrew/bin").join(exe_file_name),
+            &get_bin_executable(Path::new("/opt/homebrew/bin"), exe_file_name, &exe),
             &resolved_file,
         )
     } else if resolved_file.starts_with("/home/linuxbrew/.linuxbrew") {
@@ -106,7 +131,11 @@ fn from(env: &PythonEnv) -> Option<PythonEnvironment> {
         // SysPrefix- /home/linuxbrew/.linuxbrew/Cellar/python@3.12/3.12.3
 
         get_python_info(
-            &PathBuf::from("/home/linuxbrew/.linuxbrew/bin").join(exe_file_name),
+            &get_bin_executable(
+                Path::new("/home/linuxbrew/.linuxbrew/bin"),
+                exe_file_name,
+                &exe,
+            ),
             &resolved_file,
         )
     } else if resolved_file.starts_with("/usr/local/Cellar") {
@@ -116,7 +145,17 @@ fn from(env: &PythonEnv) -> Option<PythonEnvironment> {
         // Real exe - /usr/local/Cellar/python@3.8/3.8.20/Frameworks/Python.framework/Versions/3.8/bin/python3.8
         // SysPrefix- /usr/local/Cellar/python@3.8/3.8.20/Frameworks/Python.framework/Versions/3.8
         get_python_info(
-            &PathBuf::from("/usr/local/bin").join(exe_file_name),
+            &get_bin_executable(Path::new("/usr/local/bin"), exe_file_name, &exe),
+            &resolved_file,
+        )
+    } else if let Some(prefix) = homebrew_prefixes
+        .iter()
+        .find(|prefix| resolved_file.starts_with(prefix.join("Cellar")))
+    {
+        // Custom prefix, e.g. `HOMEBREW_PREFIX=/opt/brew`
+        // Real exe - /opt/brew/Cellar/python@3.12/3.12.3/bin/python3.12
+        get_python_info(
+            &get_bin_executable(&prefix.join("bin"), exe_file_name, &exe),
             &resolved_file,
         )
     } else {
@@ -128,19 +167,34 @@ impl Locator for Homebrew {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::Homebrew
     }
+    fn description(&self) -> &'static str {
+        "Finds Python installed by Homebrew, reads HOMEBREW_PREFIX, /opt/homebrew, /usr/local and /home/linuxbrew/.linuxbrew"
+    }
+    fn priority(&self) -> u8 {
+        250
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::Homebrew]
     }
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
-        from(env)
+        from(env, &get_homebrew_prefixes(&self.environment))
     }
 
     fn find(&self, reporter: &dyn Reporter) {
-        let homebrew_prefix_bins = get_homebrew_prefix_bin(&self.environment);
-        thread::scope(|s| {
-            for homebrew_prefix_bin in &homebrew_prefix_bins {
-                let homebrew_python_exes = find_executables(homebrew_prefix_bin);
-                for file in homebrew_python_exes.iter().filter(|f| {
+        let homebrew_prefixes = get_homebrew_prefixes(&self.environment);
+        // Look in the bin directories first, so that linked kegs are reported with the executable in the bin directory,
+        // Then look in the Cellar for kegs that have not been linked.
+        let mut python_exes = vec![];
+        for homebrew_prefix_bin in get_homebrew_prefix_bin(&self.environment) {
+            python_exes.push(find_executables(homebrew_prefix_bin));
+        }
+        for homebrew_prefix in &homebrew_prefixes {
+            python_exes.push(get_cellar_python_executables(homebrew_prefix));
+        }
+        let reported = Mutex::new(HashSet::new());
+        for python_exes in python_exes {
+            thread::scope(|s| {
+                for file in python_exes.iter().filter(|f| {
                     let file_name = f
                         .file_name()
                         .unwrap_or_default()
@@ -155,18 +209,23 @@ impl Locator for Homebrew {
                         && file_name != "python"
                 }) {
                     let file = file.clone();
+                    let homebrew_prefixes = &homebrew_prefixes;
+                    let reported = &reported;
                     s.spawn(move || {
                         // Sometimes we end up with other python installs in the Homebrew bin directory.
                         // E.g. /usr/local/bin is treated as a location where homebrew can be found (homebrew bin)
                         // However this is a very generic location, and we might end up with other python installs here.
                         // Hence call `resolve` to correctly identify homebrew python installs.
                         let env_to_resolve = PythonEnv::new(file.clone(), None, None);
-                        if let Some(env) = from(&env_to_resolve) {
-                            reporter.report_environment(&env);
+                        if let Some(env) = from(&env_to_resolve, homebrew_prefixes) {
+                            // The exes in the Cellar of linked kegs have already been reported via the bin directory.
+                            if reported.lock().unwrap().insert(env.executable.clone()) {
+                                reporter.report_environment(&env);
+                            }
                         }
                     });
                 }
-            }
-        });
+            });
+        }
     }
 }
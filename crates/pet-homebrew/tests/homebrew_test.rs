@@ -0,0 +1,100 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_linked_and_unlinked_kegs_in_custom_prefix() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Locator};
+    use pet_homebrew::Homebrew;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let homebrew_prefix = resolve_test_path!(&["unix", "homebrew"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "HOMEBREW_PREFIX".to_string(),
+            homebrew_prefix.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Homebrew::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    // Ignore Homebrew installed on this machine.
+    let mut environments = reporter
+        .to_locator_result()
+        .environments
+        .iter()
+        .filter(|env| {
+            env.executable
+                .clone()
+                .unwrap_or_default()
+                .starts_with(&homebrew_prefix)
+        })
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Homebrew));
+            (
+                env.executable.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            (
+                homebrew_prefix.join("Cellar/python@3.10/3.10.14/bin/python3.10"),
+                "3.10.14".to_string()
+            ),
+            // The shortest of the symlinks is reported as the executable.
+            (homebrew_prefix.join("bin/python3"), "3.11.9".to_string()),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_executable_in_custom_prefix() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_homebrew::Homebrew;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let homebrew_prefix = resolve_test_path!(&["unix", "homebrew"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "HOMEBREW_PREFIX".to_string(),
+            homebrew_prefix.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Homebrew::from(&environment);
+
+    let env = locator
+        .try_from(&PythonEnv::new(
+            homebrew_prefix.join("bin/python3.11"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Homebrew));
+    assert_eq!(env.executable, Some(homebrew_prefix.join("bin/python3")));
+    assert_eq!(env.version, "3.11.9".parse().ok());
+    assert!(env
+        .symlinks
+        .unwrap_or_default()
+        .contains(&homebrew_prefix.join("Cellar/python@3.11/3.11.9/bin/python3.11")));
+
+    // Not in the Cellar of any Homebrew prefix.
+    let other = resolve_test_path!(&["unix", "python3.11"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(other, None, None))
+        .is_none());
+}
@@ -2,10 +2,27 @@
 // Licensed under the MIT License.
 
 use serde::{Deserialize, Serialize};
-use std::io::{self, Write};
+use std::{
+    collections::BTreeSet,
+    io::{self, Write},
+    sync::Mutex,
+};
 
 pub mod server;
 
+/// Error code sent when a request is received with the id of a request that is still being processed.
+pub const DUPLICATE_REQUEST_ID: i32 = -32000;
+
+/// Error code sent when a request could not be completed in the time allotted for it.
+pub const REQUEST_TIMEOUT: i32 = -32001;
+
+/// Error code sent when a request has been cancelled by the client (`$/cancelRequest`).
+pub const REQUEST_CANCELLED: i32 = -32800;
+
+/// Ids of the requests that have not yet been replied to.
+/// Request ids must be unique within a session, else replies would be ambiguous.
+static PENDING_REQUESTS: Mutex<BTreeSet<u32>> = Mutex::new(BTreeSet::new());
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[derive(Debug)]
@@ -35,21 +52,36 @@ pub fn send_reply<T: serde::Serialize>(id: u32, payload: Option<T>) {
         "result": payload,
         "id": id
     });
-    let message = serde_json::to_string(&payload).unwrap();
-    print!(
-        "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}",
-        message.len(),
-        message
-    );
-    let _ = io::stdout().flush();
+    complete_request(id);
+    send_payload(payload);
 }
 
 pub fn send_error(id: Option<u32>, code: i32, message: String) {
+    if let Some(id) = id {
+        complete_request(id);
+    }
+    send_error_payload(id, code, message);
+}
+
+/// Keeps track of the request, returns false if there's already a pending request with the same id.
+fn start_request(id: u32) -> bool {
+    PENDING_REQUESTS.lock().unwrap().insert(id)
+}
+
+fn complete_request(id: u32) {
+    PENDING_REQUESTS.lock().unwrap().remove(&id);
+}
+
+fn send_error_payload(id: Option<u32>, code: i32, message: String) {
     let payload = serde_json::json!({
         "jsonrpc": "2.0",
         "error": { "code": code, "message": message },
         "id": id
     });
+    send_payload(payload);
+}
+
+fn send_payload(payload: serde_json::Value) {
     let message = serde_json::to_string(&payload).unwrap();
     print!(
         "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}",
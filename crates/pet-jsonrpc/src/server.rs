@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::send_error;
+use crate::{send_error, send_error_payload, start_request, DUPLICATE_REQUEST_ID};
 use serde_json::{self, Value};
 use std::{
     collections::HashMap,
@@ -55,6 +55,16 @@ impl<C> HandlersKeyedByMethodName<C> {
         match message["method"].as_str() {
             Some(method) => {
                 if let Some(id) = message["id"].as_u64() {
+                    if !start_request(id as u32) {
+                        eprint!("Duplicate request id {id} for method: {method}");
+                        // Do not complete the pending request with the same id.
+                        send_error_payload(
+                            Some(id as u32),
+                            DUPLICATE_REQUEST_ID,
+                            format!("Request id {id} is already in use by a pending request"),
+                        );
+                        return;
+                    }
                     if let Some(handler) = self.requests.get(method) {
                         handler(self.context.clone(), id as u32, message["params"].clone());
                     } else {
@@ -150,3 +160,30 @@ fn get_content_length(line: &str) -> Result<usize, String> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::send_reply;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[test]
+    fn duplicate_request_ids_are_not_processed() {
+        let mut handlers = HandlersKeyedByMethodName::new(Arc::new(Mutex::new(vec![])));
+        // Handler does not reply, hence the request remains pending.
+        handlers.add_request_handler("refresh", |context: Arc<Mutex<Vec<u32>>>, id, _params| {
+            context.lock().unwrap().push(id);
+        });
+        let request = json!({"jsonrpc": "2.0", "id": 4242, "method": "refresh"});
+
+        handlers.handle_request(request.clone());
+        handlers.handle_request(request.clone());
+        assert_eq!(*handlers.context.lock().unwrap(), vec![4242]);
+
+        // Once replied to, the id can be used again.
+        send_reply(4242, None::<()>);
+        handlers.handle_request(request);
+        assert_eq!(*handlers.context.lock().unwrap(), vec![4242, 4242]);
+    }
+}
@@ -56,6 +56,12 @@ impl Locator for LinuxGlobalPython {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::LinuxGlobal
     }
+    fn description(&self) -> &'static str {
+        "Finds Python installed globally on Linux in /bin, /usr/bin and /usr/local/bin"
+    }
+    fn priority(&self) -> u8 {
+        250
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::LinuxGlobal]
     }
@@ -209,8 +215,9 @@ fn get_python_in_bin(env: &PythonEnv, is_64bit: bool) -> Option<PythonEnvironmen
 
     Some(
         PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::LinuxGlobal))
+            .source_locator(Some(LocatorKind::LinuxGlobal.name()))
             .executable(Some(executable))
-            .version(env.version.clone())
+            .version(env.version.clone().and_then(|v| v.parse().ok()))
             .arch(if is_64bit {
                 Some(Architecture::X64)
             } else {
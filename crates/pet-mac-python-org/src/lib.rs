@@ -12,7 +12,54 @@ use pet_python_utils::executable::find_executables;
 use pet_python_utils::version;
 use pet_virtualenv::is_virtualenv;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Python installed using the installer from python.org.
+const PYTHON_ORG_FRAMEWORK_VERSIONS: &str = "/Library/Frameworks/Python.framework/Versions";
+/// Python shipped with older versions of macOS (removed in macOS 12.3).
+const SYSTEM_FRAMEWORK_VERSIONS: &str = "/System/Library/Frameworks/Python.framework/Versions";
+
+/// Gets the version from the name of the framework directory, e.g. `3.12` for `.../Versions/3.12`.
+/// This is only the major & minor version, hence only used when the header files are not available.
+pub fn get_version_from_framework_dir(prefix: &Path) -> Option<String> {
+    let name = prefix.file_name()?.to_str()?;
+    let (major, minor) = name.split_once('.')?;
+    if !major.is_empty()
+        && !minor.is_empty()
+        && major
+            .chars()
+            .chain(minor.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+/// Finds the executables in the given directories that are symlinks to one of the given executables.
+/// E.g. the python.org installer creates symlinks in `/usr/local/bin`.
+pub fn find_symlinks(bin_dirs: &[PathBuf], executables: &[PathBuf]) -> Vec<PathBuf> {
+    let mut symlinks = vec![];
+    for bin in bin_dirs {
+        for file in find_executables(bin) {
+            if let Some(symlink) = resolve_symlink(&file) {
+                if executables.contains(&symlink) {
+                    symlinks.push(file);
+                }
+            }
+        }
+    }
+    symlinks
+}
+
+fn get_kind(versions_dir: &str) -> PythonEnvironmentKind {
+    if versions_dir == SYSTEM_FRAMEWORK_VERSIONS {
+        PythonEnvironmentKind::MacOsFramework
+    } else {
+        PythonEnvironmentKind::MacPythonOrg
+    }
+}
 
 pub struct MacPythonOrg {}
 
@@ -30,8 +77,17 @@ impl Locator for MacPythonOrg {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::MacPythonOrg
     }
+    fn description(&self) -> &'static str {
+        "Finds Python installed from python.org on macOS in /Library/Frameworks/Python.framework"
+    }
+    fn priority(&self) -> u8 {
+        250
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
-        vec![PythonEnvironmentKind::MacPythonOrg]
+        vec![
+            PythonEnvironmentKind::MacPythonOrg,
+            PythonEnvironmentKind::MacOsFramework,
+        ]
     }
 
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
@@ -46,46 +102,45 @@ impl Locator for MacPythonOrg {
         }
 
         let mut executable = resolve_symlink(&env.executable).unwrap_or(env.executable.clone());
-        if !executable
-            .to_string_lossy()
-            .starts_with("/Library/Frameworks/Python.framework/Versions/")
-        {
-            return None;
-        }
+        let versions_dir = [PYTHON_ORG_FRAMEWORK_VERSIONS, SYSTEM_FRAMEWORK_VERSIONS]
+            .into_iter()
+            .find(|dir| executable.starts_with(dir))?;
+        let current = Path::new(versions_dir).join("Current");
+        let current_bin = current.join("bin");
 
         let mut version_is_current = false;
         let mut symlinks = vec![executable.clone(), env.executable.clone()];
-        if executable.starts_with("/Library/Frameworks/Python.framework/Versions/Current") {
+        // The system framework only has Python 2.7, which does not have a `python3` exe.
+        let exe_to_resolve = [current_bin.join("python3"), current_bin.join("python")]
+            .into_iter()
+            .find(|exe| exe.exists())
+            .unwrap_or(current_bin.join("python3"));
+        if executable.starts_with(&current) {
             // This is a symlink to the python executable, lets resolve it
-            let exe_to_resolve =
-                "/Library/Frameworks/Python.framework/Versions/Current/bin/python3";
             if let Some(exe) = resolve_symlink(&exe_to_resolve) {
-                if exe.starts_with("/Library/Frameworks/Python.framework/Versions")
-                    && !exe.starts_with("/Library/Frameworks/Python.framework/Versions/Current")
-                {
+                if exe.starts_with(versions_dir) && !exe.starts_with(&current) {
                     // Given that the exe we were given is the `Current/bin/python`, we know this is current.
                     version_is_current = true;
                     symlinks.push(exe.clone());
-                    symlinks.push(PathBuf::from(exe_to_resolve));
+                    symlinks.push(exe_to_resolve.clone());
                     executable = exe;
                 }
             }
         } else {
             // Check if this is the current version.
-            let exe_to_resolve =
-                "/Library/Frameworks/Python.framework/Versions/Current/bin/python3";
             if let Some(exe) = resolve_symlink(&exe_to_resolve) {
                 if exe == executable {
                     // Yes, this is the current version
                     version_is_current = true;
                     symlinks.push(exe.clone());
-                    symlinks.push(PathBuf::from(exe_to_resolve));
+                    symlinks.push(exe_to_resolve.clone());
                 }
             }
         }
 
         let prefix = executable.parent()?.parent()?;
-        let version = version::from_header_files(prefix)?;
+        let version = version::from_header_files(prefix)
+            .or_else(|| get_version_from_framework_dir(prefix))?;
 
         // Also look for other python* files in the same directory as the above executable
         for exe in find_executables(executable.parent()?) {
@@ -99,39 +154,27 @@ impl Locator for MacPythonOrg {
             }
         }
 
+        // If we're looking in the `Current/bin`, then no need to resolve symlinks
+        // As we already know this is the current version.
+        // Note: We can resolve the symlink for /Library/Frameworks/Python.framework/Versions/Current/bin/python3
+        // However in rust for some reason we cannot resolve the symlink for /Library/Frameworks/Python.framework/Versions/Current/bin/python3.10
+        if version_is_current {
+            symlinks.extend(find_executables(&current_bin));
+        }
         // We know files in /usr/local/bin & /Library/Frameworks/Python.framework/Versions/Current/bin end up being symlinks to this python exe as well
         // Documented here https://docs.python.org/3/using/mac.html
         // Hence look for those symlinks as well.
-        for bin in [
-            PathBuf::from("/usr/local/bin"),
-            PathBuf::from("/Library/Frameworks/Python.framework/Versions/Current/bin"),
-        ] {
-            for file in find_executables(&bin) {
-                // If we're looking in the `Current/bin`, then no need to resolve symlinks
-                // As we already know this is the current version.
-                // Note: We can resolve the symlink for /Library/Frameworks/Python.framework/Versions/Current/bin/python3
-                // However in rust for some reason we cannot resolve the symlink for /Library/Frameworks/Python.framework/Versions/Current/bin/python3.10
-                if version_is_current
-                    && file.starts_with("/Library/Frameworks/Python.framework/Versions/Current/bin")
-                {
-                    symlinks.push(file);
-                    continue;
-                }
-                if let Some(symlink) = resolve_symlink(&file) {
-                    if symlinks.contains(&symlink) {
-                        symlinks.push(file);
-                    }
-                }
-            }
-        }
+        let found = find_symlinks(&[PathBuf::from("/usr/local/bin"), current_bin], &symlinks);
+        symlinks.extend(found);
 
         symlinks.sort();
         symlinks.dedup();
 
         Some(
-            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::MacPythonOrg))
+            PythonEnvironmentBuilder::new(Some(get_kind(versions_dir)))
+                .source_locator(Some(LocatorKind::MacPythonOrg.name()))
                 .executable(Some(executable.clone()))
-                .version(Some(version))
+                .version(version.parse().ok())
                 .prefix(Some(prefix.to_path_buf()))
                 .symlinks(Some(symlinks))
                 .build(),
@@ -143,19 +186,29 @@ impl Locator for MacPythonOrg {
             return;
         }
 
-        if let Ok(reader) = fs::read_dir("/Library/Frameworks/Python.framework/Versions/") {
+        for versions_dir in [PYTHON_ORG_FRAMEWORK_VERSIONS, SYSTEM_FRAMEWORK_VERSIONS] {
+            let reader = match fs::read_dir(versions_dir) {
+                Ok(reader) => reader,
+                Err(_) => continue,
+            };
             for file in reader.filter_map(Result::ok) {
                 let prefix = file.path();
-                // Ignore the `/Library/Frameworks/Python.framework/Versions/Current` folder, as this only contains symlinks to the actual python installations
+                // Ignore the `Versions/Current` folder, as this only contains symlinks to the actual python installations
                 // We will account for the symlinks in these folder later
-                if prefix
-                    .to_string_lossy()
-                    .starts_with("/Library/Frameworks/Python.framework/Versions/Current")
-                {
+                if prefix.starts_with(Path::new(versions_dir).join("Current")) {
                     continue;
                 }
 
-                let executable = prefix.join("bin").join("python3");
+                let executable = match [
+                    prefix.join("bin").join("python3"),
+                    prefix.join("bin").join("python"),
+                ]
+                .into_iter()
+                .find(|exe| exe.exists())
+                {
+                    Some(executable) => executable,
+                    None => continue,
+                };
                 let version = version::from_header_files(&prefix);
 
                 if let Some(env) = self.try_from(&PythonEnv::new(executable, Some(prefix), version))
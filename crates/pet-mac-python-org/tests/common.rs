@@ -0,0 +1,13 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+pub fn resolve_test_path(paths: &[&str]) -> PathBuf {
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+
+    paths.iter().for_each(|p| root.push(p));
+
+    root
+}
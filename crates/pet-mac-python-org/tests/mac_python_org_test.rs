@@ -0,0 +1,99 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+mod common;
+
+#[cfg(unix)]
+#[test]
+fn find_symlinks_to_framework_python() {
+    use common::resolve_test_path;
+    use pet_mac_python_org::find_symlinks;
+    use std::fs;
+
+    let framework_exe = fs::canonicalize(resolve_test_path(&[
+        "unix",
+        "Library",
+        "Frameworks",
+        "Python.framework",
+        "Versions",
+        "3.12",
+        "bin",
+        "python3.12",
+    ]))
+    .unwrap();
+    let usr_local_bin = resolve_test_path(&["unix", "usr", "local", "bin"]);
+
+    let mut symlinks = find_symlinks(std::slice::from_ref(&usr_local_bin), &[framework_exe]);
+    symlinks.sort();
+
+    // `python3.11` is not a symlink to the framework, hence must be ignored.
+    assert_eq!(
+        symlinks,
+        vec![
+            usr_local_bin.join("python3"),
+            usr_local_bin.join("python3.12")
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn no_symlinks_to_other_framework_python() {
+    use common::resolve_test_path;
+    use pet_mac_python_org::find_symlinks;
+    use std::fs;
+
+    let system_exe = fs::canonicalize(resolve_test_path(&[
+        "unix",
+        "System",
+        "Library",
+        "Frameworks",
+        "Python.framework",
+        "Versions",
+        "2.7",
+        "bin",
+        "python2.7",
+    ]))
+    .unwrap();
+    let usr_local_bin = resolve_test_path(&["unix", "usr", "local", "bin"]);
+
+    assert!(find_symlinks(&[usr_local_bin], &[system_exe]).is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn version_from_framework_dir() {
+    use common::resolve_test_path;
+    use pet_mac_python_org::get_version_from_framework_dir;
+
+    let versions = resolve_test_path(&[
+        "unix",
+        "Library",
+        "Frameworks",
+        "Python.framework",
+        "Versions",
+    ]);
+
+    assert_eq!(
+        get_version_from_framework_dir(&versions.join("3.12")),
+        Some("3.12".to_string())
+    );
+    assert_eq!(
+        get_version_from_framework_dir(&resolve_test_path(&[
+            "unix",
+            "System",
+            "Library",
+            "Frameworks",
+            "Python.framework",
+            "Versions",
+            "2.7"
+        ])),
+        Some("2.7".to_string())
+    );
+    assert_eq!(
+        get_version_from_framework_dir(&versions.join("Current")),
+        None
+    );
+    assert_eq!(get_version_from_framework_dir(&versions.join("3.x")), None);
+    assert_eq!(get_version_from_framework_dir(&versions.join("3")), None);
+}
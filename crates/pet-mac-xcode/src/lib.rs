@@ -2,7 +2,6 @@
 // Licensed under the MIT License.
 
 use pet_core::{
-    arch::Architecture,
     env::PythonEnv,
     python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
     reporter::Reporter,
@@ -30,6 +29,12 @@ impl Locator for MacXCode {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::MacXCode
     }
+    fn description(&self) -> &'static str {
+        "Finds Python shipped with Xcode on macOS in /Applications/Xcode*.app"
+    }
+    fn priority(&self) -> u8 {
+        250
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::MacCommandLineTools]
     }
@@ -110,13 +115,9 @@ impl Locator for MacXCode {
                         resolved_environments.push(resolved_env.clone());
                         symlinks.push(possible_exes);
                         // Use the latest accurate information we have.
+                        arch = Some(resolved_env.arch());
                         version = Some(resolved_env.version);
                         prefix = Some(resolved_env.prefix);
-                        arch = if resolved_env.is64_bit {
-                            Some(Architecture::X64)
-                        } else {
-                            Some(Architecture::X86)
-                        };
                     }
                 }
             }
@@ -161,19 +162,16 @@ impl Locator for MacXCode {
         if version.is_none() || prefix.is_none() {
             if let Some(resolved_env) = ResolvedPythonEnv::from(&env.executable) {
                 resolved_environments.push(resolved_env.clone());
+                arch = Some(resolved_env.arch());
                 version = Some(resolved_env.version);
                 prefix = Some(resolved_env.prefix);
-                arch = if resolved_env.is64_bit {
-                    Some(Architecture::X64)
-                } else {
-                    Some(Architecture::X86)
-                };
             }
         }
 
         let env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::MacXCode))
+            .source_locator(Some(LocatorKind::MacXCode.name()))
             .executable(Some(env.executable.clone()))
-            .version(version)
+            .version(version.and_then(|v| v.parse().ok()))
             .prefix(prefix)
             .arch(arch)
             .symlinks(Some(symlinks))
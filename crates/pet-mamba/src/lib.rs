@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use log::trace;
+use manager::{find_executable, MambaManager};
+use pet_conda::{environments::get_conda_environment_info, utils::is_conda_env};
+use pet_core::{
+    env::PythonEnv,
+    manager::EnvManagerType,
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod env_variables;
+pub mod mamba_info;
+pub mod manager;
+
+fn get_prefix(env: &PythonEnv) -> Option<PathBuf> {
+    if let Some(prefix) = &env.prefix {
+        return Some(prefix.clone());
+    }
+    let parent_dir = env.executable.parent()?;
+    if is_conda_env(parent_dir) {
+        // Generally the case on Windows, the exe is in the root of the env.
+        Some(parent_dir.to_path_buf())
+    } else if parent_dir.ends_with("bin") || parent_dir.ends_with("Scripts") {
+        Some(parent_dir.parent()?.to_path_buf())
+    } else {
+        None
+    }
+}
+
+/// Mamba & micromamba environments have the same layout as conda environments,
+/// hence they are reported as conda environments, but with the mamba (or micromamba) manager.
+fn get_environment(prefix: &Path, manager: &MambaManager) -> Option<PythonEnvironment> {
+    let env = get_conda_environment_info(prefix, &None)?;
+    let mut env =
+        env.to_python_environment(manager.info.root_prefix.clone(), Some(manager.to_manager()));
+    env.source_locator = Some(LocatorKind::Mamba.name());
+    Some(env)
+}
+
+pub struct Mamba {
+    pub env_vars: EnvVariables,
+    mamba_executable: Arc<Mutex<Option<PathBuf>>>,
+    micromamba_executable: Arc<Mutex<Option<PathBuf>>>,
+    managers: Arc<Mutex<Option<Vec<MambaManager>>>>,
+    /// Time after which mamba (or micromamba) is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Mamba {
+    pub fn from(environment: &dyn Environment) -> Mamba {
+        Mamba {
+            env_vars: EnvVariables::from(environment),
+            mamba_executable: Arc::new(Mutex::new(None)),
+            micromamba_executable: Arc::new(Mutex::new(None)),
+            managers: Arc::new(Mutex::new(None)),
+            timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+    fn get_managers(&self) -> Vec<MambaManager> {
+        let mut managers = self.managers.lock().unwrap();
+        if managers.is_none() {
+            let timeout = *self.timeout.lock().unwrap();
+            let mamba = self
+                .mamba_executable
+                .lock()
+                .unwrap()
+                .clone()
+                .or_else(|| find_executable(EnvManagerType::Mamba, &self.env_vars))
+                .and_then(|exe| MambaManager::from(&exe, EnvManagerType::Mamba, timeout));
+            let micromamba = self
+                .micromamba_executable
+                .lock()
+                .unwrap()
+                .clone()
+                .or_else(|| find_executable(EnvManagerType::MicroMamba, &self.env_vars))
+                .and_then(|exe| MambaManager::from(&exe, EnvManagerType::MicroMamba, timeout));
+            managers.replace(mamba.into_iter().chain(micromamba).collect());
+        }
+        managers.clone().unwrap_or_default()
+    }
+}
+
+impl Locator for Mamba {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Mamba
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by mamba & micromamba, reads MAMBA_ROOT_PREFIX"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(exe) = &config.mamba_executable {
+            self.mamba_executable.lock().unwrap().replace(exe.clone());
+        }
+        if let Some(exe) = &config.micromamba_executable {
+            self.micromamba_executable
+                .lock()
+                .unwrap()
+                .replace(exe.clone());
+        }
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+        self.managers.lock().unwrap().take();
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("mamba")
+                | Some("mamba.exe")
+                | Some("mamba.bat")
+                | Some("micromamba")
+                | Some("micromamba.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Conda]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let prefix = get_prefix(env)?;
+        if !is_conda_env(&prefix) {
+            return None;
+        }
+        // Conda environments that do not belong to mamba are left to the conda locator.
+        let manager = self
+            .get_managers()
+            .into_iter()
+            .find(|manager| manager.manages(&prefix))?;
+        get_environment(&prefix, &manager)
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        self.managers.lock().unwrap().take();
+        let mut reported = HashSet::new();
+        for manager in self.get_managers() {
+            reporter.report_manager(&manager.to_manager());
+
+            let mut prefixes = vec![];
+            prefixes.extend(manager.info.root_prefix.clone());
+            prefixes.extend(manager.info.envs.clone());
+            for envs_dir in &manager.info.envs_dirs {
+                trace!(
+                    "Searching for {:?} environments in {:?}",
+                    manager.tool,
+                    envs_dir
+                );
+                prefixes.extend(list_dirs(envs_dir));
+            }
+            for prefix in prefixes {
+                if !is_conda_env(&prefix) || !reported.insert(prefix.clone()) {
+                    continue;
+                }
+                if let Some(env) = get_environment(&prefix, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
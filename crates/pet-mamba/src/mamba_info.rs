@@ -0,0 +1,98 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::{trace, warn};
+use pet_process::run;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+/// Output of `mamba info --json`.
+/// mamba 1.x is built on top of conda & returns the same keys as `conda info --json`,
+/// whereas micromamba (& mamba 2.x) use human readable keys such as `envs directories`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct MambaInfo {
+    #[serde(
+        alias = "mamba_version",
+        alias = "mamba version",
+        alias = "micromamba version"
+    )]
+    pub version: Option<String>,
+    #[serde(alias = "base environment")]
+    pub root_prefix: Option<PathBuf>,
+    #[serde(default, alias = "envs directories")]
+    pub envs_dirs: Vec<PathBuf>,
+    #[serde(default, alias = "package cache")]
+    pub pkgs_dirs: Vec<PathBuf>,
+    #[serde(default)]
+    pub envs: Vec<PathBuf>,
+}
+
+/// Output of `mamba env list --json`.
+#[derive(Debug, serde::Deserialize)]
+struct MambaEnvList {
+    #[serde(default)]
+    envs: Vec<PathBuf>,
+}
+
+pub fn parse_info_output(output: &str) -> Option<MambaInfo> {
+    match serde_json::from_str::<MambaInfo>(output.trim()) {
+        Ok(info) => Some(info),
+        Err(err) => {
+            warn!(
+                "Failed to parse the output of mamba info as JSON: {:?}",
+                err
+            );
+            None
+        }
+    }
+}
+
+pub fn parse_env_list_output(output: &str) -> Vec<PathBuf> {
+    match serde_json::from_str::<MambaEnvList>(output.trim()) {
+        Ok(list) => list.envs,
+        Err(err) => {
+            warn!(
+                "Failed to parse the output of mamba env list as JSON: {:?}",
+                err
+            );
+            vec![]
+        }
+    }
+}
+
+fn spawn(executable: &Path, args: &[&str], timeout: Option<Duration>) -> Option<String> {
+    trace!("Executing {:?} {}", executable, args.join(" "));
+    let output = match run(Command::new(executable).args(args), timeout) {
+        Ok(output) => output,
+        Err(err) => {
+            warn!("Failed to spawn {:?}: {:?}", executable, err);
+            return None;
+        }
+    };
+    if !output.status.success() {
+        warn!(
+            "{:?} {} failed ({:?}) {}",
+            executable,
+            args.join(" "),
+            output.status.code().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+impl MambaInfo {
+    pub fn from(executable: &Path, timeout: Option<Duration>) -> Option<MambaInfo> {
+        parse_info_output(&spawn(executable, &["info", "--json"], timeout)?)
+    }
+}
+
+pub fn list_environments(executable: &Path, timeout: Option<Duration>) -> Vec<PathBuf> {
+    spawn(executable, &["env", "list", "--json"], timeout)
+        .map(|output| parse_env_list_output(&output))
+        .unwrap_or_default()
+}
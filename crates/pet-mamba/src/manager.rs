@@ -0,0 +1,92 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{
+    env_variables::EnvVariables,
+    mamba_info::{list_environments, MambaInfo},
+};
+use log::trace;
+use pet_core::manager::{EnvManager, EnvManagerType};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+fn get_exe_names(tool: EnvManagerType) -> Vec<&'static str> {
+    match (tool, std::env::consts::OS == "windows") {
+        (EnvManagerType::MicroMamba, true) => vec!["micromamba.exe", "micromamba"],
+        (EnvManagerType::MicroMamba, false) => vec!["micromamba"],
+        (_, true) => vec!["mamba.exe", "mamba.bat", "mamba"],
+        (_, false) => vec!["mamba"],
+    }
+}
+
+/// Looks for the mamba (or micromamba) executable in the PATH variable & the default install locations.
+pub fn find_executable(tool: EnvManagerType, env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        // https://mamba.readthedocs.io/en/latest/installation/micromamba-installation.html
+        search_paths.push(home.join(".local").join("bin"));
+        search_paths.push(home.join("micromamba").join("bin"));
+    }
+    for dir in search_paths {
+        for name in get_exe_names(tool) {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("{:?} exe not found", tool);
+    None
+}
+
+#[derive(Debug, Clone)]
+pub struct MambaManager {
+    pub executable: PathBuf,
+    pub tool: EnvManagerType,
+    pub info: MambaInfo,
+}
+
+impl MambaManager {
+    pub fn from(
+        executable: &Path,
+        tool: EnvManagerType,
+        timeout: Option<Duration>,
+    ) -> Option<MambaManager> {
+        let mut info = MambaInfo::from(executable, timeout)?;
+        // micromamba does not include the environments in the info (unlike conda).
+        for env in list_environments(executable, timeout) {
+            if !info.envs.contains(&env) {
+                info.envs.push(env);
+            }
+        }
+        Some(MambaManager {
+            executable: executable.to_path_buf(),
+            tool,
+            info,
+        })
+    }
+    pub fn to_manager(&self) -> EnvManager {
+        let mut manager = EnvManager::new(
+            self.executable.clone(),
+            self.tool,
+            self.info.version.clone(),
+        );
+        manager.install_path = self.info.root_prefix.clone();
+        manager
+    }
+    /// Whether the environment is the base environment or in one of the envs directories of this manager.
+    pub fn manages(&self, prefix: &Path) -> bool {
+        self.info.root_prefix.as_deref() == Some(prefix)
+            || self.info.envs.iter().any(|env| env == prefix)
+            || prefix
+                .parent()
+                .map(|dir| self.info.envs_dirs.iter().any(|envs_dir| envs_dir == dir))
+                .unwrap_or_default()
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_micromamba_environments() {
+    use pet_core::{manager::EnvManagerType, python_environment::PythonEnvironmentKind, Locator};
+    use pet_mamba::Mamba;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let bin = resolve_test_path!(&["unix", "bin"]);
+    let root = resolve_test_path!(&["unix", "micromamba_root"]);
+    let environment = create_test_environment(
+        HashMap::from([("PATH".to_string(), bin.to_str().unwrap().to_string())]),
+        None,
+    );
+    let locator = Mamba::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    assert_eq!(result.managers.len(), 1);
+    let manager = result.managers[0].clone();
+    assert_eq!(manager.tool, EnvManagerType::MicroMamba);
+    assert_eq!(manager.executable, bin.join("micromamba"));
+    assert_eq!(manager.version, Some("1.5.8".to_string()));
+    assert_eq!(manager.install_path, Some(root.clone()));
+
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
+            assert_eq!(env.manager, Some(manager.clone()));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.name.clone(),
+                env.version.as_ref().map(|v| v.to_string()),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            (
+                resolve_test_path!(&["unix", "custom_envs", "other"]),
+                None,
+                Some("3.12.1".to_string())
+            ),
+            (root.clone(), Some("base".to_string()), None),
+            (
+                root.join("envs").join("myenv"),
+                Some("myenv".to_string()),
+                Some("3.12.1".to_string())
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn identify_micromamba_environments() {
+    use pet_core::{env::PythonEnv, manager::EnvManagerType, Configuration, Locator};
+    use pet_mamba::Mamba;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Mamba::from(&environment);
+    locator.configure(&Configuration {
+        micromamba_executable: Some(resolve_test_path!(&["unix", "bin", "micromamba"])),
+        ..Default::default()
+    });
+
+    let prefix = resolve_test_path!(&["unix", "micromamba_root", "envs", "myenv"]);
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.version, "3.12.1".parse().ok());
+    assert_eq!(
+        env.manager.map(|m| m.tool),
+        Some(EnvManagerType::MicroMamba)
+    );
+    assert_eq!(env.source_locator, Some("Mamba"));
+}
+
+#[cfg(unix)]
+#[test]
+fn conda_environments_not_managed_by_mamba_are_ignored() {
+    use pet_core::{env::PythonEnv, Locator};
+    use pet_mamba::Mamba;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    // Without mamba (or micromamba) we cannot tell whether it created the environment.
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Mamba::from(&environment);
+
+    let prefix = resolve_test_path!(&["unix", "micromamba_root", "envs", "myenv"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None
+        ))
+        .is_none());
+}
+
+#[test]
+fn parse_mamba_info() {
+    use pet_mamba::mamba_info::parse_info_output;
+    use std::path::PathBuf;
+
+    // mamba 1.x returns the same output as `conda info --json`.
+    let info = parse_info_output(
+        r#"{
+  "conda_version": "24.3.0",
+  "mamba_version": "1.5.8",
+  "root_prefix": "/home/user/miniforge3",
+  "envs_dirs": ["/home/user/miniforge3/envs", "/home/user/.conda/envs"],
+  "pkgs_dirs": ["/home/user/miniforge3/pkgs"],
+  "envs": ["/home/user/miniforge3", "/home/user/miniforge3/envs/myenv"]
+}"#,
+    )
+    .unwrap();
+    assert_eq!(info.version, Some("1.5.8".to_string()));
+    assert_eq!(
+        info.root_prefix,
+        Some(PathBuf::from("/home/user/miniforge3"))
+    );
+    assert_eq!(
+        info.envs_dirs,
+        vec![
+            PathBuf::from("/home/user/miniforge3/envs"),
+            PathBuf::from("/home/user/.conda/envs")
+        ]
+    );
+    assert_eq!(
+        info.pkgs_dirs,
+        vec![PathBuf::from("/home/user/miniforge3/pkgs")]
+    );
+    assert_eq!(info.envs.len(), 2);
+
+    // micromamba (& mamba 2.x) use human readable keys.
+    let info = parse_info_output(
+        r#"{
+  "libmamba version": "2.0.2",
+  "mamba version": "2.0.2",
+  "base environment": "/home/user/.local/share/mamba",
+  "envs directories": ["/home/user/.local/share/mamba/envs"],
+  "package cache": ["/home/user/.local/share/mamba/pkgs"]
+}"#,
+    )
+    .unwrap();
+    assert_eq!(info.version, Some("2.0.2".to_string()));
+    assert_eq!(
+        info.root_prefix,
+        Some(PathBuf::from("/home/user/.local/share/mamba"))
+    );
+    assert_eq!(
+        info.envs_dirs,
+        vec![PathBuf::from("/home/user/.local/share/mamba/envs")]
+    );
+    assert!(info.envs.is_empty());
+
+    assert!(parse_info_output("not json").is_none());
+}
@@ -0,0 +1,33 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::os_environment::Environment;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+// NOTE: Do not implement Default trait, as we do not want to ever forget to set the values.
+// Lets be explicit, this way we never miss a value (in Windows or Unix).
+pub struct EnvVariables {
+    pub home: Option<PathBuf>,
+    pub path: Option<String>,
+    pub xdg_data_home: Option<String>,
+    /// Directory where mise installs tools, defaults to `~/.local/share/mise`.
+    pub mise_data_dir: Option<String>,
+    /// Global config file, superseded by `MISE_GLOBAL_CONFIG_FILE`.
+    pub mise_config_file: Option<String>,
+    /// Global config file, defaults to `~/.config/mise/config.toml`.
+    pub mise_global_config_file: Option<String>,
+}
+
+impl EnvVariables {
+    pub fn from(env: &dyn Environment) -> Self {
+        EnvVariables {
+            home: env.get_user_home(),
+            path: env.get_env_var("PATH".to_string()),
+            xdg_data_home: env.get_env_var("XDG_DATA_HOME".to_string()),
+            mise_data_dir: env.get_env_var("MISE_DATA_DIR".to_string()),
+            mise_config_file: env.get_env_var("MISE_CONFIG_FILE".to_string()),
+            mise_global_config_file: env.get_env_var("MISE_GLOBAL_CONFIG_FILE".to_string()),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// See https://mise.jdx.dev/configuration.html#environment-variables
+pub fn get_mise_data_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(mise_data_dir) = &env_vars.mise_data_dir {
+        return Some(norm_case(PathBuf::from(mise_data_dir)));
+    }
+    if let Some(xdg_data_home) = &env_vars.xdg_data_home {
+        return Some(norm_case(PathBuf::from(xdg_data_home)).join("mise"));
+    }
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".local").join("share").join("mise"))
+}
+
+/// Directory containing the Python versions installed by mise, e.g. `~/.local/share/mise/installs/python/3.12.4`.
+pub fn get_python_installs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    get_mise_data_dir(env_vars).map(|data_dir| data_dir.join("installs").join("python"))
+}
+
+/// The global config file, where the default versions are selected.
+pub fn get_global_config_file(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(file) = env_vars
+        .mise_global_config_file
+        .as_ref()
+        .or(env_vars.mise_config_file.as_ref())
+    {
+        return Some(norm_case(PathBuf::from(file)));
+    }
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".config").join("mise").join("config.toml"))
+}
+
+/// Python versions selected in a mise config file, e.g. `python = "3.12"` or `python = ["3.12", "3.11"]` in the `[tools]` section.
+/// The first version is the one that is active, the others are fallbacks.
+pub fn get_python_versions_from_config(config_file: &Path) -> Vec<String> {
+    let tools = match fs::read_to_string(config_file)
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok())
+        .and_then(|config| config.get("tools").cloned())
+    {
+        Some(tools) => tools,
+        None => return vec![],
+    };
+    let get_version = |value: &toml::Value| match value {
+        toml::Value::String(version) => Some(version.clone()),
+        // E.g. `python = { version = "3.12", virtualenv = ".venv" }`
+        toml::Value::Table(table) => table
+            .get("version")
+            .and_then(|version| version.as_str())
+            .map(|version| version.to_string()),
+        _ => None,
+    };
+    match tools.get("python") {
+        Some(toml::Value::Array(versions)) => versions.iter().filter_map(get_version).collect(),
+        Some(value) => get_version(value).into_iter().collect(),
+        None => vec![],
+    }
+}
+
+/// Python versions selected in the `.tool-versions` file (asdf compatible), e.g. `python 3.12.4 3.11.9`.
+pub fn get_python_versions_from_tool_versions(tool_versions: &Path) -> Vec<String> {
+    let contents = match fs::read_to_string(tool_versions) {
+        Ok(contents) => contents,
+        Err(_) => return vec![],
+    };
+    for line in contents.lines() {
+        // Comments can be at the end of a line as well.
+        let line = line.split('#').next().unwrap_or_default();
+        let mut parts = line.split_whitespace();
+        if parts.next() == Some("python") {
+            return parts.map(|version| version.to_string()).collect();
+        }
+    }
+    vec![]
+}
+
+/// Python versions selected for a project.
+/// mise config files take precedence over the `.tool-versions` file.
+pub fn get_project_python_versions(project_dir: &Path) -> Vec<String> {
+    for config_file in [
+        project_dir.join("mise.toml"),
+        project_dir.join(".mise.toml"),
+        project_dir.join(".config").join("mise.toml"),
+    ] {
+        let versions = get_python_versions_from_config(&config_file);
+        if !versions.is_empty() {
+            return versions;
+        }
+    }
+    get_python_versions_from_tool_versions(&project_dir.join(".tool-versions"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_config_file() {
+        let env_vars = EnvVariables {
+            home: Some(PathBuf::from("/home/user")),
+            path: None,
+            xdg_data_home: None,
+            mise_data_dir: None,
+            mise_config_file: None,
+            mise_global_config_file: None,
+        };
+        assert_eq!(
+            get_global_config_file(&env_vars),
+            Some(PathBuf::from("/home/user/.config/mise/config.toml"))
+        );
+        assert_eq!(
+            get_python_installs_dir(&env_vars),
+            Some(PathBuf::from(
+                "/home/user/.local/share/mise/installs/python"
+            ))
+        );
+
+        let env_vars = EnvVariables {
+            mise_config_file: Some("/etc/mise/config.toml".to_string()),
+            ..env_vars
+        };
+        assert_eq!(
+            get_global_config_file(&env_vars),
+            Some(PathBuf::from("/etc/mise/config.toml"))
+        );
+
+        // `MISE_GLOBAL_CONFIG_FILE` takes precedence.
+        let env_vars = EnvVariables {
+            mise_global_config_file: Some("/home/user/mise.toml".to_string()),
+            ..env_vars
+        };
+        assert_eq!(
+            get_global_config_file(&env_vars),
+            Some(PathBuf::from("/home/user/mise.toml"))
+        );
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::{get_project_python_versions, get_python_installs_dir};
+use log::trace;
+use manager::{find_mise_executable, list_current_python};
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_python_utils::executable::{find_executable, find_executables};
+use pet_python_utils::version;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+/// Python versions are installed in directories named after the version, e.g. `3.12.4`.
+/// Other implementations (e.g. `pypy3.10-7.3.15`) use the names from pyenv, for those rely on the header files.
+fn get_version(prefix: &Path) -> Option<String> {
+    let name = prefix.file_name()?.to_str()?;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(name.to_string())
+    } else {
+        version::from_header_files(prefix)
+    }
+}
+
+/// Resolves the requested version (e.g. `3.12` or `latest`) to the latest matching version that is installed.
+pub fn resolve_installed_version(requested: &str, installed: &[String]) -> Option<String> {
+    if installed.iter().any(|version| version == requested) {
+        return Some(requested.to_string());
+    }
+    let prefix = format!("{requested}.");
+    installed
+        .iter()
+        .filter(|version| requested == "latest" || version.starts_with(&prefix))
+        .max_by_key(|version| {
+            version
+                .split('.')
+                .map(|part| part.parse::<u64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .cloned()
+}
+
+/// mise creates symlinks for the requested versions, e.g. `3.12 -> ./3.12.4` & `latest -> ./3.12.4`.
+/// Hence ignore the symlinks, else we end up with duplicates.
+fn list_installs(path: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(path) {
+        Ok(reader) => reader
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or_default())
+            .map(|e| e.path())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+pub struct Mise {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+    /// Time after which mise is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Mise {
+    pub fn from(environment: &dyn Environment) -> Mise {
+        Mise {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+            timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = find_mise_executable(&self.env_vars)?;
+        let mut manager = EnvManager::new(executable, EnvManagerType::Mise, None);
+        manager.install_path = get_python_installs_dir(&self.env_vars);
+        Some(manager)
+    }
+    /// The first project that selects this version in its `mise.toml` or `.tool-versions` file.
+    fn get_project(&self, installs_dir: &Path, version: &str) -> Option<PathBuf> {
+        let installed = list_installs(installs_dir)
+            .iter()
+            .filter_map(|prefix| prefix.file_name()?.to_str().map(|v| v.to_string()))
+            .collect::<Vec<_>>();
+        self.workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| {
+                get_project_python_versions(dir)
+                    .first()
+                    .and_then(|requested| resolve_installed_version(requested, &installed))
+                    .as_deref()
+                    == Some(version)
+            })
+            .cloned()
+    }
+    /// Python installed by mise, e.g. `~/.local/share/mise/installs/python/3.12.4`.
+    fn get_environment(
+        &self,
+        prefix: &Path,
+        executable: Option<PathBuf>,
+        manager: &Option<EnvManager>,
+    ) -> Option<PythonEnvironment> {
+        let executable = executable.or_else(|| find_executable(prefix))?;
+        let project = match (prefix.parent(), prefix.file_name().and_then(|n| n.to_str())) {
+            (Some(installs_dir), Some(name)) => self.get_project(installs_dir, name),
+            _ => None,
+        };
+        Some(
+            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Mise))
+                .source_locator(Some(LocatorKind::Mise.name()))
+                .executable(Some(executable))
+                .version(get_version(prefix).and_then(|v| v.parse().ok()))
+                .prefix(Some(prefix.to_path_buf()))
+                .project(project)
+                .manager(manager.clone())
+                .symlinks(Some(find_executables(prefix.join("bin"))))
+                .build(),
+        )
+    }
+}
+
+impl Locator for Mise {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Mise
+    }
+    fn description(&self) -> &'static str {
+        "Finds Python versions installed by mise, reads MISE_DATA_DIR and XDG_DATA_HOME (defaults to ~/.local/share/mise)"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("mise") | Some("mise.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Mise]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let installs_dir = get_python_installs_dir(&self.env_vars)?;
+        let path = env.executable.strip_prefix(&installs_dir).ok()?;
+        // The version is the first directory under `$MISE_DATA_DIR/installs/python`.
+        let name = path.components().next()?;
+        let mut prefix = installs_dir.join(name);
+        // E.g. `installs/python/3.12/bin/python` where `3.12` is a symlink to `3.12.4`.
+        if prefix.is_symlink() {
+            let target = fs::read_link(&prefix).ok()?;
+            prefix = installs_dir.join(target.file_name()?);
+        }
+        self.get_environment(&prefix, Some(env.executable.clone()), &self.get_manager())
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+
+        let mut prefixes = vec![];
+        if let Some(installs_dir) = get_python_installs_dir(&self.env_vars) {
+            trace!("Searching for mise Python versions in {:?}", installs_dir);
+            prefixes.extend(list_installs(&installs_dir));
+        }
+        // mise could be using a different data directory (e.g. `MISE_DATA_DIR` set in the shell profile).
+        if let Some(manager) = &manager {
+            for install in list_current_python(&manager.executable, *self.timeout.lock().unwrap()) {
+                if let Some(install_path) = install.install_path {
+                    if install_path.is_dir() && !prefixes.contains(&install_path) {
+                        prefixes.push(install_path);
+                    }
+                }
+            }
+        }
+
+        for prefix in prefixes {
+            if let Some(env) = self.get_environment(&prefix, None, &manager) {
+                reporter.report_environment(&env);
+            }
+        }
+    }
+}
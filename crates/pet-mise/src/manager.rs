@@ -0,0 +1,125 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::{error, trace};
+use pet_process::run;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+fn get_mise_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["mise.exe", "mise"]
+    } else {
+        vec!["mise"]
+    }
+}
+
+/// Looks for the mise executable in the PATH variable & `~/.local/bin` (where the installer puts it).
+pub fn find_mise_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        search_paths.push(home.join(".local").join("bin"));
+    }
+    for dir in search_paths {
+        for name in get_mise_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("mise exe not found");
+    None
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MiseInstall {
+    pub version: String,
+    pub install_path: Option<PathBuf>,
+}
+
+/// Parses the output of `mise ls --current --json`, i.e. the versions of each tool keyed by the name of the tool.
+pub fn parse_ls_output(output: &str) -> Vec<MiseInstall> {
+    match serde_json::from_str::<HashMap<String, Vec<MiseInstall>>>(output) {
+        Ok(mut tools) => tools.remove("python").unwrap_or_default(),
+        Err(err) => {
+            error!("Failed to parse the output of mise ls: {:?}", err);
+            vec![]
+        }
+    }
+}
+
+/// Lists the current Python versions by spawning `mise ls --current --json`.
+pub fn list_current_python(mise: &Path, timeout: Option<Duration>) -> Vec<MiseInstall> {
+    let start = std::time::SystemTime::now();
+    let output = run(
+        Command::new(mise).arg("ls").arg("--current").arg("--json"),
+        timeout,
+    );
+    trace!(
+        "Executed mise ls in {:?}",
+        start.elapsed().unwrap_or_default()
+    );
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_ls_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            error!(
+                "Failed to list Python versions using mise {:?}: {:?}",
+                mise,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            vec![]
+        }
+        Err(err) => {
+            error!(
+                "Failed to list Python versions using mise {:?}: {:?}",
+                mise, err
+            );
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_current_python_versions() {
+        let output = r#"{
+            "node": [{"version": "20.15.0", "install_path": "/home/user/.local/share/mise/installs/node/20.15.0"}],
+            "python": [
+                {
+                    "version": "3.12.4",
+                    "requested_version": "3.12",
+                    "install_path": "/home/user/.local/share/mise/installs/python/3.12.4",
+                    "source": {"type": "mise.toml", "path": "/home/user/project/mise.toml"},
+                    "installed": true,
+                    "active": true
+                }
+            ]
+        }"#;
+        assert_eq!(
+            parse_ls_output(output),
+            vec![MiseInstall {
+                version: "3.12.4".to_string(),
+                install_path: Some(PathBuf::from(
+                    "/home/user/.local/share/mise/installs/python/3.12.4"
+                )),
+            }]
+        );
+        assert_eq!(parse_ls_output("{}"), vec![]);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_mise_python_versions() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_mise::Mise;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let mise_data_dir = resolve_test_path!(&["unix", "mise_data"]);
+    let app_mise = resolve_test_path!(&["unix", "app_mise"]);
+    let app_tool_versions = resolve_test_path!(&["unix", "app_tool_versions"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "MISE_DATA_DIR".to_string(),
+            mise_data_dir.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Mise::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![
+            resolve_test_path!(&["unix", "app_none"]),
+            app_tool_versions.clone(),
+            app_mise.clone(),
+        ]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    // mise is not installed.
+    assert!(result.managers.is_empty());
+
+    let installs_dir = mise_data_dir.join("installs").join("python");
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Mise));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            // Selected in the `.tool-versions` file.
+            (
+                installs_dir.join("3.11.9"),
+                "3.11.9".to_string(),
+                Some(app_tool_versions)
+            ),
+            (installs_dir.join("3.12.1"), "3.12.1".to_string(), None),
+            // `3.12` in `mise.toml` resolves to the latest 3.12 installed.
+            (
+                installs_dir.join("3.12.4"),
+                "3.12.4".to_string(),
+                Some(app_mise)
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn find_current_python_versions_using_mise() {
+    use pet_core::{manager::EnvManagerType, Locator};
+    use pet_mise::Mise;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let mise_data_dir = resolve_test_path!(&["unix", "mise_data"]);
+    let bin = resolve_test_path!(&["unix", "bin"]);
+    let environment = create_test_environment(
+        HashMap::from([
+            (
+                "MISE_DATA_DIR".to_string(),
+                mise_data_dir.to_string_lossy().to_string(),
+            ),
+            ("PATH".to_string(), bin.to_string_lossy().to_string()),
+        ]),
+        None,
+    );
+    let locator = Mise::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    assert_eq!(result.managers.len(), 1);
+    assert_eq!(result.managers[0].tool, EnvManagerType::Mise);
+    assert_eq!(result.managers[0].executable, bin.join("mise"));
+
+    // Installed in a data directory other than `MISE_DATA_DIR`.
+    let other = resolve_test_path!(&["unix", "other_mise_data", "installs", "python", "3.10.14"]);
+    let env = result
+        .environments
+        .iter()
+        .find(|env| env.prefix == Some(other.clone()))
+        .unwrap();
+    assert_eq!(env.version, "3.10.14".parse().ok());
+    assert_eq!(env.manager, Some(result.managers[0].clone()));
+    assert_eq!(result.environments.len(), 4);
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_mise_python_executable() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_mise::Mise;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let mise_data_dir = resolve_test_path!(&["unix", "mise_data"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "MISE_DATA_DIR".to_string(),
+            mise_data_dir.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Mise::from(&environment);
+    let installs_dir = mise_data_dir.join("installs").join("python");
+
+    // `3.12` is a symlink to `3.12.4`.
+    let env = locator
+        .try_from(&PythonEnv::new(
+            installs_dir.join("3.12").join("bin").join("python3"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Mise));
+    assert_eq!(env.prefix, Some(installs_dir.join("3.12.4")));
+    assert_eq!(env.version, "3.12.4".parse().ok());
+
+    // Python not installed by mise.
+    let other = resolve_test_path!(&["unix", "bin", "python3"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(other, None, None))
+        .is_none());
+}
@@ -0,0 +1,134 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use log::trace;
+use manager::find_nox_executable;
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub mod env_variables;
+pub mod manager;
+
+/// Environment created by nox for a session, i.e. `<project>/.nox/<session name>`.
+/// Parametrized sessions get a directory each, e.g. `tests(python='3.12')` is created in `.nox/tests-3-12`.
+fn get_session_environment(
+    project_dir: &Path,
+    prefix: &Path,
+    executable: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    // Unless `--reuse-existing-virtualenvs` is used, nox re-creates the environment for every run,
+    // Hence the directory could be empty.
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    let session = prefix.file_name()?.to_str()?.to_string();
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Nox))
+            .source_locator(Some(LocatorKind::Nox.name()))
+            .name(Some(session))
+            .executable(Some(executable))
+            .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .project(Some(project_dir.to_path_buf()))
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+pub struct Nox {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Nox {
+    pub fn from(environment: &dyn Environment) -> Nox {
+        Nox {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = find_nox_executable(&self.env_vars)?;
+        Some(EnvManager::new(executable, EnvManagerType::Nox, None))
+    }
+}
+
+impl Locator for Nox {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Nox
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by nox in the .nox directory of workspace folders"
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("nox") | Some("nox.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Nox]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let project_dir = self
+            .workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| env.executable.starts_with(dir.join(".nox")))
+            .cloned()?;
+        // The session is the first directory under `.nox`.
+        let nox_dir = project_dir.join(".nox");
+        let session = env
+            .executable
+            .strip_prefix(&nox_dir)
+            .ok()?
+            .components()
+            .next()?;
+        get_session_environment(
+            &project_dir,
+            &nox_dir.join(session),
+            Some(env.executable.clone()),
+            &self.get_manager(),
+        )
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        for project_dir in workspace_directories {
+            let nox_dir = project_dir.join(".nox");
+            trace!("Searching for nox environments in {:?}", nox_dir);
+            for prefix in list_dirs(&nox_dir) {
+                if let Some(env) = get_session_environment(&project_dir, &prefix, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
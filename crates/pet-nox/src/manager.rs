@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::trace;
+use std::{env, path::PathBuf};
+
+fn get_nox_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["nox.exe", "nox"]
+    } else {
+        vec!["nox"]
+    }
+}
+
+/// Looks for the nox executable in the PATH variable & `~/.local/bin` (where pipx installs it).
+pub fn find_nox_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        search_paths.push(home.join(".local").join("bin"));
+    }
+    for dir in search_paths {
+        for name in get_nox_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("nox exe not found");
+    None
+}
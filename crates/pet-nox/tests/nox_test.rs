@@ -0,0 +1,113 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_environments_of_sessions() {
+    use pet_core::{
+        manager::EnvManagerType, python_environment::PythonEnvironmentKind, Configuration, Locator,
+    };
+    use pet_nox::Nox;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let project = resolve_test_path!(&["unix", "project"]);
+    let bin = resolve_test_path!(&["unix", "bin"]);
+    let environment = create_test_environment(
+        HashMap::from([("PATH".to_string(), bin.to_string_lossy().to_string())]),
+        None,
+    );
+    let locator = Nox::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    assert_eq!(result.managers.len(), 1);
+    assert_eq!(result.managers[0].tool, EnvManagerType::Nox);
+    assert_eq!(result.managers[0].executable, bin.join("nox"));
+
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Nox));
+            assert_eq!(env.project, Some(project.clone()));
+            assert_eq!(env.manager, Some(result.managers[0].clone()));
+            (
+                env.name.clone().unwrap_or_default(),
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    // The environment of the `docs` session is being re-created (nox was run without `--reuse-existing-virtualenvs`).
+    assert_eq!(
+        environments,
+        vec![
+            (
+                "lint".to_string(),
+                project.join(".nox").join("lint"),
+                "3.12.4".to_string()
+            ),
+            (
+                "tests-3-11".to_string(),
+                project.join(".nox").join("tests-3-11"),
+                "3.11.9".to_string()
+            ),
+            (
+                "tests-3-12".to_string(),
+                project.join(".nox").join("tests-3-12"),
+                "3.12.4".to_string()
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_environment_of_reused_session() {
+    use pet_core::{
+        env::PythonEnv, python_environment::PythonEnvironmentKind, Configuration, Locator,
+    };
+    use pet_nox::Nox;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let project = resolve_test_path!(&["unix", "project"]);
+    let locator = Nox::from(&create_test_environment(HashMap::new(), None));
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project.clone()]),
+        ..Default::default()
+    });
+
+    let prefix = project.join(".nox").join("tests-3-12");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Nox));
+    assert_eq!(env.name, Some("tests-3-12".to_string()));
+    assert_eq!(env.display_name, None);
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, Some(project));
+    assert_eq!(env.manager, None);
+
+    // Not a project in the workspace.
+    let other = resolve_test_path!(&["unix", "other_project", ".nox", "lint", "bin", "python"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(other, None, None))
+        .is_none());
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::os_environment::Environment;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+// NOTE: Do not implement Default trait, as we do not want to ever forget to set the values.
+// Lets be explicit, this way we never miss a value (in Windows or Unix).
+pub struct EnvVariables {
+    pub home: Option<PathBuf>,
+    pub path: Option<String>,
+    /// Directory where PDM was installed (using the install script).
+    pub pdm_home: Option<String>,
+    /// When set, PDM ignores the interpreter saved in `.pdm-python` & uses the active virtual env instead.
+    pub pdm_ignore_saved_python: Option<String>,
+    pub virtual_env: Option<String>,
+    pub xdg_data_home: Option<String>,
+    pub localappdata: Option<String>,
+}
+
+impl EnvVariables {
+    pub fn from(env: &dyn Environment) -> Self {
+        EnvVariables {
+            home: env.get_user_home(),
+            path: env.get_env_var("PATH".to_string()),
+            pdm_home: env.get_env_var("PDM_HOME".to_string()),
+            pdm_ignore_saved_python: env.get_env_var("PDM_IGNORE_SAVED_PYTHON".to_string()),
+            virtual_env: env.get_env_var("VIRTUAL_ENV".to_string()),
+            xdg_data_home: env.get_env_var("XDG_DATA_HOME".to_string()),
+            localappdata: env.get_env_var("LOCALAPPDATA".to_string()),
+        }
+    }
+}
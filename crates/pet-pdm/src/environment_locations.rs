@@ -0,0 +1,27 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use std::path::PathBuf;
+
+/// Directory where PDM creates virtual environments that are not in the project (`venv.location`).
+/// See https://pdm-project.org/latest/usage/venv/#virtualenv-auto-creation
+pub fn get_venvs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if cfg!(windows) {
+        if let Some(localappdata) = &env_vars.localappdata {
+            return Some(
+                PathBuf::from(localappdata)
+                    .join("pdm")
+                    .join("pdm")
+                    .join("venvs"),
+            );
+        }
+    }
+    if let Some(xdg_data_home) = &env_vars.xdg_data_home {
+        return Some(PathBuf::from(xdg_data_home).join("pdm").join("venvs"));
+    }
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".local").join("share").join("pdm").join("venvs"))
+}
@@ -0,0 +1,213 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::get_venvs_dir;
+use log::trace;
+use manager::{find_pdm_executable, list_venvs};
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables, get_prefix_from_executable};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+/// Whether the directory is a project managed by PDM.
+fn is_pdm_project(project_dir: &Path) -> bool {
+    if project_dir.join("pdm.lock").is_file() {
+        return true;
+    }
+    fs::read_to_string(project_dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok())
+        .and_then(|pyproject| pyproject.get("tool")?.get("pdm").cloned())
+        .is_some()
+}
+
+/// Returns true if the `pyvenv.cfg` file contains `pdm = true`.
+pub fn has_pdm_marker(prefix: &Path) -> bool {
+    match fs::read_to_string(prefix.join("pyvenv.cfg")) {
+        Ok(contents) => contents.lines().any(|line| {
+            line.split_once('=')
+                .map(|(key, value)| key.trim() == "pdm" && value.trim() == "true")
+                .unwrap_or_default()
+        }),
+        Err(_) => false,
+    }
+}
+
+pub struct Pdm {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+    /// Time after which PDM is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Pdm {
+    pub fn from(environment: &dyn Environment) -> Pdm {
+        Pdm {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+            timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = find_pdm_executable(&self.env_vars)?;
+        let mut manager = EnvManager::new(executable, EnvManagerType::Pdm, None);
+        manager.install_path = get_venvs_dir(&self.env_vars);
+        Some(manager)
+    }
+    /// The virtual env used by the project.
+    /// This is the interpreter saved in `.pdm-python`, or the active virtual env if `PDM_IGNORE_SAVED_PYTHON` is set.
+    fn get_selected_venv(&self, project_dir: &Path) -> Option<PathBuf> {
+        if self.env_vars.pdm_ignore_saved_python.is_some() {
+            return self.env_vars.virtual_env.as_ref().map(PathBuf::from);
+        }
+        let saved_python = fs::read_to_string(project_dir.join(".pdm-python")).ok()?;
+        get_prefix_from_executable(Path::new(saved_python.trim()))
+    }
+    fn get_project(&self, prefix: &Path) -> Option<PathBuf> {
+        // In project virtual env, i.e. `<project>/.venv`.
+        if prefix.ends_with(".venv") {
+            if let Some(project_dir) = prefix.parent() {
+                if is_pdm_project(project_dir) {
+                    return Some(project_dir.to_path_buf());
+                }
+            }
+        }
+        self.workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| {
+                is_pdm_project(dir) && self.get_selected_venv(dir).as_deref() == Some(prefix)
+            })
+            .cloned()
+    }
+    fn get_environment(
+        &self,
+        prefix: &Path,
+        executable: Option<PathBuf>,
+        project: Option<PathBuf>,
+        manager: &Option<EnvManager>,
+    ) -> Option<PythonEnvironment> {
+        let executable = executable.or_else(|| find_executable(prefix))?;
+        Some(
+            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Pdm))
+                .source_locator(Some(LocatorKind::Pdm.name()))
+                .executable(Some(executable))
+                .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+                .prefix(Some(prefix.to_path_buf()))
+                .project(project)
+                .manager(manager.clone())
+                .symlinks(Some(find_executables(prefix)))
+                .build(),
+        )
+    }
+}
+
+impl Locator for Pdm {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Pdm
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by PDM, reads .pdm-python of workspace folders and PDM_HOME"
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("pdm") | Some("pdm.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Pdm]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let prefix = env
+            .prefix
+            .clone()
+            .or_else(|| get_prefix_from_executable(&env.executable))?;
+        if !prefix.join("pyvenv.cfg").is_file() {
+            return None;
+        }
+        let project = self.get_project(&prefix);
+        let in_venvs_dir = get_venvs_dir(&self.env_vars)
+            .map(|dir| prefix.starts_with(dir))
+            .unwrap_or_default();
+        if project.is_none() && !in_venvs_dir && !has_pdm_marker(&prefix) {
+            return None;
+        }
+        self.get_environment(
+            &prefix,
+            Some(env.executable.clone()),
+            project,
+            &self.get_manager(),
+        )
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+        let mut reported = HashSet::new();
+
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        let timeout = *self.timeout.lock().unwrap();
+        for project_dir in workspace_directories.iter().filter(|d| is_pdm_project(d)) {
+            let mut venvs = vec![project_dir.join(".venv")];
+            venvs.extend(self.get_selected_venv(project_dir));
+            // Spawning PDM is slower, but finds venvs in custom locations (`venv.location`).
+            if let Some(manager) = &manager {
+                venvs.extend(list_venvs(&manager.executable, project_dir, timeout));
+            }
+            for prefix in venvs {
+                if !prefix.join("pyvenv.cfg").is_file() || !reported.insert(prefix.clone()) {
+                    continue;
+                }
+                if let Some(env) =
+                    self.get_environment(&prefix, None, Some(project_dir.clone()), &manager)
+                {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+
+        if let Some(venvs_dir) = get_venvs_dir(&self.env_vars) {
+            trace!("Searching for PDM environments in {:?}", venvs_dir);
+            for prefix in list_dirs(&venvs_dir) {
+                if !prefix.join("pyvenv.cfg").is_file() || !reported.insert(prefix.clone()) {
+                    continue;
+                }
+                if let Some(env) = self.get_environment(&prefix, None, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::{error, trace};
+use pet_process::run;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+/// Looks for the pdm executable in `PDM_HOME`, the PATH variable & the default install locations.
+pub fn find_pdm_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(pdm_home) = &env_vars.pdm_home {
+        search_paths.push(PathBuf::from(pdm_home).join("bin"));
+        search_paths.push(PathBuf::from(pdm_home).join("Scripts"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        // Installed using the install script or pipx.
+        search_paths.push(home.join(".local").join("bin"));
+    }
+    let names = if cfg!(windows) {
+        vec!["pdm.exe", "pdm"]
+    } else {
+        vec!["pdm"]
+    };
+    for dir in search_paths {
+        for name in names.iter() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("PDM exe not found");
+    None
+}
+
+/// Lists the virtual environments of the project by spawning `pdm venv list`.
+pub fn list_venvs(pdm: &Path, project_dir: &Path, timeout: Option<Duration>) -> Vec<PathBuf> {
+    let output = run(
+        Command::new(pdm)
+            .arg("venv")
+            .arg("list")
+            .current_dir(project_dir),
+        timeout,
+    );
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_venv_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            trace!(
+                "Failed to list PDM venvs for {:?}: {:?}",
+                project_dir,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            vec![]
+        }
+        Err(err) => {
+            error!("Failed to spawn {:?} for {:?}: {:?}", pdm, project_dir, err);
+            vec![]
+        }
+    }
+}
+
+/// Sample output of `pdm venv list`:
+/// ```text
+/// Virtualenvs created with this project:
+///
+/// *  in-project: /home/user/project/.venv
+/// -  3.11: /home/user/.local/share/pdm/venvs/project-8Sgk-3.11
+/// ```
+pub fn parse_venv_list(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if !line.starts_with('*') && !line.starts_with('-') {
+                return None;
+            }
+            let (_, path) = line.split_once(": ")?;
+            let path = PathBuf::from(path.trim());
+            if path.is_absolute() {
+                Some(path)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
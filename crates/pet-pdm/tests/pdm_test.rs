@@ -0,0 +1,149 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_pdm_environments() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_pdm::Pdm;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path!(&["unix", "user_home"]);
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()));
+    let locator = Pdm::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    // PDM is not installed.
+    assert!(result.managers.is_empty());
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Pdm));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            (
+                project.join(".venv"),
+                "3.12.1".to_string(),
+                Some(project.clone())
+            ),
+            (
+                home.join(".local")
+                    .join("share")
+                    .join("pdm")
+                    .join("venvs")
+                    .join("my_app-3fG9kL2x-3.11"),
+                "3.11.9".to_string(),
+                None
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn identify_pdm_environments() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_pdm::Pdm;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(
+        HashMap::new(),
+        Some(resolve_test_path!(&["unix", "user_home"])),
+    );
+    let locator = Pdm::from(&environment);
+
+    // In project virtual env.
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let prefix = project.join(".venv");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Pdm));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, Some(project));
+
+    // Virtual env with the `pdm` marker in `pyvenv.cfg`.
+    let prefix = resolve_test_path!(&["unix", "other_venv"]);
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.version, "3.10.14".parse().ok());
+    assert_eq!(env.project, None);
+
+    // Virtual envs not created by PDM.
+    let prefix = resolve_test_path!(&["unix", "plain_venv"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            Some(prefix),
+            None
+        ))
+        .is_none());
+}
+
+#[test]
+fn parse_pdm_venv_list() {
+    use pet_pdm::manager::parse_venv_list;
+    use std::path::PathBuf;
+
+    let output = r#"Virtualenvs created with this project:
+
+*  in-project: /home/user/my_app/.venv
+-  3.11: /home/user/.local/share/pdm/venvs/my_app-3fG9kL2x-3.11
+"#;
+
+    assert_eq!(
+        parse_venv_list(output),
+        vec![
+            PathBuf::from("/home/user/my_app/.venv"),
+            PathBuf::from("/home/user/.local/share/pdm/venvs/my_app-3fG9kL2x-3.11"),
+        ]
+    );
+}
+
+#[test]
+fn manages_pdm_executables() {
+    use pet_core::Locator;
+    use pet_pdm::Pdm;
+    use pet_python_utils::test_utils::create_test_environment;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Pdm::from(&environment);
+
+    assert!(locator.manages_executable(&PathBuf::from("/home/user/.local/bin/pdm")));
+    assert!(locator.manages_executable(&PathBuf::from("pdm.exe")));
+    assert!(!locator.manages_executable(&PathBuf::from("/home/user/.local/bin/python")));
+}
@@ -2,19 +2,30 @@
 // Licensed under the MIT License.
 
 use pet_core::os_environment::Environment;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 // NOTE: Do not implement Default trait, as we do not want to ever forget to set the values.
 // Lets be explicit, this way we never miss a value (in Windows or Unix).
 pub struct EnvVariables {
+    pub home: Option<PathBuf>,
+    pub path: Option<String>,
     #[allow(dead_code)]
     pub pipenv_max_depth: u16,
     pub pipenv_pipfile: String,
+    /// When enabled, the virtual env is created in the `.venv` directory of the project.
+    pub pipenv_venv_in_project: Option<String>,
+    /// Directory where pipenv was installed, e.g. a pipx venv.
+    pub pipenv_home: Option<String>,
+    /// Directory containing the virtual envs created by pipenv.
+    pub workon_home: Option<String>,
 }
 
 impl EnvVariables {
     pub fn from(env: &dyn Environment) -> Self {
         EnvVariables {
+            home: env.get_user_home(),
+            path: env.get_env_var("PATH".to_string()),
             pipenv_max_depth: env
                 .get_env_var("PIPENV_MAX_DEPTH".to_string())
                 .map(|s| s.parse::<u16>().ok().unwrap_or(3))
@@ -22,6 +33,9 @@ impl EnvVariables {
             pipenv_pipfile: env
                 .get_env_var("PIPENV_PIPFILE".to_string())
                 .unwrap_or("Pipfile".to_string()),
+            pipenv_venv_in_project: env.get_env_var("PIPENV_VENV_IN_PROJECT".to_string()),
+            pipenv_home: env.get_env_var("PIPENV_HOME".to_string()),
+            workon_home: env.get_env_var("WORKON_HOME".to_string()),
         }
     }
 }
@@ -0,0 +1,83 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use base64::{engine::general_purpose, Engine as _};
+use lazy_static::lazy_static;
+use pet_fs::path::norm_case;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    static ref SANITIZE_NAME: Regex = Regex::new(r#"[ &$`!*@"()\[\]\\\r\n\t]"#)
+        .expect("Error generating RegEx for pipenv project name");
+}
+
+/// Whether the virtual env is created in the `.venv` directory of the project.
+pub fn is_venv_in_project(env_vars: &EnvVariables) -> bool {
+    matches!(
+        env_vars
+            .pipenv_venv_in_project
+            .as_deref()
+            .map(|value| value.trim().to_lowercase()),
+        Some(value) if value == "1" || value == "true" || value == "yes" || value == "on"
+    )
+}
+
+/// Directories containing the virtual envs created by pipenv.
+/// See https://pipenv.pypa.io/en/latest/virtualenv.html
+pub fn get_workon_home_dirs(env_vars: &EnvVariables) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(workon_home) = &env_vars.workon_home {
+        dirs.push(norm_case(PathBuf::from(workon_home)));
+    }
+    if let Some(home) = &env_vars.home {
+        dirs.push(home.join(".local").join("share").join("virtualenvs"));
+        if cfg!(target_os = "macos") {
+            dirs.push(home.join(".virtualenvs"));
+        }
+    }
+    dirs
+}
+
+// Source from https://github.com/pypa/pipenv/blob/v2024.0.1/pipenv/project.py#L543
+pub fn get_virtualenv_name(project_dir: &Path, pipfile: &Path) -> String {
+    // name = self._sanitize(name)
+    // hash = hashlib.sha256(location.encode()).digest()[:6]
+    // encoded_hash = base64.urlsafe_b64encode(hash).decode()
+    let name = project_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let sanitized_name = SANITIZE_NAME
+        .replace_all(&name, "_")
+        .chars()
+        .take(42)
+        .collect::<String>();
+    let mut hasher = Sha256::new();
+    hasher.update(pipfile.to_string_lossy().as_bytes());
+    let h_bytes = hasher.finalize();
+    let h_str = general_purpose::URL_SAFE
+        .encode(&h_bytes[..6])
+        .chars()
+        .take(8)
+        .collect::<String>();
+    format!("{sanitized_name}-{h_str}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_virtualenv_name() {
+        let project_dir = PathBuf::from("/home/user/my project");
+
+        assert_eq!(
+            get_virtualenv_name(&project_dir, &project_dir.join("Pipfile")),
+            "my_project-45EusJEJ"
+        );
+    }
+}
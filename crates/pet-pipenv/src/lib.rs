@@ -2,35 +2,41 @@
 // Licensed under the MIT License.
 
 use env_variables::EnvVariables;
+use environment_locations::{get_virtualenv_name, get_workon_home_dirs, is_venv_in_project};
+use manager::{find_pipenv_executable, get_venv};
 use pet_core::env::PythonEnv;
 use pet_core::os_environment::Environment;
-use pet_core::LocatorKind;
 use pet_core::{
     python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
     reporter::Reporter,
     Locator,
 };
+use pet_core::{Configuration, LocatorKind};
 use pet_fs::path::norm_case;
-use pet_python_utils::executable::find_executables;
+use pet_python_utils::executable::{find_executable, find_executables};
 use pet_python_utils::version;
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::{fs, path::PathBuf};
 
 mod env_variables;
+pub mod environment_locations;
+pub mod manager;
 
-fn get_pipenv_project(env: &PythonEnv) -> Option<PathBuf> {
+fn get_prefix(env: &PythonEnv) -> Option<PathBuf> {
     if let Some(prefix) = &env.prefix {
-        get_pipenv_project_from_prefix(prefix)
+        return Some(prefix.clone());
+    }
+    // If the parent is bin or script, then get the parent.
+    let bin = env.executable.parent()?;
+    if bin.file_name().unwrap_or_default() == Path::new("bin")
+        || bin.file_name().unwrap_or_default() == Path::new("Scripts")
+    {
+        Some(bin.parent()?.to_path_buf())
     } else {
-        // If the parent is bin or script, then get the parent.
-        let bin = env.executable.parent()?;
-        if bin.file_name().unwrap_or_default() == Path::new("bin")
-            || bin.file_name().unwrap_or_default() == Path::new("Scripts")
-        {
-            get_pipenv_project_from_prefix(env.executable.parent()?.parent()?)
-        } else {
-            get_pipenv_project_from_prefix(env.executable.parent()?)
-        }
+        Some(bin.to_path_buf())
     }
 }
 
@@ -45,77 +51,159 @@ fn get_pipenv_project_from_prefix(prefix: &Path) -> Option<PathBuf> {
     }
 }
 
-fn is_pipenv(env: &PythonEnv, env_vars: &EnvVariables) -> bool {
-    if let Some(project_path) = get_pipenv_project(env) {
-        if project_path.join(env_vars.pipenv_pipfile.clone()).exists() {
-            return true;
-        }
-    }
-    // If we have a Pipfile, then this is a pipenv environment.
-    // Else likely a virtualenvwrapper or the like.
-    if let Some(project_path) = get_pipenv_project(env) {
-        project_path.join(env_vars.pipenv_pipfile.clone()).exists()
-    } else {
-        false
-    }
-}
-
 pub struct PipEnv {
     env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+    /// Time after which pipenv is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
 }
 
 impl PipEnv {
     pub fn from(environment: &dyn Environment) -> PipEnv {
         PipEnv {
             env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+            timeout: Arc::new(Mutex::new(None)),
         }
     }
+    fn is_pipenv_project(&self, project_dir: &Path) -> bool {
+        project_dir.join(&self.env_vars.pipenv_pipfile).is_file()
+    }
+    /// The project the environment belongs to.
+    fn get_pipenv_project(&self, prefix: &Path) -> Option<PathBuf> {
+        // If we have a Pipfile, then this is a pipenv environment.
+        // Else likely a virtualenvwrapper or the like.
+        if let Some(project_dir) = get_pipenv_project_from_prefix(prefix) {
+            return Some(project_dir).filter(|dir| self.is_pipenv_project(dir));
+        }
+        // In project virtual env, i.e. `<project>/.venv`.
+        if prefix.ends_with(".venv") {
+            if let Some(project_dir) = prefix.parent() {
+                if self.is_pipenv_project(project_dir) {
+                    return Some(project_dir.to_path_buf());
+                }
+            }
+        }
+        // Virtual envs are named after the project & a hash of the path to the Pipfile.
+        let name = prefix.file_name()?.to_string_lossy().to_string();
+        self.workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|dir| {
+                self.is_pipenv_project(dir)
+                    && get_virtualenv_name(dir, &dir.join(&self.env_vars.pipenv_pipfile)) == name
+            })
+            .cloned()
+    }
+    /// The virtual env pipenv would use for the project (without spawning pipenv).
+    fn get_project_venv(&self, project_dir: &Path) -> Option<PathBuf> {
+        let in_project_venv = project_dir.join(".venv");
+        if is_venv_in_project(&self.env_vars) || in_project_venv.is_dir() {
+            return Some(in_project_venv);
+        }
+        let name = get_virtualenv_name(
+            project_dir,
+            &project_dir.join(&self.env_vars.pipenv_pipfile),
+        );
+        get_workon_home_dirs(&self.env_vars)
+            .into_iter()
+            .map(|dir| dir.join(&name))
+            .find(|prefix| prefix.is_dir())
+    }
 }
+
+fn get_environment(
+    env: &PythonEnv,
+    prefix: Option<PathBuf>,
+    project: PathBuf,
+) -> Option<PythonEnvironment> {
+    let bin = env.executable.parent()?;
+    let symlinks = find_executables(bin);
+    let mut version = env.version.clone();
+    if version.is_none() && prefix.is_some() {
+        if let Some(prefix) = &prefix {
+            version = version::from_creator_for_virtual_env(prefix);
+        }
+    }
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Pipenv))
+            .source_locator(Some(LocatorKind::PipEnv.name()))
+            .executable(Some(env.executable.clone()))
+            .version(version.and_then(|v| v.parse().ok()))
+            .prefix(prefix)
+            .project(Some(project))
+            .symlinks(Some(symlinks))
+            .build(),
+    )
+}
+
 impl Locator for PipEnv {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::PipEnv
     }
+    fn description(&self) -> &'static str {
+        "Finds environments created by Pipenv, reads WORKON_HOME, PIPENV_VENV_IN_PROJECT and .project files"
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("pipenv") | Some("pipenv.exe")
+        )
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::Pipenv]
     }
 
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
-        if !is_pipenv(env, &self.env_vars) {
-            return None;
-        }
-        let project_path = get_pipenv_project(env)?;
-        let mut prefix = env.prefix.clone();
-        if prefix.is_none() {
-            if let Some(bin) = env.executable.parent() {
-                if bin.file_name().unwrap_or_default() == Path::new("bin")
-                    || bin.file_name().unwrap_or_default() == Path::new("Scripts")
-                {
-                    if let Some(dir) = bin.parent() {
-                        prefix = Some(dir.to_owned());
-                    }
-                }
+        let prefix = get_prefix(env)?;
+        let project_path = self.get_pipenv_project(&prefix)?;
+        // The directory of the executable is only the prefix when its in `bin` or `Scripts`.
+        let prefix = if env.prefix.is_some() || prefix != env.executable.parent()? {
+            Some(prefix)
+        } else {
+            None
+        };
+        get_environment(env, prefix, project_path)
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        // Environments in known locations are identified by callers using `try_from`,
+        // here we only look for the environments of the projects in the workspace.
+        let pipenv = find_pipenv_executable(&self.env_vars);
+        let mut reported = HashSet::new();
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        let timeout = *self.timeout.lock().unwrap();
+        for project_dir in workspace_directories
+            .iter()
+            .filter(|dir| self.is_pipenv_project(dir))
+        {
+            // pipenv knows of the custom venv names (`PIPENV_CUSTOM_VENV_NAME`) & other locations.
+            let prefix = match pipenv
+                .as_ref()
+                .and_then(|pipenv| get_venv(pipenv, project_dir, timeout))
+                .or_else(|| self.get_project_venv(project_dir))
+            {
+                Some(prefix) => prefix,
+                None => continue,
+            };
+            if !reported.insert(prefix.clone()) {
+                continue;
             }
-        }
-        let bin = env.executable.parent()?;
-        let symlinks = find_executables(bin);
-        let mut version = env.version.clone();
-        if version.is_none() && prefix.is_some() {
-            if let Some(prefix) = &prefix {
-                version = version::from_creator_for_virtual_env(prefix);
+            if let Some(executable) = find_executable(&prefix) {
+                let env = PythonEnv::new(executable, Some(prefix.clone()), None);
+                if let Some(env) = get_environment(&env, Some(prefix), project_dir.clone()) {
+                    reporter.report_environment(&env);
+                }
             }
         }
-        Some(
-            PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Pipenv))
-                .executable(Some(env.executable.clone()))
-                .version(version)
-                .prefix(prefix)
-                .project(Some(project_path))
-                .symlinks(Some(symlinks))
-                .build(),
-        )
-    }
-
-    fn find(&self, _reporter: &dyn Reporter) {
-        //
     }
 }
@@ -0,0 +1,76 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::{trace, warn};
+use pet_process::run;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+fn get_pipenv_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["pipenv.exe", "pipenv"]
+    } else {
+        vec!["pipenv"]
+    }
+}
+
+/// Looks for the pipenv executable in `PIPENV_HOME`, the PATH variable & the default install locations.
+pub fn find_pipenv_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(pipenv_home) = &env_vars.pipenv_home {
+        search_paths.push(PathBuf::from(pipenv_home));
+        search_paths.push(PathBuf::from(pipenv_home).join("bin"));
+        search_paths.push(PathBuf::from(pipenv_home).join("Scripts"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        // Installed using `pip install --user pipenv` or pipx.
+        search_paths.push(home.join(".local").join("bin"));
+    }
+    for dir in search_paths {
+        for name in get_pipenv_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("pipenv exe not found");
+    None
+}
+
+/// Virtual env of the project, as reported by `pipenv --venv`.
+pub fn get_venv(pipenv: &Path, project_dir: &Path, timeout: Option<Duration>) -> Option<PathBuf> {
+    let output = match run(
+        Command::new(pipenv).arg("--venv").current_dir(project_dir),
+        timeout,
+    ) {
+        Ok(output) => output,
+        Err(err) => {
+            warn!("Failed to spawn {:?} --venv: {:?}", pipenv, err);
+            return None;
+        }
+    };
+    if !output.status.success() {
+        trace!(
+            "{:?} --venv failed in {:?}: {}",
+            pipenv,
+            project_dir,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    let venv = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if venv.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(venv))
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_pipenv_environments_in_workspace() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_pipenv::PipEnv;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let environment = create_test_environment(
+        HashMap::from([("PIPENV_VENV_IN_PROJECT".to_string(), "1".to_string())]),
+        None,
+    );
+    let locator = PipEnv::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![
+            project.clone(),
+            resolve_test_path!(&["unix", "no_pipfile"]),
+        ]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let environments = reporter.to_locator_result().environments;
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].kind, Some(PythonEnvironmentKind::Pipenv));
+    assert_eq!(environments[0].prefix, Some(project.join(".venv")));
+    assert_eq!(environments[0].project, Some(project));
+}
+
+#[cfg(unix)]
+#[test]
+fn identify_in_project_pipenv_environments() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_pipenv::PipEnv;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = PipEnv::from(&environment);
+
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let prefix = project.join(".venv");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Pipenv));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, Some(project));
+
+    // No Pipfile in the parent directory.
+    let prefix = resolve_test_path!(&["unix", "no_pipfile", ".venv"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            Some(prefix),
+            None
+        ))
+        .is_none());
+}
@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// See https://pixi.sh/latest/reference/environment_variables/
+pub fn get_pixi_home(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(pixi_home) = &env_vars.pixi_home {
+        return Some(norm_case(PathBuf::from(pixi_home)));
+    }
+    env_vars.home.as_ref().map(|home| home.join(".pixi"))
+}
+
+/// Directory containing the environments of the tools installed using `pixi global install`.
+pub fn get_global_envs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    get_pixi_home(env_vars).map(|pixi_home| pixi_home.join("envs"))
+}
+
+/// Directory containing the environments of a project, i.e. `<project>/.pixi/envs`.
+pub fn get_project_envs_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".pixi").join("envs")
+}
+
+/// Names of the environments defined in the manifest of a project (`pixi.toml` or the `[tool.pixi]` section of `pyproject.toml`).
+/// Every project has a `default` environment, others are defined in the `[environments]` section.
+/// Returns None if this is not a pixi project.
+pub fn get_environment_names(project_dir: &Path) -> Option<Vec<String>> {
+    let manifest = if let Some(manifest) = read_toml(&project_dir.join("pixi.toml")) {
+        manifest
+    } else {
+        read_toml(&project_dir.join("pyproject.toml"))?
+            .get("tool")?
+            .get("pixi")?
+            .clone()
+    };
+    let mut names = vec!["default".to_string()];
+    if let Some(environments) = manifest.get("environments").and_then(|e| e.as_table()) {
+        for name in environments.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    Some(names)
+}
+
+fn read_toml(file: &Path) -> Option<toml::Value> {
+    fs::read_to_string(file).ok()?.parse::<toml::Value>().ok()
+}
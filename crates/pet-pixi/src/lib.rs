@@ -0,0 +1,157 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::{get_environment_names, get_global_envs_dir, get_project_envs_dir};
+use log::trace;
+use manager::find_pixi_executable;
+use pet_conda::{environments::get_conda_environment_info, utils::is_conda_env};
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::find_executables;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+fn get_prefix(env: &PythonEnv) -> Option<PathBuf> {
+    if let Some(prefix) = &env.prefix {
+        return Some(prefix.clone());
+    }
+    let parent_dir = env.executable.parent()?;
+    if is_conda_env(parent_dir) {
+        // Generally the case on Windows, the exe is in the root of the env.
+        Some(parent_dir.to_path_buf())
+    } else if parent_dir.ends_with("bin") || parent_dir.ends_with("Scripts") {
+        Some(parent_dir.parent()?.to_path_buf())
+    } else {
+        None
+    }
+}
+
+/// pixi environments have the same layout as conda environments.
+fn get_environment(
+    prefix: &Path,
+    project: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    if !is_conda_env(prefix) {
+        return None;
+    }
+    let env = get_conda_environment_info(prefix, &None)?;
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Pixi))
+            .source_locator(Some(LocatorKind::Pixi.name()))
+            .name(prefix.file_name().map(|n| n.to_string_lossy().to_string()))
+            .executable(env.executable)
+            .version(env.version.and_then(|v| v.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .arch(env.arch)
+            .project(project)
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+pub struct Pixi {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Pixi {
+    pub fn from(environment: &dyn Environment) -> Pixi {
+        Pixi {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = find_pixi_executable(&self.env_vars)?;
+        Some(EnvManager::new(executable, EnvManagerType::Pixi, None))
+    }
+}
+
+impl Locator for Pixi {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Pixi
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by pixi in the .pixi directory of workspace folders, reads PIXI_HOME"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("pixi") | Some("pixi.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Pixi]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let prefix = get_prefix(env)?;
+        let envs_dir = prefix.parent()?;
+        // Environment of a project, i.e. `<project>/.pixi/envs/<name>`.
+        if envs_dir.ends_with(Path::new(".pixi").join("envs")) {
+            let project = envs_dir.parent()?.parent()?.to_path_buf();
+            return get_environment(&prefix, Some(project), &self.get_manager());
+        }
+        if get_global_envs_dir(&self.env_vars).as_deref() == Some(envs_dir) {
+            return get_environment(&prefix, None, &self.get_manager());
+        }
+        None
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        for project_dir in workspace_directories {
+            let envs_dir = get_project_envs_dir(&project_dir);
+            trace!("Searching for pixi environments in {:?}", envs_dir);
+            let prefixes = match get_environment_names(&project_dir) {
+                Some(names) => names.iter().map(|name| envs_dir.join(name)).collect(),
+                None => list_dirs(&envs_dir),
+            };
+            for prefix in prefixes {
+                if let Some(env) = get_environment(&prefix, Some(project_dir.clone()), &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+
+        if let Some(envs_dir) = get_global_envs_dir(&self.env_vars) {
+            trace!("Searching for global pixi environments in {:?}", envs_dir);
+            for prefix in list_dirs(&envs_dir) {
+                if let Some(env) = get_environment(&prefix, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
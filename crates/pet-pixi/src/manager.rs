@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{env_variables::EnvVariables, environment_locations::get_pixi_home};
+use log::trace;
+use std::{env, path::PathBuf};
+
+fn get_pixi_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["pixi.exe", "pixi"]
+    } else {
+        vec!["pixi"]
+    }
+}
+
+/// Looks for the pixi executable in the bin directory of `PIXI_HOME` & the PATH variable.
+pub fn find_pixi_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(pixi_home) = get_pixi_home(env_vars) {
+        search_paths.push(pixi_home.join("bin"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    for dir in search_paths {
+        for name in get_pixi_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("pixi exe not found");
+    None
+}
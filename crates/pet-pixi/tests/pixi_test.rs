@@ -0,0 +1,126 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_environments_of_single_and_multi_env_projects() {
+    use pet_core::{
+        manager::EnvManagerType, python_environment::PythonEnvironmentKind, Configuration, Locator,
+    };
+    use pet_pixi::Pixi;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let pixi_home = resolve_test_path!(&["unix", "pixi_home"]);
+    let single_env = resolve_test_path!(&["unix", "single_env"]);
+    let multi_env = resolve_test_path!(&["unix", "multi_env"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "PIXI_HOME".to_string(),
+            pixi_home.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Pixi::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![single_env.clone(), multi_env.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    assert_eq!(result.managers.len(), 1);
+    assert_eq!(result.managers[0].tool, EnvManagerType::Pixi);
+    assert_eq!(
+        result.managers[0].executable,
+        pixi_home.join("bin").join("pixi")
+    );
+
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Pixi));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.name.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    // `lint` has not been installed & `removed` is no longer defined in the manifest.
+    assert_eq!(
+        environments,
+        vec![
+            (
+                multi_env.join(".pixi/envs/default"),
+                "default".to_string(),
+                "3.12.4".to_string(),
+                Some(multi_env.clone())
+            ),
+            (
+                multi_env.join(".pixi/envs/py311"),
+                "py311".to_string(),
+                "3.11.9".to_string(),
+                Some(multi_env.clone())
+            ),
+            (
+                pixi_home.join("envs/ipython"),
+                "ipython".to_string(),
+                "3.12.4".to_string(),
+                None
+            ),
+            (
+                single_env.join(".pixi/envs/default"),
+                "default".to_string(),
+                "3.12.4".to_string(),
+                Some(single_env.clone())
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_pixi_environments() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_pixi::Pixi;
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use std::collections::HashMap;
+
+    let pixi_home = resolve_test_path!(&["unix", "pixi_home"]);
+    let multi_env = resolve_test_path!(&["unix", "multi_env"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "PIXI_HOME".to_string(),
+            pixi_home.to_string_lossy().to_string(),
+        )]),
+        None,
+    );
+    let locator = Pixi::from(&environment);
+
+    // Projects need not be in the workspace.
+    let prefix = multi_env.join(".pixi/envs/py311");
+    let env = locator
+        .try_from(&PythonEnv::new(prefix.join("bin/python"), None, None))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Pixi));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.version, "3.11.9".parse().ok());
+    assert_eq!(env.project, Some(multi_env));
+
+    let prefix = pixi_home.join("envs/ipython");
+    let env = locator
+        .try_from(&PythonEnv::new(prefix.join("bin/python"), None, None))
+        .unwrap();
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, None);
+}
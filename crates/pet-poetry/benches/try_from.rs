@@ -0,0 +1,66 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Measures `Poetry::try_from` with 50 Poetry environments.
+//! Run with `cargo bench -p pet-poetry`.
+
+#[path = "../tests/common.rs"]
+mod common;
+
+use common::create_test_environment;
+use pet_core::{env::PythonEnv, Configuration, Locator};
+use pet_poetry::Poetry;
+use std::{collections::HashMap, fs, hint::black_box, time::Instant};
+
+const ENVIRONMENT_COUNT: usize = 50;
+const ITERATIONS: u32 = 1_000;
+
+fn main() {
+    let root = std::env::temp_dir().join(format!("pet_poetry_bench_{}", std::process::id()));
+    let virtualenvs = root.join("virtualenvs");
+    let executables = (0..ENVIRONMENT_COUNT)
+        .map(|i| {
+            let prefix = virtualenvs.join(format!("project{i}-gNT2WXAV-py3.12"));
+            let bin = prefix.join(if cfg!(windows) { "Scripts" } else { "bin" });
+            fs::create_dir_all(&bin).unwrap();
+            fs::write(prefix.join("pyvenv.cfg"), "version_info = 3.12.1\n").unwrap();
+            fs::write(bin.join("activate"), "").unwrap();
+            let python = bin.join(if cfg!(windows) {
+                "python.exe"
+            } else {
+                "python"
+            });
+            fs::write(&python, "").unwrap();
+            PythonEnv::new(python, Some(prefix), None)
+        })
+        .collect::<Vec<_>>();
+
+    let environment = create_test_environment(
+        HashMap::from([(
+            "POETRY_VIRTUALENVS_PATH".to_string(),
+            virtualenvs.to_string_lossy().to_string(),
+        )]),
+        Some(root.join("home")),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        poetry_scan_prefix_dirs: Some(true),
+        ..Default::default()
+    });
+    // The first call searches for the environments, subsequent calls use the cached lookup.
+    let start = Instant::now();
+    assert!(locator.try_from(&executables[0]).is_some());
+    let first = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for env in &executables {
+            black_box(locator.try_from(env));
+        }
+    }
+    let cached = start.elapsed() / (ITERATIONS * ENVIRONMENT_COUNT as u32);
+    let _ = fs::remove_dir_all(&root);
+
+    println!("{ENVIRONMENT_COUNT} Poetry environments, try_from: first call {first:?}, cached {cached:?}");
+}
@@ -241,13 +241,10 @@ create = false
 
 "#;
 
-        assert_eq!(
-            parse_contents(&cfg.to_string())
-                .unwrap()
-                .virtualenvs_in_project
-                .unwrap_or_default(),
-            false
-        );
+        assert!(!parse_contents(cfg)
+            .unwrap()
+            .virtualenvs_in_project
+            .unwrap_or_default());
 
         let cfg = r#"
 [virtualenvs]
@@ -255,47 +252,35 @@ in-project = true
 create = false
 
 "#;
-        assert_eq!(
-            parse_contents(&cfg.to_string())
-                .unwrap()
-                .virtualenvs_in_project
-                .unwrap_or_default(),
-            true
-        );
+        assert!(parse_contents(cfg)
+            .unwrap()
+            .virtualenvs_in_project
+            .unwrap_or_default());
 
         let cfg = r#"
 [virtualenvs]
 create = false
 
 "#;
-        assert_eq!(
-            parse_contents(&cfg.to_string())
-                .unwrap()
-                .virtualenvs_in_project
-                .unwrap_or_default(),
-            false
-        );
+        assert!(!parse_contents(cfg)
+            .unwrap()
+            .virtualenvs_in_project
+            .unwrap_or_default());
 
         let cfg = r#"
 virtualenvs.in-project = true # comment
 "#;
-        assert_eq!(
-            parse_contents(&cfg.to_string())
-                .unwrap()
-                .virtualenvs_in_project
-                .unwrap_or_default(),
-            true
-        );
+        assert!(parse_contents(cfg)
+            .unwrap()
+            .virtualenvs_in_project
+            .unwrap_or_default());
 
         let cfg = r#"
 "#;
-        assert_eq!(
-            parse_contents(&cfg.to_string())
-                .unwrap()
-                .virtualenvs_in_project
-                .unwrap_or_default(),
-            false
-        );
+        assert!(!parse_contents(cfg)
+            .unwrap()
+            .virtualenvs_in_project
+            .unwrap_or_default());
     }
 
     #[test]
@@ -305,7 +290,7 @@ cache-dir = "/path/to/cache/directory"
 
 "#;
         assert_eq!(
-            parse_contents(&cfg.to_string()).unwrap().cache_dir,
+            parse_contents(cfg).unwrap().cache_dir,
             Some(PathBuf::from("/path/to/cache/directory".to_string()))
         );
 
@@ -313,7 +298,7 @@ cache-dir = "/path/to/cache/directory"
 some-other-value = 1234
 
 "#;
-        assert_eq!(parse_contents(&cfg.to_string()).unwrap().cache_dir, None);
+        assert_eq!(parse_contents(cfg).unwrap().cache_dir, None);
     }
 
     #[test]
@@ -323,7 +308,7 @@ virtualenvs.path = "/path/to/virtualenvs"
 
 "#;
         assert_eq!(
-            parse_contents(&cfg.to_string()).unwrap().virtualenvs_path,
+            parse_contents(cfg).unwrap().virtualenvs_path,
             Some(PathBuf::from("/path/to/virtualenvs".to_string()))
         );
 
@@ -331,10 +316,7 @@ virtualenvs.path = "/path/to/virtualenvs"
 some-other-value = 1234
 
 "#;
-        assert_eq!(
-            parse_contents(&cfg.to_string()).unwrap().virtualenvs_path,
-            None
-        );
+        assert_eq!(parse_contents(cfg).unwrap().virtualenvs_path, None);
     }
 
     #[test]
@@ -343,7 +325,7 @@ some-other-value = 1234
 cache-dir = "/path/to/cache/directory"
 "#;
         assert_eq!(
-            parse_contents(&cfg.to_string()).unwrap().virtualenvs_path,
+            parse_contents(cfg).unwrap().virtualenvs_path,
             Some(PathBuf::from("/path/to/cache/directory/virtualenvs"))
         );
     }
@@ -3,8 +3,9 @@
 
 use std::path::PathBuf;
 
-use pet_core::python_environment::{
-    PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind,
+use pet_core::{
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    LocatorKind,
 };
 use pet_python_utils::{executable::find_executables, version};
 
@@ -12,7 +13,7 @@ use crate::manager::PoetryManager;
 
 pub fn create_poetry_env(
     prefix: &PathBuf,
-    project_dir: PathBuf,
+    project_dir: Option<PathBuf>,
     manager: Option<PoetryManager>,
 ) -> Option<PythonEnvironment> {
     if !prefix.exists() {
@@ -25,11 +26,12 @@ pub fn create_poetry_env(
     let version = version::from_creator_for_virtual_env(prefix);
     Some(
         PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Poetry))
+            .source_locator(Some(LocatorKind::Poetry.name()))
             .executable(Some(executables[0].clone()))
             .prefix(Some(prefix.clone()))
-            .version(version)
+            .version(version.and_then(|v| v.parse().ok()))
             .manager(manager.map(|m| m.to_manager()))
-            .project(Some(project_dir.clone()))
+            .project(project_dir)
             .symlinks(Some(executables))
             .build(),
     )
@@ -21,6 +21,9 @@ use crate::{
 lazy_static! {
     static ref SANITIZE_NAME: Regex = Regex::new("[ $`!*@\"\\\r\n\t]")
         .expect("Error generating RegEx for poetry file path hash generator");
+    /// Name of virtual envs created by Poetry, `<name>-<hash>-py<major>.<minor>`
+    static ref POETRY_ENV_NAME: Regex = Regex::new(r"^.+-[A-Za-z0-9_-]{8}-py\d+\.\d+$")
+        .expect("Error generating RegEx for poetry environment name");
 }
 
 pub fn list_environments(
@@ -53,33 +56,52 @@ pub fn list_environments(
     }
 
     for (workspace_dir, pyproject_toml) in workspace_dirs {
-        let virtualenv_prefix = generate_env_name(&pyproject_toml.name, workspace_dir);
-        trace!(
-            "Found pyproject.toml ({}): {:?} in {:?}",
-            virtualenv_prefix,
-            pyproject_toml.name,
-            workspace_dir
-        );
+        // Packages of a Poetry workspace (monorepo) can be Poetry projects in their own right.
+        // All of their environments belong to the workspace.
+        let mut project_dirs = vec![(workspace_dir.clone(), pyproject_toml.name.clone())];
+        for package_dir in pyproject_toml.package_roots(workspace_dir) {
+            if let Some(package_pyproject_toml) = PyProjectToml::find(&package_dir) {
+                project_dirs.push((package_dir, package_pyproject_toml.name));
+            }
+        }
+
+        for (project_dir, name) in project_dirs {
+            let virtualenv_prefix = generate_env_name(&name, &project_dir);
+            trace!(
+                "Found pyproject.toml ({}): {:?} in {:?}",
+                virtualenv_prefix,
+                name,
+                project_dir
+            );
 
-        for virtual_env in [
-            list_all_environments_from_project_config(&global_config, workspace_dir, env)
-                .unwrap_or_default(),
-            global_envs.clone(),
-        ]
-        .concat()
-        {
-            // Check if this virtual env belongs to this project
-            let name = virtual_env
-                .file_name()
-                .unwrap_or_default()
-                .to_str()
-                .unwrap_or_default();
-            // Look for .venv as well, in case we create the virtual envs in the local project folder.
-            if name.starts_with(&virtualenv_prefix) || name.starts_with(".venv") {
-                if let Some(env) =
-                    create_poetry_env(&virtual_env, workspace_dir.clone(), manager.clone())
-                {
-                    envs.push(env);
+            for virtual_env in [
+                list_all_environments_from_project_config(&global_config, &project_dir, env)
+                    .unwrap_or_default(),
+                global_envs.clone(),
+            ]
+            .concat()
+            {
+                // Check if this virtual env belongs to this project
+                let name = virtual_env
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_str()
+                    .unwrap_or_default();
+                // Look for .venv as well, in case we create the virtual envs in the local project folder.
+                if name.starts_with(&virtualenv_prefix) || name.starts_with(".venv") {
+                    if let Some(env) = create_poetry_env(
+                        &virtual_env,
+                        Some(workspace_dir.clone()),
+                        manager.clone(),
+                    ) {
+                        // Multiple packages can share the same environment.
+                        if !envs
+                            .iter()
+                            .any(|e: &PythonEnvironment| e.prefix == env.prefix)
+                        {
+                            envs.push(env);
+                        }
+                    }
                 }
             }
         }
@@ -88,6 +110,33 @@ pub fn list_environments(
     Some(envs)
 }
 
+/// Lists the environments in the Poetry virtualenvs directory that follow the Poetry naming convention.
+/// This does not require the project directories nor spawning Poetry (useful in ephemeral CI environments),
+/// however the project associated with these environments is unknown.
+pub fn list_environments_in_virtualenvs_dir(
+    env: &EnvVariables,
+    manager: Option<PoetryManager>,
+) -> Vec<PythonEnvironment> {
+    let config = match Config::find_global(env) {
+        Some(config) => config,
+        None => return vec![],
+    };
+    trace!(
+        "Scanning Poetry virtualenvs directory {:?}",
+        config.virtualenvs_path
+    );
+    list_all_environments_from_config(&config)
+        .unwrap_or_default()
+        .iter()
+        .filter(|path| is_poetry_env_name(path))
+        .filter_map(|path| create_poetry_env(path, None, manager.clone()))
+        .collect()
+}
+
+fn is_poetry_env_name(path: &Path) -> bool {
+    POETRY_ENV_NAME.is_match(&path.file_name().unwrap_or_default().to_string_lossy())
+}
+
 fn list_all_environments_from_project_config(
     global: &Option<Config>,
     path: &Path,
@@ -218,6 +267,15 @@ mod tests {
         assert_eq!(hashed_name, "new-project-TbBV0MKD-py");
     }
 
+    #[test]
+    fn test_poetry_env_name() {
+        assert!(is_poetry_env_name(Path::new("poetry-demo-gNT2WXAV-py3.12")));
+        assert!(is_poetry_env_name(Path::new("new-project-Tb_V0-KD-py3.9")));
+        assert!(!is_poetry_env_name(Path::new("poetry-demo-gNT2WXAV-py")));
+        assert!(!is_poetry_env_name(Path::new("poetry-demo-py3.12")));
+        assert!(!is_poetry_env_name(Path::new(".venv")));
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_hash_generation_windows() {
@@ -3,11 +3,19 @@
 
 use lazy_static::lazy_static;
 use log::{error, trace};
-use pet_core::python_environment::PythonEnvironment;
+use pet_core::{python_environment::PythonEnvironment, reporter::Reporter};
+use pet_fs::path::is_absolute_on_any_platform;
+use pet_process::run;
 use regex::Regex;
-use std::{path::PathBuf, time::SystemTime};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
 
-use crate::{environment::create_poetry_env, manager::PoetryManager};
+use crate::{
+    environment::create_poetry_env, manager::PoetryManager, pyproject_toml::PyProjectToml,
+};
 
 lazy_static! {
     static ref SANITIZE_NAME: Regex = Regex::new("[ $`!*@\"\\\r\n\t]")
@@ -18,14 +26,38 @@ pub fn list_environments(
     executable: &PathBuf,
     workspace_dirs: &Vec<PathBuf>,
     manager: &PoetryManager,
+    timeout: Option<Duration>,
+    reporter: &dyn Reporter,
 ) -> Vec<PythonEnvironment> {
     let mut envs = vec![];
     for workspace_dir in workspace_dirs {
-        if let Some(workspace_envs) = get_environments(executable, workspace_dir) {
-            for workspace_env in workspace_envs {
-                if let Some(env) =
-                    create_poetry_env(&workspace_env, workspace_dir.clone(), Some(manager.clone()))
+        // Packages of a Poetry workspace (monorepo) can be Poetry projects in their own right.
+        let mut project_dirs = vec![workspace_dir.clone()];
+        if let Some(pyproject_toml) = PyProjectToml::find(workspace_dir) {
+            project_dirs.extend(pyproject_toml.package_roots(workspace_dir));
+        }
+        for project_dir in project_dirs {
+            // A project can have an environment for each Python version, hence list all of them.
+            // The active environment is always in that list, `env info` is only used if listing fails.
+            let project_envs = get_environments(executable, &project_dir, timeout, reporter)
+                .or_else(|| {
+                    get_active_environment(executable, &project_dir, timeout, reporter)
+                        .map(|env| vec![env])
+                })
+                .unwrap_or_default();
+            for workspace_env in project_envs {
+                // Multiple packages can share the same environment.
+                if envs
+                    .iter()
+                    .any(|e: &PythonEnvironment| e.prefix.as_ref() == Some(&workspace_env))
                 {
+                    continue;
+                }
+                if let Some(env) = create_poetry_env(
+                    &workspace_env,
+                    Some(workspace_dir.clone()),
+                    Some(manager.clone()),
+                ) {
                     envs.push(env);
                 }
             }
@@ -34,14 +66,21 @@ pub fn list_environments(
     envs
 }
 
-fn get_environments(executable: &PathBuf, workspace_dir: &PathBuf) -> Option<Vec<PathBuf>> {
+fn get_environments(
+    executable: &PathBuf,
+    workspace_dir: &PathBuf,
+    timeout: Option<Duration>,
+    reporter: &dyn Reporter,
+) -> Option<Vec<PathBuf>> {
     let start = SystemTime::now();
-    let result = std::process::Command::new(executable)
-        .arg("env")
-        .arg("list")
-        .arg("--full-path")
-        .current_dir(workspace_dir)
-        .output();
+    let result = run(
+        Command::new(executable)
+            .arg("env")
+            .arg("list")
+            .arg("--full-path")
+            .current_dir(workspace_dir),
+        timeout,
+    );
     trace!(
         "Executed Poetry ({}ms): {:?} env list --full-path for {:?}",
         start.elapsed().unwrap_or_default().as_millis(),
@@ -76,12 +115,68 @@ fn get_environments(executable: &PathBuf, workspace_dir: &PathBuf) -> Option<Vec
             }
         }
         Err(err) => {
-            error!("Failed to execute Poetry env list {:?}", err);
+            reporter.report_error(&format!("Failed to execute Poetry env list {:?}", err));
             None
         }
     }
 }
 
+/// Returns the environment Poetry would use for the project, using `poetry env info --path`.
+pub fn get_active_environment(
+    executable: &Path,
+    project_dir: &Path,
+    timeout: Option<Duration>,
+    reporter: &dyn Reporter,
+) -> Option<PathBuf> {
+    let start = SystemTime::now();
+    let result = run(
+        Command::new(executable)
+            .arg("env")
+            .arg("info")
+            .arg("--path")
+            .current_dir(project_dir),
+        timeout,
+    );
+    trace!(
+        "Executed Poetry ({}ms): {:?} env info --path for {:?}",
+        start.elapsed().unwrap_or_default().as_millis(),
+        executable,
+        project_dir
+    );
+    match result {
+        Ok(output) => {
+            if output.status.success() {
+                parse_env_info_path_output(&String::from_utf8_lossy(&output.stdout))
+            } else {
+                trace!(
+                    "Failed to get active Poetry Env using exe {:?} ({:?}) {}",
+                    executable,
+                    output.status.code().unwrap_or_default(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                None
+            }
+        }
+        Err(err) => {
+            reporter.report_error(&format!("Failed to execute Poetry env info {:?}", err));
+            None
+        }
+    }
+}
+
+/// Poetry can print warnings (e.g. about the Python version being used) before the path, hence the last line is the path.
+fn parse_env_info_path_output(output: &str) -> Option<PathBuf> {
+    let line = output
+        .lines()
+        .map(|line| line.trim())
+        .rfind(|line| !line.is_empty())?;
+    if is_absolute_on_any_platform(line) {
+        Some(PathBuf::from(line))
+    } else {
+        None
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PoetryConfig {
     pub cache_dir: Option<PathBuf>,
@@ -89,11 +184,15 @@ pub struct PoetryConfig {
     pub virtualenvs_path: Option<PathBuf>,
 }
 
-pub fn get_config(executable: &PathBuf, workspace_dir: &PathBuf) -> PoetryConfig {
-    let cache_dir = get_config_path(executable, workspace_dir, "cache-dir");
-    let virtualenvs_path = get_config_path(executable, workspace_dir, "virtualenvs.path");
+pub fn get_config(
+    executable: &PathBuf,
+    workspace_dir: &PathBuf,
+    timeout: Option<Duration>,
+) -> PoetryConfig {
+    let cache_dir = get_config_path(executable, workspace_dir, "cache-dir", timeout);
+    let virtualenvs_path = get_config_path(executable, workspace_dir, "virtualenvs.path", timeout);
     let virtualenvs_in_project =
-        get_config_bool(executable, workspace_dir, "virtualenvs.in-project");
+        get_config_bool(executable, workspace_dir, "virtualenvs.in-project", timeout);
     PoetryConfig {
         cache_dir,
         virtualenvs_in_project,
@@ -101,8 +200,13 @@ pub fn get_config(executable: &PathBuf, workspace_dir: &PathBuf) -> PoetryConfig
     }
 }
 
-fn get_config_bool(executable: &PathBuf, workspace_dir: &PathBuf, setting: &str) -> Option<bool> {
-    match get_config_value(executable, workspace_dir, setting) {
+fn get_config_bool(
+    executable: &PathBuf,
+    workspace_dir: &PathBuf,
+    setting: &str,
+    timeout: Option<Duration>,
+) -> Option<bool> {
+    match get_config_value(executable, workspace_dir, setting, timeout) {
         Some(output) => {
             let output = output.trim();
             if output.starts_with("true") {
@@ -120,21 +224,26 @@ fn get_config_path(
     executable: &PathBuf,
     workspace_dir: &PathBuf,
     setting: &str,
+    timeout: Option<Duration>,
 ) -> Option<PathBuf> {
-    get_config_value(executable, workspace_dir, setting).map(|output| PathBuf::from(output.trim()))
+    get_config_value(executable, workspace_dir, setting, timeout)
+        .map(|output| PathBuf::from(output.trim()))
 }
 
 fn get_config_value(
     executable: &PathBuf,
     workspace_dir: &PathBuf,
     setting: &str,
+    timeout: Option<Duration>,
 ) -> Option<String> {
     let start = SystemTime::now();
-    let result = std::process::Command::new(executable)
-        .arg("config")
-        .arg(setting)
-        .current_dir(workspace_dir)
-        .output();
+    let result = run(
+        Command::new(executable)
+            .arg("config")
+            .arg(setting)
+            .current_dir(workspace_dir),
+        timeout,
+    );
     trace!(
         "Executed Poetry ({}ms): {executable:?} config {setting} {workspace_dir:?}",
         start.elapsed().unwrap_or_default().as_millis(),
@@ -159,3 +268,110 @@ fn get_config_value(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_info_path() {
+        assert_eq!(
+            parse_env_info_path_output(
+                "/home/user/.cache/pypoetry/virtualenvs/demo-gNT2WXAV-py3.12\n"
+            ),
+            Some(PathBuf::from(
+                "/home/user/.cache/pypoetry/virtualenvs/demo-gNT2WXAV-py3.12"
+            ))
+        );
+        assert_eq!(
+            parse_env_info_path_output(
+                "C:\\Users\\user\\AppData\\Local\\pypoetry\\Cache\\virtualenvs\\demo-gNT2WXAV-py3.12\r\n"
+            ),
+            Some(PathBuf::from(
+                "C:\\Users\\user\\AppData\\Local\\pypoetry\\Cache\\virtualenvs\\demo-gNT2WXAV-py3.12"
+            ))
+        );
+        // In project environment.
+        assert_eq!(
+            parse_env_info_path_output("/home/user/demo/.venv"),
+            Some(PathBuf::from("/home/user/demo/.venv"))
+        );
+    }
+
+    #[test]
+    fn parse_env_info_path_with_warnings() {
+        let output =
+            "The currently activated Python version 3.8.10 is not supported by the project (^3.11).
+Trying to find and use a compatible version.
+Using python3.11 (3.11.4)
+/home/user/.cache/pypoetry/virtualenvs/demo-gNT2WXAV-py3.11
+
+";
+        assert_eq!(
+            parse_env_info_path_output(output),
+            Some(PathBuf::from(
+                "/home/user/.cache/pypoetry/virtualenvs/demo-gNT2WXAV-py3.11"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_env_info_path_without_environment() {
+        assert_eq!(parse_env_info_path_output(""), None);
+        assert_eq!(parse_env_info_path_output("\n\n"), None);
+        assert_eq!(parse_env_info_path_output("Virtualenv not found\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn all_environments_of_a_project_are_listed() {
+        use pet_core::reporter::VecReporter;
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let root = fs::canonicalize(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "pet_poetry_list_environments_{}",
+                std::process::id()
+            ));
+        let _ = fs::remove_dir_all(&root);
+        let project = root.join("project");
+        fs::create_dir_all(&project).unwrap();
+        let envs = ["project-gNT2WXAV-py3.11", "project-gNT2WXAV-py3.12"]
+            .iter()
+            .map(|name| {
+                let prefix = root.join("virtualenvs").join(name);
+                fs::create_dir_all(prefix.join("bin")).unwrap();
+                fs::write(prefix.join("bin").join("python"), "").unwrap();
+                prefix
+            })
+            .collect::<Vec<_>>();
+        // Poetry reports the 3.12 env as the active env, however the 3.11 env also belongs to the project.
+        let poetry = root.join("poetry");
+        fs::write(
+            &poetry,
+            format!(
+                "#!/bin/sh\nif [ \"$2\" = \"list\" ]; then\n  echo '{}'\n  echo '{} (Activated)'\nelse\n  echo '{}'\nfi\n",
+                envs[0].display(),
+                envs[1].display(),
+                envs[1].display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&poetry, fs::Permissions::from_mode(0o755)).unwrap();
+        let manager = PoetryManager {
+            executable: poetry.clone(),
+            version: None,
+            virtualenvs_path: None,
+        };
+
+        let prefixes =
+            list_environments(&poetry, &vec![project], &manager, None, &VecReporter::new())
+                .into_iter()
+                .map(|env| env.prefix.unwrap())
+                .collect::<Vec<_>>();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(prefixes, envs);
+    }
+}
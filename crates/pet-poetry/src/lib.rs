@@ -2,7 +2,7 @@
 // Licensed under the MIT License.
 
 use env_variables::EnvVariables;
-use environment_locations::list_environments;
+use environment_locations::{list_environments, list_environments_in_virtualenvs_dir};
 use log::trace;
 use manager::PoetryManager;
 use pet_core::{
@@ -12,12 +12,16 @@ use pet_core::{
     reporter::Reporter,
     Configuration, Locator, LocatorKind, LocatorResult,
 };
-use pet_virtualenv::is_virtualenv;
+use pet_fs::path::norm_case;
+use pet_virtualenv::{is_virtualenv, is_virtualenv_dir};
 use std::{
-    path::PathBuf,
+    collections::HashMap,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
+    time::Duration,
 };
 use telemetry::report_missing_envs;
+pub use telemetry::TelemetryResult;
 
 pub mod config;
 pub mod env_variables;
@@ -33,32 +37,84 @@ pub trait PoetryLocator: Send + Sync {
         &self,
         reporter: &dyn Reporter,
         poetry_executable: Option<PathBuf>,
-    ) -> Option<()>;
+    ) -> Option<TelemetryResult>;
 }
 
+/// Clones share the same state (configuration & cached environments).
+#[derive(Clone)]
 pub struct Poetry {
     pub workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
     pub env_vars: EnvVariables,
     pub poetry_executable: Arc<Mutex<Option<PathBuf>>>,
+    /// Whether to scan the virtualenvs directory for all Poetry environments (see `Configuration::poetry_scan_prefix_dirs`).
+    pub scan_prefix_dirs: Arc<Mutex<bool>>,
+    /// Time after which Poetry is killed when spawned (see `Configuration::timeout_ms`).
+    pub timeout: Arc<Mutex<Option<Duration>>>,
     search_result: Arc<Mutex<Option<LocatorResult>>>,
+    /// Environments in the search result keyed by their executables (symlinks), for fast lookups in `try_from`.
+    environments_by_executable: Arc<Mutex<Option<HashMap<PathBuf, PythonEnvironment>>>>,
 }
 
 impl Poetry {
     pub fn new(environment: &dyn Environment) -> Self {
         Poetry {
             search_result: Arc::new(Mutex::new(None)),
+            environments_by_executable: Arc::new(Mutex::new(None)),
             workspace_directories: Arc::new(Mutex::new(vec![])),
             env_vars: EnvVariables::from(environment),
             poetry_executable: Arc::new(Mutex::new(None)),
+            scan_prefix_dirs: Arc::new(Mutex::new(false)),
+            timeout: Arc::new(Mutex::new(None)),
         }
     }
     fn clear(&self) {
         self.poetry_executable.lock().unwrap().take();
         self.search_result.lock().unwrap().take();
+        self.environments_by_executable.lock().unwrap().take();
     }
     pub fn from(environment: &dyn Environment) -> Poetry {
         Poetry::new(environment)
     }
+    /// Returns the Poetry environments associated with the given project directory.
+    /// The project directory must be one of the configured workspace directories.
+    pub fn environments_for_project(&self, project_dir: &Path) -> Vec<PythonEnvironment> {
+        let project_dir = norm_case(project_dir);
+        self.find_with_cache()
+            .map(|result| {
+                result
+                    .environments
+                    .into_iter()
+                    .filter(|env| env.project.as_ref() == Some(&project_dir))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    /// Async version of `environments_for_project`, the discovery is performed in a separate thread.
+    /// Returns an empty list if cancelled before the discovery completes.
+    #[cfg(feature = "async")]
+    pub async fn list_project_environments_async(
+        &self,
+        project_dir: &Path,
+        cancellation: pet_core::cancellation::CancellationToken,
+    ) -> Vec<PythonEnvironment> {
+        let poetry = self.clone();
+        let project_dir = project_dir.to_path_buf();
+        pet_core::blocking::spawn_blocking(
+            move || poetry.environments_for_project(&project_dir),
+            Some(cancellation),
+        )
+        .await
+        .unwrap_or_default()
+    }
+    /// Returns the Poetry environment that is currently active, i.e. the one pointed to by `VIRTUAL_ENV`.
+    /// Similar to `try_from`, however this works with the prefix instead of the executable.
+    pub fn get_active_environment(&self, virtual_env: &Path) -> Option<PythonEnvironment> {
+        let virtual_env = norm_case(virtual_env);
+        self.find_with_cache()?
+            .environments
+            .into_iter()
+            .find(|env| env.prefix.as_ref() == Some(&virtual_env))
+    }
     fn find_with_cache(&self) -> Option<LocatorResult> {
         let mut search_result = self.search_result.lock().unwrap();
         if let Some(result) = search_result.clone() {
@@ -69,24 +125,43 @@ impl Poetry {
         let manager = manager::PoetryManager::find(
             self.poetry_executable.lock().unwrap().clone(),
             &self.env_vars,
+            *self.timeout.lock().unwrap(),
         );
         trace!("Poetry Manager {:?}", manager);
+        let workspace_dirs = self.workspace_directories.lock().unwrap().clone();
+        let envs =
+            list_environments(&self.env_vars, &workspace_dirs, manager.clone()).unwrap_or_default();
         let mut result = LocatorResult {
-            managers: vec![],
-            environments: vec![],
-        };
-        if let Some(manager) = &manager {
-            result.managers.push(manager.to_manager());
+            managers: manager.iter().map(|m| m.to_manager()).collect(),
+            ..Default::default()
         }
+        .merge(LocatorResult {
+            environments: envs,
+            ..Default::default()
+        });
 
-        let workspace_dirs = self.workspace_directories.lock().unwrap().clone();
-        let envs = list_environments(&self.env_vars, &workspace_dirs, manager).unwrap_or_default();
-        result.environments.extend(envs.clone());
+        if *self.scan_prefix_dirs.lock().unwrap() {
+            // Environments associated with a project take precedence.
+            result = result.merge(LocatorResult {
+                environments: list_environments_in_virtualenvs_dir(&self.env_vars, manager),
+                ..Default::default()
+            });
+        }
 
         // Having a value in the search result means that we have already searched for environments
         search_result.replace(result.clone());
+        let mut environments_by_executable = HashMap::new();
+        for env in &result.environments {
+            for symlink in env.symlinks.clone().unwrap_or_default() {
+                environments_by_executable.insert(norm_case(symlink), env.clone());
+            }
+        }
+        self.environments_by_executable
+            .lock()
+            .unwrap()
+            .replace(environments_by_executable);
 
-        if result.managers.is_empty() && result.environments.is_empty() {
+        if result.is_empty() {
             None
         } else {
             Some(result)
@@ -99,9 +174,10 @@ impl PoetryLocator for Poetry {
         &self,
         reporter: &dyn Reporter,
         poetry_executable: Option<PathBuf>,
-    ) -> Option<()> {
+    ) -> Option<TelemetryResult> {
         let user_provided_poetry_exe = poetry_executable.is_some();
-        let manager = PoetryManager::find(poetry_executable.clone(), &self.env_vars)?;
+        let timeout = *self.timeout.lock().unwrap();
+        let manager = PoetryManager::find(poetry_executable.clone(), &self.env_vars, timeout)?;
         let poetry_executable = manager.executable.clone();
 
         let workspace_dirs = self.workspace_directories.lock().unwrap().clone();
@@ -109,10 +185,12 @@ impl PoetryLocator for Poetry {
             &poetry_executable,
             &workspace_dirs,
             &manager,
+            timeout,
+            reporter,
         );
 
         let result = self.search_result.lock().unwrap().clone();
-        let _ = report_missing_envs(
+        report_missing_envs(
             reporter,
             &poetry_executable,
             workspace_dirs,
@@ -120,9 +198,8 @@ impl PoetryLocator for Poetry {
             &environments_using_spawn,
             result,
             user_provided_poetry_exe,
-        );
-
-        Some(())
+            timeout,
+        )
     }
 }
 
@@ -130,6 +207,9 @@ impl Locator for Poetry {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::Poetry
     }
+    fn description(&self) -> &'static str {
+        "Finds environments created by Poetry, reads POETRY_VIRTUALENVS_PATH, POETRY_CACHE_DIR and poetry.toml"
+    }
     fn configure(&self, config: &Configuration) {
         if let Some(workspace_directories) = &config.workspace_directories {
             self.workspace_directories.lock().unwrap().clear();
@@ -143,26 +223,71 @@ impl Locator for Poetry {
         if let Some(exe) = &config.poetry_executable {
             self.poetry_executable.lock().unwrap().replace(exe.clone());
         }
+        *self.scan_prefix_dirs.lock().unwrap() = config.poetry_scan_prefix_dirs.unwrap_or_default();
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+    }
+
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("poetry") | Some("poetry.exe")
+        )
     }
 
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::Poetry]
     }
 
+    fn validate(&self, env: &PythonEnvironment) -> bool {
+        // Identifying the environment could require spawning Poetry, checking the virtual env is sufficient.
+        match (&env.executable, &env.prefix) {
+            (Some(executable), Some(prefix)) => executable.is_file() && is_virtualenv_dir(prefix),
+            _ => false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let executable = self.poetry_executable.lock().unwrap().clone();
+        let timeout = *self.timeout.lock().unwrap();
+        match PoetryManager::find(executable, &self.env_vars, timeout) {
+            Some(manager) => format!(
+                "{} ({:?}), executable: {}, version: {}",
+                self.get_kind().name(),
+                PythonEnvironmentKind::Poetry,
+                manager.executable.display(),
+                manager.version.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None => format!(
+                "{} ({:?}), executable: not found",
+                self.get_kind().name(),
+                PythonEnvironmentKind::Poetry
+            ),
+        }
+    }
+
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
         if !is_virtualenv(env) {
             return None;
         }
-        if let Some(result) = self.find_with_cache() {
-            for found_env in result.environments {
-                if let Some(symlinks) = &found_env.symlinks {
-                    if symlinks.contains(&env.executable) {
-                        return Some(found_env.clone());
-                    }
-                }
-            }
+        let executable = norm_case(&env.executable);
+        if let Some(environments) = self.environments_by_executable.lock().unwrap().as_ref() {
+            return environments.get(&executable).cloned();
         }
-        None
+        // We haven't searched for the environments yet (this builds the lookup).
+        self.find_with_cache()?;
+        self.environments_by_executable
+            .lock()
+            .unwrap()
+            .as_ref()?
+            .get(&executable)
+            .cloned()
+    }
+
+    fn refresh(&self) -> LocatorResult {
+        // Unlike `clear`, retain the configured Poetry executable.
+        self.search_result.lock().unwrap().take();
+        self.environments_by_executable.lock().unwrap().take();
+        self.find_with_cache().unwrap_or_default()
     }
 
     fn find(&self, reporter: &dyn Reporter) {
@@ -176,4 +301,11 @@ impl Locator for Poetry {
             }
         }
     }
+
+    fn get_environments(&self) -> Vec<PythonEnvironment> {
+        self.clear();
+        self.find_with_cache()
+            .map(|result| result.environments)
+            .unwrap_or_default()
+    }
 }
@@ -1,22 +1,51 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use log::trace;
+use lazy_static::lazy_static;
+use log::{trace, warn};
 use pet_core::manager::{EnvManager, EnvManagerType};
-use std::{env, path::PathBuf};
+use pet_process::run_with_timeout;
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+    time::Duration,
+};
 
-use crate::env_variables::EnvVariables;
+use crate::{config::Config, env_variables::EnvVariables};
+
+/// Time after which `poetry --version` is killed, when a timeout has not been configured.
+const DEFAULT_VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    /// `poetry --version` prints `Poetry (version 1.8.3)`, older versions print `Poetry version 1.1.13`.
+    static ref POETRY_VERSION: Regex = Regex::new(r"Poetry \(?version ([0-9][^\s)]*)\)?")
+        .expect("error parsing poetry version regex");
+    // Spawning Poetry is expensive, hence cache the version of each executable for the lifetime of the process.
+    static ref POETRY_VERSIONS: Mutex<HashMap<PathBuf, Option<String>>> = Mutex::new(HashMap::new());
+}
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct PoetryManager {
     pub executable: PathBuf,
+    pub version: Option<String>,
+    /// Directory where Poetry creates virtual environments.
+    pub virtualenvs_path: Option<PathBuf>,
 }
 
 impl PoetryManager {
-    pub fn find(executable: Option<PathBuf>, env_variables: &EnvVariables) -> Option<Self> {
+    /// Finds the Poetry executable & gets its version, `timeout` is used when spawning Poetry (see `Configuration::timeout_ms`).
+    pub fn find(
+        executable: Option<PathBuf>,
+        env_variables: &EnvVariables,
+        timeout: Option<Duration>,
+    ) -> Option<Self> {
         if let Some(executable) = executable {
             if executable.is_file() {
-                return Some(PoetryManager { executable });
+                return Some(PoetryManager::new(executable, env_variables, timeout));
             }
         }
 
@@ -107,22 +136,23 @@ impl PoetryManager {
             }
             for executable in search_paths {
                 if executable.is_file() {
-                    return Some(PoetryManager { executable });
+                    return Some(PoetryManager::new(executable, env_variables, timeout));
                 }
             }
+        }
 
-            // Look for poetry in current PATH.
-            if let Some(env_path) = &env_variables.path {
-                for each in env::split_paths(env_path) {
-                    let executable = each.join("poetry");
+        // Look for poetry in current PATH.
+        // Even without a home directory, e.g. nix-shell or devenv put poetry in locations such as `/nix/store/<hash>-poetry-1.8.0/bin/poetry`.
+        if let Some(env_path) = &env_variables.path {
+            for each in env::split_paths(env_path) {
+                let executable = each.join("poetry");
+                if executable.is_file() {
+                    return Some(PoetryManager::new(executable, env_variables, timeout));
+                }
+                if std::env::consts::OS == "windows" {
+                    let executable = each.join("poetry.exe");
                     if executable.is_file() {
-                        return Some(PoetryManager { executable });
-                    }
-                    if std::env::consts::OS == "windows" {
-                        let executable = each.join("poetry.exe");
-                        if executable.is_file() {
-                            return Some(PoetryManager { executable });
-                        }
+                        return Some(PoetryManager::new(executable, env_variables, timeout));
                     }
                 }
             }
@@ -130,11 +160,97 @@ impl PoetryManager {
         trace!("Poetry exe not found");
         None
     }
+    fn new(executable: PathBuf, env_variables: &EnvVariables, timeout: Option<Duration>) -> Self {
+        let version =
+            PoetryManager::detect_version(&executable, timeout.unwrap_or(DEFAULT_VERSION_TIMEOUT));
+        let virtualenvs_path = Config::find_global(env_variables).map(|cfg| cfg.virtualenvs_path);
+        PoetryManager {
+            executable,
+            version,
+            virtualenvs_path,
+        }
+    }
+    /// Gets the version by spawning `poetry --version`, the result is cached for each executable.
+    pub fn detect_version(executable: &Path, timeout: Duration) -> Option<String> {
+        if let Some(version) = POETRY_VERSIONS.lock().unwrap().get(executable) {
+            return version.clone();
+        }
+        let version = get_poetry_version(executable, timeout);
+        POETRY_VERSIONS
+            .lock()
+            .unwrap()
+            .insert(executable.to_path_buf(), version.clone());
+        version
+    }
     pub fn to_manager(&self) -> EnvManager {
         EnvManager {
             executable: self.executable.clone(),
-            version: None,
+            version: self.version.clone(),
             tool: EnvManagerType::Poetry,
+            install_path: self.virtualenvs_path.clone(),
         }
     }
 }
+
+pub fn parse_poetry_version(output: &str) -> Option<String> {
+    POETRY_VERSION
+        .captures(output)
+        .and_then(|captures| captures.get(1))
+        .map(|version| version.as_str().to_string())
+}
+
+fn get_poetry_version(executable: &Path, timeout: Duration) -> Option<String> {
+    let mut cmd = Command::new(executable);
+    cmd.arg("--version").arg("--no-ansi");
+    match run_with_timeout(&mut cmd, timeout) {
+        Ok(output) if output.status.success() => {
+            parse_poetry_version(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            warn!(
+                "Failed to get the version of Poetry {:?}: {:?}",
+                executable,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            None
+        }
+        Err(err) => {
+            warn!(
+                "Failed to get the version of Poetry {:?}: {}",
+                executable, err
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            parse_poetry_version("Poetry (version 1.8.3)\n"),
+            Some("1.8.3".to_string())
+        );
+        assert_eq!(
+            parse_poetry_version("Poetry (version 2.0.0.dev0)\n"),
+            Some("2.0.0.dev0".to_string())
+        );
+        assert_eq!(
+            parse_poetry_version("Poetry (version 1.2.0b3)\n"),
+            Some("1.2.0b3".to_string())
+        );
+        assert_eq!(
+            parse_poetry_version("Poetry version 1.1.13\n"),
+            Some("1.1.13".to_string())
+        );
+        assert_eq!(
+            parse_poetry_version("Configuration file exists at /home/user/.config/pypoetry\nPoetry (version 1.5.1)\n"),
+            Some("1.5.1".to_string())
+        );
+        assert_eq!(parse_poetry_version("poetry: command not found"), None);
+        assert_eq!(parse_poetry_version(""), None);
+    }
+}
@@ -10,16 +10,30 @@ use log::{error, trace};
 
 pub struct PyProjectToml {
     pub name: String,
+    /// Directories of the packages listed in `[tool.poetry.packages]` (relative to the project directory).
+    pub packages: Vec<PathBuf>,
 }
 
 impl PyProjectToml {
-    fn new(name: String, file: PathBuf) -> Self {
+    fn new(name: String, packages: Vec<PathBuf>, file: PathBuf) -> Self {
         trace!("Poetry project: {:?} with name {:?}", file, name);
-        PyProjectToml { name }
+        PyProjectToml { name, packages }
     }
     pub fn find(path: &Path) -> Option<Self> {
         parse(&path.join("pyproject.toml"))
     }
+    /// Directories of the packages in this project that are Poetry projects in their own right,
+    /// i.e. monorepos where each package has its own `pyproject.toml`.
+    pub fn package_roots(&self, project_dir: &Path) -> Vec<PathBuf> {
+        let mut roots = vec![];
+        for package in &self.packages {
+            let package_dir = project_dir.join(package);
+            if package_dir.join("pyproject.toml").is_file() && !roots.contains(&package_dir) {
+                roots.push(package_dir);
+            }
+        }
+        roots
+    }
 }
 
 fn parse(file: &Path) -> Option<PyProjectToml> {
@@ -31,14 +45,26 @@ fn parse_contents(contents: &str, file: &Path) -> Option<PyProjectToml> {
     match toml::from_str::<toml::Value>(contents) {
         Ok(value) => {
             let mut name = None;
+            let mut packages = vec![];
             if let Some(tool) = value.get("tool") {
                 if let Some(poetry) = tool.get("poetry") {
                     if let Some(name_value) = poetry.get("name") {
                         name = name_value.as_str().map(|s| s.to_string());
                     }
+                    // packages = [{ include = "pkg1" }, { include = "pkg2", from = "src" }]
+                    if let Some(values) = poetry.get("packages").and_then(|p| p.as_array()) {
+                        for package in values {
+                            if let Some(include) = package.get("include").and_then(|i| i.as_str()) {
+                                match package.get("from").and_then(|f| f.as_str()) {
+                                    Some(from) => packages.push(PathBuf::from(from).join(include)),
+                                    None => packages.push(PathBuf::from(include)),
+                                }
+                            }
+                        }
+                    }
                 }
             }
-            name.map(|name| PyProjectToml::new(name, file.into()))
+            name.map(|name| PyProjectToml::new(name, packages, file.into()))
         }
         Err(e) => {
             error!("Error parsing toml file: {:?}", e);
@@ -71,10 +97,33 @@ requires = ["poetry-core"]
 build-backend = "poetry.core.masonry.api"
 "#;
         assert_eq!(
-            parse_contents(&cfg.to_string(), Path::new("pyproject.toml"))
+            parse_contents(cfg, Path::new("pyproject.toml"))
                 .unwrap()
                 .name,
             "poetry-demo"
         );
     }
+
+    #[test]
+    fn extract_packages_from_pyproject_toml() {
+        let cfg = r#"
+[tool.poetry]
+name = "monorepo"
+version = "0.1.0"
+packages = [
+    { include = "packages/pkg1" },
+    { include = "pkg2", from = "src" },
+    { from = "src" },
+]
+"#;
+        assert_eq!(
+            parse_contents(cfg, Path::new("pyproject.toml"))
+                .unwrap()
+                .packages,
+            vec![
+                PathBuf::from("packages/pkg1"),
+                PathBuf::from("src").join("pkg2")
+            ]
+        );
+    }
 }
@@ -1,7 +1,11 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use log::warn;
 use pet_core::{
@@ -11,8 +15,73 @@ use pet_core::{
     LocatorResult,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{config::Config, env_variables::EnvVariables, environment_locations_spawn::get_config};
 
+/// Number of Poetry environments found for the workspace folders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryResult {
+    /// Environments found by spawning Poetry.
+    pub via_spawn: usize,
+    /// Environments found by us (without spawning Poetry), i.e. the cached search result.
+    pub via_cache: usize,
+    /// Environments found by spawning Poetry that we did not find.
+    pub newly_found: usize,
+}
+
+impl TelemetryResult {
+    fn add(&mut self, other: &TelemetryResult) {
+        self.via_spawn += other.via_spawn;
+        self.via_cache += other.via_cache;
+        self.newly_found += other.newly_found;
+    }
+}
+
+fn get_environments_for_workspace(
+    workspace_dir: &Path,
+    environments: &[PythonEnvironment],
+) -> LocatorResult {
+    LocatorResult {
+        environments: environments
+            .iter()
+            .filter(|e| e.project.as_deref() == Some(workspace_dir))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn get_prefixes_for_workspace(
+    workspace_dir: &Path,
+    environments: &[PythonEnvironment],
+) -> HashSet<PathBuf> {
+    get_environments_for_workspace(workspace_dir, environments)
+        .environments
+        .into_iter()
+        .flat_map(|e| e.prefix)
+        .collect()
+}
+
+fn count_environments(
+    workspace_dir: &Path,
+    envs_discovered_by_poetry: &[PythonEnvironment],
+    envs_discovered_by_us: &[PythonEnvironment],
+) -> TelemetryResult {
+    let envs_discovered_by_poetry =
+        get_prefixes_for_workspace(workspace_dir, envs_discovered_by_poetry);
+    let envs_discovered_by_us = get_prefixes_for_workspace(workspace_dir, envs_discovered_by_us);
+    TelemetryResult {
+        via_spawn: envs_discovered_by_poetry.len(),
+        via_cache: envs_discovered_by_us.len(),
+        newly_found: envs_discovered_by_poetry
+            .difference(&envs_discovered_by_us)
+            .count(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn report_missing_envs(
     reporter: &dyn Reporter,
     executable: &PathBuf,
@@ -21,9 +90,11 @@ pub fn report_missing_envs(
     envs_discovered_by_poetry: &[PythonEnvironment],
     envs_discovered_by_us: Option<LocatorResult>,
     user_provided_poetry_exe: bool,
-) -> Option<()> {
+    timeout: Option<Duration>,
+) -> Option<TelemetryResult> {
+    let mut result = TelemetryResult::default();
     for workspace_dir in workspace_dirs {
-        let config = get_config(executable, &workspace_dir);
+        let config = get_config(executable, &workspace_dir, timeout);
         let global_config = Config::find_global(env_vars);
         let local_config = Config::find_local(&workspace_dir, env_vars);
 
@@ -41,24 +112,25 @@ pub fn report_missing_envs(
 
         let poetry_exe_not_found = envs_discovered_by_us.clone().map(|e| e.managers.is_empty());
         let global_config_not_found = Some(global_config.is_none());
-        let envs_discovered_by_us: HashSet<_> = envs_discovered_by_us
-            .clone()
+        let environments_discovered_by_us = envs_discovered_by_us
             .as_ref()
             .map(|e| e.environments.clone())
-            .unwrap_or_default()
-            .iter()
-            .filter(|e| e.project == Some(workspace_dir.clone()))
-            .flat_map(|e| e.prefix.clone())
-            .collect();
-        let envs_discovered_by_poetry: HashSet<_> = envs_discovered_by_poetry
-            .iter()
-            .filter(|e| e.project == Some(workspace_dir.clone()))
-            .flat_map(|e| e.prefix.clone())
-            .collect();
-
-        let missing_envs = envs_discovered_by_poetry
-            .difference(&envs_discovered_by_us)
-            .collect::<Vec<_>>();
+            .unwrap_or_default();
+        result.add(&count_environments(
+            &workspace_dir,
+            envs_discovered_by_poetry,
+            &environments_discovered_by_us,
+        ));
+        let missing_envs =
+            get_environments_for_workspace(&workspace_dir, envs_discovered_by_poetry)
+                .difference(&get_environments_for_workspace(
+                    &workspace_dir,
+                    &environments_discovered_by_us,
+                ))
+                .environments
+                .into_iter()
+                .flat_map(|e| e.prefix)
+                .collect::<Vec<_>>();
         let missing_path = config.virtualenvs_path.as_ref().map(|path| {
             missing_envs
                 .iter()
@@ -136,5 +208,73 @@ pub fn report_missing_envs(
 
         reporter.report_telemetry(&TelemetryEvent::MissingPoetryEnvironments(missing_info));
     }
-    Some(())
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_env(project: &str, prefix: &str) -> PythonEnvironment {
+        PythonEnvironment {
+            project: Some(PathBuf::from(project)),
+            prefix: Some(PathBuf::from(prefix)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn count_environments_found_via_spawn_and_cache() {
+        let envs_discovered_by_poetry = vec![
+            create_env("/project", "/virtualenvs/project-1"),
+            create_env("/project", "/virtualenvs/project-2"),
+            create_env("/project", "/virtualenvs/project-3"),
+            create_env("/other", "/virtualenvs/other-1"),
+        ];
+        let envs_discovered_by_us = vec![
+            create_env("/project", "/virtualenvs/project-1"),
+            // Found by us, but not by Poetry.
+            create_env("/project", "/project/.venv"),
+            create_env("/other", "/virtualenvs/other-1"),
+        ];
+
+        let result = count_environments(
+            Path::new("/project"),
+            &envs_discovered_by_poetry,
+            &envs_discovered_by_us,
+        );
+
+        assert_eq!(
+            result,
+            TelemetryResult {
+                via_spawn: 3,
+                via_cache: 2,
+                newly_found: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn counts_are_added_across_workspaces() {
+        let mut result = TelemetryResult {
+            via_spawn: 1,
+            via_cache: 2,
+            newly_found: 0,
+        };
+
+        result.add(&count_environments(
+            Path::new("/project"),
+            &[create_env("/project", "/virtualenvs/project-1")],
+            &[],
+        ));
+
+        assert_eq!(
+            result,
+            TelemetryResult {
+                via_spawn: 2,
+                via_cache: 2,
+                newly_found: 1,
+            }
+        );
+    }
 }
@@ -0,0 +1,93 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+/// Minimal executor, the API does not depend on any particular async runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn async_environments_match_sync_environments() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::cancellation::CancellationToken;
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let project_b = resolve_test_path(&["unix", "multi_project", "project_b"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone(), project_b.clone()]),
+        ..Default::default()
+    });
+
+    for project in [&project_a, &project_b] {
+        let envs =
+            block_on(locator.list_project_environments_async(project, CancellationToken::new()));
+        assert_eq!(envs.len(), 1);
+        assert_eq!(envs, locator.environments_for_project(project));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn cancelled_requests_return_no_environments() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::cancellation::CancellationToken;
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone()]),
+        ..Default::default()
+    });
+
+    let cancellation = CancellationToken::new();
+    cancellation.cancel();
+    let envs = block_on(locator.list_project_environments_async(&project_a, cancellation));
+    assert!(envs.is_empty());
+}
@@ -1,6 +1,9 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+// The `ci` feature is passed to the whole workspace when running the tests in CI, it is not a feature of this crate.
+#![allow(unexpected_cfgs)]
+
 mod common;
 
 #[cfg(unix)]
@@ -69,14 +72,11 @@ fn global_config_with_specific_values() {
             "config.toml"
         ]))
     );
-    assert_eq!(
-        config
-            .clone()
-            .unwrap()
-            .virtualenvs_in_project
-            .unwrap_or_default(),
-        true
-    );
+    assert!(config
+        .clone()
+        .unwrap()
+        .virtualenvs_in_project
+        .unwrap_or_default());
     assert_eq!(
         config.clone().unwrap().virtualenvs_path,
         PathBuf::from("some/path/virtualenvs".to_string())
@@ -86,7 +86,6 @@ fn global_config_with_specific_values() {
 #[cfg(unix)]
 #[cfg_attr(any(feature = "ci",), test)]
 #[allow(dead_code)]
-
 fn local_config_with_specific_values() {
     use std::path::PathBuf;
 
@@ -117,14 +116,11 @@ fn local_config_with_specific_values() {
             "poetry.toml"
         ]))
     );
-    assert_eq!(
-        config
-            .clone()
-            .unwrap()
-            .virtualenvs_in_project
-            .unwrap_or_default(),
-        false
-    );
+    assert!(!config
+        .clone()
+        .unwrap()
+        .virtualenvs_in_project
+        .unwrap_or_default());
     assert_eq!(
         config.clone().unwrap().virtualenvs_path,
         PathBuf::from("/directory/virtualenvs".to_string())
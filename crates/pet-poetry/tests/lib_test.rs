@@ -0,0 +1,433 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+mod common;
+
+#[test]
+fn manages_poetry_executables() {
+    use common::create_test_environment;
+    use pet_core::Locator;
+    use pet_poetry::Poetry;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let environment = create_test_environment(HashMap::new(), None, None);
+    let locator = Poetry::from(&environment);
+
+    assert!(locator.manages_executable(&PathBuf::from("/home/user/.local/bin/poetry")));
+    assert!(locator.manages_executable(&PathBuf::from("poetry.exe")));
+    assert!(!locator.manages_executable(&PathBuf::from("/home/user/.local/bin/python")));
+    assert!(!locator.manages_executable(&PathBuf::from("/opt/conda/bin/conda")));
+}
+
+#[cfg(unix)]
+#[test]
+fn describe_includes_poetry_executable_and_version() {
+    use common::create_test_environment;
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::{collections::HashMap, fs, os::unix::fs::PermissionsExt};
+
+    let root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join(format!("pet_poetry_describe_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    let executable = root.join("poetry");
+    fs::write(&executable, "#!/bin/sh\necho 'Poetry (version 1.8.3)'\n").unwrap();
+    fs::set_permissions(&executable, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let environment = create_test_environment(HashMap::new(), None, None);
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        poetry_executable: Some(executable.clone()),
+        ..Default::default()
+    });
+    let description = locator.describe();
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(
+        description,
+        format!(
+            "Poetry (Poetry), executable: {}, version: 1.8.3",
+            executable.display()
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn environments_for_project() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let project_b = resolve_test_path(&["unix", "multi_project", "project_b"]);
+    let unknown_project = resolve_test_path(&["unix", "multi_project", "project_c"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone(), project_b.clone()]),
+        ..Default::default()
+    });
+
+    let envs = locator.environments_for_project(&project_a);
+    assert_eq!(envs.len(), 1);
+    assert_eq!(envs[0].prefix, Some(project_a.join(".venv")));
+    assert_eq!(envs[0].project, Some(project_a.clone()));
+
+    let envs = locator.environments_for_project(&project_b);
+    assert_eq!(envs.len(), 1);
+    assert_eq!(envs[0].prefix, Some(project_b.join(".venv")));
+    assert_eq!(envs[0].project, Some(project_b.clone()));
+
+    assert!(locator
+        .environments_for_project(&unknown_project)
+        .is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn scan_virtualenvs_dir_for_poetry_envs() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "virtualenvs_dir", "user_home"]);
+    let virtualenvs = resolve_test_path(&["unix", "virtualenvs_dir", "virtualenvs"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "POETRY_VIRTUALENVS_PATH".to_string(),
+            virtualenvs.to_string_lossy().to_string(),
+        )]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+
+    // Without a workspace directory none of the environments can be associated with a project.
+    assert!(locator.get_environments().is_empty());
+
+    locator.configure(&Configuration {
+        poetry_scan_prefix_dirs: Some(true),
+        ..Default::default()
+    });
+    let mut prefixes = locator
+        .get_environments()
+        .iter()
+        .map(|e| e.prefix.clone().unwrap())
+        .collect::<Vec<_>>();
+    prefixes.sort();
+
+    assert_eq!(
+        prefixes,
+        vec![
+            virtualenvs.join("other-project-TbBV0MKD-py3.11"),
+            virtualenvs.join("poetry-demo-gNT2WXAV-py3.12"),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn environments_are_tagged_with_source_locator() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone()]),
+        ..Default::default()
+    });
+
+    let envs = locator.environments_for_project(&project_a);
+    assert_eq!(envs.len(), 1);
+    assert_eq!(envs[0].source_locator, Some("Poetry"));
+
+    // Purely diagnostic, hence not considered when comparing environments.
+    let mut other = envs[0].clone();
+    other.source_locator = None;
+    assert_eq!(envs[0], other);
+}
+
+#[cfg(unix)]
+#[test]
+fn environments_for_packages_in_workspace() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "monorepo", "user_home"]);
+    let workspace = resolve_test_path(&["unix", "monorepo", "workspace"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![workspace.clone()]),
+        ..Default::default()
+    });
+
+    let mut envs = locator.environments_for_project(&workspace);
+    envs.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs[0].prefix, Some(workspace.join(".venv")));
+    assert_eq!(
+        envs[1].prefix,
+        Some(workspace.join("packages").join("pkg1").join(".venv"))
+    );
+    assert!(envs.iter().all(|e| e.project == Some(workspace.clone())));
+}
+
+#[cfg(unix)]
+#[test]
+fn get_active_environment() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let project_b = resolve_test_path(&["unix", "multi_project", "project_b"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone(), project_b.clone()]),
+        ..Default::default()
+    });
+
+    let env = locator
+        .get_active_environment(&project_b.join(".venv"))
+        .unwrap();
+    assert_eq!(env.prefix, Some(project_b.join(".venv")));
+    assert_eq!(env.project, Some(project_b.clone()));
+
+    assert!(locator
+        .get_active_environment(&project_a.join(".venv").join("bin"))
+        .is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn try_from_poetry_environment() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{env::PythonEnv, Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "multi_project", "user_home"]);
+    let project_a = resolve_test_path(&["unix", "multi_project", "project_a"]);
+    let project_b = resolve_test_path(&["unix", "multi_project", "project_b"]);
+    let environment = create_test_environment(
+        HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        Some(home),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone(), project_b.clone()]),
+        ..Default::default()
+    });
+
+    for project in [&project_a, &project_b] {
+        let prefix = project.join(".venv");
+        let env = locator
+            .try_from(&PythonEnv::new(
+                prefix.join("bin").join("python"),
+                Some(prefix.clone()),
+                None,
+            ))
+            .unwrap();
+        assert_eq!(env.prefix, Some(prefix));
+        assert_eq!(env.project, Some(project.clone()));
+    }
+
+    let prefix = resolve_test_path(&["unix", "virtualenvs_dir", "virtualenvs", "not_a_poetry_env"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            Some(prefix.clone()),
+            None,
+        ))
+        .is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn manager_install_path_is_virtualenvs_dir() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{manager::EnvManagerType, Locator};
+    use pet_poetry::Poetry;
+    use pet_reporter::collect;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path(&["unix", "virtualenvs_dir", "user_home"]);
+    let virtualenvs = resolve_test_path(&["unix", "virtualenvs_dir", "virtualenvs"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "POETRY_VIRTUALENVS_PATH".to_string(),
+            virtualenvs.to_string_lossy().to_string(),
+        )]),
+        Some(home.clone()),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+
+    let reporter = collect::create_reporter();
+    locator.find(&reporter);
+    let managers = reporter.managers.lock().unwrap().clone();
+
+    assert_eq!(managers.len(), 1);
+    assert_eq!(managers[0].tool, EnvManagerType::Poetry);
+    assert_eq!(
+        managers[0].executable,
+        home.join(".local").join("bin").join("poetry")
+    );
+    assert_eq!(managers[0].install_path, Some(virtualenvs));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn find_manager_in_nix_store_on_path() {
+    use common::create_test_environment;
+    use pet_poetry::{env_variables::EnvVariables, manager::PoetryManager};
+    use std::{collections::HashMap, fs};
+
+    let root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join(format!("pet_poetry_nix_store_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let bin = root
+        .join("nix")
+        .join("store")
+        .join("abc-poetry-1.8.0")
+        .join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(bin.join("poetry"), "").unwrap();
+    let path = std::env::join_paths([root.join("usr").join("bin"), bin.clone()]).unwrap();
+
+    // Without a home directory (e.g. nix-shell), only the PATH can be used to find poetry.
+    let environment = create_test_environment(
+        HashMap::from([("PATH".to_string(), path.to_string_lossy().to_string())]),
+        None,
+        None,
+    );
+    let manager = PoetryManager::find(None, &EnvVariables::from(&environment), None);
+    let without_path = PoetryManager::find(
+        None,
+        &EnvVariables::from(&create_test_environment(HashMap::new(), None, None)),
+        None,
+    );
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(manager.map(|m| m.executable), Some(bin.join("poetry")));
+    assert_eq!(without_path, None);
+}
+
+#[cfg(unix)]
+#[test]
+fn refresh_finds_new_environments() {
+    use common::create_test_environment;
+    use pet_core::{Configuration, Locator};
+    use pet_poetry::Poetry;
+    use std::{collections::HashMap, fs, path::Path};
+
+    fn create_env(virtualenvs: &Path, name: &str) {
+        let prefix = virtualenvs.join(name);
+        fs::create_dir_all(prefix.join("bin")).unwrap();
+        fs::write(prefix.join("pyvenv.cfg"), "version_info = 3.12.1\n").unwrap();
+        fs::write(prefix.join("bin").join("python"), "").unwrap();
+    }
+
+    let root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_poetry_refresh_test");
+    let _ = fs::remove_dir_all(&root);
+    let virtualenvs = root.join("virtualenvs");
+    create_env(&virtualenvs, "first-AAAAAAAA-py3.12");
+    let environment = create_test_environment(
+        HashMap::from([(
+            "POETRY_VIRTUALENVS_PATH".to_string(),
+            virtualenvs.to_string_lossy().to_string(),
+        )]),
+        Some(root.clone()),
+        None,
+    );
+    let locator = Poetry::from(&environment);
+    locator.configure(&Configuration {
+        poetry_scan_prefix_dirs: Some(true),
+        ..Default::default()
+    });
+
+    let result = locator.refresh();
+    assert_eq!(result.environments.len(), 1);
+
+    create_env(&virtualenvs, "second-BBBBBBBB-py3.12");
+    let mut prefixes = locator
+        .refresh()
+        .environments
+        .iter()
+        .map(|e| e.prefix.clone().unwrap())
+        .collect::<Vec<_>>();
+    prefixes.sort();
+
+    let _ = fs::remove_dir_all(&root);
+    assert_eq!(
+        prefixes,
+        vec![
+            virtualenvs.join("first-AAAAAAAA-py3.12"),
+            virtualenvs.join("second-BBBBBBBB-py3.12"),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn validate_poetry_env_without_spawning_poetry() {
+    use common::{create_test_environment, resolve_test_path};
+    use pet_core::{
+        python_environment::{PythonEnvironmentBuilder, PythonEnvironmentKind},
+        Locator,
+    };
+    use pet_poetry::Poetry;
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(HashMap::new(), None, None);
+    let locator = Poetry::from(&environment);
+    let prefix = resolve_test_path(&["unix", "multi_project", "project_a", ".venv"]);
+    let env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Poetry))
+        .executable(Some(prefix.join("bin").join("python")))
+        .prefix(Some(prefix.clone()))
+        .build();
+    let deleted_env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Poetry))
+        .executable(Some(prefix.join("deleted").join("bin").join("python")))
+        .prefix(Some(prefix.join("deleted")))
+        .build();
+
+    assert!(locator.validate(&env));
+    assert!(!locator.validate(&deleted_env));
+}
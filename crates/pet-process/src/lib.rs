@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::warn;
+use std::{
+    fmt, io,
+    io::Read,
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Time after which spawned tools are killed, when a timeout has not been configured.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the child process is polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The process did not complete within the allotted time (the process has been killed).
+    Timeout,
+    IoError(io::Error),
+}
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::Timeout => write!(f, "Process timed out"),
+            ProcessError::IoError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {}
+
+impl From<io::Error> for ProcessError {
+    fn from(err: io::Error) -> Self {
+        ProcessError::IoError(err)
+    }
+}
+
+/// Same as `Command::output`, however the process is killed if it does not complete within the given time
+/// (`DEFAULT_TIMEOUT` when a timeout has not been configured).
+pub fn run(cmd: &mut Command, timeout: Option<Duration>) -> Result<Output, ProcessError> {
+    run_with_timeout(cmd, timeout.unwrap_or(DEFAULT_TIMEOUT))
+}
+
+/// Runs the command & captures its output, killing the process if it does not complete within the given time.
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<Output, ProcessError> {
+    let start = Instant::now();
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Read the output in background threads, else the process could block when the pipes are full.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            warn!("Killing {:?} as it did not complete in {:?}", cmd, timeout);
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ProcessError::Timeout);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(reader: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(mut reader) = reader {
+            let _ = reader.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
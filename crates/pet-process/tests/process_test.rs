@@ -0,0 +1,53 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn output_is_captured_when_process_completes_in_time() {
+    use pet_process::run_with_timeout;
+    use std::{process::Command, time::Duration};
+
+    let output = run_with_timeout(
+        Command::new("sh")
+            .arg("-c")
+            .arg("echo hello; echo world >&2"),
+        Duration::from_secs(30),
+    )
+    .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hello\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "world\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn process_is_killed_when_it_does_not_complete_in_time() {
+    use pet_process::{run_with_timeout, ProcessError};
+    use std::{
+        process::Command,
+        time::{Duration, Instant},
+    };
+
+    let start = Instant::now();
+    let result = run_with_timeout(Command::new("sleep").arg("30"), Duration::from_millis(100));
+
+    assert!(matches!(result, Err(ProcessError::Timeout)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn spawn_errors_are_reported() {
+    use pet_process::{run, ProcessError};
+    use std::{process::Command, time::Duration};
+
+    let mut command = Command::new("pet-process-executable-that-does-not-exist");
+    assert!(matches!(
+        run(&mut command, Some(Duration::from_secs(1))),
+        Err(ProcessError::IoError(_))
+    ));
+    assert!(matches!(
+        run(&mut command, None),
+        Err(ProcessError::IoError(_))
+    ));
+}
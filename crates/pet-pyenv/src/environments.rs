@@ -6,6 +6,7 @@ use pet_core::{
     arch::Architecture,
     manager::EnvManager,
     python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    LocatorKind,
 };
 use pet_python_utils::executable::find_executables;
 use pet_python_utils::version;
@@ -44,8 +45,9 @@ pub fn get_generic_python_environment(
 
     Some(
         PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Pyenv))
+            .source_locator(Some(LocatorKind::PyEnv.name()))
             .executable(Some(executable.to_path_buf()))
-            .version(version)
+            .version(version.and_then(|v| v.parse().ok()))
             .prefix(Some(path.to_path_buf()))
             .manager(manager.clone())
             .arch(arch)
@@ -62,8 +64,9 @@ pub fn get_virtual_env_environment(
     let version = version::from_pyvenv_cfg(path)?;
     Some(
         PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::PyenvVirtualEnv))
+            .source_locator(Some(LocatorKind::PyEnv.name()))
             .executable(Some(executable.to_path_buf()))
-            .version(Some(version))
+            .version(version.parse().ok())
             .prefix(Some(path.to_path_buf()))
             .manager(manager.clone())
             .symlinks(Some(find_executables(path)))
@@ -6,6 +6,7 @@ use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
+    time::Duration,
 };
 
 use env_variables::EnvVariables;
@@ -19,20 +20,24 @@ use pet_core::{
     os_environment::Environment,
     python_environment::{PythonEnvironment, PythonEnvironmentKind},
     reporter::Reporter,
-    Locator, LocatorKind,
+    Configuration, Locator, LocatorKind, LocatorResult,
 };
 use pet_python_utils::executable::find_executable;
+use pet_reporter::collect;
 
 pub mod env_variables;
 mod environment_locations;
 mod environments;
 mod manager;
+pub mod python_version_file;
 
 pub struct PyEnv {
     pub env_vars: EnvVariables,
     pub conda_locator: Arc<dyn CondaLocator>,
     manager: Arc<Mutex<Option<EnvManager>>>,
     versions_dir: Arc<Mutex<Option<PathBuf>>>,
+    /// Time after which pyenv is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
 }
 
 impl PyEnv {
@@ -45,6 +50,7 @@ impl PyEnv {
             conda_locator,
             manager: Arc::new(Mutex::new(None)),
             versions_dir: Arc::new(Mutex::new(None)),
+            timeout: Arc::new(Mutex::new(None)),
         }
     }
     fn clear(&self) {
@@ -55,11 +61,12 @@ impl PyEnv {
         let mut managers = self.manager.lock().unwrap();
         let mut versions = self.versions_dir.lock().unwrap();
         if managers.is_none() || versions.is_none() {
-            let pyenv_info = PyEnvInfo::from(&self.env_vars);
+            let pyenv_info = PyEnvInfo::from(&self.env_vars, *self.timeout.lock().unwrap());
             trace!("PyEnv Info {:?}", pyenv_info);
             if let Some(ref exe) = pyenv_info.exe {
                 let version = pyenv_info.version.clone();
-                let manager = EnvManager::new(exe.clone(), EnvManagerType::Pyenv, version);
+                let mut manager = EnvManager::new(exe.clone(), EnvManagerType::Pyenv, version);
+                manager.install_path = pyenv_info.root.clone();
                 managers.replace(manager);
             } else {
                 managers.take();
@@ -78,6 +85,15 @@ impl Locator for PyEnv {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::PyEnv
     }
+    fn description(&self) -> &'static str {
+        "Finds Python versions installed by pyenv and pyenv-virtualenv, reads PYENV_ROOT and PYENV (defaults to ~/.pyenv)"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![
             PythonEnvironmentKind::Pyenv,
@@ -123,6 +139,13 @@ impl Locator for PyEnv {
         None
     }
 
+    fn refresh(&self) -> LocatorResult {
+        // Finding the environments clears the cached state.
+        let reporter = collect::create_reporter();
+        self.find(&reporter);
+        reporter.to_locator_result()
+    }
+
     fn find(&self, reporter: &dyn Reporter) {
         self.clear();
 
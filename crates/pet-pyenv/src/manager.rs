@@ -6,8 +6,11 @@ use crate::{
     environment_locations::{get_binary_from_known_paths, get_home_pyenv_dir, get_pyenv_dir},
 };
 use lazy_static::lazy_static;
+use pet_process::run;
 use regex::Regex;
 use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
 use std::{fs, path::PathBuf};
 
 lazy_static! {
@@ -25,24 +28,28 @@ pub struct PyEnvInfo {
     pub exe: Option<PathBuf>,
     pub versions: Option<PathBuf>,
     pub version: Option<String>,
+    /// The pyenv root directory (`PYENV_ROOT`), i.e. the directory containing `versions`.
+    pub root: Option<PathBuf>,
 }
 
 impl PyEnvInfo {
-    pub fn from(environment: &EnvVariables) -> PyEnvInfo {
-        get_pyenv_info(environment)
+    pub fn from(environment: &EnvVariables, timeout: Option<Duration>) -> PyEnvInfo {
+        get_pyenv_info(environment, timeout)
     }
 }
 
-fn get_pyenv_info(environment: &EnvVariables) -> PyEnvInfo {
+fn get_pyenv_info(environment: &EnvVariables, timeout: Option<Duration>) -> PyEnvInfo {
     let mut pyenv = PyEnvInfo {
         exe: None,
         versions: None,
         version: None,
+        root: None,
     };
     if let Some(dir) = get_pyenv_dir(environment) {
         let versions = dir.join("versions");
         if versions.exists() {
             pyenv.versions = Some(versions);
+            pyenv.root = Some(dir.clone());
         }
         let exe = dir.join("bin").join("pyenv");
         if exe.exists() {
@@ -65,6 +72,7 @@ fn get_pyenv_info(environment: &EnvVariables) -> PyEnvInfo {
                 let versions = path.join("versions");
                 if versions.exists() {
                     pyenv.versions = Some(versions);
+                    pyenv.root = Some(path.clone());
                 }
             }
         }
@@ -72,7 +80,8 @@ fn get_pyenv_info(environment: &EnvVariables) -> PyEnvInfo {
 
     // Get the version of the pyenv manager
     if let Some(ref exe) = pyenv.exe {
-        pyenv.version = get_pyenv_manager_version(exe, environment);
+        pyenv.version = get_pyenv_manager_version(exe, environment)
+            .or_else(|| get_pyenv_manager_version_by_spawning(exe, timeout));
     }
 
     pyenv
@@ -117,3 +126,44 @@ fn get_pyenv_manager_version(pyenv_exe: &Path, _environment: &EnvVariables) -> O
             .to_string(),
     )
 }
+
+/// Last resort, as spawning pyenv is slow, e.g. pyenv installed using git has no version in its path.
+fn get_pyenv_manager_version_by_spawning(
+    pyenv_exe: &Path,
+    timeout: Option<Duration>,
+) -> Option<String> {
+    let output = run(Command::new(pyenv_exe).arg("--version"), timeout).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_pyenv_version_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Sample output of `pyenv --version` is `pyenv 2.4.0` or `pyenv 2.4.0-12-g4b4a8b2c`.
+fn parse_pyenv_version_output(output: &str) -> Option<String> {
+    Some(
+        PYENV_VERSION_FROM_VERSION_FILE
+            .captures(output.trim())?
+            .get(1)?
+            .as_str()
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_from_pyenv_output() {
+        assert_eq!(
+            parse_pyenv_version_output("pyenv 2.4.0\n"),
+            Some("2.4.0".to_string())
+        );
+        assert_eq!(
+            parse_pyenv_version_output("pyenv 2.3.36-12-g4b4a8b2c"),
+            Some("2.3.36".to_string())
+        );
+        assert_eq!(parse_pyenv_version_output("pyenv: command not found"), None);
+    }
+}
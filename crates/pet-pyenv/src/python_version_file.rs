@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{
+    env_variables::EnvVariables,
+    environment_locations::{get_home_pyenv_dir, get_pyenv_dir},
+};
+use std::{fs, path::Path};
+
+const PYTHON_VERSION_FILE: &str = ".python-version";
+
+/// Version of Python pyenv uses for the given directory.
+/// I.e. the version in the closest `.python-version` file (looking in the directory & its parents),
+/// else the global version in `$PYENV_ROOT/version`.
+/// See https://github.com/pyenv/pyenv#choosing-the-python-version
+pub fn get_active_version(project_dir: &Path, env_vars: &EnvVariables) -> Option<String> {
+    for dir in project_dir.ancestors() {
+        if let Some(version) = read_version_file(&dir.join(PYTHON_VERSION_FILE)) {
+            return Some(version);
+        }
+    }
+    let pyenv_dir = get_pyenv_dir(env_vars).or_else(|| get_home_pyenv_dir(env_vars))?;
+    read_version_file(&pyenv_dir.join("version"))
+}
+
+/// The file can contain multiple versions (the first one is used) & comments.
+fn read_version_file(file: &Path) -> Option<String> {
+    let contents = fs::read_to_string(file).ok()?;
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .flat_map(|line| line.split_whitespace())
+        .next()
+        .map(|version| version.to_string())
+}
@@ -29,8 +29,8 @@ fn does_not_find_any_pyenv_envs() {
     let environments = reporter.environments.lock().unwrap().clone();
     let managers = reporter.managers.lock().unwrap().clone();
 
-    assert_eq!(managers.is_empty(), true);
-    assert_eq!(environments.is_empty(), true);
+    assert!(managers.is_empty());
+    assert!(environments.is_empty());
 }
 
 #[test]
@@ -48,7 +48,7 @@ fn does_not_find_any_pyenv_envs_even_with_pyenv_installed() {
     use pet_pyenv::PyEnv;
     use pet_reporter::{cache::CacheReporter, collect};
     use serde_json::json;
-    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use std::{collections::HashMap, sync::Arc};
 
     let home = resolve_test_path(&["unix", "pyenv_without_envs", "user_home"]);
     let homebrew_bin = resolve_test_path(&[
@@ -60,12 +60,8 @@ fn does_not_find_any_pyenv_envs_even_with_pyenv_installed() {
         "bin",
     ]);
     let pyenv_exe = resolve_test_path(&[homebrew_bin.to_str().unwrap(), "pyenv"]);
-    let environment = create_test_environment(
-        HashMap::new(),
-        Some(home.clone()),
-        vec![PathBuf::from(homebrew_bin)],
-        None,
-    );
+    let environment =
+        create_test_environment(HashMap::new(), Some(home.clone()), vec![homebrew_bin], None);
 
     let conda = Arc::new(Conda::from(&environment));
     let locator = PyEnv::from(&environment, conda);
@@ -80,10 +76,47 @@ fn does_not_find_any_pyenv_envs_even_with_pyenv_installed() {
         executable: pyenv_exe.clone(),
         version: None,
         tool: EnvManagerType::Pyenv,
+        install_path: None,
     };
     assert_eq!(json!(expected_manager), json!(managers[0]));
 }
 
+#[test]
+#[cfg(unix)]
+fn pyenv_manager_install_path_is_pyenv_root() {
+    use crate::common::create_test_environment;
+    use common::resolve_test_path;
+    use pet_conda::Conda;
+    use pet_core::{manager::EnvManagerType, Locator};
+    use pet_pyenv::PyEnv;
+    use pet_reporter::{cache::CacheReporter, collect};
+    use std::{collections::HashMap, sync::Arc};
+
+    let pyenv_root = resolve_test_path(&["unix", "pyenv", "user_home", ".pyenv"]);
+    let homebrew_bin = resolve_test_path(&["unix", "pyenv", "home", "opt", "homebrew", "bin"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "PYENV_ROOT".to_string(),
+            pyenv_root.to_str().unwrap().to_string(),
+        )]),
+        None,
+        vec![homebrew_bin],
+        None,
+    );
+
+    let conda = Arc::new(Conda::from(&environment));
+    let locator = PyEnv::from(&environment, conda);
+    let reporter = Arc::new(collect::create_reporter());
+    locator.find(&CacheReporter::new(reporter.clone()));
+
+    let managers = reporter.managers.lock().unwrap().clone();
+    let manager = managers
+        .iter()
+        .find(|m| m.tool == EnvManagerType::Pyenv)
+        .expect("Pyenv manager not found");
+    assert_eq!(manager.install_path, Some(pyenv_root));
+}
+
 #[test]
 #[cfg(unix)]
 fn find_pyenv_envs() {
@@ -101,7 +134,7 @@ fn find_pyenv_envs() {
     use pet_pyenv::PyEnv;
     use pet_reporter::{cache::CacheReporter, collect};
     use serde_json::json;
-    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use std::{collections::HashMap, sync::Arc};
 
     let home = resolve_test_path(&["unix", "pyenv", "user_home"]);
     let homebrew_bin = resolve_test_path(&["unix", "pyenv", "home", "opt", "homebrew", "bin"]);
@@ -116,12 +149,8 @@ fn find_pyenv_envs() {
     ]);
     let conda_exe = conda_dir.join("bin").join("conda");
 
-    let environment = create_test_environment(
-        HashMap::new(),
-        Some(home.clone()),
-        vec![PathBuf::from(homebrew_bin)],
-        None,
-    );
+    let environment =
+        create_test_environment(HashMap::new(), Some(home.clone()), vec![homebrew_bin], None);
 
     let conda = Arc::new(Conda::from(&environment));
     let locator = PyEnv::from(&environment, conda);
@@ -137,11 +166,13 @@ fn find_pyenv_envs() {
         executable: pyenv_exe.clone(),
         version: None,
         tool: EnvManagerType::Pyenv,
+        install_path: Some(home.join(".pyenv")),
     };
     let expected_conda_manager = EnvManager {
         executable: conda_exe.clone(),
         version: Some("23.11.0".to_string()),
         tool: EnvManagerType::Conda,
+        install_path: Some(conda_dir.clone()),
     };
 
     let mut expected = vec![
@@ -161,7 +192,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/3.9.9/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.9.9".to_string()),
+        version: "3.9.9".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/3.9.9",
@@ -172,6 +203,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/3.9.9/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_virtual_env = PythonEnvironment {
@@ -183,7 +215,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/my-virtual-env/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::PyenvVirtualEnv),
-        version: Some("3.10.13".to_string()),
+        version: "3.10.13".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/my-virtual-env",
@@ -194,6 +226,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/my-virtual-env/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_3_12_1 = PythonEnvironment {
@@ -205,7 +238,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/3.12.1/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.12.1".to_string()),
+        version: "3.12.1".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/3.12.1",
@@ -216,6 +249,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/3.12.1/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_3_13_dev = PythonEnvironment {
@@ -227,7 +261,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/3.13-dev/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.13-dev".to_string()),
+        version: "3.13-dev".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/3.13-dev",
@@ -238,6 +272,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/3.13-dev/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_3_12_1a3 = PythonEnvironment {
@@ -249,7 +284,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/3.12.1a3/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.12.1a3".to_string()),
+        version: "3.12.1a3".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/3.12.1a3",
@@ -260,6 +295,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/3.12.1a3/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_no_gil = PythonEnvironment {
@@ -271,7 +307,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/nogil-3.9.10-1/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.9.10".to_string()),
+        version: "3.9.10".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/nogil-3.9.10-1",
@@ -282,6 +318,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/nogil-3.9.10-1/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
     let expected_pypy = PythonEnvironment {
@@ -293,7 +330,7 @@ fn find_pyenv_envs() {
             ".pyenv/versions/pypy3.9-7.3.15/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.9.18".to_string()),
+        version: "3.9.18".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/pypy3.9-7.3.15",
@@ -304,6 +341,7 @@ fn find_pyenv_envs() {
             home.to_str().unwrap(),
             ".pyenv/versions/pypy3.9-7.3.15/bin/python",
         ])]),
+        source_locator: Some("PyEnv"),
         ..Default::default()
     };
 
@@ -313,11 +351,12 @@ fn find_pyenv_envs() {
         name: Some("base".to_string()),
         executable: Some(conda_dir.join("bin").join("python")),
         kind: Some(PythonEnvironmentKind::Conda),
-        version: Some("3.11.5".to_string()),
+        version: "3.11.5".parse().ok(),
         prefix: Some(conda_dir.clone()),
         manager: Some(expected_conda_manager.clone()),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![conda_dir.join("bin").join("python")]),
+        source_locator: Some("Conda"),
         ..Default::default()
     };
     let expected_conda_one = PythonEnvironment {
@@ -326,11 +365,12 @@ fn find_pyenv_envs() {
         name: Some("one".to_string()),
         executable: Some(conda_dir.join("envs").join("one").join("python")),
         kind: Some(PythonEnvironmentKind::Conda),
-        version: Some("3.11.1".to_string()),
+        version: "3.11.1".parse().ok(),
         prefix: Some(conda_dir.join("envs").join("one")),
         manager: Some(expected_conda_manager.clone()),
         arch: None,
         symlinks: Some(vec![conda_dir.join("envs").join("one").join("python")]),
+        source_locator: Some("Conda"),
         ..Default::default()
     };
     let expected_conda_two = PythonEnvironment {
@@ -339,11 +379,12 @@ fn find_pyenv_envs() {
         name: Some("two".to_string()),
         executable: Some(conda_dir.join("envs").join("two").join("python")),
         kind: Some(PythonEnvironmentKind::Conda),
-        version: Some("3.11.1".to_string()),
+        version: "3.11.1".parse().ok(),
         prefix: Some(conda_dir.join("envs").join("two")),
         manager: Some(expected_conda_manager.clone()),
         symlinks: Some(vec![conda_dir.join("envs").join("two").join("python")]),
         arch: None,
+        source_locator: Some("Conda"),
         ..Default::default()
     };
 
@@ -396,6 +437,7 @@ fn resolve_pyenv_environment() {
         executable: pyenv_exe.clone(),
         version: None,
         tool: EnvManagerType::Pyenv,
+        install_path: Some(home.join(".pyenv")),
     };
 
     let executable =
@@ -406,7 +448,7 @@ fn resolve_pyenv_environment() {
         name: None,
         executable: Some(executable.clone()),
         kind: Some(PythonEnvironmentKind::Pyenv),
-        version: Some("3.9.9".to_string()),
+        version: "3.9.9".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/3.9.9",
@@ -425,7 +467,7 @@ fn resolve_pyenv_environment() {
             ".pyenv/versions/my-virtual-env/bin/python",
         ])),
         kind: Some(PythonEnvironmentKind::PyenvVirtualEnv),
-        version: Some("3.10.13".to_string()),
+        version: "3.10.13".parse().ok(),
         prefix: Some(resolve_test_path(&[
             home.to_str().unwrap(),
             ".pyenv/versions/my-virtual-env",
@@ -479,7 +521,7 @@ fn resolve_pyenv_environment() {
         None,
     ));
 
-    assert_eq!(result.is_none(), true);
+    assert!(result.is_none());
 
     // Should not resolve conda envs using Conda Locator
     let result = conda.try_from(&PythonEnv::new(
@@ -494,6 +536,86 @@ fn resolve_pyenv_environment() {
         None,
     ));
 
-    assert_eq!(result.is_some(), true);
+    assert!(result.is_some());
     assert_eq!(result.unwrap().kind, Some(PythonEnvironmentKind::Conda));
 }
+
+#[test]
+#[cfg(unix)]
+fn active_version_from_python_version_files() {
+    use common::{create_env_variables, resolve_test_path};
+    use pet_pyenv::python_version_file::get_active_version;
+
+    let root = resolve_test_path(&["unix", "pyenv_version_file"]);
+    let mut env_vars = create_env_variables(root.join("user_home"), root.clone());
+    env_vars.pyenv_root = Some(root.join(".pyenv").to_str().unwrap().to_string());
+
+    // First version in the closest .python-version file.
+    assert_eq!(
+        get_active_version(&root.join("project"), &env_vars),
+        Some("3.12.1".to_string())
+    );
+    assert_eq!(
+        get_active_version(&root.join("project").join("src"), &env_vars),
+        Some("3.12.1".to_string())
+    );
+    // Global version.
+    assert_eq!(
+        get_active_version(&root.join("other"), &env_vars),
+        Some("3.9.9".to_string())
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn refresh_finds_new_versions() {
+    use crate::common::create_test_environment;
+    use pet_conda::Conda;
+    use pet_core::Locator;
+    use pet_pyenv::PyEnv;
+    use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+    fn install_version(pyenv_root: &Path, version: &str) {
+        let bin = pyenv_root.join("versions").join(version).join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join("python"), "").unwrap();
+    }
+
+    let pyenv_root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join(format!("pet_pyenv_refresh_test_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&pyenv_root);
+    install_version(&pyenv_root, "3.12.1");
+    let environment = create_test_environment(
+        HashMap::from([(
+            "PYENV_ROOT".to_string(),
+            pyenv_root.to_str().unwrap().to_string(),
+        )]),
+        None,
+        vec![],
+        None,
+    );
+    let conda = Arc::new(Conda::from(&environment));
+    let locator = PyEnv::from(&environment, conda);
+
+    let result = locator.refresh();
+    assert_eq!(result.environments.len(), 1);
+
+    install_version(&pyenv_root, "3.13.0");
+    let mut prefixes = locator
+        .refresh()
+        .environments
+        .iter()
+        .map(|e| e.prefix.clone().unwrap())
+        .collect::<Vec<_>>();
+    prefixes.sort();
+
+    let _ = fs::remove_dir_all(&pyenv_root);
+    assert_eq!(
+        prefixes,
+        vec![
+            pyenv_root.join("versions").join("3.12.1"),
+            pyenv_root.join("versions").join("3.13.0"),
+        ]
+    );
+}
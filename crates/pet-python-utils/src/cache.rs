@@ -2,18 +2,28 @@
 // Licensed under the MIT License.
 
 use lazy_static::lazy_static;
-use log::{trace, warn};
+use log::{debug, trace, warn};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     io,
-    path::PathBuf,
-    sync::{Arc, Mutex},
-    time::SystemTime,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, Weak,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
 };
 
 use crate::{
     env::ResolvedPythonEnv,
-    fs_cache::{delete_cache_file, get_cache_from_file, store_cache_in_file},
+    fs_cache::{
+        cache_contains_key, cache_entry_count, clear_manifest, delete_cache_file,
+        generate_cache_file, get_cache_from_file, get_cache_stats as get_file_cache_stats,
+        get_cached_environments, has_file_changed, is_cache_entry_expired, remove_expired_entries,
+        remove_oldest_entries, remove_stale_entries, store_cache_in_file, write_file_atomically,
+        CacheStats,
+    },
 };
 
 lazy_static! {
@@ -34,6 +44,46 @@ pub fn create_cache(executable: PathBuf) -> Arc<Mutex<Box<dyn CacheEntry>>> {
     CACHE.create_cache(executable)
 }
 
+/// Checks whether the interpreter details of the executable have been cached.
+pub fn contains_key(executable: &Path) -> bool {
+    CACHE.contains_key(executable)
+}
+
+/// Removes the cached interpreter details of the executable, e.g. when the environment has been deleted.
+/// Returns `true` if an entry was removed.
+pub fn remove(executable: &Path) -> bool {
+    CACHE.remove(executable)
+}
+
+/// Periodically removes stale entries from the cache, useful in long running processes.
+/// The background thread is stopped when the returned handle is dropped.
+pub fn start_background_flush(interval: Duration) -> BackgroundFlushHandle {
+    CACHE.start_background_flush(interval)
+}
+
+/// Removes the stale & expired entries from the cache.
+/// Returns the number of entries removed.
+pub fn evict_stale() -> usize {
+    CACHE.evict_stale()
+}
+
+/// Cache entries older than `max_age` are ignored (and evicted when flushing the cache).
+/// By default cache entries never expire.
+pub fn set_cache_max_age(max_age: Duration) {
+    CACHE.set_max_age(max_age)
+}
+
+/// Statistics of the cache, `None` if a cache directory has not been set.
+pub fn get_cache_stats() -> Option<CacheStats> {
+    get_cache_directory().map(|cache_directory| get_file_cache_stats(&cache_directory))
+}
+
+/// Once the number of entries in the cache exceeds `max_entries`, the least recently stored entries are evicted.
+/// By default the number of entries is not limited.
+pub fn set_cache_max_entries(max_entries: usize) {
+    CACHE.set_max_entries(max_entries)
+}
+
 pub fn get_cache_directory() -> Option<PathBuf> {
     CACHE.get_cache_directory()
 }
@@ -42,23 +92,98 @@ pub fn set_cache_directory(cache_dir: PathBuf) {
     CACHE.set_cache_directory(cache_dir)
 }
 
+/// Exports all of the environments in the cache into a single file, e.g. to share the cache between machines.
+pub fn export_cache(path: &Path) -> Result<(), CacheError> {
+    CACHE.export(path)
+}
+
+/// Imports the environments exported using `export_cache` into the cache.
+/// Returns the number of environments imported.
+pub fn import_cache(path: &Path) -> Result<usize, CacheError> {
+    CACHE.import(path)
+}
+
+#[derive(Debug)]
+pub enum CacheError {
+    IoError(io::Error),
+    SerializationError(serde_json::Error),
+    DeserializationError(serde_json::Error),
+}
+
+impl std::fmt::Display for CacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::IoError(err) => write!(f, "IO error: {}", err),
+            CacheError::SerializationError(err) => write!(f, "Serialization error: {}", err),
+            CacheError::DeserializationError(err) => write!(f, "Deserialization error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CacheError {}
+
 pub type LockableCacheEntry = Arc<Mutex<Box<dyn CacheEntry>>>;
 
+/// Stops the background flush thread when dropped.
+pub struct BackgroundFlushHandle {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for BackgroundFlushHandle {
+    fn drop(&mut self) {
+        // Dropping the sender wakes the thread up immediately.
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
 /// Cache of Interpreter details for a given executable.
 /// Uses in memory cache as well as a file cache as backing store.
 struct CacheImpl {
     cache_dir: Arc<Mutex<Option<PathBuf>>>,
-    locks: Mutex<HashMap<PathBuf, LockableCacheEntry>>,
+    /// Entries in the file cache older than this are treated as stale, `None` means they never expire.
+    max_age: Arc<Mutex<Option<Duration>>>,
+    /// Maximum number of entries in the file cache, `None` means there is no limit.
+    max_entries: Arc<Mutex<Option<usize>>>,
+    locks: Arc<Mutex<HashMap<PathBuf, LockableCacheEntry>>>,
 }
 
 impl CacheImpl {
     fn new(cache_dir: Option<PathBuf>) -> CacheImpl {
         CacheImpl {
             cache_dir: Arc::new(Mutex::new(cache_dir)),
-            locks: Mutex::new(HashMap::<PathBuf, LockableCacheEntry>::new()),
+            max_age: Arc::new(Mutex::new(None)),
+            max_entries: Arc::new(Mutex::new(None)),
+            locks: Arc::new(Mutex::new(HashMap::<PathBuf, LockableCacheEntry>::new())),
         }
     }
 
+    #[cfg(test)]
+    fn new_with_max_age(cache_dir: Option<PathBuf>, max_age: Duration) -> CacheImpl {
+        let cache = CacheImpl::new(cache_dir);
+        cache.set_max_age(max_age);
+        cache
+    }
+
+    #[cfg(test)]
+    fn with_max_entries(self, max_entries: usize) -> CacheImpl {
+        self.set_max_entries(max_entries);
+        self
+    }
+
+    fn set_max_age(&self, max_age: Duration) {
+        trace!("Setting cache max age to {:?}", max_age);
+        self.max_age.lock().unwrap().replace(max_age);
+    }
+
+    fn set_max_entries(&self, max_entries: usize) {
+        trace!("Setting cache max entries to {:?}", max_entries);
+        self.max_entries.lock().unwrap().replace(max_entries);
+    }
+
     fn get_cache_directory(&self) -> Option<PathBuf> {
         self.cache_dir.lock().unwrap().clone()
     }
@@ -80,18 +205,116 @@ impl CacheImpl {
         trace!("Clearing cache");
         self.locks.lock().unwrap().clear();
         if let Some(cache_directory) = self.cache_dir.lock().unwrap().clone() {
+            clear_manifest(&cache_directory);
             std::fs::remove_dir_all(cache_directory)
         } else {
             Ok(())
         }
     }
+    fn contains_key(&self, executable: &Path) -> bool {
+        match self.cache_dir.lock().unwrap().clone() {
+            Some(cache_directory) => cache_contains_key(&cache_directory, executable),
+            None => false,
+        }
+    }
+    /// Removes the stale (and expired) entries from the file cache as well as the in memory cache.
+    /// Returns the number of entries removed.
+    fn evict_stale(&self) -> usize {
+        let cache_directory = match self.cache_dir.lock().unwrap().clone() {
+            Some(cache_directory) => cache_directory,
+            None => return 0,
+        };
+        let mut removed = remove_stale_entries(&cache_directory);
+        if let Some(max_age) = *self.max_age.lock().unwrap() {
+            removed.append(&mut remove_expired_entries(&cache_directory, max_age));
+        }
+        let mut locks = self.locks.lock().unwrap();
+        for executable in removed.iter() {
+            locks.remove(executable);
+        }
+        drop(locks);
+        debug!(
+            "Flushed cache {:?}, removed {} stale entries, {} entries remaining",
+            cache_directory,
+            removed.len(),
+            cache_entry_count(&cache_directory)
+        );
+        removed.len()
+    }
+    fn start_background_flush(&'static self, interval: Duration) -> BackgroundFlushHandle {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                self.evict_stale();
+            }
+        });
+        BackgroundFlushHandle {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+    fn remove(&self, executable: &Path) -> bool {
+        let removed_from_memory = self.locks.lock().unwrap().remove(executable).is_some();
+        let removed_from_disk = match self.cache_dir.lock().unwrap().clone() {
+            Some(cache_directory) => {
+                let executable = executable.to_path_buf();
+                let exists = cache_contains_key(&cache_directory, &executable)
+                    || generate_cache_file(&cache_directory, &executable).exists();
+                if exists {
+                    trace!("Removing cache for {:?}", executable);
+                    delete_cache_file(&cache_directory, &executable);
+                }
+                exists
+            }
+            None => false,
+        };
+        removed_from_memory || removed_from_disk
+    }
+    fn export(&self, path: &Path) -> Result<(), CacheError> {
+        let environments = match self.cache_dir.lock().unwrap().clone() {
+            Some(cache_directory) => get_cached_environments(&cache_directory),
+            None => vec![],
+        };
+        trace!(
+            "Exporting {} cached environments to {:?}",
+            environments.len(),
+            path
+        );
+        let contents =
+            serde_json::to_vec_pretty(&environments).map_err(CacheError::SerializationError)?;
+        write_file_atomically(path, &contents).map_err(CacheError::IoError)
+    }
+    fn import(&self, path: &Path) -> Result<usize, CacheError> {
+        let contents = std::fs::read(path).map_err(CacheError::IoError)?;
+        let environments: Vec<ResolvedPythonEnv> =
+            serde_json::from_slice(&contents).map_err(CacheError::DeserializationError)?;
+        trace!(
+            "Importing {} environments from {:?}",
+            environments.len(),
+            path
+        );
+        for environment in environments.iter() {
+            self.create_cache(environment.executable.clone())
+                .lock()
+                .unwrap()
+                .store(environment.clone());
+        }
+        Ok(environments.len())
+    }
     fn create_cache(&self, executable: PathBuf) -> LockableCacheEntry {
         let cache_directory = self.cache_dir.lock().unwrap().clone();
+        let max_age = *self.max_age.lock().unwrap();
+        let max_entries = *self.max_entries.lock().unwrap();
         match self.locks.lock().unwrap().entry(executable.clone()) {
             Entry::Occupied(lock) => lock.get().clone(),
             Entry::Vacant(lock) => {
-                let cache = Box::new(CacheEntryImpl::create(cache_directory.clone(), executable))
-                    as Box<(dyn CacheEntry + 'static)>;
+                let cache = Box::new(CacheEntryImpl::create(
+                    cache_directory.clone(),
+                    max_age,
+                    max_entries,
+                    Arc::downgrade(&self.locks),
+                    executable,
+                )) as Box<dyn CacheEntry + 'static>;
                 lock.insert(Arc::new(Mutex::new(cache))).clone()
             }
         }
@@ -102,15 +325,29 @@ type FilePathWithMTimeCTime = (PathBuf, SystemTime, SystemTime);
 
 struct CacheEntryImpl {
     cache_directory: Option<PathBuf>,
+    max_age: Option<Duration>,
+    max_entries: Option<usize>,
+    /// In memory cache entries, used to remove the entries evicted from the file cache.
+    /// Weak reference, as the in memory cache holds on to this entry.
+    locks: Weak<Mutex<HashMap<PathBuf, LockableCacheEntry>>>,
     executable: PathBuf,
     envoronment: Arc<Mutex<Option<ResolvedPythonEnv>>>,
     /// List of known symlinks to this executable.
     symlinks: Arc<Mutex<Vec<FilePathWithMTimeCTime>>>,
 }
 impl CacheEntryImpl {
-    pub fn create(cache_directory: Option<PathBuf>, executable: PathBuf) -> impl CacheEntry {
+    pub fn create(
+        cache_directory: Option<PathBuf>,
+        max_age: Option<Duration>,
+        max_entries: Option<usize>,
+        locks: Weak<Mutex<HashMap<PathBuf, LockableCacheEntry>>>,
+        executable: PathBuf,
+    ) -> impl CacheEntry {
         CacheEntryImpl {
             cache_directory,
+            max_age,
+            max_entries,
+            locks,
             executable,
             envoronment: Arc::new(Mutex::new(None)),
             symlinks: Arc::new(Mutex::new(Vec::new())),
@@ -119,22 +356,18 @@ impl CacheEntryImpl {
     pub fn verify_in_memory_cache(&self) {
         // Check if any of the exes have changed since we last cached this.
         for symlink_info in self.symlinks.lock().unwrap().iter() {
-            if let Ok(metadata) = symlink_info.0.metadata() {
-                if metadata.modified().ok() != Some(symlink_info.1)
-                    || metadata.created().ok() != Some(symlink_info.2)
-                {
-                    trace!(
-                        "Symlink {:?} has changed since we last cached it. original mtime & ctime {:?}, {:?}, current mtime & ctime {:?}, {:?}",
-                        symlink_info.0,
-                        symlink_info.1,
-                        symlink_info.2,
-                        metadata.modified().ok(),
-                        metadata.created().ok()
-                    );
-                    self.envoronment.lock().unwrap().take();
-                    if let Some(cache_directory) = &self.cache_directory {
-                        delete_cache_file(cache_directory, &self.executable);
-                    }
+            if symlink_info.0.exists()
+                && has_file_changed(&symlink_info.0, symlink_info.1, symlink_info.2)
+            {
+                trace!(
+                    "Symlink {:?} has changed since we last cached it. original mtime & ctime {:?}, {:?}",
+                    symlink_info.0,
+                    symlink_info.1,
+                    symlink_info.2,
+                );
+                self.envoronment.lock().unwrap().take();
+                if let Some(cache_directory) = &self.cache_directory {
+                    delete_cache_file(cache_directory, &self.executable);
                 }
             }
         }
@@ -153,6 +386,12 @@ impl CacheEntry for CacheEntryImpl {
         }
 
         if let Some(ref cache_directory) = self.cache_directory {
+            if let Some(max_age) = self.max_age {
+                if is_cache_entry_expired(cache_directory, &self.executable, max_age) {
+                    trace!("Ignoring expired cache for {:?}", self.executable);
+                    return None;
+                }
+            }
             let (env, symlinks) = get_cache_from_file(cache_directory, &self.executable)?;
             self.envoronment.lock().unwrap().replace(env.clone());
             self.symlinks.lock().unwrap().clear();
@@ -190,7 +429,17 @@ impl CacheEntry for CacheEntryImpl {
         trace!("Caching interpreter info for {:?}", self.executable);
 
         if let Some(ref cache_directory) = self.cache_directory {
-            store_cache_in_file(cache_directory, &self.executable, &environment, symlinks)
+            store_cache_in_file(cache_directory, &self.executable, &environment, symlinks);
+
+            if let Some(max_entries) = self.max_entries {
+                let evicted = remove_oldest_entries(cache_directory, max_entries);
+                if let Some(locks) = self.locks.upgrade() {
+                    let mut locks = locks.lock().unwrap();
+                    for executable in evicted.iter() {
+                        locks.remove(executable);
+                    }
+                }
+            }
         }
     }
 
@@ -230,3 +479,266 @@ impl CacheEntry for CacheEntryImpl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty directory unique to the test (and the process), so tests never share files.
+    fn create_test_dir(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    /// Stores a resolved environment in the cache for each of the executables.
+    fn store_environments(cache: &CacheImpl, executables: &[PathBuf]) {
+        for executable in executables {
+            let environment = ResolvedPythonEnv {
+                executable: executable.clone(),
+                prefix: executable.parent().unwrap().parent().unwrap().into(),
+                version: "3.12.0".to_string(),
+                is64_bit: true,
+                symlinks: Some(vec![executable.clone()]),
+            };
+            cache
+                .create_cache(executable.clone())
+                .lock()
+                .unwrap()
+                .store(environment);
+        }
+    }
+
+    fn remove_test_dir(root: &Path, cache_directories: &[&Path]) {
+        let _ = std::fs::remove_dir_all(root);
+        for cache_directory in cache_directories {
+            clear_manifest(cache_directory);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn remove_deletes_the_cache_entry() {
+        let root = create_test_dir("pet_cache_remove_test");
+        let cache_directory = root.join("cache");
+        let cache = CacheImpl::new(Some(cache_directory.clone()));
+        let executable = root.join("env").join("bin").join("python");
+        let other_executable = root.join("other").join("bin").join("python");
+        store_environments(&cache, &[executable.clone(), other_executable.clone()]);
+        assert!(cache.contains_key(&executable));
+
+        assert!(cache.remove(&executable));
+
+        assert!(!cache.contains_key(&executable));
+        assert!(!generate_cache_file(&cache_directory, &executable).exists());
+        assert!(cache.contains_key(&other_executable));
+        // Nothing left to remove.
+        assert!(!cache.remove(&executable));
+
+        remove_test_dir(&root, &[&cache_directory]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn clear_removes_all_cache_entries() {
+        let root = create_test_dir("pet_cache_clear_test");
+        let cache_directory = root.join("cache");
+        let cache = CacheImpl::new(Some(cache_directory.clone()));
+        let executables = [
+            root.join("env").join("bin").join("python"),
+            root.join("other").join("bin").join("python"),
+        ];
+        store_environments(&cache, &executables);
+
+        cache.clear().unwrap();
+
+        assert!(!cache_directory.exists());
+        // Nothing left to remove.
+        assert!(!cache.remove(&executables[0]));
+        for executable in executables.iter() {
+            assert!(!cache.contains_key(executable));
+            // In memory cache has been cleared as well.
+            let entry = cache.create_cache(executable.clone());
+            assert!(entry.lock().unwrap().get().is_none());
+        }
+
+        remove_test_dir(&root, &[&cache_directory]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn background_flush_removes_stale_entries() {
+        let root = create_test_dir("pet_cache_background_flush_test");
+        let cache_directory = root.join("cache");
+        let cache: &'static CacheImpl =
+            Box::leak(Box::new(CacheImpl::new(Some(cache_directory.clone()))));
+        let executable = root.join("env").join("bin").join("python");
+        let other_executable = root.join("other").join("bin").join("python");
+        for executable in [&executable, &other_executable] {
+            std::fs::create_dir_all(executable.parent().unwrap()).unwrap();
+            std::fs::write(executable, "").unwrap();
+        }
+        store_environments(cache, &[executable.clone(), other_executable.clone()]);
+        assert!(cache.contains_key(&executable));
+
+        // Environment has been deleted, hence the cache entry is stale.
+        std::fs::remove_file(&executable).unwrap();
+        let handle = cache.start_background_flush(Duration::from_millis(10));
+        let mut attempts = 0;
+        while cache.contains_key(&executable) && attempts < 500 {
+            thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        drop(handle);
+
+        assert!(!cache.contains_key(&executable));
+        assert!(!generate_cache_file(&cache_directory, &executable).exists());
+        assert!(cache.contains_key(&other_executable));
+
+        remove_test_dir(&root, &[&cache_directory]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn expired_entries_are_ignored_and_evicted() {
+        let root = create_test_dir("pet_cache_max_age_test");
+        let cache_directory = root.join("cache");
+        let max_age = Duration::from_secs(60 * 60);
+        let cache = CacheImpl::new_with_max_age(Some(cache_directory.clone()), max_age);
+        let executable = root.join("env").join("bin").join("python");
+        let other_executable = root.join("other").join("bin").join("python");
+        store_environments(&cache, &[executable.clone(), other_executable.clone()]);
+
+        // Pretend the cache entry was written a while ago.
+        std::fs::File::options()
+            .write(true)
+            .open(generate_cache_file(&cache_directory, &executable))
+            .unwrap()
+            .set_modified(SystemTime::now() - max_age * 2)
+            .unwrap();
+
+        // Without a max age, the entries never expire.
+        let cache_without_max_age = CacheImpl::new(Some(cache_directory.clone()));
+        for executable in [&executable, &other_executable] {
+            let entry = cache_without_max_age.create_cache(executable.clone());
+            assert!(entry.lock().unwrap().get().is_some());
+        }
+
+        let cache = CacheImpl::new_with_max_age(Some(cache_directory.clone()), max_age);
+        let entry = cache.create_cache(executable.clone());
+        assert!(entry.lock().unwrap().get().is_none());
+        let entry = cache.create_cache(other_executable.clone());
+        assert!(entry.lock().unwrap().get().is_some());
+
+        assert_eq!(cache.evict_stale(), 1);
+
+        assert!(!cache.contains_key(&executable));
+        assert!(!generate_cache_file(&cache_directory, &executable).exists());
+        assert!(cache.contains_key(&other_executable));
+        // Nothing left to evict.
+        assert_eq!(cache.evict_stale(), 0);
+
+        remove_test_dir(&root, &[&cache_directory]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn oldest_entries_are_evicted_when_exceeding_max_entries() {
+        let root = create_test_dir("pet_cache_max_entries_test");
+        let cache_directory = root.join("cache");
+        let max_entries = 3;
+        let cache = CacheImpl::new(Some(cache_directory.clone())).with_max_entries(max_entries);
+        let executables = (0..=max_entries)
+            .map(|i| root.join(format!("env{i}")).join("bin").join("python"))
+            .collect::<Vec<_>>();
+        for (i, executable) in executables.iter().enumerate() {
+            store_environments(&cache, std::slice::from_ref(executable));
+            // Eviction uses the time the entries were stored (kept in the manifest), not the modified time of the files.
+            if i == 0 {
+                std::fs::File::options()
+                    .write(true)
+                    .open(generate_cache_file(&cache_directory, executable))
+                    .unwrap()
+                    .set_modified(SystemTime::now() + Duration::from_secs(60 * 60))
+                    .unwrap();
+            }
+        }
+
+        assert_eq!(cache_entry_count(&cache_directory), max_entries);
+        assert!(!generate_cache_file(&cache_directory, &executables[0]).exists());
+        assert!(!cache.contains_key(&executables[0]));
+        assert!(!cache.locks.lock().unwrap().contains_key(&executables[0]));
+        for executable in executables.iter().skip(1) {
+            assert!(cache.contains_key(executable));
+            assert!(generate_cache_file(&cache_directory, executable).exists());
+        }
+
+        remove_test_dir(&root, &[&cache_directory]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn exported_cache_can_be_imported() {
+        let root = create_test_dir("pet_cache_export_test");
+        let cache_directory = root.join("cache");
+        let other_cache_directory = root.join("other_cache");
+        let cache = CacheImpl::new(Some(cache_directory.clone()));
+        let executables = [
+            root.join("env").join("bin").join("python"),
+            root.join("other").join("bin").join("python"),
+        ];
+        store_environments(&cache, &executables);
+        let export_file = root.join("export.json");
+
+        cache.export(&export_file).unwrap();
+
+        let other_cache = CacheImpl::new(Some(other_cache_directory.clone()));
+        assert_eq!(other_cache.import(&export_file).unwrap(), executables.len());
+        for executable in executables.iter() {
+            assert!(other_cache.contains_key(executable));
+            // Read from the file cache.
+            let environment = CacheImpl::new(Some(other_cache_directory.clone()))
+                .create_cache(executable.clone())
+                .lock()
+                .unwrap()
+                .get()
+                .unwrap();
+            assert_eq!(&environment.executable, executable);
+            assert_eq!(environment.version, "3.12.0");
+        }
+
+        remove_test_dir(&root, &[&cache_directory, &other_cache_directory]);
+    }
+
+    #[test]
+    fn importing_malformed_files_fails_gracefully() {
+        let root = create_test_dir("pet_cache_import_test");
+        let cache = CacheImpl::new(None);
+
+        let import_file = root.join("import.json");
+        std::fs::write(&import_file, "[{").unwrap();
+        assert!(matches!(
+            cache.import(&import_file),
+            Err(CacheError::DeserializationError(_))
+        ));
+        assert!(matches!(
+            cache.import(&root.join("missing.json")),
+            Err(CacheError::IoError(_))
+        ));
+
+        remove_test_dir(&root, &[]);
+    }
+
+    #[test]
+    fn dropping_the_handle_stops_the_background_flush() {
+        let cache: &'static CacheImpl = Box::leak(Box::new(CacheImpl::new(None)));
+        let handle = cache.start_background_flush(Duration::from_secs(60 * 60));
+        let start = std::time::Instant::now();
+
+        // Should not have to wait for the interval to elapse.
+        drop(handle);
+
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+}
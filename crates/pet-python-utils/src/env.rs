@@ -42,16 +42,30 @@ impl ResolvedPythonEnv {
         env.symlinks.clone_from(&self.symlinks);
         env
     }
-    pub fn add_to_cache(&self, environment: PythonEnvironment) {
-        // Verify whether we have been given the right exe.
-        let arch = Some(if self.is64_bit {
+    /// Architecture of the interpreter.
+    /// On macOS this is read from the Mach-O header, to distinguish arm64 & x86_64 (Rosetta) builds.
+    pub fn arch(&self) -> Architecture {
+        #[cfg(target_os = "macos")]
+        if let Some(arch) = crate::executable::get_mach_o_architecture(&self.executable) {
+            return arch;
+        }
+        if self.is64_bit {
             Architecture::X64
         } else {
             Architecture::X86
-        });
+        }
+    }
+    pub fn add_to_cache(&self, environment: PythonEnvironment) {
+        // Verify whether we have been given the right exe.
+        let arch = Some(self.arch());
         let symlinks = environment.symlinks.clone().unwrap_or_default();
         if symlinks.contains(&self.executable)
-            && environment.version.clone().unwrap_or_default() == self.version
+            && environment
+                .version
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+                == self.version
             && environment.prefix.clone().unwrap_or_default() == self.prefix
             && environment.arch == arch
         {
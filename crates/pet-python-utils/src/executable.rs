@@ -3,17 +3,38 @@
 
 use lazy_static::lazy_static;
 use log::trace;
+use pet_core::arch::Architecture;
 use regex::Regex;
 use std::{
+    collections::HashSet,
     fs,
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 
 lazy_static! {
-    static ref WINDOWS_EXE: Regex =
-        Regex::new(r"python(\d+\.?)*.exe").expect("error parsing Windows executable regex");
-    static ref UNIX_EXE: Regex =
-        Regex::new(r"python(\d+\.?)*$").expect("error parsing Unix executable regex");
+    static ref WINDOWS_EXE: Regex = Regex::new(r"^python(\d+\.?)*\.exe$")
+        .expect("error parsing Windows executable regex");
+    static ref UNIX_EXE: Regex = Regex::new(r"^python(\d+\.?)*$")
+        .expect("error parsing Unix executable regex");
+    /// PyPy, e.g. `pypy3`, `pypy3.10` & `pypy3.10.exe`.
+    static ref PYPY_WINDOWS_EXE: Regex = Regex::new(r"^pypy(\d+\.?)*\.exe$")
+        .expect("error parsing PyPy Windows executable regex");
+    static ref PYPY_UNIX_EXE: Regex = Regex::new(r"^pypy(\d+\.?)*$")
+        .expect("error parsing PyPy Unix executable regex");
+    /// GraalPy, e.g. `graalpy`, `graalpy3` & `graalpy3.10`.
+    static ref GRAALPY_WINDOWS_EXE: Regex = Regex::new(r"^graalpy(\d+\.?)*\.exe$")
+        .expect("error parsing GraalPy Windows executable regex");
+    static ref GRAALPY_UNIX_EXE: Regex = Regex::new(r"^graalpy(\d+\.?)*$")
+        .expect("error parsing GraalPy Unix executable regex");
+}
+
+#[cfg(feature = "detect-free-threaded")]
+lazy_static! {
+    /// Free-threaded builds use the `t` ABI suffix, e.g. `python3.13t`, `lib/python3.13t` & `python313t.dll`.
+    static ref FREE_THREADED_NAME: Regex =
+        Regex::new(r"(?i)^python\d+\.?\d+t(\.exe|\.dll)?$")
+            .expect("error parsing free-threaded executable regex");
 }
 
 #[cfg(windows)]
@@ -40,10 +61,41 @@ pub fn find_executable(env_path: &Path) -> Option<PathBuf> {
     .find(|path| path.is_file())
 }
 
+/// Returns the prefix of the environment containing the executable, i.e. the parent of the `bin` (or `Scripts`) directory.
+/// On Windows the executable can also be in the root of the environment.
+pub fn get_prefix_from_executable(executable: &Path) -> Option<PathBuf> {
+    let parent = executable.parent()?;
+    if parent.ends_with("bin") || parent.ends_with("Scripts") {
+        Some(parent.parent()?.to_path_buf())
+    } else {
+        Some(parent.to_path_buf())
+    }
+}
+
+/// Whether this is the `%LOCALAPPDATA%\Microsoft\WindowsApps` directory containing the app execution aliases.
+pub fn is_windows_apps_dir(path: &Path) -> bool {
+    path.to_string_lossy()
+        .to_lowercase()
+        .replace('/', "\\")
+        .trim_end_matches('\\')
+        .ends_with("\\microsoft\\windowsapps")
+}
+
 pub fn find_executables<T: AsRef<Path>>(env_path: T) -> Vec<PathBuf> {
+    try_find_executables(env_path).unwrap_or_default()
+}
+
+/// Same as `find_executables`, however errors enumerating the directory are returned to the caller.
+/// E.g. directories in the PATH variable that the user cannot read will return `PermissionDenied`.
+pub fn try_find_executables<T: AsRef<Path>>(env_path: T) -> io::Result<Vec<PathBuf>> {
     // Never find exes in `.pyenv/shims/` folder, they are not valid exes
     if env_path.as_ref().ends_with(".pyenv/shims") {
-        return vec![];
+        return Ok(vec![]);
+    }
+    // Never find exes in the WindowsApps folder, these are app execution aliases (not real exes)
+    // for the Windows Store Python & are reported by the Windows Store locator.
+    if is_windows_apps_dir(env_path.as_ref()) {
+        return Ok(vec![]);
     }
     let mut python_executables = vec![];
     let bin = if cfg!(windows) { "Scripts" } else { "bin" };
@@ -67,6 +119,13 @@ pub fn find_executables<T: AsRef<Path>>(env_path: T) -> Vec<PathBuf> {
     } else {
         "python3"
     };
+    // PyPy & GraalPy do not always install a `python` exe.
+    let pypy3_exe = if cfg!(windows) { "pypy3.exe" } else { "pypy3" };
+    let graalpy_exe = if cfg!(windows) {
+        "graalpy.exe"
+    } else {
+        "graalpy"
+    };
 
     // On linux /home/linuxbrew/.linuxbrew/bin does not contain a `python` file
     // If you install python@3.10, then only a python3.10 exe is created in that bin directory.
@@ -74,22 +133,164 @@ pub fn find_executables<T: AsRef<Path>>(env_path: T) -> Vec<PathBuf> {
     // Else enumerating entire directories is very expensive.
     if env_path.join(python_exe).exists()
         || env_path.join(python3_exe).exists()
+        || env_path.join(pypy3_exe).exists()
+        || env_path.join(graalpy_exe).exists()
         || env_path.ends_with(bin)
     {
         // Enumerate this directory and get all `python` & `pythonX.X` files.
-        if let Ok(entries) = fs::read_dir(env_path) {
-            for entry in entries.filter_map(Result::ok) {
-                let file = entry.path();
-                if file.is_file() && is_python_executable_name(&file) {
-                    python_executables.push(file);
-                }
+        for entry in fs::read_dir(env_path)?.filter_map(Result::ok) {
+            let file = entry.path();
+            if file.is_file() && is_python_executable_name(&file) {
+                python_executables.push(file);
             }
         }
     }
 
     // Ensure the exe `python` is first, instead of `python3.10`
     python_executables.sort();
-    python_executables
+    Ok(python_executables)
+}
+
+/// Whether the executable is a free-threaded build of CPython (3.13+, built with `Py_GIL_DISABLED`).
+/// Determined from the `t` ABI suffix of the executable (e.g. `python3.13t`) or, for virtual environments,
+/// of the base interpreter & the `lib/python3.13t` directory of the environment, hence Python is never spawned.
+///
+/// Other files next to the executable are not considered, e.g. the Windows installer puts
+/// `python3.13t.exe` (& `python313t.dll`) in the same directory as the regular `python.exe`.
+#[cfg(feature = "detect-free-threaded")]
+pub fn is_free_threaded_build(exe: &Path) -> bool {
+    let is_free_threaded_name = |path: &Path| {
+        path.file_name()
+            .map(|name| FREE_THREADED_NAME.is_match(&name.to_string_lossy()))
+            .unwrap_or_default()
+    };
+
+    // `python3` & `python` are generally symlinks to `python3.13t`.
+    let real_exe = fs::canonicalize(exe).unwrap_or(exe.to_path_buf());
+    if is_free_threaded_name(exe) || is_free_threaded_name(&real_exe) {
+        return true;
+    }
+    let prefix = match exe.parent().and_then(|bin| bin.parent()) {
+        Some(prefix) => prefix,
+        None => return false,
+    };
+    let contents = match fs::read_to_string(prefix.join("pyvenv.cfg")) {
+        Ok(contents) => contents,
+        Err(_) => return false,
+    };
+    contents.lines().any(|line| {
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => return false,
+        };
+        match key {
+            // Base interpreter the virtual environment was created from (Python 3.11+).
+            "executable" => is_free_threaded_name(Path::new(value)),
+            // Virtual environments created with `--copies` only have a `lib/python3.13t` directory.
+            "version" | "version_info" => {
+                let major_minor = value.split('.').take(2).collect::<Vec<_>>().join(".");
+                prefix
+                    .join("lib")
+                    .join(format!("python{major_minor}t"))
+                    .is_dir()
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Returns the Python executable the virtual environment was created from.
+/// The `home` value in `pyvenv.cfg` is the directory containing the base interpreter.
+pub fn get_base_python(venv_root: &Path) -> Option<PathBuf> {
+    let contents = fs::read_to_string(venv_root.join("pyvenv.cfg")).ok()?;
+    let home = contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() == "home" {
+            Some(PathBuf::from(value.trim()))
+        } else {
+            None
+        }
+    })?;
+    let names = if cfg!(windows) {
+        vec!["python.exe"]
+    } else {
+        vec!["python3", "python"]
+    };
+    names
+        .into_iter()
+        .map(|name| home.join(name))
+        .find(|exe| exe.is_file())
+}
+
+/// Follows the symlinks starting from the given path and returns every path in the chain.
+/// The first item is always the given path and the last item is the real binary.
+/// E.g. `/usr/local/bin/python3` => `../Cellar/python@3.11/3.11.9/bin/python3.11` => ...
+pub fn resolve_symlink_chain(path: &Path) -> Vec<PathBuf> {
+    let mut chain = vec![path.to_path_buf()];
+    let mut visited = HashSet::new();
+    visited.insert(path.to_path_buf());
+    let mut current = path.to_path_buf();
+    while let Ok(target) = fs::read_link(&current) {
+        // Relative links are relative to the directory containing the link.
+        let target = match current.parent() {
+            Some(parent) if target.is_relative() => normalize_link_target(&parent.join(target)),
+            _ => target,
+        };
+        if !visited.insert(target.clone()) {
+            trace!("Cycle detected resolving symlinks of {:?}", path);
+            break;
+        }
+        chain.push(target.clone());
+        current = target;
+    }
+    chain
+}
+
+// Get rid of the `..` in paths such as `/usr/local/bin/../Cellar/python@3.11/3.11.9/bin/python3.11`
+fn normalize_link_target(target: &Path) -> PathBuf {
+    match (target.parent(), target.file_name()) {
+        (Some(parent), Some(file_name)) => fs::canonicalize(parent)
+            .map(|parent| parent.join(file_name))
+            .unwrap_or(target.to_path_buf()),
+        _ => target.to_path_buf(),
+    }
+}
+
+/// Whether the file is a script (starts with a shebang) instead of a real Python binary.
+/// E.g. the deadsnakes PPA on Ubuntu installs scripts such as `/usr/bin/python3.11`
+/// that print `python3.11 not found, install with apt-get install python3.11` and exit with 1.
+/// Real Python binaries are ELF/PE/Mach-O files.
+pub fn is_stub_executable(path: &Path) -> bool {
+    let mut header = [0u8; 4];
+    match fs::File::open(path).and_then(|mut file| file.read(&mut header)) {
+        Ok(read) => header[..read].starts_with(b"#!/"),
+        Err(_) => false,
+    }
+}
+
+/// Returns the CPU architecture of a Mach-O (macOS) executable, read from the header, hence Python is never spawned.
+/// Used to distinguish native arm64 & x86_64 (Rosetta) builds on Apple Silicon.
+/// Universal (fat) binaries contain multiple architectures, in which case `None` is returned.
+pub fn get_mach_o_architecture(exe: &Path) -> Option<Architecture> {
+    const MH_MAGIC: u32 = 0xFEEDFACE;
+    const MH_MAGIC_64: u32 = 0xFEEDFACF;
+    const CPU_TYPE_X86: u32 = 7;
+    const CPU_TYPE_ARM: u32 = 12;
+    const CPU_ARCH_ABI64: u32 = 0x01000000;
+
+    let mut header = [0u8; 8];
+    fs::File::open(exe).ok()?.read_exact(&mut header).ok()?;
+    let magic = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    if magic != MH_MAGIC && magic != MH_MAGIC_64 {
+        return None;
+    }
+    let cpu_type = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    Some(match cpu_type {
+        CPU_TYPE_X86 => Architecture::X86,
+        t if t == CPU_TYPE_X86 | CPU_ARCH_ABI64 => Architecture::X64,
+        t if t == CPU_TYPE_ARM | CPU_ARCH_ABI64 => Architecture::Arm64,
+        _ => Architecture::Unknown,
+    })
 }
 
 fn is_python_executable_name(exe: &Path) -> bool {
@@ -99,21 +300,108 @@ fn is_python_executable_name(exe: &Path) -> bool {
         .to_str()
         .unwrap_or_default()
         .to_lowercase();
-    if !name.starts_with("python") {
-        return false;
-    }
-    // Regex to match pythonX.X.exe
+    // Regex to match pythonX.X.exe, pypyX.X.exe & graalpyX.X.exe
     if cfg!(windows) {
-        WINDOWS_EXE.is_match(&name)
+        [&*WINDOWS_EXE, &*PYPY_WINDOWS_EXE, &*GRAALPY_WINDOWS_EXE]
+            .iter()
+            .any(|exe| exe.is_match(&name))
     } else {
-        UNIX_EXE.is_match(&name)
+        [&*UNIX_EXE, &*PYPY_UNIX_EXE, &*GRAALPY_UNIX_EXE]
+            .iter()
+            .any(|exe| exe.is_match(&name))
+    }
+}
+
+/// Paths in `exclude_paths` (and their sub directories) are never searched.
+pub fn should_search_for_environments_in_path<P: AsRef<Path>>(
+    path: &P,
+    exclude_paths: &[PathBuf],
+) -> bool {
+    if exclude_paths.iter().any(|p| path.as_ref().starts_with(p)) {
+        trace!("Ignoring excluded folder: {:?}", path.as_ref());
+        return false;
+    }
+    // Never search in the .git folder
+    // Never search in the node_modules folder
+    // Mostly copied from https://github.com/github/gitignore/blob/main/Python.gitignore
+    let folders_to_ignore = [
+        "node_modules",
+        ".cargo",
+        ".devcontainer",
+        ".github",
+        ".git",
+        ".tox", // Environments in here are found by the tox locator.
+        ".nox", // Environments in here are found by the nox locator.
+        ".hypothesis",
+        ".ipynb_checkpoints",
+        ".eggs",
+        ".coverage",
+        ".cache",
+        ".pyre",
+        ".ptype",
+        ".pytest_cache",
+        ".vscode",
+        "__pycache__",
+        "__pypackages__",
+        ".mypy_cache",
+        "cython_debug",
+        "env.bak",
+        "venv.bak",
+        "Scripts", // If the folder ends bin/scripts, then ignore it, as the parent is most likely an env.
+        "bin", // If the folder ends bin/scripts, then ignore it, as the parent is most likely an env.
+    ];
+    for folder in folders_to_ignore.iter() {
+        if path.as_ref().ends_with(folder) {
+            trace!("Ignoring folder: {:?}", path.as_ref());
+            return false;
+        }
     }
+
+    true
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn should_search_for_environments_in_path_test() {
+        assert!(should_search_for_environments_in_path(
+            &PathBuf::from("/home/user/project"),
+            &[]
+        ));
+        assert!(!should_search_for_environments_in_path(
+            &PathBuf::from("/home/user/project/node_modules"),
+            &[]
+        ));
+        let exclude_paths = [PathBuf::from("/home/user/project/data")];
+        assert!(!should_search_for_environments_in_path(
+            &PathBuf::from("/home/user/project/data"),
+            &exclude_paths
+        ));
+        assert!(!should_search_for_environments_in_path(
+            &PathBuf::from("/home/user/project/data/nested"),
+            &exclude_paths
+        ));
+        assert!(should_search_for_environments_in_path(
+            &PathBuf::from("/home/user/project/database"),
+            &exclude_paths
+        ));
+    }
+
+    #[test]
+    fn is_windows_apps_dir_test() {
+        assert!(is_windows_apps_dir(Path::new(
+            "C:/Users/user/AppData/Local/Microsoft/WindowsApps"
+        )));
+        assert!(is_windows_apps_dir(Path::new(
+            "C:/Users/user/AppData/Local/microsoft/windowsapps/"
+        )));
+        assert!(!is_windows_apps_dir(Path::new(
+            "C:/Users/user/AppData/Local/Programs/Python/Python312"
+        )));
+    }
+
     #[test]
     fn is_python_executable_test() {
         #[cfg(unix)]
@@ -155,6 +443,40 @@ mod tests {
         assert!(is_python_executable_name(
             PathBuf::from("python4.10.exe").as_path()
         ));
+
+        #[cfg(unix)]
+        assert!(is_python_executable_name(PathBuf::from("pypy").as_path()));
+        #[cfg(unix)]
+        assert!(is_python_executable_name(PathBuf::from("pypy3").as_path()));
+        #[cfg(unix)]
+        assert!(is_python_executable_name(
+            PathBuf::from("pypy3.10").as_path()
+        ));
+        #[cfg(unix)]
+        assert!(is_python_executable_name(
+            PathBuf::from("graalpy").as_path()
+        ));
+        #[cfg(unix)]
+        assert!(is_python_executable_name(
+            PathBuf::from("graalpy3").as_path()
+        ));
+        #[cfg(unix)]
+        assert!(is_python_executable_name(
+            PathBuf::from("graalpy3.10").as_path()
+        ));
+
+        #[cfg(windows)]
+        assert!(is_python_executable_name(
+            PathBuf::from("pypy3.exe").as_path()
+        ));
+        #[cfg(windows)]
+        assert!(is_python_executable_name(
+            PathBuf::from("pypy3.10.exe").as_path()
+        ));
+        #[cfg(windows)]
+        assert!(is_python_executable_name(
+            PathBuf::from("graalpy.exe").as_path()
+        ));
     }
     #[test]
     fn is_not_python_executable_test() {
@@ -166,6 +488,14 @@ mod tests {
         assert!(!is_python_executable_name(
             PathBuf::from("pythonw3").as_path()
         ));
+        #[cfg(unix)]
+        assert!(!is_python_executable_name(
+            PathBuf::from("pypy-config").as_path()
+        ));
+        #[cfg(unix)]
+        assert!(!is_python_executable_name(
+            PathBuf::from("python3-config").as_path()
+        ));
 
         #[cfg(windows)]
         assert!(!is_python_executable_name(
@@ -175,45 +505,13 @@ mod tests {
         assert!(!is_python_executable_name(
             PathBuf::from("pythonw3.exe").as_path()
         ));
+        #[cfg(windows)]
+        assert!(!is_python_executable_name(
+            PathBuf::from("pypy2w.exe").as_path()
+        ));
+        #[cfg(unix)]
+        assert!(!is_python_executable_name(
+            PathBuf::from("graalpy-jvm").as_path()
+        ));
     }
 }
-
-pub fn should_search_for_environments_in_path<P: AsRef<Path>>(path: &P) -> bool {
-    // Never search in the .git folder
-    // Never search in the node_modules folder
-    // Mostly copied from https://github.com/github/gitignore/blob/main/Python.gitignore
-    let folders_to_ignore = [
-        "node_modules",
-        ".cargo",
-        ".devcontainer",
-        ".github",
-        ".git",
-        ".tox",
-        ".nox",
-        ".hypothesis",
-        ".ipynb_checkpoints",
-        ".eggs",
-        ".coverage",
-        ".cache",
-        ".pyre",
-        ".ptype",
-        ".pytest_cache",
-        ".vscode",
-        "__pycache__",
-        "__pypackages__",
-        ".mypy_cache",
-        "cython_debug",
-        "env.bak",
-        "venv.bak",
-        "Scripts", // If the folder ends bin/scripts, then ignore it, as the parent is most likely an env.
-        "bin", // If the folder ends bin/scripts, then ignore it, as the parent is most likely an env.
-    ];
-    for folder in folders_to_ignore.iter() {
-        if path.as_ref().ends_with(folder) {
-            trace!("Ignoring folder: {:?}", path.as_ref());
-            return false;
-        }
-    }
-
-    true
-}
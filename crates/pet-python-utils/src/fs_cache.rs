@@ -1,35 +1,325 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use log::{error, trace};
-use pet_fs::path::norm_case;
+use lazy_static::lazy_static;
+use log::{error, trace, warn};
+use pet_fs::{
+    path::norm_case,
+    times::{get_mtime, to_nanos},
+};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{
+    collections::{BTreeMap, BinaryHeap, HashMap},
     fs::{self, File},
-    io::BufReader,
+    io::{self, BufReader, Write},
     path::{Path, PathBuf},
-    time::SystemTime,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime},
 };
 
 use crate::env::ResolvedPythonEnv;
 
 type FilePathWithMTimeCTime = (PathBuf, SystemTime, SystemTime);
 
+/// Maps the executable (key) to its cache file.
+type Manifest = BTreeMap<PathBuf, ManifestEntry>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestEntry {
+    /// Name of the cache file.
+    pub file_name: String,
+    /// When the cache file was written, used to evict the oldest entries without reading the metadata of every file.
+    pub stored_at: SystemTime,
+}
+
+static MANIFEST_FILE: &str = "manifest.json";
+
+/// Version of the format of the cache entries written by this version of the tool.
+pub const CURRENT_VERSION: u32 = 1;
+/// Cache entries older than this version are ignored (and deleted).
+/// Entries without a version (written by older versions of the tool) are treated as version `0`.
+pub const CURRENT_MINIMUM_VERSION: u32 = 1;
+
+lazy_static! {
+    /// In memory copy of the manifest for each cache directory.
+    /// This is also used to ensure updates to the manifest file are not lost when storing from multiple threads.
+    static ref MANIFESTS: Mutex<HashMap<PathBuf, Manifest>> = Mutex::new(HashMap::new());
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CacheEntry {
+    #[serde(default)]
+    pub version: u32,
     pub environment: ResolvedPythonEnv,
     pub symlinks: Vec<FilePathWithMTimeCTime>,
 }
 
-pub fn generate_cache_file(cache_directory: &Path, executable: &PathBuf) -> PathBuf {
+/// Statistics of the file cache, useful for diagnostics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entry_count: usize,
+    pub total_bytes: u64,
+    pub oldest_entry_age: Option<Duration>,
+    pub newest_entry_age: Option<Duration>,
+}
+
+pub fn generate_cache_file(cache_directory: &Path, executable: &Path) -> PathBuf {
     cache_directory.join(format!("{}.3.json", generate_hash(executable)))
 }
 
 pub fn delete_cache_file(cache_directory: &Path, executable: &PathBuf) {
     let cache_file = generate_cache_file(cache_directory, executable);
     let _ = fs::remove_file(cache_file);
+    update_manifest(cache_directory, |manifest| {
+        manifest.remove(&norm_case(executable)).is_some()
+    });
+}
+
+/// Checks whether the cache contains an entry for the executable.
+/// Only the manifest is read (and kept in memory), the cache file is not validated.
+pub fn cache_contains_key(cache_directory: &Path, executable: &Path) -> bool {
+    let key = norm_case(executable);
+    let mut manifests = MANIFESTS.lock().unwrap();
+    get_or_load_manifest(&mut manifests, cache_directory).contains_key(&key)
+}
+
+/// Removes the cache entries that are no longer valid, e.g. the executable has been deleted or updated.
+/// Returns the executables that were removed.
+pub fn remove_stale_entries(cache_directory: &Path) -> Vec<PathBuf> {
+    let executables = {
+        let mut manifests = MANIFESTS.lock().unwrap();
+        get_or_load_manifest(&mut manifests, cache_directory)
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let mut removed = vec![];
+    for executable in executables {
+        // Invalid entries are deleted when read, however missing or corrupt files are not.
+        if get_cache_from_file(cache_directory, &executable).is_none() {
+            delete_cache_file(cache_directory, &executable);
+            removed.push(executable);
+        }
+    }
+    removed
+}
+
+/// Checks whether the cache entry of the executable was written more than `max_age` ago.
+pub fn is_cache_entry_expired(
+    cache_directory: &Path,
+    executable: &Path,
+    max_age: Duration,
+) -> bool {
+    let cache_file = generate_cache_file(cache_directory, executable);
+    match (get_mtime(&cache_file), to_nanos(SystemTime::now())) {
+        (Some(mtime), Some(now)) => now.saturating_sub(mtime) > max_age.as_nanos(),
+        _ => false,
+    }
+}
+
+/// Removes the cache entries that were written more than `max_age` ago, even if they are still valid.
+/// Returns the executables that were removed.
+pub fn remove_expired_entries(cache_directory: &Path, max_age: Duration) -> Vec<PathBuf> {
+    let executables = {
+        let mut manifests = MANIFESTS.lock().unwrap();
+        get_or_load_manifest(&mut manifests, cache_directory)
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let mut removed = vec![];
+    for executable in executables {
+        if is_cache_entry_expired(cache_directory, &executable, max_age) {
+            trace!("Cache entry for {:?} has expired", executable);
+            delete_cache_file(cache_directory, &executable);
+            removed.push(executable);
+        }
+    }
+    removed
+}
+
+/// Removes the least recently stored entries (oldest cache files) until there are at most `max_entries` entries.
+/// Returns the executables that were removed.
+pub fn remove_oldest_entries(cache_directory: &Path, max_entries: usize) -> Vec<PathBuf> {
+    let executables = {
+        let mut manifests = MANIFESTS.lock().unwrap();
+        get_or_load_manifest(&mut manifests, cache_directory)
+            .iter()
+            .map(|(executable, entry)| (executable.clone(), entry.stored_at))
+            .collect::<Vec<_>>()
+    };
+    if executables.len() <= max_entries {
+        return vec![];
+    }
+    let count = executables.len() - max_entries;
+    // Max heap of the oldest entries, the newest of those is popped when there are too many.
+    let mut oldest = BinaryHeap::with_capacity(count + 1);
+    for (executable, stored_at) in executables {
+        oldest.push((stored_at, executable));
+        if oldest.len() > count {
+            oldest.pop();
+        }
+    }
+    let mut removed = vec![];
+    for (_, executable) in oldest.into_vec() {
+        trace!("Evicting cache entry for {:?}", executable);
+        delete_cache_file(cache_directory, &executable);
+        removed.push(executable);
+    }
+    removed
+}
+
+/// Gets all of the (valid) environments in the cache.
+pub fn get_cached_environments(cache_directory: &Path) -> Vec<ResolvedPythonEnv> {
+    let executables = {
+        let mut manifests = MANIFESTS.lock().unwrap();
+        get_or_load_manifest(&mut manifests, cache_directory)
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let mut environments: Vec<ResolvedPythonEnv> = vec![];
+    for executable in executables {
+        if let Some((environment, _)) = get_cache_from_file(cache_directory, &executable) {
+            // Same environment can be cached against each of its symlinks.
+            if !environments
+                .iter()
+                .any(|e| e.executable == environment.executable)
+            {
+                environments.push(environment);
+            }
+        }
+    }
+    environments
+}
+
+/// Number of entries in the cache (as per the manifest).
+pub fn cache_entry_count(cache_directory: &Path) -> usize {
+    let mut manifests = MANIFESTS.lock().unwrap();
+    get_or_load_manifest(&mut manifests, cache_directory).len()
+}
+
+/// Computes the statistics of the cache files.
+/// Only the metadata of the files is read (not the contents), hence cheap.
+pub fn get_cache_stats(cache_directory: &Path) -> CacheStats {
+    let mut stats = CacheStats::default();
+    let files = match fs::read_dir(cache_directory) {
+        Ok(reader) => reader.filter_map(Result::ok),
+        Err(_) => return stats,
+    };
+    let now = SystemTime::now();
+    for file in files {
+        let file_name = file.file_name().to_string_lossy().to_string();
+        if file_name == MANIFEST_FILE || !file_name.ends_with(".json") {
+            continue;
+        }
+        let metadata = match file.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        stats.entry_count += 1;
+        stats.total_bytes += metadata.len();
+        if let Ok(age) = metadata.modified().map(|mtime| now.duration_since(mtime)) {
+            // Files modified after `now` (clock skew) are treated as brand new.
+            let age = age.unwrap_or_default();
+            stats.oldest_entry_age = stats.oldest_entry_age.max(Some(age));
+            stats.newest_entry_age = Some(stats.newest_entry_age.map_or(age, |a| a.min(age)));
+        }
+    }
+    stats
+}
+
+/// Removes the in memory copy of the manifest, e.g. when the cache directory is deleted.
+pub fn clear_manifest(cache_directory: &Path) {
+    MANIFESTS.lock().unwrap().remove(cache_directory);
+}
+
+fn update_manifest<F: FnOnce(&mut Manifest) -> bool>(cache_directory: &Path, update: F) {
+    let mut manifests = MANIFESTS.lock().unwrap();
+    let manifest = get_or_load_manifest(&mut manifests, cache_directory);
+    if !update(manifest) {
+        return;
+    }
+    let manifest_file = cache_directory.join(MANIFEST_FILE);
+    match serde_json::to_vec(manifest) {
+        Ok(contents) => {
+            if let Err(err) = write_file_atomically(&manifest_file, &contents) {
+                error!("Error writing manifest file {:?} {:?}", manifest_file, err);
+            }
+        }
+        Err(err) => error!("Error serializing manifest {:?} {:?}", manifest_file, err),
+    }
+}
+
+fn get_or_load_manifest<'a>(
+    manifests: &'a mut HashMap<PathBuf, Manifest>,
+    cache_directory: &Path,
+) -> &'a mut Manifest {
+    manifests
+        .entry(cache_directory.to_path_buf())
+        .or_insert_with(|| {
+            let manifest_file = cache_directory.join(MANIFEST_FILE);
+            File::open(&manifest_file)
+                .ok()
+                .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+                .unwrap_or_else(|| {
+                    if manifest_file.exists() {
+                        warn!(
+                            "Manifest file {:?} is corrupt (or of an older format), rebuilding",
+                            manifest_file
+                        );
+                    }
+                    rebuild_manifest(cache_directory)
+                })
+        })
+}
+
+/// Builds the manifest from the cache files in the cache directory.
+/// The key of a cache file is the symlink (executable) that hashes to the name of the cache file.
+fn rebuild_manifest(cache_directory: &Path) -> Manifest {
+    let mut manifest = Manifest::new();
+    let files = match fs::read_dir(cache_directory) {
+        Ok(reader) => reader.filter_map(Result::ok).map(|e| e.path()),
+        Err(_) => return manifest,
+    };
+    for file in files {
+        let file_name = file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        if file_name == MANIFEST_FILE || !file_name.ends_with(".json") {
+            continue;
+        }
+        let cache: Option<CacheEntry> = File::open(&file)
+            .ok()
+            .and_then(|f| serde_json::from_reader(BufReader::new(f)).ok());
+        if let Some(cache) = cache {
+            // Missing times are treated as the oldest.
+            let stored_at = fs::metadata(&file)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            for symlink in cache.environment.symlinks.unwrap_or_default() {
+                if generate_cache_file(cache_directory, &symlink) == file {
+                    manifest.insert(
+                        norm_case(&symlink),
+                        ManifestEntry {
+                            file_name: file_name.clone(),
+                            stored_at,
+                        },
+                    );
+                }
+            }
+        }
+    }
+    manifest
 }
 
 pub fn get_cache_from_file(
@@ -39,7 +329,17 @@ pub fn get_cache_from_file(
     let cache_file = generate_cache_file(cache_directory, executable);
     let file = File::open(cache_file.clone()).ok()?;
     let reader = BufReader::new(file);
+    // Files in the original format (without the `CacheEntry` wrapper) cannot be read, hence ignored.
     let cache: CacheEntry = serde_json::from_reader(reader).ok()?;
+    if cache.version < CURRENT_MINIMUM_VERSION {
+        trace!(
+            "Cache file {:?} is of an older version {:?}, ignoring it",
+            cache_file,
+            cache.version
+        );
+        delete_cache_file(cache_directory, executable);
+        return None;
+    }
     // Account for conflicts in the cache file
     // i.e. the hash generated is same for another file, remember we only take the first 16 chars.
     if !cache
@@ -59,25 +359,35 @@ pub fn get_cache_from_file(
     }
 
     // Check if any of the exes have changed since we last cached them.
-    let cache_is_valid = cache.symlinks.iter().all(|symlink| {
-        if let Ok(metadata) = symlink.0.metadata() {
-            metadata.modified().ok() == Some(symlink.1)
-                && metadata.created().ok() == Some(symlink.2)
-        } else {
-            // File may have been deleted.
-            false
-        }
-    });
+    // Files that have been deleted are treated as changed.
+    let cache_is_valid = cache
+        .symlinks
+        .iter()
+        .all(|symlink| !has_file_changed(&symlink.0, symlink.1, symlink.2));
 
     if cache_is_valid {
         trace!("Using cache from {:?} for {:?}", cache_file, executable);
         Some((cache.environment, cache.symlinks))
     } else {
-        let _ = fs::remove_file(cache_file);
+        delete_cache_file(cache_directory, executable);
         None
     }
 }
 
+/// Checks whether the file has been modified since the given times were recorded.
+/// The file is only stat'ed once, the created time is only compared when the modified time is the same.
+pub fn has_file_changed(file: &Path, mtime: SystemTime, ctime: SystemTime) -> bool {
+    let metadata = match file.metadata() {
+        Ok(metadata) => metadata,
+        Err(_) => return true,
+    };
+    let modified = metadata.modified().ok().and_then(to_nanos);
+    if modified.is_none() || modified != to_nanos(mtime) {
+        return true;
+    }
+    metadata.created().ok().and_then(to_nanos) != to_nanos(ctime)
+}
+
 pub fn store_cache_in_file(
     cache_directory: &Path,
     executable: &PathBuf,
@@ -88,18 +398,35 @@ pub fn store_cache_in_file(
     match std::fs::create_dir_all(cache_directory) {
         Ok(_) => {
             let cache = CacheEntry {
+                version: CURRENT_VERSION,
                 environment: environment.clone(),
                 symlinks: symlinks_with_times,
             };
-            match std::fs::File::create(cache_file.clone()) {
-                Ok(file) => {
-                    trace!("Caching {:?} in {:?}", executable, cache_file);
-                    match serde_json::to_writer_pretty(file, &cache) {
-                        Ok(_) => (),
-                        Err(err) => error!("Error writing cache file {:?} {:?}", cache_file, err),
-                    }
+            trace!("Caching {:?} in {:?}", executable, cache_file);
+            let contents = match serde_json::to_vec_pretty(&cache) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    error!("Error serializing cache for {:?} {:?}", executable, err);
+                    return;
+                }
+            };
+            match write_file_atomically(&cache_file, &contents) {
+                Ok(_) => {
+                    let file_name = cache_file
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string();
+                    let entry = ManifestEntry {
+                        file_name,
+                        stored_at: SystemTime::now(),
+                    };
+                    update_manifest(cache_directory, |manifest| {
+                        manifest.insert(norm_case(executable), entry);
+                        true
+                    });
                 }
-                Err(err) => error!("Error creating cache file {:?} {:?}", cache_file, err),
+                Err(err) => error!("Error writing cache file {:?} {:?}", cache_file, err),
             }
         }
         Err(err) => error!(
@@ -109,7 +436,29 @@ pub fn store_cache_in_file(
     }
 }
 
-fn generate_hash(executable: &PathBuf) -> String {
+/// Writes the contents into a temporary file in the same directory & then renames it to the final file.
+/// This way readers never see a partially written file, even if the process is killed or multiple threads write the same file.
+/// The rename replaces the existing file atomically on unix (`rename`) & windows (`MoveFileExW` with `MOVEFILE_REPLACE_EXISTING`).
+pub(crate) fn write_file_atomically(file: &Path, contents: &[u8]) -> io::Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let temp_file = file.with_file_name(format!(
+        "{}.{}.{}.tmp",
+        file.file_name().unwrap_or_default().to_string_lossy(),
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = File::create(&temp_file)
+        .and_then(|mut f| f.write_all(contents))
+        .and_then(|_| fs::rename(&temp_file, file));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_file);
+    }
+    result
+}
+
+/// Hash of the executable used as the name of the cache file.
+/// Must be stable across Rust versions & platforms, hence SHA-256 (and not `DefaultHasher`).
+fn generate_hash(executable: &Path) -> String {
     let mut hasher = Sha256::new();
     hasher.update(norm_case(executable).to_string_lossy().as_bytes());
     let h_bytes = hasher.finalize();
@@ -144,6 +493,237 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_generation_is_stable() {
+        let executable = Path::new("python3");
+        assert_eq!(generate_hash(executable), generate_hash(executable));
+        assert_eq!(generate_hash(executable), "c1cc69e61c0f1c7a");
+        assert_ne!(
+            generate_hash(executable),
+            generate_hash(Path::new("python"))
+        );
+    }
+
+    #[test]
+    fn older_versions_of_cache_files_are_ignored() {
+        let cache_directory = std::env::temp_dir().join("pet_cache_version_test");
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+        fs::create_dir_all(&cache_directory).unwrap();
+        let executable = cache_directory.join("env").join("bin").join("python");
+        let environment = ResolvedPythonEnv {
+            executable: executable.clone(),
+            prefix: cache_directory.join("env"),
+            version: "3.12.0".to_string(),
+            is64_bit: true,
+            symlinks: Some(vec![executable.clone()]),
+        };
+        let cache_file = generate_cache_file(&cache_directory, &executable);
+
+        // Version 0, i.e. the environment was stored without the `CacheEntry` wrapper.
+        fs::write(&cache_file, serde_json::to_string(&environment).unwrap()).unwrap();
+        assert!(get_cache_from_file(&cache_directory, &executable).is_none());
+        assert_eq!(
+            remove_stale_entries(&cache_directory),
+            Vec::<PathBuf>::new()
+        );
+
+        // Wrapped, however without a version.
+        let mut cache = serde_json::to_value(CacheEntry {
+            version: CURRENT_VERSION,
+            environment: environment.clone(),
+            symlinks: vec![],
+        })
+        .unwrap();
+        cache.as_object_mut().unwrap().remove("version");
+        fs::write(&cache_file, cache.to_string()).unwrap();
+        clear_manifest(&cache_directory);
+        assert!(cache_contains_key(&cache_directory, &executable));
+        assert!(get_cache_from_file(&cache_directory, &executable).is_none());
+        assert!(!cache_file.exists());
+        assert!(!cache_contains_key(&cache_directory, &executable));
+
+        store_cache_in_file(&cache_directory, &executable, &environment, vec![]);
+        let (cached_environment, _) = get_cache_from_file(&cache_directory, &executable).unwrap();
+        assert_eq!(cached_environment.version, environment.version);
+
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+    }
+
+    #[test]
+    fn concurrent_stores_of_the_same_executable_leave_a_valid_cache_file() {
+        let cache_directory = std::env::temp_dir().join("pet_cache_atomic_write_test");
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+        let executable = cache_directory.join("env").join("bin").join("python");
+
+        std::thread::scope(|s| {
+            for i in 0..20 {
+                let cache_directory = &cache_directory;
+                let executable = &executable;
+                s.spawn(move || {
+                    let environment = ResolvedPythonEnv {
+                        executable: executable.clone(),
+                        prefix: cache_directory.join("env"),
+                        version: format!("3.12.{i}"),
+                        is64_bit: true,
+                        // Make the files differ in size.
+                        symlinks: Some(vec![executable.clone(); i + 1]),
+                    };
+                    store_cache_in_file(cache_directory, executable, &environment, vec![]);
+                });
+            }
+        });
+
+        let cache_file = generate_cache_file(&cache_directory, &executable);
+        let cache: CacheEntry = serde_json::from_reader(File::open(&cache_file).unwrap()).unwrap();
+        assert_eq!(cache.environment.executable, executable);
+        assert!(get_cache_from_file(&cache_directory, &executable).is_some());
+        // No temporary files left behind.
+        let temp_files = fs::read_dir(&cache_directory)
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(temp_files, 0);
+
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+    }
+
+    #[test]
+    fn file_has_changed_when_its_modified_time_changes() {
+        let file =
+            std::env::temp_dir().join(format!("pet_has_file_changed_test_{}", process::id()));
+        fs::write(&file, "").unwrap();
+        let metadata = file.metadata().unwrap();
+        let (mtime, ctime) = (metadata.modified().unwrap(), metadata.created().unwrap());
+
+        assert!(!has_file_changed(&file, mtime, ctime));
+
+        File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(mtime - Duration::from_secs(60))
+            .unwrap();
+        assert!(has_file_changed(&file, mtime, ctime));
+
+        let _ = fs::remove_file(&file);
+        assert!(has_file_changed(&file, mtime, ctime));
+    }
+
+    #[test]
+    fn cache_stats_are_computed_from_the_cache_files() {
+        let cache_directory = std::env::temp_dir().join("pet_cache_stats_test");
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+        assert_eq!(get_cache_stats(&cache_directory), CacheStats::default());
+
+        let executables = (0..3)
+            .map(|i| {
+                cache_directory
+                    .join(format!("env{i}"))
+                    .join("bin")
+                    .join("python")
+            })
+            .collect::<Vec<_>>();
+        for executable in executables.iter() {
+            let environment = ResolvedPythonEnv {
+                executable: executable.clone(),
+                prefix: executable.parent().unwrap().parent().unwrap().into(),
+                version: "3.12.0".to_string(),
+                is64_bit: true,
+                symlinks: Some(vec![executable.clone()]),
+            };
+            store_cache_in_file(&cache_directory, executable, &environment, vec![]);
+        }
+        // Pretend one of the entries was written a while ago.
+        let age = Duration::from_secs(60 * 60);
+        File::options()
+            .write(true)
+            .open(generate_cache_file(&cache_directory, &executables[0]))
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+
+        let stats = get_cache_stats(&cache_directory);
+
+        assert_eq!(stats.entry_count, 3);
+        let total_bytes = executables
+            .iter()
+            .map(|e| {
+                generate_cache_file(&cache_directory, e)
+                    .metadata()
+                    .unwrap()
+                    .len()
+            })
+            .sum::<u64>();
+        assert_eq!(stats.total_bytes, total_bytes);
+        assert!(stats.oldest_entry_age.unwrap() >= age);
+        assert!(stats.newest_entry_age.unwrap() < age);
+
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn manifest_is_consistent_when_storing_from_multiple_threads() {
+        let cache_directory = std::env::temp_dir().join("pet_manifest_concurrency_test");
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+        let executables = (0..20)
+            .map(|i| PathBuf::from(format!("/tmp/pet_manifest_test/env{i}/bin/python")))
+            .collect::<Vec<_>>();
+
+        std::thread::scope(|s| {
+            for executable in executables.iter() {
+                let cache_directory = &cache_directory;
+                s.spawn(move || {
+                    let environment = ResolvedPythonEnv {
+                        executable: executable.clone(),
+                        prefix: executable.parent().unwrap().parent().unwrap().into(),
+                        version: "3.12.0".to_string(),
+                        is64_bit: true,
+                        symlinks: Some(vec![executable.clone()]),
+                    };
+                    store_cache_in_file(cache_directory, executable, &environment, vec![]);
+                });
+            }
+        });
+
+        for executable in executables.iter() {
+            assert!(cache_contains_key(&cache_directory, executable));
+        }
+        assert!(!cache_contains_key(
+            &cache_directory,
+            Path::new("/tmp/pet_manifest_test/unknown/bin/python")
+        ));
+
+        // Manifest on disk must contain all of the entries as well.
+        let manifest: Manifest =
+            serde_json::from_reader(File::open(cache_directory.join(MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(manifest.len(), executables.len());
+
+        // Deleting a cache file should remove it from the manifest.
+        delete_cache_file(&cache_directory, &executables[0]);
+        assert!(!cache_contains_key(&cache_directory, &executables[0]));
+
+        // Corrupt manifest files are rebuilt from the cache files.
+        fs::write(cache_directory.join(MANIFEST_FILE), "{").unwrap();
+        clear_manifest(&cache_directory);
+        assert!(!cache_contains_key(&cache_directory, &executables[0]));
+        for executable in executables.iter().skip(1) {
+            assert!(cache_contains_key(&cache_directory, executable));
+        }
+
+        let _ = fs::remove_dir_all(&cache_directory);
+        clear_manifest(&cache_directory);
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_hash_generation() {
@@ -6,5 +6,8 @@ pub mod env;
 pub mod executable;
 pub mod fs_cache;
 mod headers;
+pub mod packages;
 pub mod platform_dirs;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub mod version;
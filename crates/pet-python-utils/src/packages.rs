@@ -0,0 +1,85 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::trace;
+use pet_core::python_environment::{InstalledPackage, PythonEnvironment};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Lists the packages installed in the site-packages directory of the environment.
+/// This reads the `*.dist-info/METADATA` files, hence no need to spawn Python or pip.
+///
+/// Note: This is not done as part of discovery, callers must opt-in to this (it can be expensive).
+pub fn scan_site_packages(env: &PythonEnvironment) -> Vec<InstalledPackage> {
+    let prefix = match &env.prefix {
+        Some(prefix) => prefix,
+        None => return vec![],
+    };
+    let mut packages = vec![];
+    for site_packages in get_site_packages_dirs(prefix) {
+        trace!("Scanning site-packages {:?}", site_packages);
+        if let Ok(reader) = fs::read_dir(&site_packages) {
+            for path in reader.filter_map(Result::ok).map(|e| e.path()) {
+                if path.extension().unwrap_or_default() != "dist-info" {
+                    continue;
+                }
+                if let Some(package) = parse_metadata(&path.join("METADATA")) {
+                    packages.push(package);
+                }
+            }
+        }
+    }
+    packages.sort();
+    packages.dedup();
+    packages
+}
+
+#[cfg(windows)]
+fn get_site_packages_dirs(prefix: &Path) -> Vec<PathBuf> {
+    vec![prefix.join("Lib").join("site-packages")]
+}
+
+#[cfg(unix)]
+fn get_site_packages_dirs(prefix: &Path) -> Vec<PathBuf> {
+    // Site packages are in a directory such as `lib/python3.12/site-packages`.
+    let mut dirs = vec![];
+    if let Ok(reader) = fs::read_dir(prefix.join("lib")) {
+        for path in reader.filter_map(Result::ok).map(|e| e.path()) {
+            if path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .starts_with("python")
+            {
+                let site_packages = path.join("site-packages");
+                if site_packages.is_dir() {
+                    dirs.push(site_packages);
+                }
+            }
+        }
+    }
+    dirs
+}
+
+fn parse_metadata(metadata: &Path) -> Option<InstalledPackage> {
+    let contents = fs::read_to_string(metadata).ok()?;
+    let mut name = None;
+    let mut version = None;
+    // The headers end at the first empty line, after which we have the description.
+    for line in contents.lines().take_while(|l| !l.trim().is_empty()) {
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Version:") {
+            version = Some(value.trim().to_string());
+        }
+        if name.is_some() && version.is_some() {
+            break;
+        }
+    }
+    Some(InstalledPackage {
+        name: name?,
+        version: version?,
+    })
+}
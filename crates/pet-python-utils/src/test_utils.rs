@@ -0,0 +1,42 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use pet_core::os_environment::Environment;
+
+/// Resolves a path relative to the `tests` directory of the crate being tested.
+#[macro_export]
+macro_rules! resolve_test_path {
+    ($paths:expr) => {{
+        let mut root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+        $paths.iter().for_each(|p| root.push(p));
+        root
+    }};
+}
+
+pub struct TestEnvironment {
+    vars: HashMap<String, String>,
+    home: Option<PathBuf>,
+}
+impl Environment for TestEnvironment {
+    fn get_env_var(&self, key: String) -> Option<String> {
+        self.vars.get(&key).cloned()
+    }
+    fn get_root(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_user_home(&self) -> Option<PathBuf> {
+        self.home.clone()
+    }
+    fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+pub fn create_test_environment(
+    vars: HashMap<String, String>,
+    home: Option<PathBuf>,
+) -> TestEnvironment {
+    TestEnvironment { vars, home }
+}
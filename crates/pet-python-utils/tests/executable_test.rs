@@ -11,8 +11,8 @@ use common::resolve_test_path;
 #[test]
 fn find_executables() {
     // .venv
-    let path: PathBuf = resolve_test_path(&["unix", "executables", ".venv"]).into();
-    let mut executables = executable::find_executables(&path.clone());
+    let path: PathBuf = resolve_test_path(&["unix", "executables", ".venv"]);
+    let mut executables = executable::find_executables(path.clone());
     executables.sort();
 
     assert_eq!(
@@ -24,8 +24,8 @@ fn find_executables() {
     );
 
     // Python3.9.9
-    let path: PathBuf = resolve_test_path(&["unix", "executables", "python3.9.9"]).into();
-    let mut executables = executable::find_executables(&path.clone());
+    let path: PathBuf = resolve_test_path(&["unix", "executables", "python3.9.9"]);
+    let mut executables = executable::find_executables(path.clone());
     executables.sort();
 
     assert_eq!(
@@ -37,14 +37,326 @@ fn find_executables() {
     );
 
     // Conda without Python.
-    let path: PathBuf = resolve_test_path(&["unix", "executables", "conda_without_python"]).into();
-    let executables = executable::find_executables(&path.clone());
+    let path: PathBuf = resolve_test_path(&["unix", "executables", "conda_without_python"]);
+    let executables = executable::find_executables(path.clone());
 
     assert_eq!(executables.len(), 0);
 
     // Bogus dir
-    let path: PathBuf = resolve_test_path(&["unix_bogus_dir"]).into();
-    let executables = executable::find_executables(&path.clone());
+    let path: PathBuf = resolve_test_path(&["unix_bogus_dir"]);
+    let executables = executable::find_executables(path.clone());
 
     assert_eq!(executables.len(), 0);
 }
+
+#[cfg(unix)]
+#[test]
+fn find_pypy_executables_without_python_exe() {
+    use std::fs;
+
+    // E.g. the directory of a PyPy install, that does not contain a `python` exe.
+    let path = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_pypy_executables");
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    for exe in ["pypy3", "pypy3.10", "pypy3-config"] {
+        fs::write(path.join(exe), "").unwrap();
+    }
+
+    let executables = executable::find_executables(path.clone());
+    let _ = fs::remove_dir_all(&path);
+
+    assert_eq!(executables, vec![path.join("pypy3"), path.join("pypy3.10")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn try_find_executables_returns_permission_denied() {
+    use std::{fs, io, os::unix::fs::PermissionsExt};
+
+    let env = std::env::temp_dir()
+        .join(format!(
+            "pet_try_find_executables_permission_denied_{}",
+            std::process::id()
+        ))
+        .join(".venv");
+    let bin = env.join("bin");
+    let _ = fs::remove_dir_all(&env);
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(bin.join("python"), "").unwrap();
+    fs::set_permissions(&bin, fs::Permissions::from_mode(0o000)).unwrap();
+
+    // Permissions are not enforced when running as root.
+    let can_read = fs::read_dir(&bin).is_ok();
+    let result = executable::try_find_executables(&env);
+
+    fs::set_permissions(&bin, fs::Permissions::from_mode(0o755)).unwrap();
+    let _ = fs::remove_dir_all(&env);
+
+    if can_read {
+        return;
+    }
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+}
+
+#[test]
+fn try_find_executables_returns_not_found() {
+    use std::io;
+
+    // E.g. a stale entry in the PATH variable.
+    let bin = std::env::temp_dir()
+        .join(format!(
+            "pet_try_find_executables_not_found_{}",
+            std::process::id()
+        ))
+        .join("bin");
+
+    let result = executable::try_find_executables(&bin);
+
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_symlink_chain() {
+    use std::{fs, os::unix::fs::symlink};
+
+    let dir = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_resolve_symlink_chain");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("Cellar").join("bin")).unwrap();
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    let real_exe = dir.join("Cellar").join("bin").join("python3.11");
+    fs::write(&real_exe, "").unwrap();
+    // Two hops, bin/python3 => bin/python3.11 => ../Cellar/bin/python3.11 (relative)
+    let python3_11 = dir.join("bin").join("python3.11");
+    let python3 = dir.join("bin").join("python3");
+    symlink(PathBuf::from("../Cellar/bin/python3.11"), &python3_11).unwrap();
+    symlink(&python3_11, &python3).unwrap();
+
+    let chain = executable::resolve_symlink_chain(&python3);
+
+    assert_eq!(
+        chain,
+        vec![python3.clone(), python3_11.clone(), real_exe.clone()]
+    );
+
+    // Not a symlink.
+    assert_eq!(
+        executable::resolve_symlink_chain(&real_exe),
+        vec![real_exe.clone()]
+    );
+
+    // Cycles.
+    let cycle_a = dir.join("bin").join("python_a");
+    let cycle_b = dir.join("bin").join("python_b");
+    symlink(&cycle_b, &cycle_a).unwrap();
+    symlink(&cycle_a, &cycle_b).unwrap();
+    assert_eq!(
+        executable::resolve_symlink_chain(&cycle_a),
+        vec![cycle_a.clone(), cycle_b.clone()]
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn is_stub_executable() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join("pet_is_stub_executable");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // Stub installed by deadsnakes PPA.
+    let stub = dir.join("python3.11");
+    fs::write(
+        &stub,
+        "#!/bin/sh\necho 'python3.11 not found, install with apt-get install python3.11'\nexit 1\n",
+    )
+    .unwrap();
+    let elf = dir.join("python3.12");
+    fs::write(&elf, b"\x7fELF\x02\x01\x01\x00").unwrap();
+    let empty = dir.join("python3");
+    fs::write(&empty, "").unwrap();
+
+    assert!(executable::is_stub_executable(&stub));
+    assert!(!executable::is_stub_executable(&elf));
+    assert!(!executable::is_stub_executable(&empty));
+    assert!(!executable::is_stub_executable(&dir.join("python3.13")));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn get_mach_o_architecture() {
+    use pet_core::arch::Architecture;
+    use std::fs;
+
+    let dir = std::env::temp_dir().join("pet_get_mach_o_architecture");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // Native build on Apple Silicon (MH_MAGIC_64, CPU_TYPE_ARM64).
+    let arm64 = dir.join("python3-arm64");
+    fs::write(&arm64, b"\xcf\xfa\xed\xfe\x0c\x00\x00\x01\x00\x00\x00\x00").unwrap();
+    // Intel build, run using Rosetta on Apple Silicon (MH_MAGIC_64, CPU_TYPE_X86_64).
+    let x86_64 = dir.join("python3-x86_64");
+    fs::write(&x86_64, b"\xcf\xfa\xed\xfe\x07\x00\x00\x01\x03\x00\x00\x00").unwrap();
+    // Universal binary (FAT_MAGIC), the architecture is only known at runtime.
+    let universal = dir.join("python3-universal2");
+    fs::write(&universal, b"\xca\xfe\xba\xbe\x00\x00\x00\x02").unwrap();
+    let elf = dir.join("python3-elf");
+    fs::write(&elf, b"\x7fELF\x02\x01\x01\x00").unwrap();
+
+    assert_eq!(
+        executable::get_mach_o_architecture(&arm64),
+        Some(Architecture::Arm64)
+    );
+    assert_eq!(
+        executable::get_mach_o_architecture(&x86_64),
+        Some(Architecture::X64)
+    );
+    assert_eq!(executable::get_mach_o_architecture(&universal), None);
+    assert_eq!(executable::get_mach_o_architecture(&elf), None);
+    assert_eq!(
+        executable::get_mach_o_architecture(&dir.join("python3.13")),
+        None
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn get_base_python() {
+    use std::fs;
+
+    let dir = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_get_base_python");
+    let _ = fs::remove_dir_all(&dir);
+
+    // pyvenv.cfg files as created by the venv module of the different Python versions.
+    for (version, cfg) in [
+        ("3.9", "home = {home}\ninclude-system-site-packages = false\nversion = 3.9.6\n"),
+        ("3.10", "home = {home}\ninclude-system-site-packages = false\nversion = 3.10.12\n"),
+        ("3.11", "home = {home}\ninclude-system-site-packages = false\nversion = 3.11.4\nexecutable = {home}/python3.11\ncommand = {home}/python3.11 -m venv /tmp/.venv\n"),
+        ("3.12", "home={home}\ninclude-system-site-packages=false\nversion=3.12.1\n"),
+    ] {
+        let home = dir.join(format!("python{}", version)).join("bin");
+        let venv = dir.join(format!("venv{}", version));
+        fs::create_dir_all(&home).unwrap();
+        fs::create_dir_all(&venv).unwrap();
+        fs::write(home.join("python3"), "").unwrap();
+        fs::write(
+            venv.join("pyvenv.cfg"),
+            cfg.replace("{home}", home.to_str().unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            executable::get_base_python(&venv),
+            Some(home.join("python3"))
+        );
+    }
+
+    // Only `python` in the home directory.
+    let home = dir.join("python_only").join("bin");
+    let venv = dir.join("venv_python_only");
+    fs::create_dir_all(&home).unwrap();
+    fs::create_dir_all(&venv).unwrap();
+    fs::write(home.join("python"), "").unwrap();
+    fs::write(
+        venv.join("pyvenv.cfg"),
+        format!("home = {}\n", home.to_str().unwrap()),
+    )
+    .unwrap();
+    assert_eq!(
+        executable::get_base_python(&venv),
+        Some(home.join("python"))
+    );
+
+    // Base interpreter no longer exists.
+    fs::remove_file(home.join("python")).unwrap();
+    assert_eq!(executable::get_base_python(&venv), None);
+    // Not a virtual environment.
+    assert_eq!(executable::get_base_python(&home), None);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(all(unix, feature = "detect-free-threaded"))]
+#[test]
+fn is_free_threaded_build() {
+    use std::{fs, os::unix::fs::symlink};
+
+    let dir = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_is_free_threaded_build");
+    let _ = fs::remove_dir_all(&dir);
+
+    // Free-threaded install, `python3` is a symlink to `python3.13t`.
+    let prefix = dir.join("free_threaded");
+    fs::create_dir_all(prefix.join("bin")).unwrap();
+    fs::write(prefix.join("bin").join("python3.13t"), "").unwrap();
+    symlink(
+        prefix.join("bin").join("python3.13t"),
+        prefix.join("bin").join("python3"),
+    )
+    .unwrap();
+    assert!(executable::is_free_threaded_build(
+        &prefix.join("bin").join("python3.13t")
+    ));
+    assert!(executable::is_free_threaded_build(
+        &prefix.join("bin").join("python3")
+    ));
+
+    // Virtual environment created (with `--copies`) from a free-threaded build.
+    let venv = dir.join("venv");
+    fs::create_dir_all(venv.join("bin")).unwrap();
+    fs::create_dir_all(venv.join("lib").join("python3.13t").join("site-packages")).unwrap();
+    fs::write(venv.join("pyvenv.cfg"), "version = 3.13.0\n").unwrap();
+    fs::write(venv.join("bin").join("python"), "").unwrap();
+    assert!(executable::is_free_threaded_build(
+        &venv.join("bin").join("python")
+    ));
+
+    // Virtual environment that records the free-threaded base interpreter.
+    let venv = dir.join("venv_executable");
+    fs::create_dir_all(venv.join("bin")).unwrap();
+    fs::write(
+        venv.join("pyvenv.cfg"),
+        format!(
+            "home = {}\nexecutable = {}\nversion = 3.13.0\n",
+            prefix.join("bin").display(),
+            prefix.join("bin").join("python3.13t").display()
+        ),
+    )
+    .unwrap();
+    fs::write(venv.join("bin").join("python"), "").unwrap();
+    assert!(executable::is_free_threaded_build(
+        &venv.join("bin").join("python")
+    ));
+
+    // Regular build installed alongside a free-threaded build of the same version.
+    let prefix = dir.join("regular");
+    fs::create_dir_all(prefix.join("bin")).unwrap();
+    fs::create_dir_all(prefix.join("lib").join("python3.13")).unwrap();
+    fs::create_dir_all(prefix.join("lib").join("python3.13t")).unwrap();
+    fs::write(prefix.join("bin").join("python3.13"), "").unwrap();
+    fs::write(prefix.join("bin").join("python3.13t"), "").unwrap();
+    symlink(
+        prefix.join("bin").join("python3.13"),
+        prefix.join("bin").join("python3"),
+    )
+    .unwrap();
+    assert!(!executable::is_free_threaded_build(
+        &prefix.join("bin").join("python3.13")
+    ));
+    assert!(!executable::is_free_threaded_build(
+        &prefix.join("bin").join("python3")
+    ));
+
+    let _ = fs::remove_dir_all(&dir);
+}
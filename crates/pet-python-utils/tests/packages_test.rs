@@ -0,0 +1,40 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+mod common;
+
+#[cfg(unix)]
+#[test]
+fn scan_site_packages() {
+    use common::resolve_test_path;
+    use pet_core::python_environment::{InstalledPackage, PythonEnvironment};
+    use pet_python_utils::packages::scan_site_packages;
+
+    let prefix = resolve_test_path(&["unix", "site_packages", ".venv"]);
+    let env = PythonEnvironment {
+        prefix: Some(prefix),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        scan_site_packages(&env),
+        vec![
+            InstalledPackage {
+                name: "mypy".to_string(),
+                version: "1.10.0".to_string()
+            },
+            InstalledPackage {
+                name: "pytest".to_string(),
+                version: "8.0.0".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn scan_site_packages_without_prefix() {
+    use pet_core::python_environment::PythonEnvironment;
+    use pet_python_utils::packages::scan_site_packages;
+
+    assert!(scan_site_packages(&PythonEnvironment::default()).is_empty());
+}
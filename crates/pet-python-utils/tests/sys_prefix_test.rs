@@ -10,11 +10,11 @@ use common::resolve_test_path;
 #[cfg(unix)]
 #[test]
 fn version_from_sys_prefix() {
-    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", ".venv"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", ".venv"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.12.1");
 
-    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", ".venv", "bin"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", ".venv", "bin"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.12.1");
 }
@@ -22,11 +22,11 @@ fn version_from_sys_prefix() {
 #[cfg(unix)]
 #[test]
 fn version_from_sys_prefix_using_version_info_format() {
-    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", "hatch_env"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", "hatch_env"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.9.6.final.0");
 
-    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", "hatch_env", "bin"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", "hatch_env", "bin"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.9.6.final.0");
 }
@@ -34,13 +34,12 @@ fn version_from_sys_prefix_using_version_info_format() {
 #[cfg(unix)]
 #[test]
 fn no_version_without_pyvenv_cfg_and_without_headers() {
-    let path: PathBuf =
-        resolve_test_path(&["unix", "pyvenv_cfg", "python3.9.9_without_headers"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "pyvenv_cfg", "python3.9.9_without_headers"]);
     let version = version::from_prefix(&path);
     assert!(version.is_none());
 
     let path: PathBuf =
-        resolve_test_path(&["unix", "pyvenv_cfg", "python3.9.9_without_headers", "bin"]).into();
+        resolve_test_path(&["unix", "pyvenv_cfg", "python3.9.9_without_headers", "bin"]);
     let version = version::from_prefix(&path);
     assert!(version.is_none());
 
@@ -50,8 +49,7 @@ fn no_version_without_pyvenv_cfg_and_without_headers() {
         "python3.9.9_without_headers",
         "bin",
         "python",
-    ])
-    .into();
+    ]);
     let version = version::from_prefix(&path);
     assert!(version.is_none());
 }
@@ -59,7 +57,7 @@ fn no_version_without_pyvenv_cfg_and_without_headers() {
 #[cfg(unix)]
 #[test]
 fn no_version_for_invalid_paths() {
-    let path: PathBuf = resolve_test_path(&["unix_1234"]).into();
+    let path: PathBuf = resolve_test_path(&["unix_1234"]);
     let version = version::from_prefix(&path);
     assert!(version.is_none());
 }
@@ -67,19 +65,19 @@ fn no_version_for_invalid_paths() {
 #[cfg(unix)]
 #[test]
 fn version_from_header_files() {
-    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.9.9"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.9.9"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.9.9");
 
-    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.9.9", "bin"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.9.9", "bin"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.9.9");
 
-    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.10-dev", "bin"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.10-dev", "bin"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.10.14+");
 
-    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.13", "bin"]).into();
+    let path: PathBuf = resolve_test_path(&["unix", "headers", "python3.13", "bin"]);
     let version = version::from_prefix(&path).unwrap();
     assert_eq!(version, "3.13.0a5");
 }
@@ -3,9 +3,11 @@
 
 use crate::environment::get_environment_key;
 use pet_core::{manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter};
+use pet_fs::path::norm_case;
 use std::{
     collections::HashMap,
-    path::PathBuf,
+    fs,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
@@ -17,14 +19,108 @@ pub struct CacheReporter {
     reporter: Arc<dyn Reporter>,
     reported_managers: Arc<Mutex<HashMap<PathBuf, EnvManager>>>,
     reported_environments: Arc<Mutex<HashMap<PathBuf, PythonEnvironment>>>,
+    /// Index of the reported environments by prefix (value is the key in `reported_environments`).
+    reported_prefixes: Arc<Mutex<HashMap<PathBuf, PathBuf>>>,
+    /// Managers known from previous searches, used to detect managers that have been upgraded.
+    known_managers: Arc<Mutex<HashMap<PathBuf, EnvManager>>>,
+    /// Environments known from previous searches, used to detect environments that have been deleted.
+    known_environments: Arc<Mutex<HashMap<PathBuf, PythonEnvironment>>>,
 }
 
 impl CacheReporter {
     pub fn new(reporter: Arc<dyn Reporter>) -> Self {
+        CacheReporter::with_known_managers(reporter, Arc::new(Mutex::new(HashMap::new())))
+    }
+    /// The known managers can be shared across multiple searches (e.g. between refresh requests),
+    /// to detect managers whose versions have changed.
+    pub fn with_known_managers(
+        reporter: Arc<dyn Reporter>,
+        known_managers: Arc<Mutex<HashMap<PathBuf, EnvManager>>>,
+    ) -> Self {
         Self {
             reporter,
             reported_managers: Arc::new(Mutex::new(HashMap::new())),
             reported_environments: Arc::new(Mutex::new(HashMap::new())),
+            reported_prefixes: Arc::new(Mutex::new(HashMap::new())),
+            known_managers,
+            known_environments: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+    /// The known environments can be shared across multiple searches (e.g. between refresh requests),
+    /// to detect environments that have since been deleted.
+    pub fn with_known_environments(
+        mut self,
+        known_environments: Arc<Mutex<HashMap<PathBuf, PythonEnvironment>>>,
+    ) -> Self {
+        self.known_environments = known_environments;
+        self
+    }
+    /// Reports the known environments that no longer exist on disk (e.g. deleted by the user).
+    /// Returns the environments that were reported as removed.
+    pub fn report_removed_environments(&self) -> Vec<PythonEnvironment> {
+        let mut known_environments = self.known_environments.lock().unwrap();
+        let removed = known_environments
+            .iter()
+            .filter(|(_, env)| {
+                env.executable
+                    .as_ref()
+                    .or(env.prefix.as_ref())
+                    .is_some_and(|path| fs::metadata(path).is_err())
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        removed
+            .iter()
+            .filter_map(|key| known_environments.remove(key))
+            .inspect(|env| self.reporter.report_removed_environment(env))
+            .collect()
+    }
+    /// Returns the reported environment with the given prefix.
+    /// Useful when only the sys prefix is known and not the exact executable.
+    pub fn get_by_prefix(&self, prefix: &Path) -> Option<PythonEnvironment> {
+        let reported_prefixes = self.reported_prefixes.lock().unwrap();
+        let key = reported_prefixes.get(&norm_case(prefix))?;
+        self.reported_environments.lock().unwrap().get(key).cloned()
+    }
+    /// Returns all of the reported environments with the given prefix.
+    pub fn get_all_by_prefix(&self, prefix: &Path) -> Vec<PythonEnvironment> {
+        let prefix = norm_case(prefix);
+        self.reported_environments
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|env| env.prefix.as_ref().map(norm_case) == Some(prefix.clone()))
+            .cloned()
+            .collect()
+    }
+    /// Returns the reported environment with the given executable (or one of its symlinks).
+    pub fn get_by_executable(&self, executable: &Path) -> Option<PythonEnvironment> {
+        let executable = norm_case(executable);
+        let reported_environments = self.reported_environments.lock().unwrap();
+        if let Some(env) = reported_environments.get(&executable) {
+            return Some(env.clone());
+        }
+        reported_environments
+            .values()
+            .find(|env| {
+                env.executable.as_ref().map(norm_case) == Some(executable.clone())
+                    || env
+                        .symlinks
+                        .as_ref()
+                        .is_some_and(|symlinks| symlinks.iter().any(|s| norm_case(s) == executable))
+            })
+            .cloned()
+    }
+    fn check_manager_version(&self, manager: &EnvManager) {
+        // The version of the manager is not always known, hence only compare when we have both versions.
+        if manager.version.is_none() {
+            return;
+        }
+        let mut known_managers = self.known_managers.lock().unwrap();
+        if let Some(old) = known_managers.insert(manager.executable.clone(), manager.clone()) {
+            if old.version.is_some() && old.version != manager.version {
+                self.reporter.report_manager_updated(&old, manager);
+            }
         }
     }
 }
@@ -33,6 +129,7 @@ impl Reporter for CacheReporter {
         self.reporter.report_telemetry(event);
     }
     fn report_manager(&self, manager: &EnvManager) {
+        self.check_manager_version(manager);
         let mut reported_managers = self.reported_managers.lock().unwrap();
         if !reported_managers.contains_key(&manager.executable) {
             reported_managers.insert(manager.executable.clone(), manager.clone());
@@ -40,11 +137,34 @@ impl Reporter for CacheReporter {
         }
     }
 
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.reporter.report_progress(completed, total);
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+
     fn report_environment(&self, env: &PythonEnvironment) {
         if let Some(key) = get_environment_key(env) {
             let mut reported_environments = self.reported_environments.lock().unwrap();
             if !reported_environments.contains_key(&key) {
                 reported_environments.insert(key.clone(), env.clone());
+                self.known_environments
+                    .lock()
+                    .unwrap()
+                    .insert(key.clone(), env.clone());
+                if let Some(prefix) = &env.prefix {
+                    self.reported_prefixes
+                        .lock()
+                        .unwrap()
+                        .insert(norm_case(prefix), key.clone());
+                }
                 self.reporter.report_environment(env);
             }
         }
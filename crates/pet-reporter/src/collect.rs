@@ -1,7 +1,9 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use pet_core::{manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter};
+use pet_core::{
+    manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter, LocatorResult,
+};
 use std::sync::{Arc, Mutex};
 
 /// Used to just collect the environments and managers and will not report anytihng anywhere.
@@ -23,6 +25,18 @@ impl CollectReporter {
             environments: Arc::new(Mutex::new(vec![])),
         }
     }
+    /// Returns what has been collected so far, the same manager or environment can be reported multiple times hence those are de-duplicated.
+    pub fn to_locator_result(&self) -> LocatorResult {
+        let mut managers = self.managers.lock().unwrap().clone();
+        managers.sort();
+        managers.dedup();
+        let mut result = LocatorResult {
+            managers,
+            environments: self.environments.lock().unwrap().clone(),
+        };
+        result.dedup_by_key();
+        result
+    }
 }
 impl Reporter for CollectReporter {
     fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
@@ -0,0 +1,60 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::trace;
+use pet_core::{
+    manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter,
+    telemetry::TelemetryEvent,
+};
+use std::{collections::HashSet, fs, path::PathBuf, sync::Mutex};
+
+/// Decorator that ensures the same interpreter is not reported more than once,
+/// e.g. when both a symlink (`/usr/local/bin/python3`) and its target are found in `PATH`.
+///
+/// Environments are compared using the canonicalized executable along with the prefix,
+/// as the executable of a virtual env is generally a symlink to the executable of the base interpreter.
+pub struct DeduplicatingReporter<'a> {
+    reporter: &'a dyn Reporter,
+    reported: Mutex<HashSet<(PathBuf, Option<PathBuf>)>>,
+}
+
+impl<'a> DeduplicatingReporter<'a> {
+    pub fn new(reporter: &'a dyn Reporter) -> Self {
+        DeduplicatingReporter {
+            reporter,
+            reported: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl Reporter for DeduplicatingReporter<'_> {
+    fn report_telemetry(&self, event: &TelemetryEvent) {
+        self.reporter.report_telemetry(event);
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.reporter.report_manager(manager);
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.reporter.report_progress(completed, total);
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if let Some(executable) = &env.executable {
+            let executable = fs::canonicalize(executable).unwrap_or(executable.clone());
+            let key = (executable, env.prefix.clone());
+            if !self.reported.lock().unwrap().insert(key) {
+                trace!("Skipping duplicate environment {:?}", env.executable);
+                return;
+            }
+        }
+        self.reporter.report_environment(env);
+    }
+}
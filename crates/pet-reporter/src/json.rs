@@ -0,0 +1,93 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::EnvManager,
+    python_environment::{get_environment_key, PythonEnvironment, PythonEnvironmentKind},
+    reporter::Reporter,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex};
+
+/// Everything found by a search, printed as a single JSON object.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FindResult {
+    pub managers: Vec<EnvManager>,
+    pub environments: Vec<PythonEnvironment>,
+    pub summary: FindSummary,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FindSummary {
+    /// Total time taken (in milliseconds).
+    pub time: u128,
+    /// Time taken by each of the locators (in milliseconds).
+    pub locators: BTreeMap<String, u128>,
+    /// Time taken by each of the search steps (in milliseconds).
+    pub breakdown: BTreeMap<String, u128>,
+    /// Paths that could not be searched as the user does not have permissions to read them.
+    pub skipped_paths: Vec<PathBuf>,
+    pub total_environments: usize,
+    pub total_managers: usize,
+}
+
+/// Buffers the managers & environments, so that they can be printed as a single JSON object once the search completes.
+pub struct JsonAccumulatingReporter {
+    kind: Option<PythonEnvironmentKind>,
+    managers: Mutex<Vec<EnvManager>>,
+    environments: Mutex<Vec<PythonEnvironment>>,
+}
+
+impl JsonAccumulatingReporter {
+    /// Returns (and clears) what has been reported so far, sorted so that the output is stable across runs.
+    /// The summary is left empty, as that is only known by the caller performing the search.
+    pub fn take_result(&self) -> FindResult {
+        let mut managers = std::mem::take(&mut *self.managers.lock().unwrap());
+        managers.sort();
+        managers.dedup();
+        let mut environments = std::mem::take(&mut *self.environments.lock().unwrap());
+        environments.sort_by(|a, b| (&a.executable, &a.prefix).cmp(&(&b.executable, &b.prefix)));
+        // The same environment can be reported more than once (e.g. once its details are resolved).
+        // Keep the last one reported, as that has the most details.
+        environments.reverse();
+        environments.dedup_by(|a, b| {
+            get_environment_key(a).is_some() && get_environment_key(a) == get_environment_key(b)
+        });
+        environments.reverse();
+        FindResult {
+            managers,
+            environments,
+            summary: FindSummary::default(),
+        }
+    }
+}
+
+impl Reporter for JsonAccumulatingReporter {
+    fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
+        //
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.managers.lock().unwrap().push(manager.clone());
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if self.kind.is_some() && env.kind != self.kind {
+            return;
+        }
+        self.environments.lock().unwrap().push(env.clone());
+    }
+    fn report_error(&self, message: &str) {
+        // Keep stdout reserved for the JSON output.
+        eprintln!("Error: {message}")
+    }
+}
+
+/// Only environments of the given kind are reported (if provided).
+pub fn create_reporter(kind: Option<PythonEnvironmentKind>) -> JsonAccumulatingReporter {
+    JsonAccumulatingReporter {
+        kind,
+        managers: Mutex::new(vec![]),
+        environments: Mutex::new(vec![]),
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::json::FindSummary;
+use log::error;
+use pet_core::{
+    manager::EnvManager,
+    python_environment::{PythonEnvironment, PythonEnvironmentKind},
+    reporter::Reporter,
+};
+use serde::Serialize;
+use std::{io::Write, sync::Mutex};
+
+/// Each line written is one of these, e.g. `{"type":"environment","data":{...}}`.
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+enum JsonLine<'a> {
+    Environment(&'a PythonEnvironment),
+    Manager(&'a EnvManager),
+    Summary(&'a FindSummary),
+}
+
+/// Writes a JSON object per line as managers & environments are reported (JSON Lines),
+/// so that the output can be consumed while the search is still in progress.
+pub struct JsonLinesReporter<W: Write + Send> {
+    kind: Option<PythonEnvironmentKind>,
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesReporter<W> {
+    /// Only environments of the given kind are reported (if provided).
+    pub fn new(writer: W, kind: Option<PythonEnvironmentKind>) -> Self {
+        JsonLinesReporter {
+            kind,
+            writer: Mutex::new(writer),
+        }
+    }
+    /// Writes the summary, generally the last line once the search completes.
+    pub fn report_summary(&self, summary: &FindSummary) {
+        self.write(&JsonLine::Summary(summary));
+    }
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap()
+    }
+    fn write(&self, line: &JsonLine) {
+        let mut writer = self.writer.lock().unwrap();
+        // Write & flush each line in full, so that consumers never see partial lines.
+        let result = serde_json::to_writer(&mut *writer, line)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writeln!(writer))
+            .and_then(|_| writer.flush());
+        if let Err(err) = result {
+            error!("Failed to write JSON line: {err}");
+        }
+    }
+}
+
+impl<W: Write + Send> Reporter for JsonLinesReporter<W> {
+    fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
+        //
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.write(&JsonLine::Manager(manager));
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if self.kind.is_some() && env.kind != self.kind {
+            return;
+        }
+        self.write(&JsonLine::Environment(env));
+    }
+    fn report_error(&self, message: &str) {
+        // Keep stdout reserved for the JSON lines.
+        eprintln!("Error: {message}")
+    }
+}
@@ -24,6 +24,24 @@ struct TelemetryData {
     data: TelemetryEvent,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug)]
+struct ManagerUpdated {
+    old: EnvManager,
+    new: EnvManager,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug)]
+pub(crate) struct Progress {
+    pub completed: usize,
+    pub total: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 impl Reporter for JsonRpcReporter {
     fn report_telemetry(&self, event: &TelemetryEvent) {
         let event = TelemetryData {
@@ -38,6 +56,45 @@ impl Reporter for JsonRpcReporter {
         send_message("manager", manager.into())
     }
 
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        trace!("Reporting Manager updated {:?} => {:?}", old, new);
+        send_message(
+            "managerUpdated",
+            Some(ManagerUpdated {
+                old: old.clone(),
+                new: new.clone(),
+            }),
+        )
+    }
+
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        trace!("Reporting progress {:?} of {:?}", completed, total);
+        send_message(
+            "pet/progress",
+            Some(Progress {
+                completed,
+                total,
+                message: None,
+            }),
+        )
+    }
+
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        trace!("Reporting removed Environment {:?}", env);
+        send_message("pet/environmentRemoved", env.into())
+    }
+
+    fn report_error(&self, message: &str) {
+        trace!("Reporting error {:?}", message);
+        send_message(
+            "log",
+            Some(Log {
+                message: message.to_string(),
+                level: LogLevel::Error,
+            }),
+        )
+    }
+
     fn report_environment(&self, env: &PythonEnvironment) {
         if let Some(report_only) = &self.report_only {
             if env.kind != Some(*report_only) {
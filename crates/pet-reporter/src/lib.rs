@@ -3,6 +3,13 @@
 
 pub mod cache;
 pub mod collect;
+pub mod dedup;
 pub mod environment;
+pub mod json;
+pub mod json_lines;
 pub mod jsonrpc;
+pub mod progress;
 pub mod stdio;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod vec;
@@ -0,0 +1,106 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::jsonrpc::Progress;
+use pet_core::{
+    manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter,
+    telemetry::TelemetryEvent,
+};
+use pet_jsonrpc::send_message;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// A `pet/progress` notification is sent every time this many environments have been found.
+pub const REPORT_EVERY: usize = 10;
+
+/// Decorator that sends the `pet/progress` notifications, including the number of environments found so far.
+/// The progress of the locators (`report_progress`) is sent as is, along with an additional notification
+/// every `REPORT_EVERY` environments.
+/// The notifications are sent from a dedicated thread (in the order they were reported),
+/// so that the search is never blocked writing to stdout.
+pub struct ProgressReporter {
+    reporter: Arc<dyn Reporter>,
+    found: AtomicUsize,
+    /// Locators completed & the total number of locators, as last reported.
+    locators: Mutex<(usize, Option<usize>)>,
+    sender: Mutex<Option<Sender<Progress>>>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ProgressReporter {
+    /// Call `end` once the search has completed.
+    pub fn begin(reporter: Arc<dyn Reporter>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            for progress in receiver {
+                send_message("pet/progress", Some(progress));
+            }
+        });
+        ProgressReporter {
+            reporter,
+            found: AtomicUsize::new(0),
+            locators: Mutex::new((0, None)),
+            sender: Mutex::new(Some(sender)),
+            handle: Mutex::new(Some(handle)),
+        }
+    }
+    /// Waits for all of the notifications to be sent.
+    pub fn end(&self) {
+        self.sender.lock().unwrap().take();
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+    }
+    fn send(&self, progress: Progress) {
+        if let Some(sender) = self.sender.lock().unwrap().as_ref() {
+            let _ = sender.send(progress);
+        }
+    }
+}
+
+impl Reporter for ProgressReporter {
+    fn report_telemetry(&self, event: &TelemetryEvent) {
+        self.reporter.report_telemetry(event);
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.reporter.report_manager(manager);
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    /// Not forwarded, the notification is sent by this reporter to preserve the order of the notifications.
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        // Hold the lock while sending, to ensure the progress is sent in increasing order.
+        let mut locators = self.locators.lock().unwrap();
+        *locators = (completed, total);
+        self.send(Progress {
+            completed,
+            total,
+            message: None,
+        });
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_environment(env);
+        let found = self.found.fetch_add(1, Ordering::SeqCst) + 1;
+        if found.is_multiple_of(REPORT_EVERY) {
+            let locators = self.locators.lock().unwrap();
+            self.send(Progress {
+                completed: locators.0,
+                total: locators.1,
+                message: Some(format!("Found {found} environments")),
+            });
+        }
+    }
+}
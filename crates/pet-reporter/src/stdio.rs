@@ -19,6 +19,9 @@ pub struct StdioReporter {
     managers: Arc<Mutex<HashMap<EnvManagerType, u16>>>,
     environments: Arc<Mutex<HashMap<Option<PythonEnvironmentKind>, u16>>>,
     kind: Option<PythonEnvironmentKind>,
+    /// When set, environments are buffered and only printed (sorted) when `flush` is called.
+    sorted_output: bool,
+    buffered: Mutex<Vec<PythonEnvironment>>,
 }
 
 pub struct Summary {
@@ -35,6 +38,17 @@ impl StdioReporter {
             environments: environments.clone(),
         }
     }
+    /// Prints the buffered environments sorted by kind, version and executable.
+    /// Does nothing unless the reporter was created with `sorted_output`.
+    pub fn flush(&self) {
+        let mut buffered = self.buffered.lock().unwrap();
+        buffered.sort_by(|a, b| {
+            (&a.kind, &a.version, &a.executable).cmp(&(&b.kind, &b.version, &b.executable))
+        });
+        for env in buffered.drain(..) {
+            println!("{env}")
+        }
+    }
 }
 impl Reporter for StdioReporter {
     fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
@@ -57,17 +71,46 @@ impl Reporter for StdioReporter {
         let count = environments.get(&env.kind).unwrap_or(&0) + 1;
         environments.insert(env.kind, count);
         if self.print_list {
-            println!("{env}")
+            if self.sorted_output {
+                self.buffered.lock().unwrap().push(env.clone());
+            } else {
+                println!("{env}")
+            }
+        }
+    }
+
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        if self.print_list {
+            println!("Removed: {env}")
+        }
+    }
+
+    fn report_error(&self, message: &str) {
+        eprintln!("Error: {message}")
+    }
+
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        if self.print_list {
+            match total {
+                Some(total) => println!("Progress: {completed} of {total}"),
+                None => println!("Progress: {completed}"),
+            }
         }
     }
 }
 
-pub fn create_reporter(print_list: bool, kind: Option<PythonEnvironmentKind>) -> StdioReporter {
+pub fn create_reporter(
+    print_list: bool,
+    kind: Option<PythonEnvironmentKind>,
+    sorted_output: bool,
+) -> StdioReporter {
     StdioReporter {
         print_list,
         managers: Arc::new(Mutex::new(HashMap::new())),
         environments: Arc::new(Mutex::new(HashMap::new())),
         kind,
+        sorted_output,
+        buffered: Mutex::new(vec![]),
     }
 }
 
@@ -0,0 +1,62 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::EnvManager, python_environment::PythonEnvironment, reporter::Reporter,
+    telemetry::TelemetryEvent,
+};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Decorator that records the time at which environments & managers are reported.
+/// Useful to identify performance regressions, e.g. a locator that delays the reporting of environments.
+pub struct TelemetryReporter<'a> {
+    reporter: &'a dyn Reporter,
+    start: Instant,
+    timings: Mutex<Vec<Duration>>,
+}
+
+impl<'a> TelemetryReporter<'a> {
+    pub fn new(reporter: &'a dyn Reporter) -> Self {
+        TelemetryReporter {
+            reporter,
+            start: Instant::now(),
+            timings: Mutex::new(vec![]),
+        }
+    }
+    /// Time (since this reporter was created) at which each environment & manager was reported.
+    pub fn timing_histogram(&self) -> Vec<Duration> {
+        self.timings.lock().unwrap().clone()
+    }
+    fn record(&self) {
+        self.timings.lock().unwrap().push(self.start.elapsed());
+    }
+}
+
+impl Reporter for TelemetryReporter<'_> {
+    fn report_telemetry(&self, event: &TelemetryEvent) {
+        self.reporter.report_telemetry(event);
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.record();
+        self.reporter.report_manager(manager);
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.reporter.report_progress(completed, total);
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        self.record();
+        self.reporter.report_environment(env);
+    }
+}
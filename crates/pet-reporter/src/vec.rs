@@ -0,0 +1,6 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+/// Collects the reported environments into a `Vec`, useful in tests.
+/// Defined in `pet-core` as it is used by the default implementation of `Locator::get_environments`.
+pub use pet_core::reporter::VecReporter;
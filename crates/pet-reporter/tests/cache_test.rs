@@ -0,0 +1,195 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::{EnvManager, EnvManagerType},
+    python_environment::PythonEnvironment,
+    reporter::Reporter,
+    telemetry::TelemetryEvent,
+};
+use pet_reporter::cache::CacheReporter;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Default)]
+struct MockReporter {
+    managers: Mutex<Vec<EnvManager>>,
+    updated_managers: Mutex<Vec<(EnvManager, EnvManager)>>,
+    errors: Mutex<Vec<String>>,
+    removed_environments: Mutex<Vec<PythonEnvironment>>,
+    progress: Mutex<Vec<(usize, Option<usize>)>>,
+}
+
+impl Reporter for MockReporter {
+    fn report_manager(&self, manager: &EnvManager) {
+        self.managers.lock().unwrap().push(manager.clone());
+    }
+    fn report_environment(&self, _env: &PythonEnvironment) {
+        //
+    }
+    fn report_telemetry(&self, _event: &TelemetryEvent) {
+        //
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.updated_managers
+            .lock()
+            .unwrap()
+            .push((old.clone(), new.clone()));
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.removed_environments.lock().unwrap().push(env.clone());
+    }
+    fn report_error(&self, message: &str) {
+        self.errors.lock().unwrap().push(message.to_string());
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.progress.lock().unwrap().push((completed, total));
+    }
+}
+
+fn create_poetry_manager(version: Option<&str>) -> EnvManager {
+    EnvManager::new(
+        PathBuf::from("/home/user/.local/bin/poetry"),
+        EnvManagerType::Poetry,
+        version.map(|v| v.to_string()),
+    )
+}
+
+#[test]
+fn report_manager_updated_when_version_changes_across_searches() {
+    let mock = Arc::new(MockReporter::default());
+    let known_managers = Arc::new(Mutex::new(HashMap::new()));
+
+    // First search.
+    let reporter = CacheReporter::with_known_managers(mock.clone(), known_managers.clone());
+    reporter.report_manager(&create_poetry_manager(Some("1.8.0")));
+    assert!(mock.updated_managers.lock().unwrap().is_empty());
+
+    // Second search, manager has been upgraded.
+    let reporter = CacheReporter::with_known_managers(mock.clone(), known_managers.clone());
+    reporter.report_manager(&create_poetry_manager(Some("1.8.3")));
+
+    assert_eq!(
+        mock.updated_managers.lock().unwrap().clone(),
+        vec![(
+            create_poetry_manager(Some("1.8.0")),
+            create_poetry_manager(Some("1.8.3"))
+        )]
+    );
+    assert_eq!(mock.managers.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn do_not_report_manager_updated_when_version_is_unchanged_or_unknown() {
+    let mock = Arc::new(MockReporter::default());
+    let known_managers = Arc::new(Mutex::new(HashMap::new()));
+
+    let reporter = CacheReporter::with_known_managers(mock.clone(), known_managers.clone());
+    reporter.report_manager(&create_poetry_manager(Some("1.8.0")));
+    reporter.report_manager(&create_poetry_manager(None));
+
+    let reporter = CacheReporter::with_known_managers(mock.clone(), known_managers.clone());
+    reporter.report_manager(&create_poetry_manager(Some("1.8.0")));
+    reporter.report_manager(&create_poetry_manager(None));
+
+    assert!(mock.updated_managers.lock().unwrap().is_empty());
+}
+
+#[test]
+fn get_environment_by_prefix() {
+    use pet_core::python_environment::{PythonEnvironmentBuilder, PythonEnvironmentKind};
+
+    let reporter = CacheReporter::new(Arc::new(MockReporter::default()));
+    let venv = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Venv))
+        .executable(Some(PathBuf::from("/home/user/project/.venv/bin/python3")))
+        .prefix(Some(PathBuf::from("/home/user/project/.venv")))
+        .build();
+    // Conda env without Python, keyed by prefix.
+    let conda = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+        .prefix(Some(PathBuf::from("/home/user/miniconda3/envs/empty")))
+        .build();
+    reporter.report_environment(&venv);
+    reporter.report_environment(&conda);
+
+    assert_eq!(
+        reporter.get_by_prefix(&PathBuf::from("/home/user/project/.venv")),
+        Some(venv)
+    );
+    assert_eq!(
+        reporter.get_by_prefix(&PathBuf::from("/home/user/miniconda3/envs/empty")),
+        Some(conda)
+    );
+    assert_eq!(
+        reporter.get_by_prefix(&PathBuf::from("/home/user/project/.venv/bin")),
+        None
+    );
+}
+
+#[test]
+fn errors_and_progress_are_forwarded() {
+    let reporter = Arc::new(MockReporter::default());
+    let cache_reporter = CacheReporter::new(reporter.clone());
+
+    cache_reporter.report_progress(1, Some(2));
+    cache_reporter.report_error("Failed to execute Poetry");
+    cache_reporter.report_progress(2, Some(2));
+
+    assert_eq!(
+        *reporter.errors.lock().unwrap(),
+        vec!["Failed to execute Poetry".to_string()]
+    );
+    assert_eq!(
+        *reporter.progress.lock().unwrap(),
+        vec![(1, Some(2)), (2, Some(2))]
+    );
+}
+
+#[test]
+fn report_environments_deleted_since_previous_searches() {
+    let root = std::env::temp_dir().join("pet_cache_reporter_removed_test");
+    let _ = std::fs::remove_dir_all(&root);
+    let env = |name: &str| {
+        let prefix = root.join(name);
+        std::fs::create_dir_all(prefix.join("bin")).unwrap();
+        std::fs::write(prefix.join("bin").join("python"), "").unwrap();
+        PythonEnvironment {
+            executable: Some(prefix.join("bin").join("python")),
+            prefix: Some(prefix),
+            ..Default::default()
+        }
+    };
+    let deleted_env = env("deleted");
+    let other_env = env("other");
+    let known_environments = Arc::new(Mutex::new(HashMap::new()));
+    let create_reporter = |reporter: Arc<MockReporter>| {
+        CacheReporter::new(reporter).with_known_environments(known_environments.clone())
+    };
+
+    let reporter = Arc::new(MockReporter::default());
+    let cache_reporter = create_reporter(reporter.clone());
+    cache_reporter.report_environment(&deleted_env);
+    cache_reporter.report_environment(&other_env);
+    assert!(cache_reporter.report_removed_environments().is_empty());
+
+    // Next search, after the environment has been deleted.
+    std::fs::remove_dir_all(deleted_env.prefix.clone().unwrap()).unwrap();
+    let reporter = Arc::new(MockReporter::default());
+    let cache_reporter = create_reporter(reporter.clone());
+    cache_reporter.report_environment(&other_env);
+
+    assert_eq!(
+        cache_reporter.report_removed_environments(),
+        vec![deleted_env.clone()]
+    );
+    assert_eq!(
+        *reporter.removed_environments.lock().unwrap(),
+        vec![deleted_env]
+    );
+    // Only reported once.
+    assert!(cache_reporter.report_removed_environments().is_empty());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::{EnvManager, EnvManagerType},
+    python_environment::{PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+};
+use pet_reporter::collect::CollectReporter;
+use std::path::PathBuf;
+
+#[test]
+fn environments_reported_by_multiple_locators_are_merged() {
+    let executable = PathBuf::from("/home/user/miniconda3/bin/python");
+    let manager = EnvManager::new(
+        PathBuf::from("/home/user/miniconda3/bin/conda"),
+        EnvManagerType::Conda,
+        None,
+    );
+    let reporter = CollectReporter::new();
+    reporter.report_environment(
+        &PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::GlobalPaths))
+            .executable(Some(executable.clone()))
+            .symlinks(Some(vec![PathBuf::from(
+                "/home/user/miniconda3/bin/python3",
+            )]))
+            .build(),
+    );
+    reporter.report_environment(
+        &PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Conda))
+            .executable(Some(executable.clone()))
+            .prefix(Some(PathBuf::from("/home/user/miniconda3")))
+            .manager(Some(manager.clone()))
+            .build(),
+    );
+
+    let environments = reporter.to_locator_result().environments;
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].kind, Some(PythonEnvironmentKind::Conda));
+    assert_eq!(environments[0].manager, Some(manager));
+    assert_eq!(
+        environments[0].symlinks,
+        Some(vec![
+            executable,
+            PathBuf::from("/home/user/miniconda3/bin/python3")
+        ])
+    );
+}
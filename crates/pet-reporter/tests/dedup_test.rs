@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::EnvManager,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+    telemetry::TelemetryEvent,
+};
+use pet_reporter::dedup::DeduplicatingReporter;
+use std::{fs, path::PathBuf, sync::Mutex};
+
+#[derive(Default)]
+struct MockReporter {
+    environments: Mutex<Vec<PythonEnvironment>>,
+}
+
+impl Reporter for MockReporter {
+    fn report_manager(&self, _manager: &EnvManager) {
+        //
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        self.environments.lock().unwrap().push(env.clone());
+    }
+    fn report_telemetry(&self, _event: &TelemetryEvent) {
+        //
+    }
+}
+
+fn create_env(executable: PathBuf, prefix: PathBuf) -> PythonEnvironment {
+    PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::GlobalPaths))
+        .executable(Some(executable))
+        .prefix(Some(prefix))
+        .build()
+}
+
+#[cfg(unix)]
+#[test]
+fn same_interpreter_found_via_symlinks_is_reported_once() {
+    let dir = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_dedup_test_symlinks");
+    let _ = fs::remove_dir_all(&dir);
+    let bin = dir.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let python = bin.join("python3.12");
+    fs::write(&python, "").unwrap();
+    std::os::unix::fs::symlink(&python, bin.join("python3")).unwrap();
+    std::os::unix::fs::symlink(&python, bin.join("python")).unwrap();
+
+    let mock = MockReporter::default();
+    let reporter = DeduplicatingReporter::new(&mock);
+    reporter.report_environment(&create_env(bin.join("python3"), dir.clone()));
+    reporter.report_environment(&create_env(bin.join("python"), dir.clone()));
+    reporter.report_environment(&create_env(python.clone(), dir.clone()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let environments = mock.environments.lock().unwrap();
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].executable, Some(bin.join("python3")));
+}
+
+#[cfg(unix)]
+#[test]
+fn environments_with_different_prefixes_are_not_duplicates() {
+    let dir = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_dedup_test_prefixes");
+    let _ = fs::remove_dir_all(&dir);
+    let bin = dir.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let python = bin.join("python3.12");
+    fs::write(&python, "").unwrap();
+    // The executable of a virtual env is a symlink to the base interpreter.
+    let venv_bin = dir.join(".venv").join("bin");
+    fs::create_dir_all(&venv_bin).unwrap();
+    std::os::unix::fs::symlink(&python, venv_bin.join("python")).unwrap();
+
+    let mock = MockReporter::default();
+    let reporter = DeduplicatingReporter::new(&mock);
+    reporter.report_environment(&create_env(python.clone(), dir.clone()));
+    reporter.report_environment(&create_env(venv_bin.join("python"), dir.join(".venv")));
+    let _ = fs::remove_dir_all(&dir);
+
+    assert_eq!(mock.environments.lock().unwrap().len(), 2);
+}
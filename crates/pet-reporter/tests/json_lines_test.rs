@@ -0,0 +1,82 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::{
+    manager::{EnvManager, EnvManagerType},
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+};
+use pet_reporter::{json::FindSummary, json_lines::JsonLinesReporter};
+use serde_json::Value;
+use std::path::PathBuf;
+
+fn create_env(name: &str, kind: PythonEnvironmentKind) -> PythonEnvironment {
+    PythonEnvironmentBuilder::new(Some(kind))
+        .executable(Some(PathBuf::from(format!("/envs/{name}/bin/python"))))
+        .prefix(Some(PathBuf::from(format!("/envs/{name}"))))
+        .build()
+}
+
+#[test]
+fn each_event_is_written_as_a_json_line() {
+    let environments = vec![
+        create_env("one", PythonEnvironmentKind::Venv),
+        create_env("two", PythonEnvironmentKind::Conda),
+        create_env("three", PythonEnvironmentKind::Poetry),
+    ];
+    let manager = EnvManager::new(
+        PathBuf::from("/conda/bin/conda"),
+        EnvManagerType::Conda,
+        None,
+    );
+    let reporter = JsonLinesReporter::new(vec![], None);
+    reporter.report_manager(&manager);
+    for env in &environments {
+        reporter.report_environment(env);
+    }
+    reporter.report_summary(&FindSummary {
+        total_environments: environments.len(),
+        total_managers: 1,
+        ..Default::default()
+    });
+
+    let output = String::from_utf8(reporter.into_inner()).unwrap();
+    let lines = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let of_type = |type_: &str| {
+        lines
+            .iter()
+            .filter(|line| line["type"] == type_)
+            .map(|line| line["data"].clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(lines.len(), 5);
+    let reported = of_type("environment")
+        .into_iter()
+        .map(|env| serde_json::from_value::<PythonEnvironment>(env).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(reported, environments);
+    assert_eq!(
+        of_type("manager")
+            .into_iter()
+            .map(|m| serde_json::from_value::<EnvManager>(m).unwrap())
+            .collect::<Vec<_>>(),
+        vec![manager]
+    );
+    assert_eq!(lines.last().unwrap()["type"], "summary");
+    assert_eq!(lines.last().unwrap()["data"]["totalEnvironments"], 3);
+}
+
+#[test]
+fn only_environments_of_the_kind_are_written() {
+    let reporter = JsonLinesReporter::new(vec![], Some(PythonEnvironmentKind::Conda));
+    reporter.report_environment(&create_env("one", PythonEnvironmentKind::Venv));
+    reporter.report_environment(&create_env("two", PythonEnvironmentKind::Conda));
+
+    let output = String::from_utf8(reporter.into_inner()).unwrap();
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.contains("/envs/two"));
+}
@@ -0,0 +1,29 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![cfg(feature = "telemetry")]
+
+use pet_core::{
+    python_environment::{PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+};
+use pet_reporter::{collect, telemetry::TelemetryReporter};
+use std::path::PathBuf;
+
+#[test]
+fn records_timing_for_each_reported_environment() {
+    let inner = collect::create_reporter();
+    let reporter = TelemetryReporter::new(&inner);
+
+    for i in 0..10 {
+        let env = PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Venv))
+            .executable(Some(PathBuf::from(format!("/env{}/bin/python", i))))
+            .build();
+        reporter.report_environment(&env);
+    }
+
+    let timings = reporter.timing_histogram();
+    assert_eq!(timings.len(), 10);
+    assert!(timings.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(inner.environments.lock().unwrap().len(), 10);
+}
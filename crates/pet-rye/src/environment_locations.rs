@@ -0,0 +1,19 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::path::PathBuf;
+
+/// See https://rye.astral.sh/guide/config/#home-folder-location
+pub fn get_rye_home(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(rye_home) = &env_vars.rye_home {
+        return Some(norm_case(PathBuf::from(rye_home)));
+    }
+    env_vars.home.as_ref().map(|home| home.join(".rye"))
+}
+
+/// Directory containing the Python toolchains managed by Rye, e.g. `~/.rye/py/cpython@3.12.3`.
+pub fn get_toolchains_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    get_rye_home(env_vars).map(|rye_home| rye_home.join("py"))
+}
@@ -0,0 +1,185 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::get_toolchains_dir;
+use log::trace;
+use manager::find_rye_executable;
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables, get_prefix_from_executable};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+
+/// Returns true if the virtual environment was created by Rye.
+/// Rye records itself in the `pyvenv.cfg` file, e.g. `rye = 0.39.0`.
+pub fn is_rye_venv(prefix: &Path) -> bool {
+    match fs::read_to_string(prefix.join("pyvenv.cfg")) {
+        Ok(contents) => contents.lines().any(|line| {
+            line.split_once('=')
+                .map(|(key, value)| key.trim() == "rye" && !value.trim().is_empty())
+                .unwrap_or_default()
+        }),
+        Err(_) => false,
+    }
+}
+
+/// Toolchains are named after the implementation & version, e.g. `cpython@3.12.3`.
+fn get_version_from_toolchain_name(name: &str) -> Option<String> {
+    let (_, version) = name.split_once('@')?;
+    if version.is_empty() {
+        None
+    } else {
+        Some(version.to_string())
+    }
+}
+
+/// Python toolchain managed by Rye, e.g. `~/.rye/py/cpython@3.12.3`.
+fn get_toolchain(
+    prefix: &Path,
+    executable: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    let version = prefix
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(get_version_from_toolchain_name);
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Rye))
+            .source_locator(Some(LocatorKind::Rye.name()))
+            .executable(Some(executable))
+            .version(version.and_then(|v| v.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+/// Virtual environment created by Rye in the root of a project, i.e. `<project>/.venv`.
+fn get_rye_venv(
+    prefix: &Path,
+    executable: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    if !is_rye_venv(prefix) {
+        return None;
+    }
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    let project = if prefix.ends_with(".venv") {
+        prefix.parent().map(|p| p.to_path_buf())
+    } else {
+        None
+    };
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Rye))
+            .source_locator(Some(LocatorKind::Rye.name()))
+            .executable(Some(executable))
+            .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .project(project)
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+pub struct Rye {
+    pub env_vars: EnvVariables,
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Rye {
+    pub fn from(environment: &dyn Environment) -> Rye {
+        Rye {
+            env_vars: EnvVariables::from(environment),
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let executable = find_rye_executable(&self.env_vars)?;
+        let mut manager = EnvManager::new(executable, EnvManagerType::Rye, None);
+        manager.install_path = get_toolchains_dir(&self.env_vars);
+        Some(manager)
+    }
+}
+
+impl Locator for Rye {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Rye
+    }
+    fn description(&self) -> &'static str {
+        "Finds Python versions and environments managed by Rye, reads RYE_HOME (defaults to ~/.rye)"
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("rye") | Some("rye.exe")
+        )
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Rye]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        if let Some(toolchains_dir) = get_toolchains_dir(&self.env_vars) {
+            if let Ok(path) = env.executable.strip_prefix(&toolchains_dir) {
+                // The toolchain is the first directory under `$RYE_HOME/py`.
+                let name = path.components().next()?;
+                let prefix = toolchains_dir.join(name);
+                return get_toolchain(&prefix, Some(env.executable.clone()), &self.get_manager());
+            }
+        }
+        let prefix = env
+            .prefix
+            .clone()
+            .or_else(|| get_prefix_from_executable(&env.executable))?;
+        get_rye_venv(&prefix, Some(env.executable.clone()), &self.get_manager())
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+
+        if let Some(toolchains_dir) = get_toolchains_dir(&self.env_vars) {
+            trace!("Searching for Rye toolchains in {:?}", toolchains_dir);
+            for prefix in list_dirs(&toolchains_dir) {
+                if let Some(env) = get_toolchain(&prefix, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        for project_dir in workspace_directories {
+            if let Some(env) = get_rye_venv(&project_dir.join(".venv"), None, &manager) {
+                reporter.report_environment(&env);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{env_variables::EnvVariables, environment_locations::get_rye_home};
+use log::trace;
+use std::{env, path::PathBuf};
+
+fn get_rye_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["rye.exe", "rye"]
+    } else {
+        vec!["rye"]
+    }
+}
+
+/// Looks for the Rye executable in the shims directory of `RYE_HOME` & the PATH variable.
+pub fn find_rye_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(rye_home) = get_rye_home(env_vars) {
+        search_paths.push(rye_home.join("shims"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    for dir in search_paths {
+        for name in get_rye_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("Rye exe not found");
+    None
+}
@@ -0,0 +1,140 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_rye_toolchains_and_project_venvs() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use pet_rye::Rye;
+    use std::collections::HashMap;
+
+    let rye_home = resolve_test_path!(&["unix", "rye_home"]);
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let other_project = resolve_test_path!(&["unix", "other_app"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "RYE_HOME".to_string(),
+            rye_home.to_str().unwrap().to_string(),
+        )]),
+        None,
+    );
+    let locator = Rye::from(&environment);
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![project.clone(), other_project]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    // Rye is not installed.
+    assert!(result.managers.is_empty());
+    let mut environments = result
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Rye));
+            (
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(
+        environments,
+        vec![
+            (
+                project.join(".venv"),
+                "3.12.3".to_string(),
+                Some(project.clone())
+            ),
+            (
+                rye_home.join("py").join("cpython@3.12.3"),
+                "3.12.3".to_string(),
+                None
+            ),
+            (
+                rye_home.join("py").join("pypy@3.10.14"),
+                "3.10.14".to_string(),
+                None
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn identify_rye_environments() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_rye::Rye;
+    use std::collections::HashMap;
+
+    let rye_home = resolve_test_path!(&["unix", "rye_home"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "RYE_HOME".to_string(),
+            rye_home.to_str().unwrap().to_string(),
+        )]),
+        None,
+    );
+    let locator = Rye::from(&environment);
+
+    let prefix = rye_home.join("py").join("cpython@3.12.3");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python3"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Rye));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.version, "3.12.3".parse().ok());
+
+    let project = resolve_test_path!(&["unix", "my_app"]);
+    let prefix = project.join(".venv");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Rye));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, Some(project));
+
+    // Virtual env not created by Rye.
+    let prefix = resolve_test_path!(&["unix", "other_app", ".venv"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            Some(prefix),
+            None
+        ))
+        .is_none());
+}
+
+#[test]
+fn manages_rye_executables() {
+    use pet_core::Locator;
+    use pet_python_utils::test_utils::create_test_environment;
+    use pet_rye::Rye;
+    use std::{collections::HashMap, path::PathBuf};
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Rye::from(&environment);
+
+    assert!(locator.manages_executable(&PathBuf::from("/home/user/.rye/shims/rye")));
+    assert!(locator.manages_executable(&PathBuf::from("rye.exe")));
+    assert!(!locator.manages_executable(&PathBuf::from("/home/user/.rye/shims/python")));
+}
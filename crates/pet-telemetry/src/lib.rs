@@ -50,8 +50,11 @@ pub fn report_inaccuracies_identified_after_resolving(
     }
 
     let invalid_version = are_versions_different(
-        &resolved.version.clone()?,
-        &env.version.clone().unwrap_or_default(),
+        &resolved.version.as_ref()?.to_string(),
+        &env.version
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
     );
 
     if invalid_executable
@@ -0,0 +1,118 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::trace;
+use pet_core::{
+    env::PythonEnv,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_python_utils::executable::{find_executable, find_executables, get_prefix_from_executable};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tox_config::get_env_names;
+
+pub mod tox_config;
+
+/// Environment created by tox in the project directory, i.e. `<project>/.tox/<env name>`.
+fn get_tox_environment(
+    project_dir: &Path,
+    prefix: &Path,
+    executable: Option<PathBuf>,
+) -> Option<PythonEnvironment> {
+    if !prefix.join("pyvenv.cfg").is_file() {
+        return None;
+    }
+    let name = prefix.file_name()?.to_str()?.to_string();
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Tox))
+            .source_locator(Some(LocatorKind::Tox.name()))
+            .name(Some(name))
+            .executable(Some(executable))
+            .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .project(Some(project_dir.to_path_buf()))
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+pub struct Tox {
+    workspace_directories: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Tox {
+    pub fn new() -> Tox {
+        Tox {
+            workspace_directories: Arc::new(Mutex::new(vec![])),
+        }
+    }
+}
+impl Default for Tox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Locator for Tox {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Tox
+    }
+    fn description(&self) -> &'static str {
+        "Finds environments created by tox in the .tox directory of workspace folders"
+    }
+    fn configure(&self, config: &Configuration) {
+        if let Some(workspace_directories) = &config.workspace_directories {
+            let mut dirs = self.workspace_directories.lock().unwrap();
+            dirs.clear();
+            dirs.extend(workspace_directories.clone());
+        }
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Tox]
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let prefix = env
+            .prefix
+            .clone()
+            .or_else(|| get_prefix_from_executable(&env.executable))?;
+        let tox_dir = prefix.parent()?;
+        if tox_dir.file_name().and_then(|name| name.to_str()) != Some(".tox") {
+            return None;
+        }
+        // Only environments of the projects we know of.
+        let project_dir = tox_dir.parent()?;
+        if !self
+            .workspace_directories
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|dir| dir == project_dir)
+        {
+            return None;
+        }
+        get_tox_environment(project_dir, &prefix, Some(env.executable.clone()))
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        let workspace_directories = self.workspace_directories.lock().unwrap().clone();
+        for project_dir in workspace_directories {
+            let tox_dir = project_dir.join(".tox");
+            if !tox_dir.is_dir() {
+                continue;
+            }
+            trace!("Searching for tox environments in {:?}", tox_dir);
+            for name in get_env_names(&project_dir) {
+                if let Some(env) = get_tox_environment(&project_dir, &tox_dir.join(name), None) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
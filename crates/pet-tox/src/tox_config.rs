@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::{fs, path::Path};
+
+/// Sections of an ini file along with the key value pairs of each section.
+/// Values can span multiple lines, the subsequent lines are indented.
+fn parse_ini(contents: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut sections: Vec<(String, Vec<(String, String)>)> = vec![];
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            let name = trimmed[1..trimmed.len() - 1].trim().to_string();
+            sections.push((name, vec![]));
+            continue;
+        }
+        let values = match sections.last_mut() {
+            Some((_, values)) => values,
+            None => continue,
+        };
+        if line.starts_with(char::is_whitespace) {
+            if let Some((_, value)) = values.last_mut() {
+                value.push('\n');
+                value.push_str(trimmed);
+            }
+        } else if let Some((key, value)) = trimmed.split_once('=') {
+            values.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    sections
+}
+
+/// Splits the list of environments on commas & new lines, ignoring the commas within braces.
+fn split_env_list(value: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut item = String::new();
+    let mut depth = 0;
+    for c in value.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' | '\n' if depth == 0 => {
+                items.push(item.trim().to_string());
+                item.clear();
+                continue;
+            }
+            _ => {}
+        }
+        item.push(c);
+    }
+    items.push(item.trim().to_string());
+    items.into_iter().filter(|item| !item.is_empty()).collect()
+}
+
+/// Expands the generative environment names, e.g. `py{311,312}-django{4,5}` into `py311-django4`, `py311-django5`, etc.
+/// See https://tox.wiki/en/latest/user_guide.html#generative-environment-list
+pub fn expand_env_name(name: &str) -> Vec<String> {
+    let (start, end) = match (name.find('{'), name.find('}')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return vec![name.to_string()],
+    };
+    let mut names = vec![];
+    for factor in name[start + 1..end].split(',') {
+        let expanded = format!("{}{}{}", &name[..start], factor.trim(), &name[end + 1..]);
+        names.extend(expand_env_name(&expanded));
+    }
+    names
+}
+
+fn get_env_names_from_ini(contents: &str, tox_sections: &[&str]) -> Vec<String> {
+    let mut names = vec![];
+    for (section, values) in parse_ini(contents) {
+        if tox_sections.contains(&section.as_str()) {
+            for (key, value) in values {
+                if key == "envlist" || key == "env_list" {
+                    for item in split_env_list(&value) {
+                        names.extend(expand_env_name(&item));
+                    }
+                }
+            }
+        } else if let Some(name) = section.strip_prefix("testenv:") {
+            names.extend(expand_env_name(name.trim()));
+        }
+    }
+    names
+}
+
+/// Configuration in the native TOML format, i.e. `tox.toml` or the `[tool.tox]` section of `pyproject.toml`.
+fn get_env_names_from_toml(config: &toml::Value) -> Vec<String> {
+    // Configuration in the ini format embedded in `pyproject.toml`.
+    if let Some(legacy_tox_ini) = config.get("legacy_tox_ini").and_then(|v| v.as_str()) {
+        return get_env_names_from_ini(legacy_tox_ini, &["tox", "tox:tox"]);
+    }
+    let mut names = vec![];
+    if let Some(env_list) = config.get("env_list").and_then(|v| v.as_array()) {
+        names.extend(
+            env_list
+                .iter()
+                .filter_map(|name| name.as_str())
+                .map(|name| name.to_string()),
+        );
+    }
+    if let Some(envs) = config.get("env").and_then(|v| v.as_table()) {
+        names.extend(envs.keys().cloned());
+    }
+    names
+}
+
+/// Names of the environments defined in the tox configuration of the project.
+/// Looks in `tox.ini`, `setup.cfg`, `pyproject.toml` & `tox.toml`, see https://tox.wiki/en/latest/config.html#discovery-and-file-types
+pub fn get_env_names(project_dir: &Path) -> Vec<String> {
+    let mut names = vec![];
+    if let Ok(contents) = fs::read_to_string(project_dir.join("tox.ini")) {
+        names.extend(get_env_names_from_ini(&contents, &["tox"]));
+    }
+    if let Ok(contents) = fs::read_to_string(project_dir.join("setup.cfg")) {
+        names.extend(get_env_names_from_ini(&contents, &["tox:tox"]));
+    }
+    if let Some(tox) = fs::read_to_string(project_dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok())
+        .and_then(|pyproject| pyproject.get("tool")?.get("tox").cloned())
+    {
+        names.extend(get_env_names_from_toml(&tox));
+    }
+    if let Some(tox) = fs::read_to_string(project_dir.join("tox.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok())
+    {
+        names.extend(get_env_names_from_toml(&tox));
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_generative_env_names() {
+        assert_eq!(expand_env_name("lint"), vec!["lint"]);
+        assert_eq!(
+            expand_env_name("py{311, 312}-django{4,5}"),
+            vec![
+                "py311-django4",
+                "py311-django5",
+                "py312-django4",
+                "py312-django5"
+            ]
+        );
+    }
+
+    #[test]
+    fn env_names_from_ini() {
+        let contents = r#"
+[tox]
+envlist = py{311,312}, lint
+    docs
+; comment
+[testenv]
+deps = pytest
+
+[testenv:type]
+deps = mypy
+"#;
+        assert_eq!(
+            get_env_names_from_ini(contents, &["tox"]),
+            vec!["py311", "py312", "lint", "docs", "type"]
+        );
+    }
+}
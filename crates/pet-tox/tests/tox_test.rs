@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(unix)]
+#[test]
+fn find_tox_environments_of_workspace_projects() {
+    use pet_core::{python_environment::PythonEnvironmentKind, Configuration, Locator};
+    use pet_python_utils::resolve_test_path;
+    use pet_reporter::collect;
+    use pet_tox::Tox;
+
+    let ini_app = resolve_test_path!(&["unix", "ini_app"]);
+    let toml_app = resolve_test_path!(&["unix", "toml_app"]);
+    let locator = Tox::new();
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![ini_app.clone(), toml_app.clone()]),
+        ..Default::default()
+    });
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let mut environments = reporter
+        .to_locator_result()
+        .environments
+        .iter()
+        .map(|env| {
+            assert_eq!(env.kind, Some(PythonEnvironmentKind::Tox));
+            (
+                env.name.clone().unwrap_or_default(),
+                env.prefix.clone().unwrap_or_default(),
+                env.version
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                env.project.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    environments.sort();
+    // Environments not defined in the configuration (e.g. `.pkg` & `old`) are ignored.
+    assert_eq!(
+        environments,
+        vec![
+            (
+                "3.12".to_string(),
+                toml_app.join(".tox").join("3.12"),
+                "3.12.4".to_string(),
+                Some(toml_app.clone())
+            ),
+            (
+                "lint".to_string(),
+                ini_app.join(".tox").join("lint"),
+                "3.11.2".to_string(),
+                Some(ini_app.clone())
+            ),
+            (
+                "py312".to_string(),
+                ini_app.join(".tox").join("py312"),
+                "3.12.4".to_string(),
+                Some(ini_app.clone())
+            ),
+            (
+                "type".to_string(),
+                toml_app.join(".tox").join("type"),
+                "3.12.4".to_string(),
+                Some(toml_app.clone())
+            ),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_tox_environment_of_known_project() {
+    use pet_core::{
+        env::PythonEnv, python_environment::PythonEnvironmentKind, Configuration, Locator,
+    };
+    use pet_python_utils::resolve_test_path;
+    use pet_tox::Tox;
+
+    let ini_app = resolve_test_path!(&["unix", "ini_app"]);
+    let locator = Tox::new();
+    locator.configure(&Configuration {
+        workspace_directories: Some(vec![ini_app.clone()]),
+        ..Default::default()
+    });
+
+    let prefix = ini_app.join(".tox").join("py312");
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Tox));
+    assert_eq!(env.name, Some("py312".to_string()));
+    assert_eq!(env.prefix, Some(prefix));
+    assert_eq!(env.project, Some(ini_app));
+
+    // Not a project in the workspace.
+    let other = resolve_test_path!(&["unix", "other_app", ".tox", "py312", "bin", "python"]);
+    assert!(locator
+        .try_from(&PythonEnv::new(other, None, None))
+        .is_none());
+}
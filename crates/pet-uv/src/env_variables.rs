@@ -0,0 +1,32 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::os_environment::Environment;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+// NOTE: Do not implement Default trait, as we do not want to ever forget to set the values.
+// Lets be explicit, this way we never miss a value (in Windows or Unix).
+pub struct EnvVariables {
+    pub home: Option<PathBuf>,
+    pub path: Option<String>,
+    /// Directory containing the uv executable.
+    pub uv_home: Option<String>,
+    /// Overrides the directory where uv installs Python.
+    pub uv_python_install_dir: Option<String>,
+    pub xdg_data_home: Option<String>,
+    pub appdata: Option<String>,
+}
+
+impl EnvVariables {
+    pub fn from(env: &dyn Environment) -> Self {
+        EnvVariables {
+            home: env.get_user_home(),
+            path: env.get_env_var("PATH".to_string()),
+            uv_home: env.get_env_var("UV_HOME".to_string()),
+            uv_python_install_dir: env.get_env_var("UV_PYTHON_INSTALL_DIR".to_string()),
+            xdg_data_home: env.get_env_var("XDG_DATA_HOME".to_string()),
+            appdata: env.get_env_var("APPDATA".to_string()),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use pet_fs::path::norm_case;
+use std::path::PathBuf;
+
+/// Directory where uv installs the Python versions it manages.
+/// See https://docs.astral.sh/uv/concepts/python-versions/#installing-a-python-version
+pub fn get_python_install_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    if let Some(dir) = &env_vars.uv_python_install_dir {
+        return Some(norm_case(PathBuf::from(dir)));
+    }
+    if cfg!(windows) {
+        if let Some(appdata) = &env_vars.appdata {
+            return Some(PathBuf::from(appdata).join("uv").join("python"));
+        }
+    }
+    if let Some(xdg_data_home) = &env_vars.xdg_data_home {
+        return Some(PathBuf::from(xdg_data_home).join("uv").join("python"));
+    }
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".local").join("share").join("uv").join("python"))
+}
+
+/// Directory containing the virtual environments created by uv (outside of projects).
+pub fn get_envs_dir(env_vars: &EnvVariables) -> Option<PathBuf> {
+    env_vars
+        .home
+        .as_ref()
+        .map(|home| home.join(".uv").join("envs"))
+}
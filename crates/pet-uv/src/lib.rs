@@ -0,0 +1,218 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use env_variables::EnvVariables;
+use environment_locations::{get_envs_dir, get_python_install_dir};
+use log::trace;
+use manager::find_uv_executable;
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    pyvenv_cfg::PyVenvCfg,
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_fs::path::list_dirs;
+use pet_python_utils::executable::{find_executable, find_executables, get_prefix_from_executable};
+use python_list::{get_version_from_key, list_python_installations};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod env_variables;
+pub mod environment_locations;
+pub mod manager;
+pub mod python_list;
+
+/// Returns true if the virtual environment was created by uv.
+/// uv records itself in the `pyvenv.cfg` file, e.g. `uv = 0.4.0` (or `uv = true`).
+pub fn is_uv_venv(prefix: &Path) -> bool {
+    match fs::read_to_string(prefix.join("pyvenv.cfg")) {
+        Ok(contents) => contents.lines().any(|line| {
+            line.split_once('=')
+                .map(|(key, value)| key.trim() == "uv" && !value.trim().is_empty())
+                .unwrap_or_default()
+        }),
+        Err(_) => false,
+    }
+}
+
+/// Python installed by uv, e.g. `~/.local/share/uv/python/cpython-3.12.4-linux-x86_64-gnu`.
+fn get_managed_python(
+    prefix: &Path,
+    executable: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    let version = prefix
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(get_version_from_key);
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Uv))
+            .source_locator(Some(LocatorKind::Uv.name()))
+            .executable(Some(executable))
+            .version(version.and_then(|v| v.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+/// Virtual environment created by uv, e.g. `uv venv`.
+fn get_uv_venv(
+    prefix: &Path,
+    executable: Option<PathBuf>,
+    manager: &Option<EnvManager>,
+) -> Option<PythonEnvironment> {
+    if !is_uv_venv(prefix) {
+        return None;
+    }
+    let executable = executable.or_else(|| find_executable(prefix))?;
+    Some(
+        PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Uv))
+            .source_locator(Some(LocatorKind::Uv.name()))
+            .executable(Some(executable))
+            .version(PyVenvCfg::find(prefix).and_then(|cfg| cfg.version.parse().ok()))
+            .prefix(Some(prefix.to_path_buf()))
+            .manager(manager.clone())
+            .symlinks(Some(find_executables(prefix)))
+            .build(),
+    )
+}
+
+pub struct Uv {
+    pub env_vars: EnvVariables,
+    manager: Arc<Mutex<Option<Option<EnvManager>>>>,
+    /// Time after which uv is killed when spawned (see `Configuration::timeout_ms`).
+    timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Uv {
+    pub fn from(environment: &dyn Environment) -> Uv {
+        Uv {
+            env_vars: EnvVariables::from(environment),
+            manager: Arc::new(Mutex::new(None)),
+            timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+    fn get_manager(&self) -> Option<EnvManager> {
+        let mut manager = self.manager.lock().unwrap();
+        if manager.is_none() {
+            let uv = find_uv_executable(&self.env_vars).map(|executable| {
+                let mut uv = EnvManager::new(executable, EnvManagerType::Uv, None);
+                uv.install_path = get_python_install_dir(&self.env_vars);
+                uv
+            });
+            manager.replace(uv);
+        }
+        manager.clone().flatten()
+    }
+}
+
+impl Locator for Uv {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Uv
+    }
+    fn description(&self) -> &'static str {
+        "Finds Python versions and environments managed by uv, reads UV_PYTHON_INSTALL_DIR and XDG_DATA_HOME"
+    }
+    fn configure(&self, config: &Configuration) {
+        *self.timeout.lock().unwrap() = config.timeout_ms.map(Duration::from_millis);
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Uv]
+    }
+    fn manages_executable(&self, exe: &Path) -> bool {
+        matches!(
+            exe.file_name().and_then(|f| f.to_str()),
+            Some("uv") | Some("uv.exe")
+        )
+    }
+
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        let prefix = env
+            .prefix
+            .clone()
+            .or_else(|| get_prefix_from_executable(&env.executable))?;
+        if is_uv_venv(&prefix) {
+            return get_uv_venv(&prefix, Some(env.executable.clone()), &self.get_manager());
+        }
+        if let Some(install_dir) = get_python_install_dir(&self.env_vars) {
+            if prefix.parent() == Some(install_dir.as_path()) {
+                return get_managed_python(
+                    &prefix,
+                    Some(env.executable.clone()),
+                    &self.get_manager(),
+                );
+            }
+        }
+        None
+    }
+
+    fn find(&self, reporter: &dyn Reporter) {
+        self.manager.lock().unwrap().take();
+        let manager = self.get_manager();
+        if let Some(manager) = &manager {
+            reporter.report_manager(manager);
+        }
+
+        // Python installed by uv, keyed by prefix.
+        let mut environments = BTreeMap::new();
+        if let Some(install_dir) = get_python_install_dir(&self.env_vars) {
+            for prefix in list_dirs(&install_dir) {
+                if let Some(env) = get_managed_python(&prefix, None, &manager) {
+                    environments.insert(prefix, env);
+                }
+            }
+        }
+        // uv knows of installations that we may not (e.g. installed with a different `UV_PYTHON_INSTALL_DIR`).
+        // It also lists Python installed by other means, uv names the directories of the ones it installs after the key.
+        if let Some(manager) = &manager {
+            for installation in
+                list_python_installations(&manager.executable, *self.timeout.lock().unwrap())
+            {
+                let executable = match &installation.path {
+                    Some(executable) => executable,
+                    None => continue,
+                };
+                let prefix = match get_prefix_from_executable(executable) {
+                    Some(prefix) => prefix,
+                    None => continue,
+                };
+                if prefix.file_name().and_then(|name| name.to_str()) != Some(&installation.key) {
+                    trace!("Ignoring Python not installed by uv {:?}", executable);
+                    continue;
+                }
+                if !environments.contains_key(&prefix) {
+                    if let Some(env) = get_managed_python(&prefix, None, &Some(manager.clone())) {
+                        environments.insert(prefix.clone(), env);
+                    }
+                }
+                if let Some(env) = environments.get_mut(&prefix) {
+                    if find_executables(&prefix).contains(executable) {
+                        env.version = installation.version.parse().ok();
+                    }
+                }
+            }
+        }
+        for env in environments.values() {
+            reporter.report_environment(env);
+        }
+
+        if let Some(envs_dir) = get_envs_dir(&self.env_vars) {
+            for prefix in list_dirs(&envs_dir) {
+                if let Some(env) = get_uv_venv(&prefix, None, &manager) {
+                    reporter.report_environment(&env);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::env_variables::EnvVariables;
+use log::trace;
+use std::{env, path::PathBuf};
+
+fn get_uv_exe_names() -> Vec<&'static str> {
+    if std::env::consts::OS == "windows" {
+        vec!["uv.exe", "uv"]
+    } else {
+        vec!["uv"]
+    }
+}
+
+/// Looks for the uv executable in `UV_HOME`, the PATH variable & the default install locations.
+pub fn find_uv_executable(env_vars: &EnvVariables) -> Option<PathBuf> {
+    let mut search_paths = vec![];
+    if let Some(uv_home) = &env_vars.uv_home {
+        search_paths.push(PathBuf::from(uv_home));
+        search_paths.push(PathBuf::from(uv_home).join("bin"));
+    }
+    if let Some(env_path) = &env_vars.path {
+        search_paths.extend(env::split_paths(env_path));
+    }
+    if let Some(home) = &env_vars.home {
+        // https://docs.astral.sh/uv/getting-started/installation/#standalone-installer
+        search_paths.push(home.join(".local").join("bin"));
+        search_paths.push(home.join(".cargo").join("bin"));
+    }
+    for dir in search_paths {
+        for name in get_uv_exe_names() {
+            let executable = dir.join(name);
+            if executable.is_file() {
+                return Some(executable);
+            }
+        }
+    }
+    trace!("uv exe not found");
+    None
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::{error, trace};
+use pet_process::run;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+/// Python installation as returned by `uv python list --output-format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UvPythonInstallation {
+    /// E.g. `cpython-3.12.4-linux-x86_64-gnu`, this is also the name of the directory uv installs Python into.
+    pub key: String,
+    pub version: String,
+    /// Executable, `None` if the version is available for download but not installed.
+    pub path: Option<PathBuf>,
+}
+
+pub fn parse_python_list(output: &str) -> Vec<UvPythonInstallation> {
+    match serde_json::from_str::<Vec<UvPythonInstallation>>(output) {
+        Ok(installations) => installations,
+        Err(err) => {
+            error!("Failed to parse output of uv python list: {:?}", err);
+            vec![]
+        }
+    }
+}
+
+/// Lists the Python installations by spawning `uv python list`.
+pub fn list_python_installations(
+    uv: &Path,
+    timeout: Option<Duration>,
+) -> Vec<UvPythonInstallation> {
+    let start = std::time::SystemTime::now();
+    let output = run(
+        Command::new(uv)
+            .arg("python")
+            .arg("list")
+            .arg("--output-format")
+            .arg("json"),
+        timeout,
+    );
+    trace!(
+        "Executed uv python list in {:?}",
+        start.elapsed().unwrap_or_default()
+    );
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_python_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            error!(
+                "Failed to list Python installations using uv {:?}: {:?}",
+                uv,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            vec![]
+        }
+        Err(err) => {
+            error!(
+                "Failed to list Python installations using uv {:?}: {:?}",
+                uv, err
+            );
+            vec![]
+        }
+    }
+}
+
+/// Gets the version from the name of the directory uv installs Python into.
+/// E.g. `3.12.4` from `cpython-3.12.4-linux-x86_64-gnu` or `3.13.0` from `cpython-3.13.0+freethreaded-macos-aarch64-none`.
+pub fn get_version_from_key(key: &str) -> Option<String> {
+    let version = key.split('-').nth(1)?;
+    let version = version.split('+').next()?;
+    if version.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
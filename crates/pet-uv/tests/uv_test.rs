@@ -0,0 +1,228 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[test]
+fn parse_versions_from_uv_python_list() {
+    use pet_uv::python_list::{parse_python_list, UvPythonInstallation};
+    use std::path::PathBuf;
+
+    let output = r#"[
+        {
+            "key": "cpython-3.13.0-linux-x86_64-gnu",
+            "version": "3.13.0",
+            "version_parts": { "major": 3, "minor": 13, "patch": 0 },
+            "path": null,
+            "symlink": null,
+            "url": "https://github.com/indygreg/python-build-standalone/releases/download/20241008/cpython-3.13.0%2B20241008-x86_64-unknown-linux-gnu-install_only_stripped.tar.gz",
+            "os": "linux",
+            "variant": "default",
+            "implementation": "cpython",
+            "arch": "x86_64",
+            "libc": "gnu"
+        },
+        {
+            "key": "cpython-3.12.4-linux-x86_64-gnu",
+            "version": "3.12.4",
+            "version_parts": { "major": 3, "minor": 12, "patch": 4 },
+            "path": "/home/user/.local/share/uv/python/cpython-3.12.4-linux-x86_64-gnu/bin/python3.12",
+            "symlink": null,
+            "url": null,
+            "os": "linux",
+            "variant": "default",
+            "implementation": "cpython",
+            "arch": "x86_64",
+            "libc": "gnu"
+        }
+    ]"#;
+
+    assert_eq!(
+        parse_python_list(output),
+        vec![
+            UvPythonInstallation {
+                key: "cpython-3.13.0-linux-x86_64-gnu".to_string(),
+                version: "3.13.0".to_string(),
+                path: None,
+            },
+            UvPythonInstallation {
+                key: "cpython-3.12.4-linux-x86_64-gnu".to_string(),
+                version: "3.12.4".to_string(),
+                path: Some(PathBuf::from(
+                    "/home/user/.local/share/uv/python/cpython-3.12.4-linux-x86_64-gnu/bin/python3.12"
+                )),
+            },
+        ]
+    );
+    assert!(parse_python_list("error: unexpected argument").is_empty());
+}
+
+#[test]
+fn parse_version_from_install_dir_name() {
+    use pet_uv::python_list::get_version_from_key;
+
+    assert_eq!(
+        get_version_from_key("cpython-3.12.4-linux-x86_64-gnu"),
+        Some("3.12.4".to_string())
+    );
+    assert_eq!(
+        get_version_from_key("pypy-3.10.14-windows-x86_64-none"),
+        Some("3.10.14".to_string())
+    );
+    assert_eq!(
+        get_version_from_key("cpython-3.13.0+freethreaded-macos-aarch64-none"),
+        Some("3.13.0".to_string())
+    );
+    assert_eq!(get_version_from_key(".lock"), None);
+    assert_eq!(get_version_from_key("cpython-latest"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn find_uv_environments() {
+    use pet_core::{
+        python_environment::{PythonEnvironment, PythonEnvironmentKind},
+        Locator,
+    };
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use pet_uv::Uv;
+    use std::collections::HashMap;
+
+    let home = resolve_test_path!(&["unix", "user_home"]);
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()));
+    let locator = Uv::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let result = reporter.to_locator_result();
+    // uv is not installed, hence environments are found without spawning uv.
+    assert!(result.managers.is_empty());
+    let install = home
+        .join(".local")
+        .join("share")
+        .join("uv")
+        .join("python")
+        .join("cpython-3.12.4-linux-x86_64-gnu");
+    let venv = home.join(".uv").join("envs").join("tools");
+    let mut environments = result.environments;
+    environments.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+    assert_eq!(
+        environments,
+        vec![
+            PythonEnvironment {
+                kind: Some(PythonEnvironmentKind::Uv),
+                executable: Some(install.join("bin").join("python")),
+                version: "3.12.4".parse().ok(),
+                prefix: Some(install.clone()),
+                symlinks: Some(vec![
+                    install.join("bin").join("python"),
+                    install.join("bin").join("python3"),
+                    install.join("bin").join("python3.12"),
+                ]),
+                ..Default::default()
+            },
+            PythonEnvironment {
+                kind: Some(PythonEnvironmentKind::Uv),
+                executable: Some(venv.join("bin").join("python")),
+                version: "3.12.4".parse().ok(),
+                prefix: Some(venv.clone()),
+                symlinks: Some(vec![venv.join("bin").join("python")]),
+                ..Default::default()
+            },
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn find_python_in_uv_python_install_dir() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_reporter::collect;
+    use pet_uv::Uv;
+    use std::collections::HashMap;
+
+    let install_dir = resolve_test_path!(&["unix", "python_install_dir"]);
+    let environment = create_test_environment(
+        HashMap::from([(
+            "UV_PYTHON_INSTALL_DIR".to_string(),
+            install_dir.to_str().unwrap().to_string(),
+        )]),
+        None,
+    );
+    let locator = Uv::from(&environment);
+    let reporter = collect::create_reporter();
+
+    locator.find(&reporter);
+
+    let prefix = install_dir.join("pypy-3.10.14-linux-x86_64-gnu");
+    let environments = reporter.to_locator_result().environments;
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].kind, Some(PythonEnvironmentKind::Uv));
+    assert_eq!(environments[0].prefix, Some(prefix.clone()));
+    assert_eq!(environments[0].version, "3.10.14".parse().ok());
+
+    let env = locator
+        .try_from(&PythonEnv::new(
+            prefix.join("bin").join("python"),
+            None,
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.prefix, Some(prefix.clone()));
+    assert_eq!(env.version, "3.10.14".parse().ok());
+}
+
+#[cfg(unix)]
+#[test]
+fn identify_venvs_created_by_uv() {
+    use pet_core::{env::PythonEnv, python_environment::PythonEnvironmentKind, Locator};
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_uv::{is_uv_venv, Uv};
+    use std::collections::HashMap;
+
+    let environment = create_test_environment(HashMap::new(), None);
+    let locator = Uv::from(&environment);
+    let venv = resolve_test_path!(&["unix", "project", ".venv"]);
+    let not_uv = resolve_test_path!(&["unix", "user_home", ".uv", "envs", "not_uv"]);
+
+    assert!(is_uv_venv(&venv));
+    assert!(!is_uv_venv(&not_uv));
+
+    let env = locator
+        .try_from(&PythonEnv::new(
+            venv.join("bin").join("python"),
+            Some(venv.clone()),
+            None,
+        ))
+        .unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Uv));
+    assert_eq!(env.executable, Some(venv.join("bin").join("python")));
+    assert_eq!(env.version, "3.13.0".parse().ok());
+    assert!(locator
+        .try_from(&PythonEnv::new(
+            not_uv.join("bin").join("python"),
+            None,
+            None
+        ))
+        .is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn find_uv_executable_in_uv_home() {
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_uv::{env_variables::EnvVariables, manager::find_uv_executable};
+    use std::collections::HashMap;
+
+    let uv_home = resolve_test_path!(&["unix", "uv_home"]);
+    let environment = create_test_environment(
+        HashMap::from([("UV_HOME".to_string(), uv_home.to_str().unwrap().to_string())]),
+        None,
+    );
+
+    assert_eq!(
+        find_uv_executable(&EnvVariables::from(&environment)),
+        Some(uv_home.join("bin").join("uv"))
+    );
+}
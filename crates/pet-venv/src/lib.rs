@@ -5,7 +5,9 @@ use std::path::Path;
 
 use pet_core::{
     env::PythonEnv,
-    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    python_environment::{
+        PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind, PythonImplementation,
+    },
     pyvenv_cfg::PyVenvCfg,
     reporter::Reporter,
     Locator, LocatorKind,
@@ -42,6 +44,12 @@ impl Locator for Venv {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::Venv
     }
+    fn description(&self) -> &'static str {
+        "Identifies virtual environments created by the venv module (containing a pyvenv.cfg)"
+    }
+    fn priority(&self) -> u8 {
+        200
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::Venv]
     }
@@ -63,10 +71,16 @@ impl Locator for Venv {
             if let Some(ref prefix) = prefix {
                 symlinks.append(&mut find_executables(prefix));
             }
+            let python_implementation = prefix
+                .as_ref()
+                .and_then(|prefix| PyVenvCfg::find(prefix)?.implementation)
+                .map(|name| PythonImplementation::from_name(&name));
             Some(
                 PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::Venv))
+                    .source_locator(Some(LocatorKind::Venv.name()))
                     .executable(Some(env.executable.clone()))
-                    .version(version)
+                    .version(version.and_then(|v| v.parse().ok()))
+                    .python_implementation(python_implementation)
                     .prefix(prefix)
                     .symlinks(Some(symlinks))
                     .build(),
@@ -5,7 +5,10 @@ use std::path::Path;
 
 use pet_core::{
     env::PythonEnv,
-    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    python_environment::{
+        PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind, PythonImplementation,
+    },
+    pyvenv_cfg::PyVenvCfg,
     reporter::Reporter,
     Locator, LocatorKind,
 };
@@ -89,6 +92,12 @@ impl Locator for VirtualEnv {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::VirtualEnv
     }
+    fn description(&self) -> &'static str {
+        "Identifies virtual environments created by virtualenv (containing activation scripts)"
+    }
+    fn priority(&self) -> u8 {
+        200
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::VirtualEnv]
     }
@@ -106,10 +115,17 @@ impl Locator for VirtualEnv {
             if let Some(ref prefix) = env.prefix {
                 symlinks.append(&mut find_executables(prefix));
             }
+            let python_implementation = env
+                .prefix
+                .as_ref()
+                .and_then(|prefix| PyVenvCfg::find(prefix)?.implementation)
+                .map(|name| PythonImplementation::from_name(&name));
             Some(
                 PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::VirtualEnv))
+                    .source_locator(Some(LocatorKind::VirtualEnv.name()))
                     .executable(Some(env.executable.clone()))
-                    .version(version)
+                    .version(version.and_then(|v| v.parse().ok()))
+                    .python_implementation(python_implementation)
                     .prefix(env.prefix.clone())
                     .symlinks(Some(symlinks))
                     .build(),
@@ -33,6 +33,9 @@ impl Locator for VirtualEnvWrapper {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::VirtualEnvWrapper
     }
+    fn description(&self) -> &'static str {
+        "Finds environments created by virtualenvwrapper, reads WORKON_HOME (defaults to ~/.virtualenvs)"
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::VirtualEnvWrapper]
     }
@@ -55,8 +58,9 @@ impl Locator for VirtualEnvWrapper {
 
         Some(
             PythonEnvironmentBuilder::new(Some(PythonEnvironmentKind::VirtualEnvWrapper))
+                .source_locator(Some(LocatorKind::VirtualEnvWrapper.name()))
                 .executable(Some(env.executable.clone()))
-                .version(version)
+                .version(version.and_then(|v| v.parse().ok()))
                 .prefix(env.prefix.clone())
                 .project(get_project(env))
                 .symlinks(Some(symlinks))
@@ -14,6 +14,7 @@ use pet_virtualenv::is_virtualenv;
 use std::sync::{Arc, Mutex};
 
 mod environments;
+pub mod registry;
 
 pub struct WindowsRegistry {
     #[allow(dead_code)]
@@ -52,6 +53,12 @@ impl Locator for WindowsRegistry {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::WindowsRegistry
     }
+    fn description(&self) -> &'static str {
+        "Finds Python & conda installs registered in the Windows Registry (HKLM & HKCU)"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![
             PythonEnvironmentKind::WindowsRegistry,
@@ -93,6 +100,11 @@ impl Locator for WindowsRegistry {
         self.clear();
         let _ = self.find_with_cache(Some(reporter));
     }
+    #[cfg(windows)]
+    fn refresh(&self) -> LocatorResult {
+        self.clear();
+        self.find_with_cache(None).unwrap_or_default()
+    }
     #[cfg(unix)]
     fn find(&self, _reporter: &dyn Reporter) {
         //
@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::path::PathBuf;
+
+/// Read access to the Windows registry, this allows the registry to be mocked in tests.
+pub trait RegistryReader {
+    /// Names of the sub keys of the key, empty if the key does not exist.
+    fn enum_keys(&self, path: &str) -> Vec<String>;
+    /// Value of the key, use an empty name for the default value.
+    fn get_value(&self, path: &str, name: &str) -> Option<String>;
+}
+
+/// Python registered as per PEP 514, `Software\Python\<Company>\<Tag>`.
+/// See https://peps.python.org/pep-0514/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryPython {
+    pub company: String,
+    pub tag: String,
+    pub install_path: PathBuf,
+    pub executable: PathBuf,
+    pub version: Option<String>,
+    pub architecture: Option<String>,
+    pub display_name: Option<String>,
+}
+
+fn get_non_empty_value(reader: &dyn RegistryReader, path: &str, name: &str) -> Option<String> {
+    reader
+        .get_value(path, name)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Enumerates all of the Python installs registered under `Software\Python`.
+pub fn get_registered_pythons(reader: &dyn RegistryReader) -> Vec<RegistryPython> {
+    let mut pythons = vec![];
+    for company in reader.enum_keys("Software\\Python") {
+        let company_path = format!("Software\\Python\\{}", company);
+        for tag in reader.enum_keys(&company_path) {
+            let tag_path = format!("{}\\{}", company_path, tag);
+            let install_path_key = format!("{}\\InstallPath", tag_path);
+            let install_path = match get_non_empty_value(reader, &install_path_key, "") {
+                Some(install_path) => PathBuf::from(install_path),
+                None => continue,
+            };
+            // Older versions of the official installer (< 3.5) do not register the executable.
+            let executable = get_non_empty_value(reader, &install_path_key, "ExecutablePath")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| install_path.join("python.exe"));
+            pythons.push(RegistryPython {
+                company: company.clone(),
+                tag: tag.clone(),
+                install_path,
+                executable,
+                version: get_non_empty_value(reader, &tag_path, "Version"),
+                architecture: get_non_empty_value(reader, &tag_path, "SysArchitecture"),
+                display_name: get_non_empty_value(reader, &tag_path, "DisplayName"),
+            });
+        }
+    }
+    pythons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Registry keys & their values, the sub keys are derived from the paths of the keys.
+    struct MockRegistry {
+        keys: HashMap<String, HashMap<String, String>>,
+    }
+
+    impl MockRegistry {
+        fn new(keys: &[(&str, &[(&str, &str)])]) -> Self {
+            MockRegistry {
+                keys: keys
+                    .iter()
+                    .map(|(path, values)| {
+                        (
+                            path.to_string(),
+                            values
+                                .iter()
+                                .map(|(name, value)| (name.to_string(), value.to_string()))
+                                .collect(),
+                        )
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl RegistryReader for MockRegistry {
+        fn enum_keys(&self, path: &str) -> Vec<String> {
+            let prefix = format!("{}\\", path);
+            let mut keys = self
+                .keys
+                .keys()
+                .filter_map(|key| key.strip_prefix(&prefix))
+                .filter_map(|key| key.split('\\').next())
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            keys
+        }
+        fn get_value(&self, path: &str, name: &str) -> Option<String> {
+            self.keys.get(path)?.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn enumerates_registered_pythons() {
+        let registry = MockRegistry::new(&[
+            (
+                "Software\\Python\\PythonCore\\3.12",
+                &[
+                    ("DisplayName", "Python 3.12 (64-bit)"),
+                    ("SysArchitecture", "64bit"),
+                    ("Version", "3.12.4"),
+                ],
+            ),
+            (
+                "Software\\Python\\PythonCore\\3.12\\InstallPath",
+                &[
+                    ("", "C:\\Python312"),
+                    ("ExecutablePath", "C:\\Python312\\python.exe"),
+                ],
+            ),
+            // Older installers do not register the executable.
+            (
+                "Software\\Python\\PythonCore\\2.7\\InstallPath",
+                &[("", "C:\\Python27\\")],
+            ),
+            // Without an install path there is nothing to report.
+            (
+                "Software\\Python\\ContinuumAnalytics\\Anaconda39-64",
+                &[("Version", "3.9.7")],
+            ),
+        ]);
+
+        assert_eq!(
+            get_registered_pythons(&registry),
+            vec![
+                RegistryPython {
+                    company: "PythonCore".to_string(),
+                    tag: "2.7".to_string(),
+                    install_path: PathBuf::from("C:\\Python27\\"),
+                    executable: PathBuf::from("C:\\Python27\\").join("python.exe"),
+                    version: None,
+                    architecture: None,
+                    display_name: None,
+                },
+                RegistryPython {
+                    company: "PythonCore".to_string(),
+                    tag: "3.12".to_string(),
+                    install_path: PathBuf::from("C:\\Python312"),
+                    executable: PathBuf::from("C:\\Python312\\python.exe"),
+                    version: Some("3.12.4".to_string()),
+                    architecture: Some("64bit".to_string()),
+                    display_name: Some("Python 3.12 (64-bit)".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_registered_pythons() {
+        let registry = MockRegistry::new(&[]);
+
+        assert!(get_registered_pythons(&registry).is_empty());
+    }
+}
@@ -9,12 +9,14 @@ use std::path::PathBuf;
 // Lets be explicit, this way we never miss a value (in Windows or Unix).
 pub struct EnvVariables {
     pub home: Option<PathBuf>,
+    pub localappdata: Option<String>,
 }
 
 impl EnvVariables {
     pub fn from(env: &dyn Environment) -> Self {
         EnvVariables {
             home: env.get_user_home(),
+            localappdata: env.get_env_var("LOCALAPPDATA".to_string()),
         }
     }
 }
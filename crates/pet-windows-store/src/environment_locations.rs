@@ -1,20 +1,85 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-#[cfg(windows)]
 use crate::env_variables::EnvVariables;
-#[cfg(windows)]
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
-#[cfg(windows)]
+/// Package family names of Store Python are of the form `PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0`.
+const PACKAGE_NAME_PREFIX: &str = "PythonSoftwareFoundation.Python.";
+
+fn get_local_app_data(environment: &EnvVariables) -> Option<PathBuf> {
+    if let Some(localappdata) = &environment.localappdata {
+        return Some(PathBuf::from(localappdata));
+    }
+    Some(environment.home.clone()?.join("AppData").join("Local"))
+}
+
+/// Directory containing the app execution aliases, e.g. `%LOCALAPPDATA%\Microsoft\WindowsApps\python3.12.exe`.
 pub fn get_search_locations(environment: &EnvVariables) -> Option<PathBuf> {
     Some(
-        environment
-            .home
-            .clone()?
-            .join("AppData")
-            .join("Local")
+        get_local_app_data(environment)?
             .join("Microsoft")
             .join("WindowsApps"),
     )
 }
+
+/// Directory containing the data of the installed Store packages.
+pub fn get_packages_dir(environment: &EnvVariables) -> Option<PathBuf> {
+    Some(get_local_app_data(environment)?.join("Packages"))
+}
+
+/// Gets the major & minor version from the package family name.
+/// E.g. `PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0` returns `3.12`.
+pub fn get_version_from_package_name(name: &str) -> Option<String> {
+    let (version, _publisher_id) = name.strip_prefix(PACKAGE_NAME_PREFIX)?.split_once('_')?;
+    let mut parts = version.split('.');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(major), Some(minor), None)
+            if !major.is_empty()
+                && !minor.is_empty()
+                && major
+                    .chars()
+                    .chain(minor.chars())
+                    .all(|c| c.is_ascii_digit()) =>
+        {
+            Some(version.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorePackage {
+    /// Package family name, e.g. `PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0`.
+    pub name: String,
+    /// Major & minor version, e.g. `3.12`.
+    pub version: String,
+    pub path: PathBuf,
+}
+
+/// Lists the Python packages installed from the Microsoft Store, sorted by name.
+pub fn list_store_packages(environment: &EnvVariables) -> Vec<StorePackage> {
+    let packages_dir = match get_packages_dir(environment) {
+        Some(dir) => dir,
+        None => return vec![],
+    };
+    let mut packages = match fs::read_dir(packages_dir) {
+        Ok(reader) => reader
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?.to_string();
+                let version = get_version_from_package_name(&name)?;
+                Some(StorePackage {
+                    name,
+                    version,
+                    path,
+                })
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => vec![],
+    };
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages
+}
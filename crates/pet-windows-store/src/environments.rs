@@ -10,12 +10,10 @@ use log::warn;
 #[cfg(windows)]
 use pet_core::python_environment::PythonEnvironment;
 #[cfg(windows)]
-use pet_core::{arch::Architecture, python_environment::PythonEnvironmentBuilder};
+use pet_core::{arch::Architecture, python_environment::PythonEnvironmentBuilder, LocatorKind};
 #[cfg(windows)]
 use pet_fs::path::norm_case;
 #[cfg(windows)]
-use pet_python_utils::executable::find_executables;
-#[cfg(windows)]
 use regex::Regex;
 use std::path::PathBuf;
 #[cfg(windows)]
@@ -60,6 +58,7 @@ impl PotentialPython {
                 PythonEnvironmentBuilder::new(Some(
                     pet_core::python_environment::PythonEnvironmentKind::WindowsStore,
                 ))
+                .source_locator(Some(LocatorKind::WindowsStore.name()))
                 .display_name(Some(result.display_name))
                 .executable(Some(exe.clone()))
                 .prefix(Some(env_path.clone()))
@@ -209,7 +208,8 @@ fn find_symlinks(exe_in_windows_app_path: PathBuf, version: String) -> Vec<PathB
             // However if we have just one Python exe of the form Python3.x.ex, then python.exe and Python3.exe are symlinks.
             let mut number_of_python_exes_with_versions = 0;
             let mut exes = vec![];
-            find_executables(windows_app_path)
+            // The app execution aliases are not returned by `find_executables`, hence list them here.
+            list_app_execution_aliases(windows_app_path)
                 .into_iter()
                 .for_each(|exe| {
                     if let Some(name) = exe.file_name().and_then(|s| s.to_str()) {
@@ -228,6 +228,20 @@ fn find_symlinks(exe_in_windows_app_path: PathBuf, version: String) -> Vec<PathB
     symlinks
 }
 
+#[cfg(windows)]
+fn list_app_execution_aliases(windows_app_path: &std::path::Path) -> Vec<PathBuf> {
+    use crate::is_app_execution_alias;
+
+    match std::fs::read_dir(windows_app_path) {
+        Ok(reader) => reader
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| is_app_execution_alias(p))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
 #[cfg(windows)]
 #[derive(Debug)]
 struct StorePythonInfo {
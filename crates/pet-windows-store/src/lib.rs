@@ -1,8 +1,8 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-mod env_variables;
-mod environment_locations;
+pub mod env_variables;
+pub mod environment_locations;
 mod environments;
 
 use crate::env_variables::EnvVariables;
@@ -13,6 +13,7 @@ use pet_core::python_environment::{PythonEnvironment, PythonEnvironmentKind};
 use pet_core::reporter::Reporter;
 use pet_core::LocatorKind;
 use pet_core::{os_environment::Environment, Locator};
+use pet_python_utils::executable::is_windows_apps_dir;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
@@ -21,6 +22,32 @@ pub fn is_windows_app_folder_in_program_files(path: &Path) -> bool {
         .starts_with(":\\program files\\windowsapps")
 }
 
+/// App execution aliases such as `%LOCALAPPDATA%\Microsoft\WindowsApps\python3.12.exe` are reparse points
+/// (not real executables) that launch the Python installed from the Microsoft Store.
+pub fn is_app_execution_alias(path: &Path) -> bool {
+    let name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name.to_lowercase(),
+        None => return false,
+    };
+    if !name.starts_with("python") || !name.ends_with(".exe") {
+        return false;
+    }
+    match path.parent() {
+        Some(dir) if is_windows_apps_dir(dir) => true,
+        // E.g. `WindowsApps\PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0\python.exe`.
+        Some(dir) => {
+            dir.file_name()
+                .map(|name| {
+                    name.to_string_lossy()
+                        .starts_with("PythonSoftwareFoundation.Python.")
+                })
+                .unwrap_or_default()
+                && dir.parent().map(is_windows_apps_dir).unwrap_or_default()
+        }
+        None => false,
+    }
+}
+
 pub struct WindowsStore {
     pub env_vars: EnvVariables,
     #[allow(dead_code)]
@@ -55,6 +82,12 @@ impl Locator for WindowsStore {
     fn get_kind(&self) -> LocatorKind {
         LocatorKind::WindowsStore
     }
+    fn description(&self) -> &'static str {
+        "Finds Python installed from the Windows Store in %LOCALAPPDATA%\\Microsoft\\WindowsApps"
+    }
+    fn priority(&self) -> u8 {
+        5
+    }
     fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
         vec![PythonEnvironmentKind::WindowsStore]
     }
@@ -63,6 +96,7 @@ impl Locator for WindowsStore {
     fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
         use std::path::PathBuf;
 
+        use environment_locations::list_store_packages;
         use pet_core::python_environment::PythonEnvironmentBuilder;
         use pet_virtualenv::is_virtualenv;
 
@@ -77,7 +111,7 @@ impl Locator for WindowsStore {
             .chain(env.symlinks.clone().unwrap_or_default())
             .collect::<Vec<PathBuf>>();
         if let Some(environments) = self.find_with_cache() {
-            for found_env in environments {
+            for found_env in &environments {
                 if let Some(symlinks) = &found_env.symlinks {
                     // Check if we have found this exe.
                     if list_of_possible_exes
@@ -97,6 +131,18 @@ impl Locator for WindowsStore {
                     }
                 }
             }
+            // The aliases `python.exe` & `python3.exe` launch the Store Python,
+            // when there's just one installed, we know which one without spawning the alias.
+            if is_app_execution_alias(&env.executable)
+                && environments.len() == 1
+                && list_store_packages(&self.env_vars).len() == 1
+            {
+                let mut symlinks = environments[0].symlinks.clone().unwrap_or_default();
+                symlinks.extend(list_of_possible_exes);
+                let builder = PythonEnvironmentBuilder::from_environment(environments[0].clone())
+                    .symlinks(Some(symlinks));
+                return Some(builder.build());
+            }
         }
         None
     }
@@ -116,6 +162,15 @@ impl Locator for WindowsStore {
         }
     }
 
+    #[cfg(windows)]
+    fn refresh(&self) -> pet_core::LocatorResult {
+        self.clear();
+        pet_core::LocatorResult {
+            environments: self.find_with_cache().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
     #[cfg(unix)]
     fn find(&self, _reporter: &dyn Reporter) {
         //
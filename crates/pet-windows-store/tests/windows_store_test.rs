@@ -0,0 +1,76 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[test]
+fn version_from_package_name() {
+    use pet_windows_store::environment_locations::get_version_from_package_name;
+
+    assert_eq!(
+        get_version_from_package_name("PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0"),
+        Some("3.12".to_string())
+    );
+    assert_eq!(
+        get_version_from_package_name("PythonSoftwareFoundation.Python.3.9_qbz5n2kfra8p0"),
+        Some("3.9".to_string())
+    );
+    assert_eq!(
+        get_version_from_package_name("PythonSoftwareFoundation.PythonManager_qbz5n2kfra8p0"),
+        None
+    );
+    assert_eq!(
+        get_version_from_package_name("Microsoft.WindowsTerminal_8wekyb3d8bbwe"),
+        None
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn list_installed_store_packages() {
+    use pet_python_utils::{resolve_test_path, test_utils::create_test_environment};
+    use pet_windows_store::{
+        env_variables::EnvVariables, environment_locations::list_store_packages,
+    };
+    use std::collections::HashMap;
+
+    let home = resolve_test_path!(&["unix", "user_home"]);
+    let environment = create_test_environment(HashMap::new(), Some(home.clone()));
+    let packages_dir = home.join("AppData").join("Local").join("Packages");
+
+    let packages = list_store_packages(&EnvVariables::from(&environment))
+        .into_iter()
+        .map(|p| (p.version, p.path))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        packages,
+        vec![
+            (
+                "3.10".to_string(),
+                packages_dir.join("PythonSoftwareFoundation.Python.3.10_qbz5n2kfra8p0")
+            ),
+            (
+                "3.12".to_string(),
+                packages_dir.join("PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn detect_app_execution_aliases() {
+    use pet_windows_store::is_app_execution_alias;
+    use std::path::Path;
+
+    let windows_apps = Path::new("C:/Users/user/AppData/Local/Microsoft/WindowsApps");
+    assert!(is_app_execution_alias(&windows_apps.join("python.exe")));
+    assert!(is_app_execution_alias(&windows_apps.join("python3.12.exe")));
+    assert!(is_app_execution_alias(
+        &windows_apps
+            .join("PythonSoftwareFoundation.Python.3.12_qbz5n2kfra8p0")
+            .join("python.exe")
+    ));
+    assert!(!is_app_execution_alias(&windows_apps.join("winget.exe")));
+    assert!(!is_app_execution_alias(Path::new(
+        "C:/Users/user/AppData/Local/Programs/Python/Python312/python.exe"
+    )));
+}
@@ -1,33 +1,79 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use log::{trace, warn};
+use log::{debug, info, trace, warn};
 use pet_conda::utils::is_conda_env;
+use pet_core::cancellation::CancellationToken;
 use pet_core::env::PythonEnv;
+use pet_core::manager::EnvManager;
 use pet_core::os_environment::Environment;
-use pet_core::python_environment::PythonEnvironmentKind;
+use pet_core::python_environment::{get_environment_key, PythonEnvironment, PythonEnvironmentKind};
 use pet_core::reporter::Reporter;
-use pet_core::{Configuration, Locator, LocatorKind};
+use pet_core::telemetry::TelemetryEvent;
+use pet_core::{Configuration, Locator, LocatorKind, DEFAULT_MAX_SEARCH_DEPTH};
 use pet_env_var_path::get_search_paths_from_env_variables;
+use pet_fs::path::norm_case;
 use pet_global_virtualenvs::list_global_virtual_envs_paths;
 use pet_python_utils::executable::{
-    find_executable, find_executables, should_search_for_environments_in_path,
+    find_executable, is_stub_executable, should_search_for_environments_in_path,
+    try_find_executables,
 };
+use pet_python_utils::packages::scan_site_packages;
+use pet_reporter::dedup::DeduplicatingReporter;
+use pet_reporter::json::FindSummary;
 use pet_virtualenv::is_virtualenv_dir;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::time::Duration;
 use std::{sync::Arc, thread};
 
-use crate::locators::identify_python_environment_using_locators;
+use crate::locators::{filter_locators, identify_python_environment_using_locators};
+use crate::thread_pool::ThreadPool;
 
 pub struct Summary {
     pub total: Duration,
     pub locators: BTreeMap<LocatorKind, Duration>,
     pub breakdown: BTreeMap<&'static str, Duration>,
+    /// Paths that could not be searched as the user does not have permissions to read them.
+    pub skipped_paths: Vec<PathBuf>,
+    /// Number of distinct environments reported.
+    pub total_environments: usize,
+    /// Number of distinct managers reported.
+    pub total_managers: usize,
+    /// Number of distinct environments reported, by kind (`Unknown` if the kind is not known).
+    pub environments_by_kind: BTreeMap<String, usize>,
+    /// Number of distinct managers reported, by tool.
+    pub managers_by_tool: BTreeMap<String, usize>,
+    /// Time (since the start of the search) at which each environment & manager was reported.
+    #[cfg(feature = "telemetry")]
+    pub report_timings: Vec<Duration>,
+}
+
+impl From<&Summary> for FindSummary {
+    fn from(summary: &Summary) -> Self {
+        FindSummary {
+            time: summary.total.as_millis(),
+            locators: summary
+                .locators
+                .iter()
+                .map(|(kind, duration)| (format!("{kind:?}"), duration.as_millis()))
+                .collect(),
+            breakdown: summary
+                .breakdown
+                .iter()
+                .map(|(step, duration)| (step.to_string(), duration.as_millis()))
+                .collect(),
+            skipped_paths: summary.skipped_paths.clone(),
+            total_environments: summary.total_environments,
+            total_managers: summary.total_managers,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -45,18 +91,72 @@ pub fn find_and_report_envs(
     locators: &Arc<Vec<Arc<dyn Locator>>>,
     environment: &dyn Environment,
     search_scope: Option<SearchScope>,
+    cancellation_token: Option<CancellationToken>,
 ) -> Arc<Mutex<Summary>> {
     let summary = Arc::new(Mutex::new(Summary {
         total: Duration::from_secs(0),
         locators: BTreeMap::new(),
         breakdown: BTreeMap::new(),
+        skipped_paths: vec![],
+        total_environments: 0,
+        total_managers: 0,
+        environments_by_kind: BTreeMap::new(),
+        managers_by_tool: BTreeMap::new(),
+        #[cfg(feature = "telemetry")]
+        report_timings: vec![],
     }));
     let start = std::time::Instant::now();
+    // Validation phase, check the configuration & prepare what is to be searched.
+    for err in configuration.validate() {
+        warn!("Invalid configuration, {}", err);
+    }
+    let packages_reporter =
+        PackagesReporter::new(reporter, configuration.scan_packages.unwrap_or_default());
+    // The same interpreter can be found via different paths, e.g. a symlink & its target in PATH.
+    let deduplicating_reporter = DeduplicatingReporter::new(&packages_reporter);
+    let counting_reporter = CountingReporter::new(&deduplicating_reporter);
+    let reporter: &dyn Reporter = &counting_reporter;
+    #[cfg(feature = "telemetry")]
+    let telemetry_reporter = pet_reporter::telemetry::TelemetryReporter::new(reporter);
+    #[cfg(feature = "telemetry")]
+    let reporter: &dyn Reporter = &telemetry_reporter;
+    // Executables that could not be identified by any of the locators in this search.
+    // The same executables are found in multiple places (e.g. /usr/bin/python3 in PATH & global locations),
+    // no point trying to identify them again (identifying an unknown exe is expensive, as we spawn Python).
+    let unknown_executables = &Mutex::new(HashSet::new());
+    let cancellation_token = cancellation_token.as_ref();
+    let filtered_locators;
+    let locators = match &configuration.kind_filter {
+        Some(kind_filter) => {
+            filtered_locators = filter_locators(locators, kind_filter);
+            trace!(
+                "Searching using locators {:?} (kind filter {:?})",
+                filtered_locators
+                    .iter()
+                    .map(|l| l.get_kind())
+                    .collect::<Vec<_>>(),
+                kind_filter
+            );
+            &filtered_locators
+        }
+        None => locators,
+    };
 
     // From settings
     let environment_directories = configuration.environment_directories.unwrap_or_default();
     let workspace_directories = configuration.workspace_directories.unwrap_or_default();
     let executables = configuration.executables.unwrap_or_default();
+    let follow_symlinks = configuration.follow_symlinks.unwrap_or_default();
+    let max_search_depth = configuration
+        .max_search_depth
+        .unwrap_or(DEFAULT_MAX_SEARCH_DEPTH);
+    let exclude_paths: Vec<PathBuf> = configuration
+        .exclude_paths
+        .unwrap_or_default()
+        .into_iter()
+        .map(norm_case)
+        .collect();
+    let thread_pool = &ThreadPool::new(configuration.max_threads);
     let search_global = match search_scope {
         Some(SearchScope::Global(_)) => true,
         Some(SearchScope::Workspace) => false,
@@ -66,29 +166,52 @@ pub fn find_and_report_envs(
         Some(SearchScope::Global(kind)) => Some(kind),
         _ => None,
     };
+    // Global locations that can contain virtual envs.
+    let global_virtual_envs_paths = if search_global {
+        list_global_virtual_envs_paths(
+            environment.get_env_var("WORKON_HOME".into()),
+            environment.get_env_var("XDG_DATA_HOME".into()),
+            environment.get_user_home(),
+            1,
+        )
+    } else {
+        vec![]
+    };
+    let validation_time = start.elapsed();
 
+    // Search phase.
+    let search_start = std::time::Instant::now();
     thread::scope(|s| {
         // 1. Find using known global locators.
         s.spawn(|| {
             // Find in all the finders
             let start = std::time::Instant::now();
             if search_global {
-                thread::scope(|s| {
-                    for locator in locators.iter() {
-                        if let Some(kind) = &search_kind {
-                            if !locator.supported_categories().contains(kind) {
-                                trace!(
-                                    "Skipping locator: {:?} as it does not support {:?} (required by refresh command)",
-                                    locator.get_kind(),
-                                    kind
-                                );
-                                continue;
-                            }
+                let locators = locators
+                    .iter()
+                    .filter(|locator| match &search_kind {
+                        Some(kind) if !locator.supported_categories().contains(kind) => {
+                            trace!(
+                                "Skipping locator: {:?} as it does not support {:?} (required by refresh command)",
+                                locator.get_kind(),
+                                kind
+                            );
+                            false
                         }
-
+                        _ => true,
+                    })
+                    .collect::<Vec<_>>();
+                let total = locators.len();
+                let completed = AtomicUsize::new(0);
+                let completed = &completed;
+                thread::scope(|s| {
+                    for locator in locators {
                         let locator = locator.clone();
                         let summary = summary.clone();
-                        s.spawn(move || {
+                        thread_pool.spawn(s, move || {
+                            if is_cancelled(cancellation_token) {
+                                return;
+                            }
                             let start = std::time::Instant::now();
                             trace!("Searching using locator: {:?}", locator.get_kind());
                             locator.find(reporter);
@@ -102,6 +225,10 @@ pub fn find_and_report_envs(
                                 .unwrap()
                                 .locators
                                 .insert(locator.get_kind(), start.elapsed());
+                            // Report while holding the lock, to ensure progress is reported in increasing order.
+                            let _summary = summary.lock().unwrap();
+                            let completed = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                            reporter.report_progress(completed, Some(total));
                         });
                     }
                 });
@@ -123,13 +250,17 @@ pub fn find_and_report_envs(
                     "Searching for environments in global folders: {:?}",
                     global_env_search_paths
                 );
-                find_python_environments(
+                let skipped_paths = find_python_environments(
                     global_env_search_paths.clone(),
                     reporter,
                     locators,
                     false,
                     &global_env_search_paths,
+                    unknown_executables,
+                    thread_pool,
+                    cancellation_token,
                 );
+                summary.lock().unwrap().skipped_paths.extend(skipped_paths);
             }
             summary
                 .lock()
@@ -138,10 +269,13 @@ pub fn find_and_report_envs(
                 .insert("Path", start.elapsed());
         });
         // Step 3: Search in some global locations for virtual envs.
-        let environment_directories_search = environment_directories.clone();
-        s.spawn(|| {
-            let start = std::time::Instant::now();
-            if search_global {
+        // No point spawning a thread if there are no such locations (e.g. in server mode).
+        if search_global
+            && (!environment_directories.is_empty() || !global_virtual_envs_paths.is_empty())
+        {
+            let environment_directories_search = environment_directories.clone();
+            s.spawn(|| {
+                let start = std::time::Instant::now();
                 let mut possible_environments = vec![];
 
                 // These are directories that contain environments, hence enumerate these directories.
@@ -157,15 +291,8 @@ pub fn find_and_report_envs(
                     }
                 }
 
-                let search_paths: Vec<PathBuf> = [
-                    list_global_virtual_envs_paths(
-                        environment.get_env_var("WORKON_HOME".into()),
-                        environment.get_env_var("XDG_DATA_HOME".into()),
-                        environment.get_user_home(),
-                    ),
-                    possible_environments,
-                ]
-                .concat();
+                let search_paths: Vec<PathBuf> =
+                    [global_virtual_envs_paths.clone(), possible_environments].concat();
                 let global_env_search_paths: Vec<PathBuf> =
                     get_search_paths_from_env_variables(environment);
 
@@ -174,20 +301,23 @@ pub fn find_and_report_envs(
                     search_paths
                 );
 
-                find_python_environments(
+                let skipped_paths = find_python_environments(
                     search_paths,
                     reporter,
                     locators,
                     false,
                     &global_env_search_paths,
+                    unknown_executables,
+                    thread_pool,
+                    cancellation_token,
                 );
-            }
-            summary
-                .lock()
-                .unwrap()
-                .breakdown
-                .insert("GlobalVirtualEnvs", start.elapsed());
-        });
+                let mut summary = summary.lock().unwrap();
+                summary.skipped_paths.extend(skipped_paths);
+                summary
+                    .breakdown
+                    .insert("GlobalVirtualEnvs", start.elapsed());
+            });
+        }
         // Step 4: Find in workspace folders too.
         // This can be merged with step 2 as well, as we're only look for environments
         // in some folders.
@@ -195,66 +325,216 @@ pub fn find_and_report_envs(
         // This list of folders generally map to workspace folders
         // & users can have a lot of workspace folders and can have a large number fo files/directories
         // that could the discovery.
-        s.spawn(|| {
-            let start = std::time::Instant::now();
-            thread::scope(|s| {
-                // Find environments in the workspace folders.
-                if !workspace_directories.is_empty() {
-                    trace!(
-                        "Searching for environments in workspace folders: {:?}",
-                        workspace_directories
-                    );
-                    let global_env_search_paths: Vec<PathBuf> =
-                        get_search_paths_from_env_variables(environment);
-                    for workspace_folder in workspace_directories {
-                        let global_env_search_paths = global_env_search_paths.clone();
-                        let environment_directories = environment_directories.clone();
-                        s.spawn(move || {
-                            find_python_environments_in_workspace_folder_recursive(
-                                &workspace_folder,
-                                reporter,
-                                locators,
-                                &global_env_search_paths,
-                                &environment_directories,
-                            );
-                        });
+        // No point spawning a thread if there are no workspace folders nor executables (e.g. in server mode).
+        if !workspace_directories.is_empty() || !executables.is_empty() {
+            s.spawn(|| {
+                let start = std::time::Instant::now();
+                thread::scope(|s| {
+                    // Find environments in the workspace folders.
+                    if !workspace_directories.is_empty() {
+                        trace!(
+                            "Searching for environments in workspace folders: {:?}",
+                            workspace_directories
+                        );
+                        let global_env_search_paths: Vec<PathBuf> =
+                            get_search_paths_from_env_variables(environment);
+                        for workspace_folder in workspace_directories {
+                            let global_env_search_paths = global_env_search_paths.clone();
+                            let environment_directories = environment_directories.clone();
+                            let exclude_paths = exclude_paths.clone();
+                            thread_pool.spawn(s, move || {
+                                find_python_environments_in_workspace_folder_recursive(
+                                    &workspace_folder,
+                                    reporter,
+                                    locators,
+                                    &global_env_search_paths,
+                                    unknown_executables,
+                                    &environment_directories,
+                                    follow_symlinks,
+                                    &exclude_paths,
+                                    max_search_depth,
+                                    cancellation_token,
+                                );
+                            });
+                        }
                     }
-                }
-                // Find the python exes provided.
-                if !executables.is_empty() {
-                    trace!("Searching for environment executables: {:?}", executables);
-                    let global_env_search_paths: Vec<PathBuf> =
-                        get_search_paths_from_env_variables(environment);
-                    identify_python_executables_using_locators(
-                        executables,
-                        locators,
-                        reporter,
-                        &global_env_search_paths,
-                    );
-                }
-            });
+                    // Find the python exes provided.
+                    if !executables.is_empty() {
+                        trace!("Searching for environment executables: {:?}", executables);
+                        let global_env_search_paths: Vec<PathBuf> =
+                            get_search_paths_from_env_variables(environment);
+                        identify_python_executables_using_locators(
+                            executables,
+                            locators,
+                            reporter,
+                            &global_env_search_paths,
+                            unknown_executables,
+                        );
+                    }
+                });
 
-            summary
-                .lock()
-                .unwrap()
-                .breakdown
-                .insert("Workspaces", start.elapsed());
-        });
+                summary
+                    .lock()
+                    .unwrap()
+                    .breakdown
+                    .insert("Workspaces", start.elapsed());
+            });
+        }
     });
-    summary.lock().unwrap().total = start.elapsed();
+    let search_time = search_start.elapsed();
+    {
+        let mut summary = summary.lock().unwrap();
+        summary.total = start.elapsed();
+        let environments = counting_reporter.environments.lock().unwrap();
+        let managers = counting_reporter.managers.lock().unwrap();
+        summary.total_environments = environments.len();
+        summary.total_managers = managers.len();
+        for kind in environments.values() {
+            *summary
+                .environments_by_kind
+                .entry(kind.clone())
+                .or_default() += 1;
+        }
+        for tool in managers.values() {
+            *summary.managers_by_tool.entry(tool.clone()).or_default() += 1;
+        }
+        #[cfg(feature = "telemetry")]
+        {
+            summary.report_timings = telemetry_reporter.timing_histogram();
+        }
+        // Machine readable, consumed by the telemetry pipeline of the clients.
+        info!(
+            "{}",
+            json!({
+                "event": "find_complete",
+                "validation_ms": validation_time.as_millis() as u64,
+                "search_ms": search_time.as_millis() as u64,
+                "total_envs": summary.total_environments,
+                "total_managers": summary.total_managers,
+            })
+        );
+    }
 
     summary
 }
 
+/// Keeps track of the distinct environments & managers reported (along with their kind & tool).
+struct CountingReporter<'a> {
+    reporter: &'a dyn Reporter,
+    environments: Mutex<HashMap<PathBuf, String>>,
+    managers: Mutex<HashMap<PathBuf, String>>,
+}
+
+impl<'a> CountingReporter<'a> {
+    fn new(reporter: &'a dyn Reporter) -> Self {
+        CountingReporter {
+            reporter,
+            environments: Mutex::new(HashMap::new()),
+            managers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Reporter for CountingReporter<'_> {
+    fn report_telemetry(&self, event: &TelemetryEvent) {
+        self.reporter.report_telemetry(event);
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.managers
+            .lock()
+            .unwrap()
+            .insert(manager.executable.clone(), format!("{:?}", manager.tool));
+        self.reporter.report_manager(manager);
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.reporter.report_progress(completed, total);
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if let Some(key) = get_environment_key(env) {
+            let kind = env
+                .kind
+                .map(|kind| format!("{kind:?}"))
+                .unwrap_or_else(|| "Unknown".to_string());
+            self.environments.lock().unwrap().insert(key, kind);
+        }
+        self.reporter.report_environment(env);
+    }
+}
+
+/// Lists the packages installed in the environments reported (when enabled via `Configuration::scan_packages`).
+struct PackagesReporter<'a> {
+    reporter: &'a dyn Reporter,
+    scan_packages: bool,
+}
+
+impl<'a> PackagesReporter<'a> {
+    fn new(reporter: &'a dyn Reporter, scan_packages: bool) -> Self {
+        PackagesReporter {
+            reporter,
+            scan_packages,
+        }
+    }
+}
+
+impl Reporter for PackagesReporter<'_> {
+    fn report_telemetry(&self, event: &TelemetryEvent) {
+        self.reporter.report_telemetry(event);
+    }
+    fn report_manager(&self, manager: &EnvManager) {
+        self.reporter.report_manager(manager);
+    }
+    fn report_manager_updated(&self, old: &EnvManager, new: &EnvManager) {
+        self.reporter.report_manager_updated(old, new);
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.reporter.report_progress(completed, total);
+    }
+    fn report_error(&self, message: &str) {
+        self.reporter.report_error(message);
+    }
+    fn report_removed_environment(&self, env: &PythonEnvironment) {
+        self.reporter.report_removed_environment(env);
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if !self.scan_packages || env.packages.is_some() {
+            self.reporter.report_environment(env);
+            return;
+        }
+        let mut env = env.clone();
+        env.packages = Some(scan_site_packages(&env));
+        self.reporter.report_environment(&env);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn find_python_environments_in_workspace_folder_recursive(
     workspace_folder: &PathBuf,
     reporter: &dyn Reporter,
     locators: &Arc<Vec<Arc<dyn Locator>>>,
     global_env_search_paths: &[PathBuf],
+    unknown_executables: &Mutex<HashSet<PathBuf>>,
     environment_directories: &[PathBuf],
+    follow_symlinks: bool,
+    exclude_paths: &[PathBuf],
+    max_depth: u32,
+    cancellation_token: Option<&CancellationToken>,
 ) {
+    let is_excluded = |path: &PathBuf| exclude_paths.iter().any(|p| path.starts_with(p));
+    if is_excluded(workspace_folder) {
+        trace!("Ignoring excluded workspace folder: {:?}", workspace_folder);
+        return;
+    }
     // When searching in a directory, give preference to some paths.
-    let paths_to_search_first = vec![
+    let paths_to_search_first: Vec<PathBuf> = vec![
         // Possible this is a virtual env
         workspace_folder.to_path_buf(),
         // Optimize for finding these first.
@@ -262,7 +542,10 @@ pub fn find_python_environments_in_workspace_folder_recursive(
         workspace_folder.join(".conda"),
         workspace_folder.join(".virtualenv"),
         workspace_folder.join("venv"),
-    ];
+    ]
+    .into_iter()
+    .filter(|p| !is_excluded(p))
+    .collect();
 
     // Possible this is an environment.
     find_python_environments_in_paths_with_locators(
@@ -271,16 +554,23 @@ pub fn find_python_environments_in_workspace_folder_recursive(
         reporter,
         true,
         global_env_search_paths,
+        unknown_executables,
+        cancellation_token,
     );
 
     // If this is a virtual env folder, no need to scan this.
-    if is_virtualenv_dir(workspace_folder) || is_conda_env(workspace_folder) {
+    if is_virtualenv_dir(workspace_folder) || is_conda_env(workspace_folder) || max_depth == 0 {
         return;
     }
     if let Ok(reader) = fs::read_dir(workspace_folder) {
         for folder in reader
             .filter_map(Result::ok)
-            .filter(|d| d.file_type().is_ok_and(|f| f.is_dir()))
+            .filter(|d| match d.file_type() {
+                // The file type of a directory entry is that of the symlink itself, not its target.
+                Ok(f) if f.is_symlink() => follow_symlinks && d.path().is_dir(),
+                Ok(f) => f.is_dir(),
+                Err(_) => false,
+            })
             .map(|p| p.path())
             .filter(|p| {
                 // If this directory is a sub directory or is in the environment_directories, then do not search in this directory.
@@ -290,39 +580,83 @@ pub fn find_python_environments_in_workspace_folder_recursive(
                 if environment_directories.iter().any(|d| p.starts_with(d)) {
                     return true;
                 }
-                should_search_for_environments_in_path(p)
+                should_search_for_environments_in_path(p, exclude_paths)
             })
             .filter(|p| !paths_to_search_first.contains(p))
         {
-            find_python_environments(vec![folder], reporter, locators, true, &[]);
+            if is_cancelled(cancellation_token) {
+                return;
+            }
+            if max_depth > 1 {
+                find_python_environments_in_workspace_folder_recursive(
+                    &folder,
+                    reporter,
+                    locators,
+                    global_env_search_paths,
+                    unknown_executables,
+                    environment_directories,
+                    follow_symlinks,
+                    exclude_paths,
+                    max_depth - 1,
+                    cancellation_token,
+                );
+                continue;
+            }
+            // No need to spawn a thread for a single directory (each workspace folder is searched in its own thread).
+            find_python_environments_in_paths_with_locators(
+                vec![folder],
+                locators,
+                reporter,
+                true,
+                &[],
+                unknown_executables,
+                cancellation_token,
+            );
         }
     }
 }
 
+/// Returns the paths that were skipped as the user does not have permissions to read them.
+#[allow(clippy::too_many_arguments)]
 fn find_python_environments(
     paths: Vec<PathBuf>,
     reporter: &dyn Reporter,
     locators: &Arc<Vec<Arc<dyn Locator>>>,
     is_workspace_folder: bool,
     global_env_search_paths: &[PathBuf],
-) {
+    unknown_executables: &Mutex<HashSet<PathBuf>>,
+    thread_pool: &ThreadPool,
+    cancellation_token: Option<&CancellationToken>,
+) -> Vec<PathBuf> {
     if paths.is_empty() {
-        return;
+        return vec![];
     }
+    // Each path is searched in a separate thread, hence remove duplicates (e.g. duplicate entries in PATH) upfront.
+    let mut searched_paths = HashSet::new();
+    let paths = paths
+        .into_iter()
+        .filter(|p| searched_paths.insert(fs::canonicalize(p).unwrap_or(p.clone())))
+        .collect::<Vec<PathBuf>>();
+    let skipped_paths = Mutex::new(vec![]);
     thread::scope(|s| {
         for item in paths {
             let locators = locators.clone();
-            s.spawn(move || {
-                find_python_environments_in_paths_with_locators(
+            let skipped_paths = &skipped_paths;
+            thread_pool.spawn(s, move || {
+                let skipped = find_python_environments_in_paths_with_locators(
                     vec![item],
                     &locators,
                     reporter,
                     is_workspace_folder,
                     global_env_search_paths,
+                    unknown_executables,
+                    cancellation_token,
                 );
+                skipped_paths.lock().unwrap().extend(skipped);
             });
         }
     });
+    skipped_paths.into_inner().unwrap()
 }
 
 fn find_python_environments_in_paths_with_locators(
@@ -331,7 +665,10 @@ fn find_python_environments_in_paths_with_locators(
     reporter: &dyn Reporter,
     is_workspace_folder: bool,
     global_env_search_paths: &[PathBuf],
-) {
+    unknown_executables: &Mutex<HashSet<PathBuf>>,
+    cancellation_token: Option<&CancellationToken>,
+) -> Vec<PathBuf> {
+    let mut skipped_paths = vec![];
     for path in paths {
         let executables = if is_workspace_folder {
             // If we're in a workspace folder, then we only need to look for bin/python or bin/python.exe
@@ -349,7 +686,25 @@ fn find_python_environments_in_paths_with_locators(
         } else {
             // Paths like /Library/Frameworks/Python.framework/Versions/3.10/bin can end up in the current PATH variable.
             // Hence do not just look for files in a bin directory of the path.
-            find_executables(path)
+            let executables = match try_find_executables(&path) {
+                Ok(executables) => executables,
+                Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+                    // Expected for some system directories, hence not a warning.
+                    debug!("Permission denied searching for executables in {:?}", path);
+                    skipped_paths.push(path);
+                    continue;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    // Stale entries in the PATH variable are common.
+                    trace!("Search path {:?} does not exist", path);
+                    continue;
+                }
+                Err(err) => {
+                    warn!("Failed to search for executables in {:?}: {}", path, err);
+                    continue;
+                }
+            };
+            executables
                 .into_iter()
                 .filter(|p| {
                     // Exclude python2 on macOS
@@ -360,14 +715,37 @@ fn find_python_environments_in_paths_with_locators(
                 })
                 .collect::<Vec<PathBuf>>()
         };
+        // Never spawn scripts masquerading as Python, they only result in spurious errors.
+        let executables = executables
+            .into_iter()
+            .filter(|exe| {
+                if is_stub_executable(exe) {
+                    trace!("Ignoring stub executable {:?}", exe);
+                    return false;
+                }
+                true
+            })
+            .collect::<Vec<PathBuf>>();
 
-        identify_python_executables_using_locators(
-            executables,
-            locators,
-            reporter,
-            global_env_search_paths,
-        );
+        // Identifying an executable could result in spawning it, hence check for cancellation before each one.
+        for executable in executables {
+            if is_cancelled(cancellation_token) {
+                return skipped_paths;
+            }
+            identify_python_executables_using_locators(
+                vec![executable],
+                locators,
+                reporter,
+                global_env_search_paths,
+                unknown_executables,
+            );
+        }
     }
+    skipped_paths
+}
+
+fn is_cancelled(cancellation_token: Option<&CancellationToken>) -> bool {
+    cancellation_token.is_some_and(|token| token.is_cancelled())
 }
 
 pub fn identify_python_executables_using_locators(
@@ -375,9 +753,14 @@ pub fn identify_python_executables_using_locators(
     locators: &Arc<Vec<Arc<dyn Locator>>>,
     reporter: &dyn Reporter,
     global_env_search_paths: &[PathBuf],
+    unknown_executables: &Mutex<HashSet<PathBuf>>,
 ) {
     for exe in executables.into_iter() {
         let executable = exe.clone();
+        if unknown_executables.lock().unwrap().contains(&executable) {
+            warn!("Unknown Python Env {:?}", executable);
+            continue;
+        }
         let env = PythonEnv::new(exe.to_owned(), None, None);
         if let Some(env) =
             identify_python_environment_using_locators(&env, locators, global_env_search_paths)
@@ -389,6 +772,7 @@ pub fn identify_python_executables_using_locators(
             continue;
         } else {
             warn!("Unknown Python Env {:?}", executable);
+            unknown_executables.lock().unwrap().insert(executable);
         }
     }
 }
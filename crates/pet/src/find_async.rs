@@ -0,0 +1,40 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_core::blocking::spawn_blocking;
+use pet_core::cancellation::CancellationToken;
+use pet_core::os_environment::Environment;
+use pet_core::reporter::Reporter;
+use pet_core::{Configuration, Locator};
+use std::sync::{Arc, Mutex};
+
+use crate::find::{find_and_report_envs, SearchScope, Summary};
+
+/// Async variant of `find_and_report_envs`, for use when embedding pet as a library.
+/// The search is run on a separate thread (the search is blocking), hence the future does not block the executor.
+/// The future is runtime agnostic, i.e. it can be awaited from any executor (tokio, async-std, etc).
+pub async fn find_and_report_envs_async(
+    reporter: Arc<dyn Reporter>,
+    configuration: Configuration,
+    locators: Arc<Vec<Arc<dyn Locator>>>,
+    environment: Arc<dyn Environment>,
+    search_scope: Option<SearchScope>,
+    cancellation_token: Option<CancellationToken>,
+) -> Arc<Mutex<Summary>> {
+    // The search itself stops when cancelled, hence the task is not cancelled (the summary is always returned).
+    spawn_blocking(
+        move || {
+            find_and_report_envs(
+                reporter.as_ref(),
+                configuration,
+                &locators,
+                environment.as_ref(),
+                search_scope,
+                cancellation_token,
+            )
+        },
+        None,
+    )
+    .await
+    .expect("search without a cancellation token always completes")
+}
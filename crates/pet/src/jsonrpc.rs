@@ -5,38 +5,56 @@ use crate::find::find_and_report_envs;
 use crate::find::find_python_environments_in_workspace_folder_recursive;
 use crate::find::identify_python_executables_using_locators;
 use crate::find::SearchScope;
+use crate::find::Summary;
 use crate::locators::create_locators;
+use crate::locators::validate_environment;
 use lazy_static::lazy_static;
-use log::{error, info, trace};
+use log::{error, info, trace, warn};
+#[cfg(feature = "watch")]
+use pet::resolve::find_env_by_prefix;
 use pet::resolve::resolve_environment;
+#[cfg(feature = "watch")]
+use pet::watch::{FileSystemWatcher, WatchEvent};
 use pet_conda::Conda;
 use pet_conda::CondaLocator;
+use pet_core::manager::EnvManager;
 use pet_core::python_environment::PythonEnvironment;
+#[cfg(feature = "watch")]
+use pet_core::python_environment::PythonEnvironmentBuilder;
 use pet_core::python_environment::PythonEnvironmentKind;
 use pet_core::telemetry::refresh_performance::RefreshPerformance;
 use pet_core::telemetry::TelemetryEvent;
 use pet_core::{
+    cancellation::CancellationToken,
     os_environment::{Environment, EnvironmentApi},
     reporter::Reporter,
-    Configuration, Locator,
+    Configuration, Locator, DEFAULT_MAX_SEARCH_DEPTH,
 };
 use pet_env_var_path::get_search_paths_from_env_variables;
+use pet_fs::path::norm_case;
 use pet_jsonrpc::{
-    send_error, send_reply,
+    send_error, send_message, send_reply,
     server::{start_server, HandlersKeyedByMethodName},
+    REQUEST_CANCELLED, REQUEST_TIMEOUT,
 };
 use pet_poetry::Poetry;
 use pet_poetry::PoetryLocator;
+use pet_poetry::TelemetryResult;
 use pet_python_utils::cache::clear_cache;
+use pet_python_utils::cache::get_cache_stats;
+use pet_python_utils::cache::remove as remove_cache_entry;
 use pet_python_utils::cache::set_cache_directory;
+use pet_python_utils::cache::start_background_flush;
+use pet_python_utils::packages::scan_site_packages;
 use pet_reporter::collect;
-use pet_reporter::{cache::CacheReporter, jsonrpc};
+use pet_reporter::{cache::CacheReporter, jsonrpc, progress::ProgressReporter};
 use pet_telemetry::report_inaccuracies_identified_after_resolving;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_json::{self, Value};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::Mutex;
 use std::time::Duration;
 use std::{
@@ -54,10 +72,48 @@ lazy_static! {
 
 pub struct Context {
     configuration: RwLock<Configuration>,
-    locators: Arc<Vec<Arc<dyn Locator>>>,
-    conda_locator: Arc<Conda>,
+    /// Replaced when `configure` provides a conda executable (see `Conda::with_executable`).
+    locators: RwLock<Arc<Vec<Arc<dyn Locator>>>>,
+    conda_locator: RwLock<Arc<Conda>>,
     poetry_locator: Arc<Poetry>,
     os_environment: Arc<dyn Environment>,
+    /// Managers found in previous refreshes, used to detect managers that have been upgraded.
+    known_managers: Arc<Mutex<HashMap<PathBuf, EnvManager>>>,
+    /// Environments found in previous refreshes, used to detect environments that have been deleted.
+    known_environments: Arc<Mutex<HashMap<PathBuf, PythonEnvironment>>>,
+    /// Environments resolved in previous resolve requests, keyed by the executable (& its symlinks).
+    resolved_environments: Mutex<HashMap<PathBuf, PythonEnvironment>>,
+    /// Tokens of the pending requests that can be cancelled, keyed by the request id.
+    cancellation_tokens: Mutex<HashMap<u32, CancellationToken>>,
+    /// Statistics of the last refresh.
+    summary: Mutex<Option<PerLocatorSummary>>,
+    /// Watches the workspace & environment directories for changes (when enabled via `configure`).
+    #[cfg(feature = "watch")]
+    watcher: Arc<Mutex<Option<FileSystemWatcher>>>,
+}
+
+impl Context {
+    fn locators(&self) -> Arc<Vec<Arc<dyn Locator>>> {
+        self.locators.read().unwrap().clone()
+    }
+    fn conda_locator(&self) -> Arc<Conda> {
+        self.conda_locator.read().unwrap().clone()
+    }
+    /// Creates the locators again, with a conda locator that uses the given executable
+    /// (see `Conda::with_executable`), else looks for one on PATH.
+    fn use_conda_executable(&self, conda_executable: Option<PathBuf>) {
+        let mut conda_locator = self.conda_locator.write().unwrap();
+        let environment = self.os_environment.as_ref();
+        *conda_locator = Arc::new(match conda_executable {
+            Some(exe) => Conda::with_executable(exe, environment),
+            None => Conda::from(environment),
+        });
+        *self.locators.write().unwrap() = create_locators(
+            conda_locator.clone(),
+            self.poetry_locator.clone(),
+            environment,
+        );
+    }
 }
 
 static MISSING_ENVS_REPORTED: AtomicBool = AtomicBool::new(false);
@@ -71,61 +127,91 @@ pub fn start_jsonrpc_server() {
     let conda_locator = Arc::new(Conda::from(&environment));
     let poetry_locator = Arc::new(Poetry::from(&environment));
     let context = Context {
-        locators: create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
-        conda_locator,
+        locators: RwLock::new(create_locators(
+            conda_locator.clone(),
+            poetry_locator.clone(),
+            &environment,
+        )),
+        conda_locator: RwLock::new(conda_locator),
         poetry_locator,
         configuration: RwLock::new(Configuration::default()),
         os_environment: Arc::new(environment),
+        known_managers: Arc::new(Mutex::new(HashMap::new())),
+        known_environments: Arc::new(Mutex::new(HashMap::new())),
+        resolved_environments: Mutex::new(HashMap::new()),
+        cancellation_tokens: Mutex::new(HashMap::new()),
+        summary: Mutex::new(None),
+        #[cfg(feature = "watch")]
+        watcher: Arc::new(Mutex::new(None)),
     };
 
     let mut handlers = HandlersKeyedByMethodName::new(Arc::new(context));
+    handlers.add_request_handler("initialize", handle_initialize);
     handlers.add_request_handler("configure", handle_configure);
     handlers.add_request_handler("refresh", handle_refresh);
     handlers.add_request_handler("resolve", handle_resolve);
     handlers.add_request_handler("find", handle_find);
     handlers.add_request_handler("condaInfo", handle_conda_telemetry);
     handlers.add_request_handler("clear", handle_clear_cache);
+    handlers.add_request_handler("getSummary", handle_get_summary);
+    handlers.add_notification_handler("$/cancelRequest", handle_cancel_request);
+
+    // Ensure stale cache entries do not accumulate in long running sessions.
+    let _cache_flush = start_background_flush(Duration::from_secs(5 * 60));
     start_server(&handlers)
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfigureOptions {
-    /// These are paths like workspace folders, where we can look for environments.
-    pub workspace_directories: Option<Vec<PathBuf>>,
-    pub conda_executable: Option<PathBuf>,
-    pub poetry_executable: Option<PathBuf>,
-    /// Custom locations where environments can be found. Generally global locations where virtualenvs & the like can be found.
-    /// Workspace directories should not be included into this list.
-    pub environment_directories: Option<Vec<PathBuf>>,
-    /// Directory to cache the Python environment details.
-    pub cache_directory: Option<PathBuf>,
+    /// The configuration replaces the existing configuration (except for the cache directory, which cannot be changed once set).
+    #[serde(flatten)]
+    pub configuration: Configuration,
+    /// Whether to search for environments once the locators have been configured.
+    #[serde(default)]
+    pub refresh: bool,
+    /// Whether to watch the workspace & environment directories and notify the client of changes to environments.
+    #[cfg(feature = "watch")]
+    #[serde(default)]
+    pub watch_file_system: bool,
 }
 
 pub fn handle_configure(context: Arc<Context>, id: u32, params: Value) {
     match serde_json::from_value::<ConfigureOptions>(params.clone()) {
         Ok(configure_options) => {
-            // Start in a new thread, we can have multiple requests.
-            thread::spawn(move || {
-                let mut cfg = context.configuration.write().unwrap();
-                cfg.workspace_directories = configure_options.workspace_directories;
-                cfg.conda_executable = configure_options.conda_executable;
-                cfg.environment_directories = configure_options.environment_directories;
-                cfg.poetry_executable = configure_options.poetry_executable;
-                // We will not support changing the cache directories once set.
-                // No point, supporting such a use case.
-                if let Some(cache_directory) = configure_options.cache_directory {
-                    set_cache_directory(cache_directory.clone());
-                    cfg.cache_directory = Some(cache_directory);
-                }
-                trace!("Configuring locators: {:?}", cfg);
-                drop(cfg);
-                let config = context.configuration.read().unwrap().clone();
-                for locator in context.locators.iter() {
-                    locator.configure(&config);
-                }
-                send_reply(id, None::<()>);
-            });
+            // Configure synchronously, so that requests received after this use the new configuration.
+            let mut cfg = context.configuration.write().unwrap();
+            let cache_directory = cfg.cache_directory.take();
+            let conda_executable = cfg.conda_executable.take();
+            *cfg = configure_options.configuration;
+            // We will not support changing the cache directories once set.
+            // No point, supporting such a use case.
+            match cfg.cache_directory.clone() {
+                Some(cache_directory) => set_cache_directory(cache_directory),
+                None => cfg.cache_directory = cache_directory,
+            }
+            for err in cfg.validate() {
+                warn!("Invalid configuration, {}", err);
+            }
+            trace!("Configuring locators: {:?}", cfg);
+            drop(cfg);
+            let config = context.configuration.read().unwrap().clone();
+            if config.conda_executable != conda_executable {
+                context.use_conda_executable(config.conda_executable.clone());
+            }
+            for locator in context.locators().iter() {
+                locator.configure(&config);
+            }
+            #[cfg(feature = "watch")]
+            watch_file_system(&context, &config, configure_options.watch_file_system);
+            send_reply(id, None::<()>);
+            if configure_options.refresh {
+                let refresh_options = RefreshOptions {
+                    search_kind: None,
+                    search_paths: None,
+                };
+                thread::spawn(move || refresh_environments(context, refresh_options, None));
+            }
         }
         Err(e) => {
             send_reply(id, None::<u128>);
@@ -134,6 +220,93 @@ pub fn handle_configure(context: Arc<Context>, id: u32, params: Value) {
     }
 }
 
+/// Starts watching the workspace & environment directories, replacing (stopping) the previous watcher.
+#[cfg(feature = "watch")]
+fn watch_file_system(context: &Arc<Context>, config: &Configuration, enabled: bool) {
+    let mut watcher = context.watcher.lock().unwrap();
+    watcher.take();
+    if !enabled {
+        return;
+    }
+    let directories = config
+        .workspace_directories
+        .iter()
+        .flatten()
+        .chain(config.environment_directories.iter().flatten())
+        .cloned()
+        .collect();
+    let locators = context.locators();
+    let known_environments = context.known_environments.clone();
+    let mut reported_environments: HashMap<PathBuf, PythonEnvironment> = HashMap::new();
+    let reporter = jsonrpc::create_reporter(None);
+    let exclude_paths = config.exclude_paths.clone().unwrap_or_default();
+    *watcher = Some(FileSystemWatcher::start(
+        directories,
+        exclude_paths,
+        move |event| {
+            match event {
+                WatchEvent::Changed(prefix) => {
+                    if let Some(env) = find_env_by_prefix(&prefix, &locators, None) {
+                        reported_environments.insert(prefix, env.clone());
+                        send_message("pet/environmentChanged", Some(env));
+                    }
+                }
+                WatchEvent::Removed(prefix) => {
+                    // Environments that existed before we started watching are only known from previous refreshes.
+                    let env = reported_environments.remove(&prefix).or_else(|| {
+                        known_environments
+                            .lock()
+                            .unwrap()
+                            .values()
+                            .find(|env| env.prefix.as_ref() == Some(&prefix))
+                            .cloned()
+                    });
+                    let env = env.unwrap_or_else(|| {
+                        PythonEnvironmentBuilder::new(None)
+                            .prefix(Some(prefix))
+                            .build()
+                    });
+                    reporter.report_removed_environment(&env);
+                }
+            }
+        },
+    ));
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    /// Kinds of environments that can be found on this platform.
+    pub environment_kinds: Vec<PythonEnvironmentKind>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResult {
+    pub version: String,
+    pub capabilities: ServerCapabilities,
+}
+
+pub fn handle_initialize(context: Arc<Context>, id: u32, _params: Value) {
+    let mut environment_kinds = vec![];
+    for kind in context
+        .locators()
+        .iter()
+        .flat_map(|locator| locator.supported_categories())
+    {
+        if !environment_kinds.contains(&kind) {
+            environment_kinds.push(kind);
+        }
+    }
+    send_reply(
+        id,
+        Some(InitializeResult {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            capabilities: ServerCapabilities { environment_kinds },
+        }),
+    );
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RefreshOptions {
@@ -150,6 +323,12 @@ pub struct RefreshResult {
     duration: u128,
 }
 
+/// Sent once the search for environments missed by the first refresh has completed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RefreshComplete {
+    poetry: Option<TelemetryResult>,
+}
+
 impl RefreshResult {
     pub fn new(duration: Duration) -> RefreshResult {
         RefreshResult {
@@ -158,6 +337,45 @@ impl RefreshResult {
     }
 }
 
+/// Statistics of the last refresh, returned by the `getSummary` request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerLocatorSummary {
+    /// Number of managers found, by tool.
+    pub managers: BTreeMap<String, usize>,
+    /// Number of environments found, by kind.
+    pub environments: BTreeMap<String, usize>,
+    /// Time taken by each locator (in milliseconds).
+    pub timings: BTreeMap<String, u128>,
+    /// Description of each locator, keyed by the name of the locator.
+    pub locators: BTreeMap<String, String>,
+    /// Statistics of the cache, `None` if a cache directory has not been configured.
+    pub cache_stats: Option<CacheStatsSummary>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatsSummary {
+    pub entry_count: usize,
+    pub total_bytes: u64,
+}
+
+impl From<&Summary> for PerLocatorSummary {
+    fn from(summary: &Summary) -> Self {
+        PerLocatorSummary {
+            managers: summary.managers_by_tool.clone(),
+            environments: summary.environments_by_kind.clone(),
+            timings: summary
+                .locators
+                .iter()
+                .map(|(kind, duration)| (format!("{kind:?}"), duration.as_millis()))
+                .collect(),
+            locators: BTreeMap::new(),
+            cache_stats: None,
+        }
+    }
+}
+
 pub fn handle_refresh(context: Arc<Context>, id: u32, params: Value) {
     let params = match params {
         Value::Null => json!({}),
@@ -171,135 +389,7 @@ pub fn handle_refresh(context: Arc<Context>, id: u32, params: Value) {
                 search_paths: None,
             });
             // Start in a new thread, we can have multiple requests.
-            thread::spawn(move || {
-                // Ensure we can have only one refresh at a time.
-                let lock = REFRESH_LOCK.lock().unwrap();
-
-                let mut config = context.configuration.read().unwrap().clone();
-                let reporter = Arc::new(CacheReporter::new(Arc::new(jsonrpc::create_reporter(
-                    refresh_options.search_kind,
-                ))));
-
-                let mut search_scope = None;
-
-                // If search kind is provided and no search_paths, then we will only search in the global locations.
-                if refresh_options.search_kind.is_some() || refresh_options.search_paths.is_some() {
-                    // Always clear this, as we will either serach in specified folder or a specific kind in global locations.
-                    config.workspace_directories = None;
-                    if let Some(search_paths) = refresh_options.search_paths {
-                        // These workspace folders are only for this refresh.
-                        config.workspace_directories = Some(
-                            search_paths
-                                .iter()
-                                .filter(|p| p.is_dir())
-                                .cloned()
-                                .collect(),
-                        );
-                        config.executables = Some(
-                            search_paths
-                                .iter()
-                                .filter(|p| p.is_file())
-                                .cloned()
-                                .collect(),
-                        );
-                        search_scope = Some(SearchScope::Workspace);
-                    } else if let Some(search_kind) = refresh_options.search_kind {
-                        config.executables = None;
-                        search_scope = Some(SearchScope::Global(search_kind));
-                    }
-
-                    // Configure the locators with the modified config.
-                    for locator in context.locators.iter() {
-                        locator.configure(&config);
-                    }
-                } else {
-                    // Re-configure the locators with an un-modified config.
-                    // Possible we congirued the locators with a modified config in the in the previous request.
-                    // & the config was scoped to a particular search folder, executables or kind.
-                    for locator in context.locators.iter() {
-                        locator.configure(&config);
-                    }
-                }
-
-                trace!("Start refreshing environments, config: {:?}", config);
-                let summary = find_and_report_envs(
-                    reporter.as_ref(),
-                    config,
-                    &context.locators,
-                    context.os_environment.deref(),
-                    search_scope,
-                );
-                let summary = summary.lock().unwrap();
-                for locator in summary.locators.iter() {
-                    info!("Locator {:?} took {:?}", locator.0, locator.1);
-                }
-                for item in summary.breakdown.iter() {
-                    info!("Locator {} took {:?}", item.0, item.1);
-                }
-                trace!("Finished refreshing environments in {:?}", summary.total);
-                send_reply(id, Some(RefreshResult::new(summary.total)));
-
-                let perf = RefreshPerformance {
-                    total: summary.total.as_millis(),
-                    locators: summary
-                        .locators
-                        .clone()
-                        .iter()
-                        .map(|(k, v)| (format!("{:?}", k), v.as_millis()))
-                        .collect::<BTreeMap<String, u128>>(),
-                    breakdown: summary
-                        .breakdown
-                        .clone()
-                        .iter()
-                        .map(|(k, v)| (k.to_string(), v.as_millis()))
-                        .collect::<BTreeMap<String, u128>>(),
-                };
-                reporter.report_telemetry(&TelemetryEvent::RefreshPerformance(perf));
-                // Find an report missing envs for the first launch of this process.
-                if MISSING_ENVS_REPORTED
-                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-                    .ok()
-                    .unwrap_or_default()
-                {
-                    // By now all conda envs have been found
-                    // Spawn conda  in a separate thread.
-                    // & see if we can find more environments by spawning conda.
-                    // But we will not wait for this to complete.
-                    let conda_locator = context.conda_locator.clone();
-                    let conda_executable = context
-                        .configuration
-                        .read()
-                        .unwrap()
-                        .conda_executable
-                        .clone();
-                    let reporter_ref = reporter.clone();
-                    thread::spawn(move || {
-                        conda_locator
-                            .find_and_report_missing_envs(reporter_ref.as_ref(), conda_executable);
-                        Some(())
-                    });
-
-                    // By now all poetry envs have been found
-                    // Spawn poetry exe in a separate thread.
-                    // & see if we can find more environments by spawning poetry.
-                    // But we will not wait for this to complete.
-                    let poetry_locator = context.poetry_locator.clone();
-                    let poetry_executable = context
-                        .configuration
-                        .read()
-                        .unwrap()
-                        .poetry_executable
-                        .clone();
-                    let reporter_ref = reporter.clone();
-                    thread::spawn(move || {
-                        poetry_locator
-                            .find_and_report_missing_envs(reporter_ref.as_ref(), poetry_executable);
-                        Some(())
-                    });
-                }
-
-                drop(lock);
-            });
+            thread::spawn(move || refresh_environments(context, refresh_options, Some(id)));
         }
         Err(e) => {
             error!("Failed to parse refresh {params:?}: {e}");
@@ -312,51 +402,236 @@ pub fn handle_refresh(context: Arc<Context>, id: u32, params: Value) {
     }
 }
 
+/// Searches for environments & reports them, the reply (with the time taken) is only sent when an id is provided.
+fn refresh_environments(context: Arc<Context>, refresh_options: RefreshOptions, id: Option<u32>) {
+    // Ensure we can have only one refresh at a time.
+    let lock = REFRESH_LOCK.lock().unwrap();
+
+    let mut config = context.configuration.read().unwrap().clone();
+    let locators = context.locators();
+    let progress_reporter = Arc::new(ProgressReporter::begin(Arc::new(jsonrpc::create_reporter(
+        refresh_options.search_kind,
+    ))));
+    let reporter = Arc::new(
+        CacheReporter::with_known_managers(
+            progress_reporter.clone(),
+            context.known_managers.clone(),
+        )
+        .with_known_environments(context.known_environments.clone()),
+    );
+
+    let mut search_scope = None;
+
+    // If search kind is provided and no search_paths, then we will only search in the global locations.
+    if refresh_options.search_kind.is_some() || refresh_options.search_paths.is_some() {
+        // Always clear this, as we will either serach in specified folder or a specific kind in global locations.
+        config.workspace_directories = None;
+        if let Some(search_paths) = refresh_options.search_paths {
+            // These workspace folders are only for this refresh.
+            config.workspace_directories = Some(
+                search_paths
+                    .iter()
+                    .filter(|p| p.is_dir())
+                    .cloned()
+                    .collect(),
+            );
+            config.executables = Some(
+                search_paths
+                    .iter()
+                    .filter(|p| p.is_file())
+                    .cloned()
+                    .collect(),
+            );
+            search_scope = Some(SearchScope::Workspace);
+        } else if let Some(search_kind) = refresh_options.search_kind {
+            config.executables = None;
+            search_scope = Some(SearchScope::Global(search_kind));
+        }
+
+        // Configure the locators with the modified config.
+        for locator in locators.iter() {
+            locator.configure(&config);
+        }
+    } else {
+        // Re-configure the locators with an un-modified config.
+        // Possible we congirued the locators with a modified config in the in the previous request.
+        // & the config was scoped to a particular search folder, executables or kind.
+        for locator in locators.iter() {
+            locator.configure(&config);
+        }
+    }
+
+    // Discard the state cached by the locators (e.g. environments found in a previous refresh).
+    thread::scope(|s| {
+        for locator in locators.iter() {
+            s.spawn(move || {
+                let result = locator.refresh();
+                trace!(
+                    "Refreshed {:?}, found {} environments",
+                    locator.get_kind(),
+                    result.environments.len()
+                );
+            });
+        }
+    });
+
+    trace!("Start refreshing environments, config: {:?}", config);
+    let summary = find_and_report_envs(
+        reporter.as_ref(),
+        config,
+        &locators,
+        context.os_environment.deref(),
+        search_scope,
+        None,
+    );
+    progress_reporter.end();
+    let summary = summary.lock().unwrap();
+    for locator in summary.locators.iter() {
+        info!("Locator {:?} took {:?}", locator.0, locator.1);
+    }
+    for item in summary.breakdown.iter() {
+        info!("Locator {} took {:?}", item.0, item.1);
+    }
+    trace!("Finished refreshing environments in {:?}", summary.total);
+    context
+        .summary
+        .lock()
+        .unwrap()
+        .replace(PerLocatorSummary::from(&*summary));
+    // Environments found in previous refreshes that have since been deleted.
+    for env in reporter.report_removed_environments() {
+        if let Some(executable) = &env.executable {
+            remove_cache_entry(executable);
+        }
+    }
+    if let Some(id) = id {
+        send_reply(id, Some(RefreshResult::new(summary.total)));
+    }
+
+    let perf = RefreshPerformance {
+        total: summary.total.as_millis(),
+        locators: summary
+            .locators
+            .clone()
+            .iter()
+            .map(|(k, v)| (format!("{:?}", k), v.as_millis()))
+            .collect::<BTreeMap<String, u128>>(),
+        breakdown: summary
+            .breakdown
+            .clone()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.as_millis()))
+            .collect::<BTreeMap<String, u128>>(),
+    };
+    reporter.report_telemetry(&TelemetryEvent::RefreshPerformance(perf));
+    // Find an report missing envs for the first launch of this process.
+    if MISSING_ENVS_REPORTED
+        .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .ok()
+        .unwrap_or_default()
+    {
+        // By now all conda envs have been found
+        // Spawn conda  in a separate thread.
+        // & see if we can find more environments by spawning conda.
+        // But we will not wait for this to complete.
+        let conda_locator = context.conda_locator();
+        let conda_executable = context
+            .configuration
+            .read()
+            .unwrap()
+            .conda_executable
+            .clone();
+        let reporter_ref = reporter.clone();
+        thread::spawn(move || {
+            conda_locator.find_and_report_missing_envs(reporter_ref.as_ref(), conda_executable);
+            Some(())
+        });
+
+        // By now all poetry envs have been found
+        // Spawn poetry exe in a separate thread.
+        // & see if we can find more environments by spawning poetry.
+        // But we will not wait for this to complete.
+        let poetry_locator = context.poetry_locator.clone();
+        let poetry_executable = context
+            .configuration
+            .read()
+            .unwrap()
+            .poetry_executable
+            .clone();
+        let reporter_ref = reporter.clone();
+        thread::spawn(move || {
+            let poetry = poetry_locator
+                .find_and_report_missing_envs(reporter_ref.as_ref(), poetry_executable);
+            send_message("pet/refreshComplete", Some(RefreshComplete { poetry }));
+        });
+    }
+
+    drop(lock);
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ResolveOptions {
     pub executable: PathBuf,
 }
 
+/// Max time allotted for a resolve request, resolving requires spawning Python which can be slow.
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn handle_resolve(context: Arc<Context>, id: u32, params: Value) {
     match serde_json::from_value::<ResolveOptions>(params.clone()) {
         Ok(request_options) => {
             let executable = request_options.executable.clone();
+            // Executables of managers (e.g. `poetry`, `conda`) do not belong to a Python environment.
+            if let Some(locator) = context
+                .locators()
+                .iter()
+                .find(|locator| locator.manages_executable(&executable))
+            {
+                trace!(
+                    "{executable:?} is a manager executable of {:?}",
+                    locator.get_kind()
+                );
+                send_reply(id, None::<PythonEnvironment>);
+                return;
+            }
+            let cached = context
+                .resolved_environments
+                .lock()
+                .unwrap()
+                .get(&norm_case(&executable))
+                .cloned();
+            // The environment could have been deleted since, in which case resolve it again.
+            if let Some(env) = cached.filter(|env| validate_environment(env, &context.locators())) {
+                trace!("Resolved env {executable:?} from cache as {env:?}");
+                send_reply(id, Some(add_packages(&context, env)));
+                return;
+            }
             // Start in a new thread, we can have multiple resolve requests.
-            let environment = context.os_environment.clone();
             thread::spawn(move || {
-                let now = SystemTime::now();
-                trace!("Resolving env {:?}", executable);
-                if let Some(result) =
-                    resolve_environment(&executable, &context.locators, environment.deref())
-                {
-                    if let Some(resolved) = result.resolved {
-                        // Gather telemetry of this resolved env and see what we got wrong.
-                        let jsonrpc_reporter = jsonrpc::create_reporter(None);
-                        let _ = report_inaccuracies_identified_after_resolving(
-                            &jsonrpc_reporter,
-                            &result.discovered,
-                            &resolved,
+                let (sender, receiver) = mpsc::channel();
+                let resolve_executable = executable.clone();
+                thread::spawn(move || {
+                    let env = resolve(&context, &resolve_executable);
+                    let _ = sender.send(env.map(|env| add_packages(&context, env)));
+                });
+                match receiver.recv_timeout(RESOLVE_TIMEOUT) {
+                    Ok(env) => send_reply(id, env),
+                    Err(RecvTimeoutError::Timeout) => {
+                        error!("Timeout resolving env {executable:?}");
+                        send_error(
+                            Some(id),
+                            REQUEST_TIMEOUT,
+                            format!("Timeout resolving env {executable:?}"),
                         );
-
-                        trace!(
-                            "Resolved env ({:?}) {executable:?} as {resolved:?}",
-                            now.elapsed()
-                        );
-                        send_reply(id, resolved.into());
-                    } else {
-                        error!(
-                            "Failed to resolve env {executable:?}, returning discovered env {:?}",
-                            result.discovered
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        error!("Failed to resolve env {executable:?}");
+                        send_error(
+                            Some(id),
+                            -4,
+                            format!("Failed to resolve env {executable:?}"),
                         );
-                        send_reply(id, result.discovered.into());
                     }
-                } else {
-                    error!("Failed to resolve env {executable:?}");
-                    send_error(
-                        Some(id),
-                        -4,
-                        format!("Failed to resolve env {executable:?}"),
-                    );
                 }
             });
         }
@@ -371,6 +646,60 @@ pub fn handle_resolve(context: Arc<Context>, id: u32, params: Value) {
     }
 }
 
+/// Lists the packages installed in the environment when enabled via `Configuration::scan_packages`.
+/// Not cached along with the resolved environment, as packages are installed & removed all the time.
+fn add_packages(context: &Context, mut env: PythonEnvironment) -> PythonEnvironment {
+    let scan_packages = context.configuration.read().unwrap().scan_packages;
+    if scan_packages.unwrap_or_default() {
+        env.packages = Some(scan_site_packages(&env));
+    }
+    env
+}
+
+/// Resolves the environment by spawning Python, returns `None` if the executable does not belong to a known environment.
+fn resolve(context: &Context, executable: &PathBuf) -> Option<PythonEnvironment> {
+    let now = SystemTime::now();
+    trace!("Resolving env {:?}", executable);
+    let Some(result) = resolve_environment(
+        executable,
+        &context.locators(),
+        context.os_environment.deref(),
+    ) else {
+        warn!("Unknown Python env {executable:?}");
+        return None;
+    };
+    let Some(resolved) = result.resolved else {
+        error!(
+            "Failed to resolve env {executable:?}, returning discovered env {:?}",
+            result.discovered
+        );
+        return Some(result.discovered);
+    };
+    // Gather telemetry of this resolved env and see what we got wrong.
+    let jsonrpc_reporter = jsonrpc::create_reporter(None);
+    let _ = report_inaccuracies_identified_after_resolving(
+        &jsonrpc_reporter,
+        &result.discovered,
+        &resolved,
+    );
+    trace!(
+        "Resolved env ({:?}) {executable:?} as {resolved:?}",
+        now.elapsed()
+    );
+
+    let mut resolved_environments = context.resolved_environments.lock().unwrap();
+    for path in resolved
+        .symlinks
+        .iter()
+        .flatten()
+        .chain(resolved.executable.iter())
+        .chain([executable])
+    {
+        resolved_environments.insert(norm_case(path), resolved.clone());
+    }
+    Some(resolved)
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FindOptions {
@@ -381,6 +710,13 @@ pub struct FindOptions {
 }
 
 pub fn handle_find(context: Arc<Context>, id: u32, params: Value) {
+    // Registered before the search starts, so that a cancellation sent right after this request is not missed.
+    let cancellation_token = CancellationToken::new();
+    context
+        .cancellation_tokens
+        .lock()
+        .unwrap()
+        .insert(id, cancellation_token.clone());
     thread::spawn(
         move || match serde_json::from_value::<FindOptions>(params.clone()) {
             Ok(find_options) => {
@@ -389,38 +725,48 @@ pub fn handle_find(context: Arc<Context>, id: u32, params: Value) {
 
                 let collect_reporter = Arc::new(collect::create_reporter());
                 let reporter = CacheReporter::new(collect_reporter.clone());
+                // Each find request is a new search, hence executables that failed previously are tried again.
+                let unknown_executables = Mutex::new(HashSet::new());
                 if find_options.search_path.is_file() {
                     identify_python_executables_using_locators(
                         vec![find_options.search_path.clone()],
-                        &context.locators,
+                        &context.locators(),
                         &reporter,
                         &global_env_search_paths,
+                        &unknown_executables,
                     );
                 } else {
+                    let config = context.configuration.read().unwrap().clone();
                     find_python_environments_in_workspace_folder_recursive(
                         &find_options.search_path,
                         &reporter,
-                        &context.locators,
+                        &context.locators(),
                         &global_env_search_paths,
-                        context
-                            .configuration
-                            .read()
-                            .unwrap()
-                            .clone()
-                            .environment_directories
-                            .as_deref()
-                            .unwrap_or(&[]),
+                        &unknown_executables,
+                        config.environment_directories.as_deref().unwrap_or(&[]),
+                        config.follow_symlinks.unwrap_or_default(),
+                        config.exclude_paths.as_deref().unwrap_or(&[]),
+                        config.max_search_depth.unwrap_or(DEFAULT_MAX_SEARCH_DEPTH),
+                        Some(&cancellation_token),
                     );
                 }
+                context.cancellation_tokens.lock().unwrap().remove(&id);
 
                 let envs = collect_reporter.environments.lock().unwrap().clone();
-                if envs.is_empty() {
+                if cancellation_token.is_cancelled() {
+                    send_error(
+                        Some(id),
+                        REQUEST_CANCELLED,
+                        format!("Find request {id} was cancelled"),
+                    );
+                } else if envs.is_empty() {
                     send_reply(id, None::<Vec<PythonEnvironment>>);
                 } else {
                     send_reply(id, envs.into());
                 }
             }
             Err(e) => {
+                context.cancellation_tokens.lock().unwrap().remove(&id);
                 error!("Failed to parse find {params:?}: {e}");
                 send_error(
                     Some(id),
@@ -432,9 +778,32 @@ pub fn handle_find(context: Arc<Context>, id: u32, params: Value) {
     );
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CancelParams {
+    pub id: u32,
+}
+
+pub fn handle_cancel_request(context: Arc<Context>, params: Value) {
+    match serde_json::from_value::<CancelParams>(params.clone()) {
+        Ok(cancel_params) => {
+            // The request may have already completed, in which case there's nothing to cancel.
+            if let Some(token) = context
+                .cancellation_tokens
+                .lock()
+                .unwrap()
+                .get(&cancel_params.id)
+            {
+                trace!("Cancelling request {}", cancel_params.id);
+                token.cancel();
+            }
+        }
+        Err(e) => error!("Failed to parse cancel request {params:?}: {e}"),
+    }
+}
+
 pub fn handle_conda_telemetry(context: Arc<Context>, id: u32, _params: Value) {
     thread::spawn(move || {
-        let conda_locator = context.conda_locator.clone();
+        let conda_locator = context.conda_locator();
         let conda_executable = context
             .configuration
             .read()
@@ -446,6 +815,25 @@ pub fn handle_conda_telemetry(context: Arc<Context>, id: u32, _params: Value) {
     });
 }
 
+pub fn handle_get_summary(context: Arc<Context>, id: u32, _params: Value) {
+    let summary = context.summary.lock().unwrap().clone().map(|mut summary| {
+        summary.locators = context
+            .locators()
+            .iter()
+            .map(|locator| {
+                let kind = format!("{:?}", locator.get_kind());
+                (kind, locator.description().to_string())
+            })
+            .collect();
+        summary.cache_stats = get_cache_stats().map(|stats| CacheStatsSummary {
+            entry_count: stats.entry_count,
+            total_bytes: stats.total_bytes,
+        });
+        summary
+    });
+    send_reply(id, summary);
+}
+
 pub fn handle_clear_cache(_context: Arc<Context>, id: u32, _params: Value) {
     thread::spawn(move || {
         if let Err(e) = clear_cache() {
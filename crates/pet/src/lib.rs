@@ -4,23 +4,48 @@
 use find::find_and_report_envs;
 use find::SearchScope;
 use locators::create_locators;
+use log::error;
 use pet_conda::Conda;
 use pet_conda::CondaLocator;
+use pet_core::cancellation::CancellationToken;
+use pet_core::manager::EnvManager;
 use pet_core::os_environment::Environment;
-use pet_core::python_environment::PythonEnvironmentKind;
+use pet_core::python_environment::{get_environment_key, PythonEnvironment, PythonEnvironmentKind};
+use pet_core::telemetry::TelemetryEvent;
 use pet_core::Locator;
 use pet_core::{os_environment::EnvironmentApi, reporter::Reporter, Configuration};
 use pet_poetry::Poetry;
 use pet_poetry::PoetryLocator;
-use pet_python_utils::cache::set_cache_directory;
+use pet_python_utils::cache::{get_cache_stats, set_cache_directory};
+use pet_reporter::json::{self, FindSummary};
+use pet_reporter::json_lines::JsonLinesReporter;
 use pet_reporter::{self, cache::CacheReporter, stdio};
 use resolve::resolve_environment;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::{collections::BTreeMap, env, sync::Arc, time::SystemTime};
 
 pub mod find;
+#[cfg(feature = "async")]
+pub mod find_async;
 pub mod locators;
 pub mod resolve;
+pub mod thread_pool;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable output.
+    #[default]
+    Text,
+    /// A single JSON object with the managers, environments & summary, printed once the search completes.
+    Json,
+    /// A JSON object per line, printed as managers & environments are found, followed by the summary.
+    Jsonl,
+}
 
 #[derive(Debug, Clone)]
 pub struct FindOptions {
@@ -32,6 +57,17 @@ pub struct FindOptions {
     pub workspace_only: bool,
     pub cache_directory: Option<PathBuf>,
     pub kind: Option<PythonEnvironmentKind>,
+    /// Only use the locators that support at least one of these kinds.
+    pub kind_filter: Option<Vec<PythonEnvironmentKind>>,
+    /// Print the environments sorted by kind, version & executable once discovery completes.
+    pub sorted_output: bool,
+    /// Number of levels of sub directories searched in the workspace folders (defaults to `DEFAULT_MAX_SEARCH_DEPTH`).
+    pub max_search_depth: Option<u32>,
+    /// Max number of threads used to search for environments at the same time.
+    pub max_threads: Option<usize>,
+    /// JSON configuration file, defaults to `pet.json` in the current directory (if it exists).
+    pub config_file: Option<PathBuf>,
+    pub output_format: OutputFormat,
 }
 
 pub fn find_and_report_envs_stdio(options: FindOptions) {
@@ -41,18 +77,21 @@ pub fn find_and_report_envs_stdio(options: FindOptions) {
         log::LevelFilter::Warn
     });
     let now = SystemTime::now();
-    let config = create_config(&options);
+    let environment = EnvironmentApi::new();
+    let config = create_config(&options, &environment);
     let search_scope = if options.workspace_only {
         Some(SearchScope::Workspace)
     } else {
         options.kind.map(SearchScope::Global)
     };
 
-    if let Some(cache_directory) = options.cache_directory.clone() {
+    if let Some(cache_directory) = config.cache_directory.clone() {
         set_cache_directory(cache_directory);
     }
-    let environment = EnvironmentApi::new();
-    let conda_locator = Arc::new(Conda::from(&environment));
+    let conda_locator = Arc::new(match config.conda_executable.clone() {
+        Some(exe) => Conda::with_executable(exe, &environment),
+        None => Conda::from(&environment),
+    });
     let poetry_locator = Arc::new(Poetry::from(&environment));
 
     let locators = create_locators(conda_locator.clone(), poetry_locator.clone(), &environment);
@@ -70,43 +109,101 @@ pub fn find_and_report_envs_stdio(options: FindOptions) {
         search_scope,
     );
 
-    println!("Completed in {}ms", now.elapsed().unwrap().as_millis())
+    if options.output_format == OutputFormat::Text {
+        println!("Completed in {}ms", now.elapsed().unwrap().as_millis())
+    }
 }
 
-fn create_config(options: &FindOptions) -> Configuration {
-    let mut config = Configuration::default();
+/// Cancels the search when the user presses Ctrl-C, so that whatever has been found so far is still reported.
+/// Pressing Ctrl-C again terminates the process as usual.
+#[cfg(unix)]
+fn cancel_on_ctrl_c(cancellation_token: &CancellationToken) {
+    use std::sync::OnceLock;
+    static CTRL_C: OnceLock<CancellationToken> = OnceLock::new();
 
-    let mut search_paths = vec![];
-    if let Some(dirs) = options.search_paths.clone() {
-        search_paths.extend(dirs);
+    extern "C" fn on_ctrl_c(_: libc::c_int) {
+        if let Some(cancellation_token) = CTRL_C.get() {
+            cancellation_token.cancel();
+        }
+        unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+        }
     }
-    // If workspace folders have been provided do not add cwd.
-    if search_paths.is_empty() {
-        if let Ok(cwd) = env::current_dir() {
-            search_paths.push(cwd);
+
+    if CTRL_C.set(cancellation_token.clone()).is_ok() {
+        unsafe {
+            libc::signal(
+                libc::SIGINT,
+                on_ctrl_c as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
         }
     }
+}
+
+fn create_config(options: &FindOptions, environment: &dyn Environment) -> Configuration {
+    let mut config = Configuration::default();
+
+    let mut search_paths = options.search_paths.clone().unwrap_or_default();
     search_paths.sort();
     search_paths.dedup();
 
-    config.workspace_directories = Some(
-        search_paths
-            .iter()
-            .filter(|d| d.is_dir())
-            .cloned()
-            .collect(),
-    );
-    config.executables = Some(
-        search_paths
-            .iter()
-            .filter(|d| d.is_file())
-            .cloned()
-            .collect(),
-    );
+    if !search_paths.is_empty() {
+        config.workspace_directories = Some(
+            search_paths
+                .iter()
+                .filter(|d| d.is_dir())
+                .cloned()
+                .collect(),
+        );
+        config.executables = Some(
+            search_paths
+                .iter()
+                .filter(|d| d.is_file())
+                .cloned()
+                .collect(),
+        );
+    }
+    config.cache_directory = options.cache_directory.clone();
+    config.max_search_depth = options.max_search_depth;
+    config.max_threads = options.max_threads;
+    config.kind_filter = options.kind_filter.clone();
+
+    // Command line options take precedence over the environment variables, which take precedence over the config file.
+    let mut config = config.merge(Configuration::from_env(environment));
+    if let Some(file_config) = load_config_file(options.config_file.as_deref()) {
+        config = config.merge(file_config);
+    }
+    // If workspace folders have been provided do not add cwd.
+    if config.workspace_directories.is_none() && config.executables.is_none() {
+        if let Ok(cwd) = env::current_dir() {
+            config.workspace_directories = Some(vec![cwd]);
+        }
+    }
 
     config
 }
 
+fn load_config_file(config_file: Option<&Path>) -> Option<Configuration> {
+    let file = match config_file {
+        Some(file) => file.to_path_buf(),
+        None => {
+            // No point reporting errors if the user did not ask for a config file.
+            let file = env::current_dir().ok()?.join("pet.json");
+            if !file.is_file() {
+                return None;
+            }
+            file
+        }
+    };
+    match Configuration::from_file(&file) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            error!("Failed to load the configuration from {:?}: {}", file, err);
+            None
+        }
+    }
+}
+
 fn find_envs(
     options: &FindOptions,
     locators: &Arc<Vec<Arc<dyn Locator>>>,
@@ -120,19 +217,63 @@ fn find_envs(
         Some(SearchScope::Global(kind)) => Some(kind),
         _ => None,
     };
-    let stdio_reporter = Arc::new(stdio::create_reporter(options.print_list, kind));
-    let reporter = CacheReporter::new(stdio_reporter.clone());
+    let stdio_reporter = Arc::new(stdio::create_reporter(
+        options.print_list,
+        kind,
+        options.sorted_output,
+    ));
+    let json_reporter = Arc::new(json::create_reporter(kind));
+    let json_lines_reporter = Arc::new(JsonLinesReporter::new(io::stdout(), kind));
+    let reporter = CacheReporter::new(match options.output_format {
+        OutputFormat::Text => stdio_reporter.clone(),
+        OutputFormat::Json => json_reporter.clone(),
+        OutputFormat::Jsonl => json_lines_reporter.clone(),
+    });
+    let cancellation_token = CancellationToken::new();
+    #[cfg(unix)]
+    cancel_on_ctrl_c(&cancellation_token);
 
-    let summary = find_and_report_envs(&reporter, config, locators, environment, search_scope);
-    if options.report_missing {
+    let summary = find_and_report_envs(
+        &reporter,
+        config,
+        locators,
+        environment,
+        search_scope,
+        Some(cancellation_token.clone()),
+    );
+    if options.report_missing && !cancellation_token.is_cancelled() {
         // By now all conda envs have been found
         // Spawn conda
         // & see if we can find more environments by spawning conda.
         let _ = conda_locator.find_and_report_missing_envs(&reporter, None);
         let _ = poetry_locator.find_and_report_missing_envs(&reporter, None);
     }
+    match options.output_format {
+        OutputFormat::Json => {
+            let mut result = json_reporter.take_result();
+            result.summary = FindSummary::from(&*summary.lock().unwrap());
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+            return;
+        }
+        OutputFormat::Jsonl => {
+            json_lines_reporter.report_summary(&FindSummary::from(&*summary.lock().unwrap()));
+            return;
+        }
+        OutputFormat::Text => {}
+    }
+    stdio_reporter.flush();
 
     if options.print_summary {
+        if options.verbose {
+            println!();
+            println!("Locators:");
+            println!("---------");
+            for locator in locators.iter() {
+                let kind = format!("{:?}", locator.get_kind());
+                println!("{:<20} : {}", kind, locator.description());
+            }
+        }
+
         let summary = summary.lock().unwrap();
         if !summary.locators.is_empty() {
             println!();
@@ -153,6 +294,38 @@ fn find_envs(
             println!();
         }
 
+        if !summary.skipped_paths.is_empty() {
+            println!(
+                "Skipped (permission denied): {}",
+                summary.skipped_paths.len()
+            );
+            println!();
+        }
+
+        if let Some(stats) = get_cache_stats() {
+            println!("Cache:");
+            println!("------");
+            println!("{:<20} : {}", "Entries", stats.entry_count);
+            println!("{:<20} : {}", "Size (bytes)", stats.total_bytes);
+            if let Some(age) = stats.oldest_entry_age {
+                println!("{:<20} : {:?}", "Oldest entry", age);
+            }
+            if let Some(age) = stats.newest_entry_age {
+                println!("{:<20} : {:?}", "Newest entry", age);
+            }
+            println!();
+        }
+
+        #[cfg(feature = "telemetry")]
+        if let Some(last) = summary.report_timings.last() {
+            println!(
+                "Reported {} items, last one after {:?}",
+                summary.report_timings.len(),
+                last
+            );
+            println!();
+        }
+
         let summary = stdio_reporter.get_summary();
         if !summary.managers.is_empty() {
             println!("Managers:");
@@ -196,7 +369,12 @@ fn find_envs(
     }
 }
 
-pub fn resolve_report_stdio(executable: PathBuf, verbose: bool, cache_directory: Option<PathBuf>) {
+pub fn resolve_report_stdio(
+    executable: PathBuf,
+    verbose: bool,
+    cache_directory: Option<PathBuf>,
+    output_format: OutputFormat,
+) {
     stdio::initialize_logger(if verbose {
         log::LevelFilter::Trace
     } else {
@@ -208,7 +386,7 @@ pub fn resolve_report_stdio(executable: PathBuf, verbose: bool, cache_directory:
         set_cache_directory(cache_directory);
     }
 
-    let stdio_reporter = Arc::new(stdio::create_reporter(true, None));
+    let stdio_reporter = Arc::new(stdio::create_reporter(true, None, false));
     let reporter = CacheReporter::new(stdio_reporter.clone());
     let environment = EnvironmentApi::new();
     let conda_locator = Arc::new(Conda::from(&environment));
@@ -224,7 +402,13 @@ pub fn resolve_report_stdio(executable: PathBuf, verbose: bool, cache_directory:
         locator.configure(&config);
     }
 
-    if let Some(result) = resolve_environment(&executable, &locators, &environment) {
+    let result = resolve_environment(&executable, &locators, &environment);
+    if output_format != OutputFormat::Text {
+        let env = result.map(|result| result.resolved.unwrap_or(result.discovered));
+        println!("{}", serde_json::to_string_pretty(&env).unwrap());
+        return;
+    }
+    if let Some(result) = result {
         //
         println!("Environment found for {:?}", executable);
         let env = &result.resolved.unwrap_or(result.discovered);
@@ -241,3 +425,61 @@ pub fn resolve_report_stdio(executable: PathBuf, verbose: bool, cache_directory:
         now.elapsed().unwrap().as_millis()
     )
 }
+
+/// Same as `find_and_report_envs`, however the environments are passed to the given closure
+/// instead of a `Reporter` (each environment is passed only once).
+///
+/// ```no_run
+/// use pet::{find_environments_streaming, locators::create_locators};
+/// use pet_conda::Conda;
+/// use pet_core::{os_environment::EnvironmentApi, Configuration};
+/// use pet_poetry::Poetry;
+/// use std::sync::Arc;
+///
+/// let environment = EnvironmentApi::new();
+/// let locators = create_locators(
+///     Arc::new(Conda::from(&environment)),
+///     Arc::new(Poetry::from(&environment)),
+///     &environment,
+/// );
+/// let mut environments = vec![];
+/// find_environments_streaming(Configuration::default(), &locators, &environment, |env| {
+///     environments.push(env)
+/// });
+/// ```
+pub fn find_environments_streaming<F: FnMut(PythonEnvironment) + Send>(
+    configuration: Configuration,
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+    environment: &dyn Environment,
+    on_env: F,
+) {
+    for locator in locators.iter() {
+        locator.configure(&configuration);
+    }
+    let reporter = CallbackReporter {
+        on_env: Mutex::new(on_env),
+        reported: Mutex::new(HashSet::new()),
+    };
+    find_and_report_envs(&reporter, configuration, locators, environment, None, None);
+}
+
+struct CallbackReporter<F: FnMut(PythonEnvironment) + Send> {
+    on_env: Mutex<F>,
+    reported: Mutex<HashSet<PathBuf>>,
+}
+
+impl<F: FnMut(PythonEnvironment) + Send> Reporter for CallbackReporter<F> {
+    fn report_manager(&self, _manager: &EnvManager) {
+        //
+    }
+    fn report_environment(&self, env: &PythonEnvironment) {
+        if let Some(key) = get_environment_key(env) {
+            if self.reported.lock().unwrap().insert(key) {
+                (self.on_env.lock().unwrap())(env.clone());
+            }
+        }
+    }
+    fn report_telemetry(&self, _event: &TelemetryEvent) {
+        //
+    }
+}
@@ -2,22 +2,32 @@
 // Licensed under the MIT License.
 
 use log::{info, trace};
+use pet_asdf::Asdf;
 use pet_conda::Conda;
-use pet_core::arch::Architecture;
 use pet_core::env::PythonEnv;
 use pet_core::os_environment::Environment;
 use pet_core::python_environment::{
     PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind,
 };
 use pet_core::Locator;
+use pet_hatch::Hatch;
 use pet_linux_global_python::LinuxGlobalPython;
 use pet_mac_commandlinetools::MacCmdLineTools;
 use pet_mac_python_org::MacPythonOrg;
 use pet_mac_xcode::MacXCode;
+use pet_mamba::Mamba;
+use pet_mise::Mise;
+use pet_nox::Nox;
+use pet_pdm::Pdm;
 use pet_pipenv::PipEnv;
+use pet_pixi::Pixi;
 use pet_poetry::Poetry;
 use pet_pyenv::PyEnv;
 use pet_python_utils::env::ResolvedPythonEnv;
+use pet_python_utils::executable::resolve_symlink_chain;
+use pet_rye::Rye;
+use pet_tox::Tox;
+use pet_uv::Uv;
 use pet_venv::Venv;
 use pet_virtualenv::VirtualEnv;
 use pet_virtualenvwrapper::VirtualEnvWrapper;
@@ -29,7 +39,7 @@ pub fn create_locators(
     poetry_locator: Arc<Poetry>,
     environment: &dyn Environment,
 ) -> Arc<Vec<Arc<dyn Locator>>> {
-    // NOTE: The order of the items matter.
+    // NOTE: The order of the items matter (& must be consistent with `Locator::priority`).
 
     let mut locators: Vec<Arc<dyn Locator>> = vec![];
 
@@ -45,14 +55,29 @@ pub fn create_locators(
         #[cfg(windows)]
         locators.push(Arc::new(WindowsRegistry::from(conda_locator.clone())))
     }
-    // 3. Pyenv Python
+    // 3. Pyenv, asdf & mise Python
     locators.push(Arc::new(PyEnv::from(environment, conda_locator.clone())));
+    locators.push(Arc::new(Asdf::from(environment)));
+    locators.push(Arc::new(Mise::from(environment)));
 
     // 4. Conda Python
+    // pixi environments have the same layout as conda environments, however they are in known directories.
+    locators.push(Arc::new(Pixi::from(environment)));
+    // Mamba only identifies the environments managed by mamba or micromamba, hence comes before conda.
+    locators.push(Arc::new(Mamba::from(environment)));
     locators.push(conda_locator);
 
     // 5. Support for Virtual Envs
     // The order of these matter.
+    // Hatch environments are in a known directory & could have been created using uv.
+    locators.push(Arc::new(Hatch::from(environment)));
+    locators.push(Arc::new(Pdm::from(environment)));
+    locators.push(Arc::new(Rye::from(environment)));
+    // tox & nox environments are in the `.tox` & `.nox` directories of a project & could have been created using uv.
+    locators.push(Arc::new(Tox::new()));
+    locators.push(Arc::new(Nox::from(environment)));
+    // uv records itself in pyvenv.cfg, hence comes before the other virtual env locators.
+    locators.push(Arc::new(Uv::from(environment)));
     // Basically PipEnv is a superset of VirtualEnvWrapper, which is a superset of Venv, which is a superset of VirtualEnv.
     locators.push(poetry_locator);
     locators.push(Arc::new(PipEnv::from(environment)));
@@ -87,6 +112,42 @@ pub fn create_locators(
     Arc::new(locators)
 }
 
+/// Returns the locators that support at least one of the given kinds of environments.
+/// The order of the locators is preserved.
+pub fn filter_locators(
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+    kind_filter: &[PythonEnvironmentKind],
+) -> Arc<Vec<Arc<dyn Locator>>> {
+    Arc::new(
+        locators
+            .iter()
+            .filter(|locator| {
+                locator
+                    .supported_categories()
+                    .iter()
+                    .any(|kind| kind_filter.contains(kind))
+            })
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Returns true if the environment found previously is still valid, using the locator that supports its kind.
+/// Environments that no locator supports are valid as long as their executable exists.
+pub fn validate_environment(
+    env: &PythonEnvironment,
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+) -> bool {
+    let locator = locators.iter().find(|locator| {
+        env.kind
+            .is_some_and(|kind| locator.supported_categories().contains(&kind))
+    });
+    match locator {
+        Some(locator) => locator.validate(env),
+        None => env.executable.as_ref().is_some_and(|exe| exe.is_file()),
+    }
+}
+
 /// Identify the Python environment using the locators.
 /// search_path : Generally refers to original folder that was being searched when the env was found.
 pub fn identify_python_environment_using_locators(
@@ -95,6 +156,8 @@ pub fn identify_python_environment_using_locators(
     global_env_search_paths: &[PathBuf],
 ) -> Option<PythonEnvironment> {
     let executable = env.executable.clone();
+    let mut locators = locators.iter().collect::<Vec<_>>();
+    locators.sort_by_key(|locator| locator.priority());
     if let Some(env) = locators.iter().fold(
         None,
         |e, loc| if e.is_some() { e } else { loc.try_from(env) },
@@ -153,16 +216,16 @@ fn create_unknown_env(
 ) -> PythonEnvironment {
     // Find all the python exes in the same bin directory.
 
+    let mut symlinks = find_symlinks(&resolved_env.executable).unwrap_or_default();
+    symlinks.extend(resolve_symlink_chain(&resolved_env.executable));
+    let arch = resolved_env.arch();
+
     PythonEnvironmentBuilder::new(fallback_category)
-        .symlinks(find_symlinks(&resolved_env.executable))
+        .symlinks(Some(symlinks))
         .executable(Some(resolved_env.executable))
         .prefix(Some(resolved_env.prefix))
-        .arch(Some(if resolved_env.is64_bit {
-            Architecture::X64
-        } else {
-            Architecture::X86
-        }))
-        .version(Some(resolved_env.version))
+        .arch(Some(arch))
+        .version(resolved_env.version.parse().ok())
         .build()
 }
 
@@ -5,12 +5,13 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 use jsonrpc::start_jsonrpc_server;
-use pet::{find_and_report_envs_stdio, resolve_report_stdio, FindOptions};
+use pet::{find_and_report_envs_stdio, resolve_report_stdio, FindOptions, OutputFormat};
 use pet_core::python_environment::PythonEnvironmentKind;
 
 mod find;
 mod jsonrpc;
 mod locators;
+mod thread_pool;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -53,6 +54,32 @@ enum Commands {
         /// Will not search in the workspace directories.
         #[arg(short, long, conflicts_with = "workspace")]
         kind: Option<PythonEnvironmentKind>,
+
+        /// Only use the locators that support at least one of these (comma separated) kinds.
+        #[arg(long, value_delimiter = ',', value_name = "KINDS")]
+        kind_filter: Option<Vec<PythonEnvironmentKind>>,
+
+        /// Print the environments sorted by kind & version (output is reproducible across runs).
+        #[arg(long)]
+        sorted: bool,
+
+        /// Number of levels of sub directories searched for environments in the workspace folders (defaults to 2).
+        #[arg(long, value_name = "N")]
+        max_depth: Option<u32>,
+
+        /// Max number of threads used to search for environments at the same time (unlimited by default).
+        #[arg(long, value_name = "N")]
+        max_threads: Option<usize>,
+
+        /// JSON configuration file (command line options take precedence).
+        /// Defaults to `pet.json` in the current directory (if it exists).
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+
+        /// Format of the output, `json` prints a single JSON object once the search completes,
+        /// `jsonl` prints a JSON object per line as environments are found.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
     },
     /// Resolves & reports the details of the the environment to the standard output.
     Resolve {
@@ -67,6 +94,10 @@ enum Commands {
         /// Whether to display verbose output (defaults to warnings).
         #[arg(short, long)]
         verbose: bool,
+
+        /// Format of the output, `json` prints the environment (or `null` if not found).
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output_format: OutputFormat,
     },
     /// Starts the JSON RPC Server.
     Server,
@@ -83,6 +114,12 @@ fn main() {
         workspace: false,
         cache_directory: None,
         kind: None,
+        kind_filter: None,
+        sorted: false,
+        max_depth: None,
+        max_threads: None,
+        config: None,
+        output_format: OutputFormat::Text,
     }) {
         Commands::Find {
             list,
@@ -92,6 +129,12 @@ fn main() {
             workspace,
             cache_directory,
             kind,
+            kind_filter,
+            sorted,
+            max_depth,
+            max_threads,
+            config,
+            output_format,
         } => {
             let mut workspace_only = workspace;
             if search_paths.clone().is_some()
@@ -113,13 +156,20 @@ fn main() {
                 workspace_only,
                 cache_directory,
                 kind,
+                kind_filter,
+                sorted_output: sorted,
+                max_search_depth: max_depth,
+                max_threads,
+                config_file: config,
+                output_format,
             });
         }
         Commands::Resolve {
             executable,
             verbose,
             cache_directory,
-        } => resolve_report_stdio(executable, verbose, cache_directory),
+            output_format,
+        } => resolve_report_stdio(executable, verbose, cache_directory, output_format),
         Commands::Server => start_jsonrpc_server(),
     }
 }
@@ -1,18 +1,26 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use log::{trace, warn};
 use pet_core::{
-    arch::Architecture,
     env::PythonEnv,
     os_environment::Environment,
     python_environment::{PythonEnvironment, PythonEnvironmentBuilder},
-    Locator,
+    python_version::{PythonVersion, VersionTolerance},
+    Locator, LocatorResult,
 };
 use pet_env_var_path::get_search_paths_from_env_variables;
-use pet_python_utils::{env::ResolvedPythonEnv, executable::find_executable};
+use pet_fs::path::norm_case;
+use pet_python_utils::{
+    env::ResolvedPythonEnv,
+    executable::{find_executable, find_executables},
+};
+use pet_reporter::cache::CacheReporter;
 
 use crate::locators::identify_python_environment_using_locators;
 
@@ -63,23 +71,20 @@ pub fn resolve_environment(
 
                 let version = Some(info.version.clone());
                 let prefix = Some(info.prefix.clone());
-                let arch = Some(if info.is64_bit {
-                    Architecture::X64
-                } else {
-                    Architecture::X86
-                });
+                let arch = Some(info.arch());
 
-                let resolved = PythonEnvironmentBuilder::new(env.kind)
+                // Retain everything the locator discovered, only update what we got from spawning Python.
+                let resolved = PythonEnvironmentBuilder::from_environment(env)
                     .arch(arch)
-                    .display_name(env.display_name)
                     .executable(Some(info.executable.clone()))
-                    .manager(env.manager)
-                    .name(env.name)
                     .prefix(prefix)
-                    .project(env.project)
                     .symlinks(Some(symlinks))
-                    .version(version)
-                    .build();
+                    .version(version.and_then(|v| v.parse().ok()));
+                #[cfg(feature = "detect-free-threaded")]
+                let resolved = resolved.is_free_threaded(Some(
+                    pet_python_utils::executable::is_free_threaded_build(&info.executable),
+                ));
+                let resolved = resolved.build();
 
                 info.add_to_cache(resolved.clone());
 
@@ -105,3 +110,80 @@ pub fn resolve_environment(
         None
     }
 }
+
+/// Identifies the environment the given executable belongs to, e.g. what kind of environment is `/usr/bin/python3`.
+/// Environments already reported to the cache are looked up first (by executable & symlinks),
+/// else the locators are asked to identify the executable.
+/// Unlike `resolve_environment`, Python is never spawned, hence this is cheap enough to call frequently.
+pub fn find_env_by_executable(
+    executable: &Path,
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+    cache: Option<Arc<CacheReporter>>,
+) -> Option<PythonEnvironment> {
+    let executable = norm_case(executable);
+    if let Some(env) = cache.and_then(|cache| cache.get_by_executable(&executable)) {
+        return Some(env);
+    }
+    let env = PythonEnv::new(executable, None, None);
+    let mut locators = locators.iter().collect::<Vec<_>>();
+    locators.sort_by_key(|locator| locator.priority());
+    locators.iter().find_map(|locator| locator.try_from(&env))
+}
+
+/// Identifies the environment with the given prefix (sys.prefix), e.g. a conda env or a virtual env directory.
+/// Environments already reported to the cache are looked up first, else the Python executable in the prefix
+/// is identified using `find_env_by_executable`.
+pub fn find_env_by_prefix(
+    prefix: &Path,
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+    cache: Option<Arc<CacheReporter>>,
+) -> Option<PythonEnvironment> {
+    let prefix = norm_case(prefix);
+    if let Some(env) = cache.and_then(|cache| cache.get_by_prefix(&prefix)) {
+        return Some(env);
+    }
+    find_env_by_executable(&find_executable(&prefix)?, locators, None)
+}
+
+/// Same as `find_env_by_prefix`, however all of the environments sharing the prefix are returned.
+/// E.g. a conda base env can contain multiple executables (python, python3, python3.12) identified separately.
+pub fn find_envs_by_prefix(
+    prefix: &Path,
+    locators: &Arc<Vec<Arc<dyn Locator>>>,
+    cache: Option<Arc<CacheReporter>>,
+) -> Vec<PythonEnvironment> {
+    let prefix = norm_case(prefix);
+    if let Some(cache) = cache {
+        let environments = cache.get_all_by_prefix(&prefix);
+        if !environments.is_empty() {
+            return environments;
+        }
+    }
+    let mut result = LocatorResult {
+        environments: find_executables(&prefix)
+            .iter()
+            .filter_map(|executable| find_env_by_executable(executable, locators, None))
+            .collect(),
+        ..Default::default()
+    };
+    result.dedup_by_key();
+    result.environments
+}
+
+/// Returns the environments matching the given version, e.g. all `3.12` environments (`VersionTolerance::MajorMinor`).
+/// Environments without a known version never match.
+pub fn find_by_version(
+    version: &PythonVersion,
+    tolerance: VersionTolerance,
+    environments: &[PythonEnvironment],
+) -> Vec<PythonEnvironment> {
+    environments
+        .iter()
+        .filter(|env| {
+            env.version
+                .as_ref()
+                .is_some_and(|v| v.matches(version, tolerance))
+        })
+        .cloned()
+        .collect()
+}
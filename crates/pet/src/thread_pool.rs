@@ -0,0 +1,64 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{Scope, ScopedJoinHandle};
+
+/// Limits the number of threads spawned via `spawn` that run at the same time.
+/// When there is no limit, threads are spawned as they would be with `Scope::spawn`.
+/// Threads spawned via the pool must not spawn threads via the same pool, else the search could deadlock.
+#[derive(Clone, Default)]
+pub struct ThreadPool {
+    max_threads: Option<usize>,
+    /// Number of threads that are currently running.
+    active: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl ThreadPool {
+    pub fn new(max_threads: Option<usize>) -> Self {
+        ThreadPool {
+            max_threads: max_threads.map(|max| max.max(1)),
+            active: Arc::new((Mutex::new(0), Condvar::new())),
+        }
+    }
+    /// Spawns the function in a new scoped thread, blocking until fewer than `max_threads` threads are running.
+    pub fn spawn<'scope, 'env, F, T>(
+        &self,
+        scope: &'scope Scope<'scope, 'env>,
+        f: F,
+    ) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let permit = self.acquire();
+        scope.spawn(move || {
+            let _permit = permit;
+            f()
+        })
+    }
+    fn acquire(&self) -> Option<Permit> {
+        let max_threads = self.max_threads?;
+        let (active, available) = &*self.active;
+        let mut active = available
+            .wait_while(active.lock().unwrap(), |active| *active >= max_threads)
+            .unwrap();
+        *active += 1;
+        Some(Permit {
+            active: self.active.clone(),
+        })
+    }
+}
+
+/// Frees up a slot in the pool once the thread completes.
+struct Permit {
+    active: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let (active, available) = &*self.active;
+        *active.lock().unwrap() -= 1;
+        available.notify_one();
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::trace;
+use pet_core::cancellation::CancellationToken;
+use pet_fs::path::norm_case;
+use pet_python_utils::executable::{find_executables, should_search_for_environments_in_path};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// How often the watched directories are checked for changes.
+/// Only needs to be shorter than the debounce window, as changes are not reported any sooner than that.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Changes to the same environment within this window are reported once (e.g. a venv being created).
+pub const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// The `pyvenv.cfg` or Python executables of the environment with this prefix were created or modified.
+    Changed(PathBuf),
+    /// The environment with this prefix no longer exists.
+    Removed(PathBuf),
+}
+
+/// Watches the `pyvenv.cfg` & Python executables of the environments in the given directories
+/// (the directories themselves and their immediate sub directories, e.g. `<workspace>/.venv`).
+/// Directories that are never searched for environments (e.g. `exclude_paths`, `node_modules`) are not watched either.
+///
+/// The file system is polled, hence no platform specific APIs (inotify, FSEvents, etc) are required.
+/// Watching stops when the watcher is dropped.
+pub struct FileSystemWatcher {
+    cancellation_token: CancellationToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FileSystemWatcher {
+    pub fn start<F>(directories: Vec<PathBuf>, exclude_paths: Vec<PathBuf>, mut on_event: F) -> Self
+    where
+        F: FnMut(WatchEvent) + Send + 'static,
+    {
+        let cancellation_token = CancellationToken::new();
+        let token = cancellation_token.clone();
+        let exclude_paths = exclude_paths.into_iter().map(norm_case).collect::<Vec<_>>();
+        // Taken before returning, so that changes made once the watcher has started are not missed.
+        let mut snapshot = take_snapshot(&directories, &exclude_paths);
+        let handle = thread::spawn(move || {
+            trace!("Watching {:?} for changes", directories);
+            let mut pending: HashMap<PathBuf, (Instant, WatchEvent)> = HashMap::new();
+            while !token.is_cancelled() {
+                thread::sleep(POLL_INTERVAL);
+                let current = take_snapshot(&directories, &exclude_paths);
+                for (prefix, modified) in &current {
+                    if snapshot.get(prefix) != Some(modified) {
+                        let event = WatchEvent::Changed(prefix.clone());
+                        pending.insert(prefix.clone(), (Instant::now(), event));
+                    }
+                }
+                for prefix in snapshot.keys().filter(|p| !current.contains_key(*p)) {
+                    let event = WatchEvent::Removed(prefix.clone());
+                    pending.insert(prefix.clone(), (Instant::now(), event));
+                }
+                snapshot = current;
+
+                let ready = pending
+                    .iter()
+                    .filter(|(_, (changed, _))| changed.elapsed() >= DEBOUNCE_WINDOW)
+                    .map(|(prefix, _)| prefix.clone())
+                    .collect::<Vec<_>>();
+                for prefix in ready {
+                    if let Some((_, event)) = pending.remove(&prefix) {
+                        trace!("File system change {:?}", event);
+                        on_event(event);
+                    }
+                }
+            }
+        });
+        FileSystemWatcher {
+            cancellation_token,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for FileSystemWatcher {
+    fn drop(&mut self) {
+        self.cancellation_token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Returns the prefixes of the environments in the directories along with the time they were last modified.
+fn take_snapshot(
+    directories: &[PathBuf],
+    exclude_paths: &[PathBuf],
+) -> HashMap<PathBuf, SystemTime> {
+    let mut snapshot = HashMap::new();
+    for directory in directories {
+        let sub_directories = fs::read_dir(directory)
+            .map(|reader| {
+                reader
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for prefix in [directory.clone()]
+            .into_iter()
+            .chain(sub_directories)
+            .filter(|path| should_search_for_environments_in_path(path, exclude_paths))
+        {
+            if let Some(modified) = get_last_modified(&prefix) {
+                snapshot.insert(prefix, modified);
+            }
+        }
+    }
+    snapshot
+}
+
+fn get_last_modified(prefix: &Path) -> Option<SystemTime> {
+    let pyvenv_cfg = prefix.join("pyvenv.cfg");
+    let executables = find_executables(prefix);
+    if executables.is_empty() && !pyvenv_cfg.is_file() {
+        return None;
+    }
+    executables
+        .iter()
+        .chain([&pyvenv_cfg])
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn excluded_and_ignored_directories_are_not_watched() {
+        let workspace =
+            std::env::temp_dir().join(format!("pet_watch_snapshot_test_{}", process::id()));
+        let _ = fs::remove_dir_all(&workspace);
+        for name in [".venv", "data", "node_modules"] {
+            let prefix = workspace.join(name);
+            fs::create_dir_all(&prefix).unwrap();
+            fs::write(prefix.join("pyvenv.cfg"), "version = 3.12.1\n").unwrap();
+        }
+
+        let snapshot = take_snapshot(
+            std::slice::from_ref(&workspace),
+            &[norm_case(workspace.join("data"))],
+        );
+        let _ = fs::remove_dir_all(&workspace);
+
+        assert_eq!(
+            snapshot.keys().collect::<Vec<_>>(),
+            vec![&workspace.join(".venv")]
+        );
+    }
+}
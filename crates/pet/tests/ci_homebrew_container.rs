@@ -25,6 +25,7 @@ fn verify_python_in_homebrew_contaner() {
     use pet_core::{
         os_environment::EnvironmentApi,
         python_environment::{PythonEnvironment, PythonEnvironmentKind},
+        python_version::VersionTolerance,
     };
     use pet_poetry::Poetry;
     use pet_reporter::{cache::CacheReporter, collect};
@@ -43,6 +44,7 @@ fn verify_python_in_homebrew_contaner() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -72,7 +74,7 @@ fn verify_python_in_homebrew_contaner() {
     let python3_12 = PythonEnvironment {
         kind: Some(PythonEnvironmentKind::Homebrew),
         executable: Some(PathBuf::from("/home/linuxbrew/.linuxbrew/bin/python3.12")),
-        version: Some("3.12.7".to_string()), // This can change on CI, so we don't check it
+        version: "3.12.7".parse().ok(), // This can change on CI, so we don't check it
         symlinks: Some(vec![
             // For older versions of Python, we do not have a tonne of symlinks,
             // E.g. for 3.12.7 (which was the latest at some point, at a lot of symlinks)
@@ -88,7 +90,7 @@ fn verify_python_in_homebrew_contaner() {
     let python3_11 = PythonEnvironment {
         kind: Some(PythonEnvironmentKind::Homebrew),
         executable: Some(PathBuf::from("/home/linuxbrew/.linuxbrew/bin/python3.11")),
-        version: Some("3.11.10".to_string()), // This can change on CI, so we don't check it
+        version: "3.11.10".parse().ok(), // This can change on CI, so we don't check it
         symlinks: Some(vec![
             // For older versions of Python, we do not have a tonne of symlinks,
             // E.g. for 3.12.7 (which was the latest at some point, at a lot of symlinks)
@@ -107,15 +109,16 @@ fn verify_python_in_homebrew_contaner() {
         let python_env = environments
             .iter()
             .find(|e| e.executable == env.executable)
-            .expect(format!("Expected to find python environment {:?}", env.executable).as_str());
+            .unwrap_or_else(|| panic!("Expected to find python environment {:?}", env.executable));
         assert_eq!(python_env.executable, env.executable);
         assert_eq!(python_env.kind, env.kind);
         assert_eq!(python_env.manager, env.manager);
-        // Compare the first 4 parts (3.12)
-        assert_eq!(
-            python_env.version.clone().unwrap_or_default()[..4],
-            env.version.clone().unwrap_or_default()[..4]
-        );
+        // Compare the major & minor versions (3.12)
+        assert!(python_env
+            .version
+            .clone()
+            .unwrap()
+            .matches(&env.version.clone().unwrap(), VersionTolerance::MajorMinor));
 
         // We know the symlinks contain the full version, hence exclude the paths that contain the full version.
         let python_env_symlinks = python_env
@@ -125,10 +128,10 @@ fn verify_python_in_homebrew_contaner() {
             .into_iter()
             .filter(|p| {
                 !p.to_string_lossy()
-                    .contains(&env.version.clone().unwrap_or_default())
+                    .contains(&env.version.clone().unwrap().to_string())
                     && !p
                         .to_string_lossy()
-                        .contains(&python_env.version.clone().unwrap_or_default())
+                        .contains(&python_env.version.clone().unwrap().to_string())
             })
             .collect::<Vec<PathBuf>>();
         assert_eq!(
@@ -46,6 +46,7 @@ fn verify_python_in_jupyter_contaner() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -55,7 +56,7 @@ fn verify_python_in_jupyter_contaner() {
         name: Some("base".to_string()),
         executable: Some(PathBuf::from("/opt/conda/bin/python")),
         prefix: Some(PathBuf::from("/opt/conda")),
-        version: Some("3.12.3".to_string()),
+        version: "3.12.3".parse().ok(),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![
             PathBuf::from("/opt/conda/bin/python"),
@@ -67,6 +68,7 @@ fn verify_python_in_jupyter_contaner() {
             tool: EnvManagerType::Conda,
             executable: PathBuf::from("/opt/conda/bin/conda"),
             version: Some("24.5.0".to_string()),
+            install_path: Some(PathBuf::from("/opt/conda")),
         }),
         ..Default::default()
     };
@@ -74,7 +76,7 @@ fn verify_python_in_jupyter_contaner() {
         kind: Some(PythonEnvironmentKind::GlobalPaths),
         executable: Some(PathBuf::from("/home/codespace/.python/current/bin/python")),
         prefix: Some(PathBuf::from("/usr/local/python/3.10.13")),
-        version: Some("3.10.13.final.0".to_string()),
+        version: "3.10.13.final.0".parse().ok(),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![
             PathBuf::from("/home/codespace/.python/current/bin/python"),
@@ -88,7 +90,7 @@ fn verify_python_in_jupyter_contaner() {
         kind: Some(PythonEnvironmentKind::GlobalPaths),
         executable: Some(PathBuf::from("/usr/local/python/current/bin/python")),
         prefix: Some(PathBuf::from("/usr/local/python/3.10.13")),
-        version: Some("3.10.13.final.0".to_string()),
+        version: "3.10.13.final.0".parse().ok(),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![
             PathBuf::from("/usr/local/python/current/bin/python"),
@@ -102,7 +104,7 @@ fn verify_python_in_jupyter_contaner() {
         kind: Some(PythonEnvironmentKind::LinuxGlobal),
         executable: Some(PathBuf::from("/usr/bin/python3")),
         prefix: Some(PathBuf::from("/usr")),
-        version: Some("3.8.10.final.0".to_string()),
+        version: "3.8.10.final.0".parse().ok(),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![
             PathBuf::from("/usr/bin/python3"),
@@ -115,7 +117,7 @@ fn verify_python_in_jupyter_contaner() {
         kind: Some(PythonEnvironmentKind::LinuxGlobal),
         executable: Some(PathBuf::from("/bin/python3")),
         prefix: Some(PathBuf::from("/usr")),
-        version: Some("3.8.10.final.0".to_string()),
+        version: "3.8.10.final.0".parse().ok(),
         arch: Some(Architecture::X64),
         symlinks: Some(vec![
             PathBuf::from("/bin/python3"),
@@ -137,7 +139,7 @@ fn verify_python_in_jupyter_contaner() {
         let python_env = environments
             .iter()
             .find(|e| e.executable == env.executable)
-            .expect(format!("Expected to find python environment {:?}", env.executable).as_str());
+            .unwrap_or_else(|| panic!("Expected to find python environment {:?}", env.executable));
         assert_eq!(
             python_env.executable, env.executable,
             "Expected exe to be same when comparing {python_env:?} and {env:?}"
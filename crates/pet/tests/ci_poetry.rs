@@ -40,8 +40,10 @@ fn verify_ci_poetry_global() {
     let environment = EnvironmentApi::new();
     let conda_locator = Arc::new(Conda::from(&environment));
     let poetry_locator = Arc::new(Poetry::from(&environment));
-    let mut config = Configuration::default();
-    config.workspace_directories = Some(vec![workspace_dir.clone()]);
+    let config = Configuration {
+        workspace_directories: Some(vec![workspace_dir.clone()]),
+        ..Default::default()
+    };
     let locators = create_locators(conda_locator.clone(), poetry_locator.clone(), &environment);
     for locator in locators.iter() {
         locator.configure(&config);
@@ -53,6 +55,7 @@ fn verify_ci_poetry_global() {
         &locators,
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -81,11 +84,19 @@ fn verify_ci_poetry_global() {
 
     poetry_envs
         .iter()
-        .find(|e| e.version.clone().unwrap_or_default().starts_with("3.12"))
+        .find(|e| {
+            e.version
+                .as_ref()
+                .is_some_and(|v| (v.major, v.minor) == (3, 12))
+        })
         .expect("Python 3.12 not found");
     poetry_envs
         .iter()
-        .find(|e| e.version.clone().unwrap_or_default().starts_with("3.11"))
+        .find(|e| {
+            e.version
+                .as_ref()
+                .is_some_and(|v| (v.major, v.minor) == (3, 11))
+        })
         .expect("Python 3.12 not found");
 }
 
@@ -110,8 +121,10 @@ fn verify_ci_poetry_project() {
     let environment = EnvironmentApi::new();
     let conda_locator = Arc::new(Conda::from(&environment));
     let poetry_locator = Arc::new(Poetry::from(&environment));
-    let mut config = Configuration::default();
-    config.workspace_directories = Some(vec![workspace_dir.clone()]);
+    let config = Configuration {
+        workspace_directories: Some(vec![workspace_dir.clone()]),
+        ..Default::default()
+    };
     let locators = create_locators(conda_locator.clone(), poetry_locator.clone(), &environment);
     for locator in locators.iter() {
         locator.configure(&config);
@@ -123,6 +136,7 @@ fn verify_ci_poetry_project() {
         &locators,
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -152,9 +166,8 @@ fn verify_ci_poetry_project() {
     assert!(
         poetry_envs[0]
             .version
-            .clone()
-            .unwrap_or_default()
-            .starts_with("3.11"),
+            .as_ref()
+            .is_some_and(|v| (v.major, v.minor) == (3, 11)),
         "Python 3.11 not found"
     );
     assert_eq!(
@@ -1,7 +1,11 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::{path::PathBuf, sync::Once};
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{Mutex, Once},
+};
 
 use common::{does_version_match, resolve_test_path};
 use lazy_static::lazy_static;
@@ -75,8 +79,10 @@ fn verify_validity_of_discovered_envs() {
     let environment = EnvironmentApi::new();
     let conda_locator = Arc::new(Conda::from(&environment));
     let poetry_locator = Arc::new(Poetry::from(&environment));
-    let mut config = Configuration::default();
-    config.workspace_directories = Some(vec![workspace_dir.clone()]);
+    let config = Configuration {
+        workspace_directories: Some(vec![workspace_dir.clone()]),
+        ..Default::default()
+    };
     let locators = create_locators(conda_locator.clone(), poetry_locator.clone(), &environment);
     for locator in locators.iter() {
         locator.configure(&config);
@@ -89,6 +95,7 @@ fn verify_validity_of_discovered_envs() {
         &locators,
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -152,6 +159,7 @@ fn check_if_virtualenvwrapper_exists() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -193,6 +201,7 @@ fn check_if_pipenv_exists() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -204,7 +213,7 @@ fn check_if_pipenv_exists() {
             env.kind == Some(PythonEnvironmentKind::Pipenv)
                 && env.project == Some(workspace_dir.clone())
         })
-        .expect(format!("Pipenv environment not found, found {environments:?}").as_str());
+        .unwrap_or_else(|| panic!("Pipenv environment not found, found {environments:?}"));
 }
 
 #[cfg(unix)]
@@ -231,6 +240,7 @@ fn check_if_pyenv_virtualenv_exists() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -267,7 +277,7 @@ fn verify_validity_of_interpreter_info(environment: PythonEnvironment) {
                 .symlinks
                 .clone()
                 .unwrap_or_default()
-                .contains(&PathBuf::from(expected_executable)),
+                .contains(&expected_executable),
             "Executable mismatch for {:?}",
             environment.clone()
         );
@@ -282,6 +292,7 @@ fn verify_validity_of_interpreter_info(environment: PythonEnvironment) {
         }
     }
     if let Some(prefix) = environment.clone().prefix {
+        #[allow(clippy::if_same_then_else)]
         if interpreter_info.clone().executable == "/usr/local/python/current/bin/python"
             && (prefix.to_str().unwrap() == "/usr/local/python/current"
                 && interpreter_info.clone().sys_prefix == "/usr/local/python/3.10.13")
@@ -322,7 +333,7 @@ fn verify_validity_of_interpreter_info(environment: PythonEnvironment) {
     if let Some(version) = environment.clone().version {
         let expected_version = &interpreter_info.clone().sys_version;
         assert!(
-            does_version_match(&version, expected_version),
+            does_version_match(&version.to_string(), expected_version),
             "Version mismatch for (expected {:?} to start with {:?}) for {:?}",
             expected_version,
             version,
@@ -366,10 +377,9 @@ fn verify_we_can_get_same_env_info_using_from_with_exe(
     let env = PythonEnv::new(executable.clone(), None, None);
     let resolved =
         identify_python_environment_using_locators(&env, &locators, &global_env_search_paths)
-            .expect(
-                format!("Failed to resolve environment using `resolve` for {environment:?}")
-                    .as_str(),
-            );
+            .unwrap_or_else(|| {
+                panic!("Failed to resolve environment using `resolve` for {environment:?}")
+            });
     trace!(
         "For exe {:?} we got Environment = {:?}, To compare against {:?}",
         executable,
@@ -423,6 +433,7 @@ fn verify_we_can_get_same_env_info_using_find_with_exe(
         &locators,
         &reporter,
         &global_env_search_paths,
+        &Mutex::new(HashSet::new()),
     );
 
     let envs = collect_reporter.environments.lock().unwrap().clone();
@@ -467,7 +478,7 @@ fn compare_environments(actual: PythonEnvironment, expected: PythonEnvironment,
         (expected.clone().version, actual.clone().version)
     {
         assert!(
-            does_version_match(&version, &expected_version),
+            does_version_match(&version.to_string(), &expected_version.to_string()),
             "Version mismatch when using {} for (expected {:?} to start with {:?}) for env = {:?} and environment = {:?}",
             method,
             expected_version,
@@ -490,6 +501,7 @@ fn compare_environments(actual: PythonEnvironment, expected: PythonEnvironment,
     actual.version = expected.clone().version;
 
     if let Some(prefix) = expected.clone().prefix {
+        #[allow(clippy::if_same_then_else)]
         if actual.clone().executable == Some(PathBuf::from("/usr/local/python/current/bin/python"))
             && (prefix.to_str().unwrap() == "/usr/local/python/current"
                 && actual.clone().prefix == Some(PathBuf::from("/usr/local/python/3.10.13")))
@@ -518,13 +530,8 @@ fn compare_environments(actual: PythonEnvironment, expected: PythonEnvironment,
             .iter()
             .filter(|p| {
                 // This is in the path, but not easy to figure out, unless we add support for codespaces or CI.
-                if p.starts_with("/Users/runner/hostedtoolcache/Python")
-                    && p.to_string_lossy().contains("arm64")
-                {
-                    false
-                } else {
-                    true
-                }
+                !(p.starts_with("/Users/runner/hostedtoolcache/Python")
+                    && p.to_string_lossy().contains("arm64"))
             })
             .map(|p| p.to_path_buf())
             .collect::<Vec<PathBuf>>(),
@@ -537,37 +544,34 @@ fn compare_environments(actual: PythonEnvironment, expected: PythonEnvironment,
             .iter()
             .filter(|p| {
                 // This is in the path, but not easy to figure out, unless we add support for codespaces or CI.
-                if p.starts_with("/Users/runner/hostedtoolcache/Python")
-                    && p.to_string_lossy().contains("arm64")
-                {
-                    false
-                } else {
-                    true
-                }
+                !(p.starts_with("/Users/runner/hostedtoolcache/Python")
+                    && p.to_string_lossy().contains("arm64"))
             })
             .map(|p| p.to_path_buf())
             .collect::<Vec<PathBuf>>(),
     );
 
     // if we know the arch, then verify it
-    if expected.arch.as_ref().is_some() && actual.arch.as_ref().is_some() {
-        if actual.arch.as_ref() != expected.arch.as_ref() {
-            error!(
-                "Arch mismatch when using {} for {:?} and {:?}",
-                method, expected, actual
-            );
-        }
+    if expected.arch.as_ref().is_some()
+        && actual.arch.as_ref().is_some()
+        && actual.arch.as_ref() != expected.arch.as_ref()
+    {
+        error!(
+            "Arch mismatch when using {} for {:?} and {:?}",
+            method, expected, actual
+        );
     }
     actual.arch = expected.clone().arch;
 
     // if we know the prefix, then verify it
-    if expected.prefix.as_ref().is_some() && actual.prefix.as_ref().is_some() {
-        if actual.prefix.as_ref() != expected.prefix.as_ref() {
-            error!(
-                "Prefirx mismatch when using {} for {:?} and {:?}",
-                method, expected, actual
-            );
-        }
+    if expected.prefix.as_ref().is_some()
+        && actual.prefix.as_ref().is_some()
+        && actual.prefix.as_ref() != expected.prefix.as_ref()
+    {
+        error!(
+            "Prefirx mismatch when using {} for {:?} and {:?}",
+            method, expected, actual
+        );
     }
     actual.prefix = expected.clone().prefix;
 
@@ -602,8 +606,10 @@ fn verify_we_can_get_same_env_info_using_resolve_with_exe(
     let os_environment = EnvironmentApi::new();
     let conda_locator = Arc::new(Conda::from(&os_environment));
     let poetry_locator = Arc::new(Poetry::from(&os_environment));
-    let mut config = Configuration::default();
-    config.workspace_directories = Some(vec![workspace_dir.clone()]);
+    let config = Configuration {
+        workspace_directories: Some(vec![workspace_dir.clone()]),
+        ..Default::default()
+    };
     let locators = create_locators(
         conda_locator.clone(),
         poetry_locator.clone(),
@@ -613,9 +619,9 @@ fn verify_we_can_get_same_env_info_using_resolve_with_exe(
         locator.configure(&config);
     }
 
-    let env = resolve_environment(&executable, &locators, &os_environment).expect(
-        format!("Failed to resolve environment using `resolve` for {environment:?}").as_str(),
-    );
+    let env = resolve_environment(executable, &locators, &os_environment).unwrap_or_else(|| {
+        panic!("Failed to resolve environment using `resolve` for {environment:?}")
+    });
     trace!(
         "For exe {:?} we got Environment = {:?}, To compare against {:?}",
         executable,
@@ -659,6 +665,7 @@ fn verify_bin_usr_bin_user_local_are_separate_python_envs() {
         &create_locators(conda_locator.clone(), poetry_locator.clone(), &environment),
         &environment,
         None,
+        None,
     );
 
     let environments = reporter.environments.lock().unwrap().clone();
@@ -723,21 +730,21 @@ fn get_python_run_command(env: &PythonEnvironment) -> Vec<String> {
             None => get_conda_exe().to_string(),
         };
         if let Some(name) = env.name.clone() {
-            return vec![
+            vec![
                 conda_exe,
                 "run".to_string(),
                 "-n".to_string(),
                 name,
                 "python".to_string(),
-            ];
+            ]
         } else if let Some(prefix) = env.prefix.clone() {
-            return vec![
+            vec![
                 conda_exe,
                 "run".to_string(),
                 "-p".to_string(),
                 prefix.to_str().unwrap_or_default().to_string(),
                 "python".to_string(),
-            ];
+            ]
         } else {
             panic!("Conda environment without name or prefix")
         }
@@ -752,8 +759,8 @@ fn get_python_run_command(env: &PythonEnvironment) -> Vec<String> {
     }
 }
 
-fn get_python_interpreter_info(cli: &Vec<String>) -> InterpreterInfo {
-    let mut cli = cli.clone();
+fn get_python_interpreter_info(cli: &[String]) -> InterpreterInfo {
+    let mut cli = cli.to_vec();
     cli.push(
         resolve_test_path(&["interpreterInfo.py"])
             .to_str()
@@ -764,12 +771,12 @@ fn get_python_interpreter_info(cli: &Vec<String>) -> InterpreterInfo {
     let output = std::process::Command::new(cli.first().unwrap())
         .args(&cli[1..])
         .output()
-        .expect(format!("Failed to execute command {cli:?}").as_str());
+        .unwrap_or_else(|_| panic!("Failed to execute command {cli:?}"));
     let output = String::from_utf8(output.stdout).unwrap();
     let output = output
         .split_once("503bebe7-c838-4cea-a1bc-0f2963bcb657")
         .unwrap()
         .1;
-    let info: InterpreterInfo = serde_json::from_str(&output).unwrap();
+    let info: InterpreterInfo = serde_json::from_str(output).unwrap();
     info
 }
@@ -0,0 +1,136 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet_reporter::json::FindResult;
+use std::{fs, path::PathBuf, process::Command};
+
+/// Creates a workspace folder with a virtual env (that does not require spawning Python to be identified).
+fn create_workspace(name: &str) -> (PathBuf, PathBuf) {
+    let workspace = fs::canonicalize(std::env::temp_dir()).unwrap().join(name);
+    let _ = fs::remove_dir_all(&workspace);
+    let env = workspace.join(".venv");
+    let bin = env.join(if cfg!(windows) { "Scripts" } else { "bin" });
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        env.join("pyvenv.cfg"),
+        "home = /usr/bin\nversion = 3.12.1\n",
+    )
+    .unwrap();
+    let python = bin.join(if cfg!(windows) {
+        "python.exe"
+    } else {
+        "python"
+    });
+    fs::write(&python, "").unwrap();
+    (workspace, python)
+}
+
+#[test]
+fn find_with_json_output_format() {
+    let (workspace, python) = create_workspace("pet_cli_test_json_output");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pet"))
+        .arg("find")
+        .arg("--workspace")
+        .arg("--output-format")
+        .arg("json")
+        .arg(&workspace)
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result: FindResult = serde_json::from_str(&stdout)
+        .unwrap_or_else(|err| panic!("Invalid JSON output ({err}): {stdout}"));
+    assert!(result
+        .environments
+        .iter()
+        .any(|env| env.executable.as_ref() == Some(&python)));
+    assert_eq!(result.summary.total_environments, result.environments.len());
+}
+
+#[test]
+fn find_with_jsonl_output_format() {
+    let (workspace, python) = create_workspace("pet_cli_test_jsonl_output");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pet"))
+        .arg("find")
+        .arg("--workspace")
+        .arg("--output-format")
+        .arg("jsonl")
+        .arg(&workspace)
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .unwrap_or_else(|err| panic!("Invalid JSON line ({err}): {line}"))
+        })
+        .collect::<Vec<_>>();
+    assert!(lines.iter().any(|line| line["type"] == "environment"
+        && line["data"]["executable"] == python.to_str().unwrap()));
+    assert_eq!(lines.last().unwrap()["type"], "summary");
+}
+
+#[test]
+fn resolve_with_json_output_format() {
+    let (workspace, python) = create_workspace("pet_cli_test_resolve_json_output");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pet"))
+        .arg("resolve")
+        .arg("--output-format")
+        .arg("json")
+        .arg(&python)
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let env: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|err| panic!("Invalid JSON output ({err}): {stdout}"));
+    assert_eq!(env["executable"], python.to_str().unwrap());
+    assert_eq!(env["kind"], "Venv");
+}
+
+#[test]
+fn find_with_kind_filter() {
+    let (workspace, python) = create_workspace("pet_cli_test_kind_filter");
+
+    let find = |kind_filter: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_pet"))
+            .arg("find")
+            .arg("--workspace")
+            .arg("--kind-filter")
+            .arg(kind_filter)
+            .arg("--output-format")
+            .arg("json")
+            .arg(&workspace)
+            .current_dir(&workspace)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result: FindResult = serde_json::from_str(&stdout)
+            .unwrap_or_else(|err| panic!("Invalid JSON output ({err}): {stdout}"));
+        result
+            .environments
+            .iter()
+            .any(|env| env.executable.as_ref() == Some(&python) && env.kind.is_some())
+    };
+    let found_with_venv = find("conda,venv");
+    let found_without_venv = find("conda,pixi");
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert!(found_with_venv);
+    assert!(!found_without_venv);
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use pet::find::find_and_report_envs;
+use pet_core::{
+    env::PythonEnv,
+    manager::{EnvManager, EnvManagerType},
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_reporter::collect;
+use serde_json::Value;
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Logger that keeps the messages logged at info level.
+struct CapturingLogger {
+    messages: Mutex<Vec<String>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::Level::Info
+    }
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(record.args().to_string());
+        }
+    }
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    messages: Mutex::new(vec![]),
+};
+
+/// Locator that reports two environments managed by the same manager.
+struct StaticLocator;
+
+impl Locator for StaticLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "StaticLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+        None
+    }
+    fn find(&self, reporter: &dyn Reporter) {
+        let manager = EnvManager::new(
+            PathBuf::from("/home/user/.local/bin/poetry"),
+            EnvManagerType::Poetry,
+            None,
+        );
+        for project in ["one", "two"] {
+            reporter.report_manager(&manager);
+            reporter.report_environment(&PythonEnvironment {
+                executable: Some(PathBuf::from(format!(
+                    "/home/user/{}/.venv/bin/python",
+                    project
+                ))),
+                kind: Some(PythonEnvironmentKind::Venv),
+                manager: Some(manager.clone()),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// Environment without a home directory, environment variables nor global locations.
+struct EmptyEnvironment;
+
+impl Environment for EmptyEnvironment {
+    fn get_user_home(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_root(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_env_var(&self, _key: String) -> Option<String> {
+        None
+    }
+    fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+#[test]
+fn logs_machine_readable_event_when_find_completes() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Info);
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(StaticLocator)]);
+    let reporter = collect::create_reporter();
+    let summary = find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &EmptyEnvironment,
+        None,
+        None,
+    );
+
+    let event = LOGGER
+        .messages
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|message| serde_json::from_str::<Value>(message).ok())
+        .find(|value| value["event"] == "find_complete")
+        .expect("find_complete event not logged");
+
+    assert!(event["validation_ms"].is_u64());
+    assert!(event["search_ms"].is_u64());
+    assert_eq!(event["total_envs"].as_u64(), Some(2));
+    assert_eq!(event["total_managers"].as_u64(), Some(1));
+
+    let summary = summary.lock().unwrap();
+    assert_eq!(summary.total_environments, 2);
+    assert_eq!(summary.total_managers, 1);
+}
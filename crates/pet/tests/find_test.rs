@@ -0,0 +1,897 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet::find::{
+    find_and_report_envs, find_python_environments_in_workspace_folder_recursive,
+    identify_python_executables_using_locators, SearchScope,
+};
+use pet::find_environments_streaming;
+use pet_core::{
+    env::PythonEnv,
+    manager::EnvManager,
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentBuilder, PythonEnvironmentKind},
+    reporter::Reporter,
+    Configuration, Locator, LocatorKind,
+};
+use pet_reporter::collect;
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Locator that never identifies any environment, but counts the number of times it was asked to.
+#[derive(Default)]
+struct CountingLocator {
+    try_from_count: AtomicUsize,
+}
+
+impl Locator for CountingLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "CountingLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+        self.try_from_count.fetch_add(1, Ordering::SeqCst);
+        None
+    }
+    fn find(&self, _reporter: &dyn Reporter) {
+        //
+    }
+}
+
+/// Reporter that records the progress reported.
+#[derive(Default)]
+struct MockReporter {
+    progress: Mutex<Vec<(usize, Option<usize>)>>,
+}
+
+impl Reporter for MockReporter {
+    fn report_manager(&self, _manager: &EnvManager) {
+        //
+    }
+    fn report_environment(&self, _env: &PythonEnvironment) {
+        //
+    }
+    fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
+        //
+    }
+    fn report_progress(&self, completed: usize, total: Option<usize>) {
+        self.progress.lock().unwrap().push((completed, total));
+    }
+}
+
+/// Environment without a home directory, environment variables nor global locations.
+struct EmptyEnvironment;
+
+impl Environment for EmptyEnvironment {
+    fn get_user_home(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_root(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_env_var(&self, _key: String) -> Option<String> {
+        None
+    }
+    fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+#[test]
+fn unknown_executables_are_only_identified_once() {
+    let locator = Arc::new(CountingLocator::default());
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![locator.clone()]);
+    let reporter = collect::create_reporter();
+    // Executable does not exist, hence cannot be resolved by spawning it either.
+    let exe = PathBuf::from("/pet_find_test/does_not_exist/bin/python");
+    let unknown_executables = Mutex::new(HashSet::new());
+
+    identify_python_executables_using_locators(
+        vec![exe.clone(), exe.clone()],
+        &locators,
+        &reporter,
+        &[],
+        &unknown_executables,
+    );
+    identify_python_executables_using_locators(
+        vec![exe.clone()],
+        &locators,
+        &reporter,
+        &[],
+        &unknown_executables,
+    );
+
+    assert_eq!(locator.try_from_count.load(Ordering::SeqCst), 1);
+    assert!(reporter.environments.lock().unwrap().is_empty());
+
+    // A new search tries to identify the executable again.
+    identify_python_executables_using_locators(
+        vec![exe.clone()],
+        &locators,
+        &reporter,
+        &[],
+        &Mutex::new(HashSet::new()),
+    );
+
+    assert_eq!(locator.try_from_count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn do_not_search_workspaces_nor_global_virtual_envs_when_there_are_none() {
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![]);
+    let reporter = collect::create_reporter();
+
+    let summary = find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &EmptyEnvironment,
+        None,
+        None,
+    );
+
+    let summary = summary.lock().unwrap();
+    assert!(summary.breakdown.contains_key("Locators"));
+    assert!(!summary.breakdown.contains_key("GlobalVirtualEnvs"));
+    assert!(!summary.breakdown.contains_key("Workspaces"));
+}
+
+#[test]
+fn progress_is_reported_after_each_locator_completes() {
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![
+        Arc::new(CountingLocator::default()),
+        Arc::new(CountingLocator::default()),
+        Arc::new(CountingLocator::default()),
+    ]);
+    let reporter = MockReporter::default();
+
+    find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &EmptyEnvironment,
+        None,
+        None,
+    );
+
+    assert_eq!(
+        *reporter.progress.lock().unwrap(),
+        vec![(1, Some(3)), (2, Some(3)), (3, Some(3))]
+    );
+}
+
+/// Locator that reports the same environment twice.
+struct DuplicateReportingLocator;
+
+impl Locator for DuplicateReportingLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "DuplicateReportingLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+        None
+    }
+    fn find(&self, reporter: &dyn Reporter) {
+        let env = PythonEnvironment {
+            executable: Some(PathBuf::from("/home/user/project/.venv/bin/python")),
+            kind: Some(PythonEnvironmentKind::Venv),
+            ..Default::default()
+        };
+        reporter.report_environment(&env);
+        reporter.report_environment(&env);
+    }
+}
+
+#[test]
+fn find_environments_using_closure() {
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(DuplicateReportingLocator)]);
+    let mut environments = vec![];
+
+    find_environments_streaming(
+        Configuration::default(),
+        &locators,
+        &EmptyEnvironment,
+        |env| environments.push(env),
+    );
+
+    assert_eq!(environments.len(), 1);
+    assert_eq!(
+        environments[0].executable,
+        Some(PathBuf::from("/home/user/project/.venv/bin/python"))
+    );
+}
+
+/// Locator that reports a virtual env with packages installed in its site-packages directory.
+#[cfg(unix)]
+struct SitePackagesLocator;
+
+#[cfg(unix)]
+impl Locator for SitePackagesLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "SitePackagesLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+        None
+    }
+    fn find(&self, reporter: &dyn Reporter) {
+        let mut prefix = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        prefix.pop();
+        let prefix = prefix.join("pet-python-utils/tests/unix/site_packages/.venv");
+        reporter.report_environment(&PythonEnvironment {
+            executable: Some(prefix.join("bin").join("python")),
+            prefix: Some(prefix),
+            kind: Some(PythonEnvironmentKind::Venv),
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn packages_are_only_listed_when_enabled() {
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(SitePackagesLocator)]);
+
+    for scan_packages in [None, Some(false), Some(true)] {
+        let mut environments = vec![];
+        find_environments_streaming(
+            Configuration {
+                scan_packages,
+                ..Default::default()
+            },
+            &locators,
+            &EmptyEnvironment,
+            |env| environments.push(env),
+        );
+
+        assert_eq!(environments.len(), 1);
+        let packages = environments[0].packages.clone().map(|packages| {
+            packages
+                .into_iter()
+                .map(|package| format!("{}=={}", package.name, package.version))
+                .collect::<Vec<_>>()
+        });
+        if scan_packages == Some(true) {
+            assert_eq!(
+                packages,
+                Some(vec![
+                    "mypy==1.10.0".to_string(),
+                    "pytest==8.0.0".to_string()
+                ])
+            );
+        } else {
+            assert_eq!(packages, None);
+        }
+    }
+}
+
+/// Locator that identifies every executable as a virtual env.
+struct IdentifyAllLocator;
+
+impl Locator for IdentifyAllLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "IdentifyAllLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        Some(PythonEnvironment {
+            executable: Some(env.executable.clone()),
+            kind: Some(PythonEnvironmentKind::Venv),
+            ..Default::default()
+        })
+    }
+    fn find(&self, _reporter: &dyn Reporter) {
+        //
+    }
+}
+
+/// Environment with a home directory and the given global locations.
+struct GlobalLocationsEnvironment {
+    home: PathBuf,
+    global_locations: Vec<PathBuf>,
+}
+
+impl Environment for GlobalLocationsEnvironment {
+    fn get_user_home(&self) -> Option<PathBuf> {
+        Some(self.home.clone())
+    }
+    fn get_root(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_env_var(&self, _key: String) -> Option<String> {
+        None
+    }
+    fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+        self.global_locations.clone()
+    }
+}
+
+#[test]
+fn duplicate_global_locations_are_searched_once() {
+    let root = std::fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_test_duplicate_global_locations");
+    let bin = root.join("bin");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::write(bin.join("python"), "").unwrap();
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(IdentifyAllLocator)]);
+    let reporter = collect::create_reporter();
+    let environment = GlobalLocationsEnvironment {
+        home: root.clone(),
+        global_locations: vec![bin.clone(), bin.clone()],
+    };
+
+    find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &environment,
+        None,
+        None,
+    );
+
+    let environments = reporter.environments.lock().unwrap().clone();
+    let _ = std::fs::remove_dir_all(&root);
+    assert_eq!(environments.len(), 1);
+    assert_eq!(environments[0].executable, Some(bin.join("python")));
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinked_workspace_directories_are_only_followed_when_enabled() {
+    use std::{fs, os::unix::fs::symlink};
+
+    let root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_test_follow_symlinks");
+    let _ = fs::remove_dir_all(&root);
+    let workspace = root.join("workspace");
+    let shared = root.join("shared").join("env");
+    for env in [&workspace.join("env"), &shared] {
+        fs::create_dir_all(env.join("bin")).unwrap();
+        fs::write(env.join("bin").join("python"), "").unwrap();
+    }
+    symlink(&shared, workspace.join("linked_env")).unwrap();
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(IdentifyAllLocator)]);
+    let find = |follow_symlinks: bool| {
+        let reporter = collect::create_reporter();
+        find_python_environments_in_workspace_folder_recursive(
+            &workspace,
+            &reporter,
+            &locators,
+            &[],
+            &Mutex::new(HashSet::new()),
+            &[],
+            follow_symlinks,
+            &[],
+            1,
+            None,
+        );
+        let mut executables = reporter
+            .environments
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.executable.clone().unwrap())
+            .collect::<Vec<_>>();
+        executables.sort();
+        executables
+    };
+
+    let followed = find(true);
+    let not_followed = find(false);
+    // Symlinks are not followed unless enabled in the configuration.
+    let reporter = collect::create_reporter();
+    find_and_report_envs(
+        &reporter,
+        Configuration {
+            workspace_directories: Some(vec![workspace.clone()]),
+            ..Default::default()
+        },
+        &locators,
+        &EmptyEnvironment,
+        Some(SearchScope::Workspace),
+        None,
+    );
+    let by_default = reporter
+        .environments
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|e| e.executable.clone().unwrap())
+        .collect::<Vec<_>>();
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(
+        followed,
+        vec![
+            workspace.join("env").join("bin").join("python"),
+            workspace.join("linked_env").join("bin").join("python"),
+        ]
+    );
+    assert_eq!(
+        not_followed,
+        vec![workspace.join("env").join("bin").join("python")]
+    );
+    assert_eq!(by_default, not_followed);
+}
+
+#[test]
+fn environments_in_excluded_paths_are_not_reported() {
+    use std::fs;
+
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_test_exclude_paths");
+    let _ = fs::remove_dir_all(&workspace);
+    let bin = if cfg!(windows) { "Scripts" } else { "bin" };
+    let python = if cfg!(windows) {
+        "python.exe"
+    } else {
+        "python"
+    };
+    for env in ["env", "data"] {
+        let bin = workspace.join(env).join(bin);
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join(python), "").unwrap();
+    }
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(IdentifyAllLocator)]);
+    let find = |exclude_paths: &[PathBuf]| {
+        let reporter = collect::create_reporter();
+        find_python_environments_in_workspace_folder_recursive(
+            &workspace,
+            &reporter,
+            &locators,
+            &[],
+            &Mutex::new(HashSet::new()),
+            &[],
+            true,
+            exclude_paths,
+            1,
+            None,
+        );
+        let mut executables = reporter
+            .environments
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.executable.clone().unwrap())
+            .collect::<Vec<_>>();
+        executables.sort();
+        executables
+    };
+
+    let all = find(&[]);
+    let excluded = find(&[workspace.join("data")]);
+    let workspace_excluded = find(std::slice::from_ref(&workspace));
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert_eq!(
+        all,
+        vec![
+            workspace.join("data").join(bin).join(python),
+            workspace.join("env").join(bin).join(python),
+        ]
+    );
+    assert_eq!(excluded, vec![workspace.join("env").join(bin).join(python)]);
+    assert!(workspace_excluded.is_empty());
+}
+
+#[test]
+fn environments_are_only_found_up_to_the_max_search_depth() {
+    use std::fs;
+
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_test_max_search_depth");
+    let _ = fs::remove_dir_all(&workspace);
+    let bin = if cfg!(windows) { "Scripts" } else { "bin" };
+    let python = if cfg!(windows) {
+        "python.exe"
+    } else {
+        "python"
+    };
+    // The environment is in the 3rd level of sub directories.
+    let env = workspace.join("projects").join("project_a").join("env");
+    fs::create_dir_all(env.join(bin)).unwrap();
+    fs::write(env.join(bin).join(python), "").unwrap();
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(IdentifyAllLocator)]);
+    let find = |max_depth: u32| {
+        let reporter = collect::create_reporter();
+        find_python_environments_in_workspace_folder_recursive(
+            &workspace,
+            &reporter,
+            &locators,
+            &[],
+            &Mutex::new(HashSet::new()),
+            &[],
+            true,
+            &[],
+            max_depth,
+            None,
+        );
+        let executables = reporter
+            .environments
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.executable.clone().unwrap())
+            .collect::<Vec<_>>();
+        executables
+    };
+
+    let depth_3 = find(3);
+    let depth_1 = find(1);
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert_eq!(depth_3, vec![env.join(bin).join(python)]);
+    assert!(depth_1.is_empty());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_find_reports_the_same_as_sync_find() {
+    use pet::find_async::find_and_report_envs_async;
+    use std::future::Future;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    /// Minimal executor, wakes the thread blocked on the future.
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(DuplicateReportingLocator)]);
+    let reporter = collect::create_reporter();
+    let summary = find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &EmptyEnvironment,
+        None,
+        None,
+    );
+    let async_reporter = Arc::new(collect::create_reporter());
+
+    let async_summary = block_on(find_and_report_envs_async(
+        async_reporter.clone(),
+        Configuration::default(),
+        locators.clone(),
+        Arc::new(EmptyEnvironment),
+        None,
+        None,
+    ));
+
+    let summary = summary.lock().unwrap();
+    let async_summary = async_summary.lock().unwrap();
+    assert_eq!(async_summary.total_environments, summary.total_environments);
+    assert_eq!(async_summary.total_managers, summary.total_managers);
+    assert_eq!(
+        async_summary.locators.keys().collect::<Vec<_>>(),
+        summary.locators.keys().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        async_summary.breakdown.keys().collect::<Vec<_>>(),
+        summary.breakdown.keys().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        async_reporter.environments.lock().unwrap().len(),
+        reporter.environments.lock().unwrap().len()
+    );
+}
+
+/// Locator that takes a while to identify every executable as a virtual env.
+struct SlowLocator;
+
+impl Locator for SlowLocator {
+    fn get_kind(&self) -> LocatorKind {
+        LocatorKind::Venv
+    }
+    fn description(&self) -> &'static str {
+        "SlowLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![PythonEnvironmentKind::Venv]
+    }
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        IdentifyAllLocator.try_from(env)
+    }
+    fn find(&self, _reporter: &dyn Reporter) {
+        //
+    }
+}
+
+/// Reporter that cancels the search once the first environment has been reported.
+struct CancellingReporter {
+    token: pet_core::cancellation::CancellationToken,
+    environments: AtomicUsize,
+}
+
+impl Reporter for CancellingReporter {
+    fn report_manager(&self, _manager: &EnvManager) {
+        //
+    }
+    fn report_environment(&self, _env: &PythonEnvironment) {
+        self.environments.fetch_add(1, Ordering::SeqCst);
+        self.token.cancel();
+    }
+    fn report_telemetry(&self, _event: &pet_core::telemetry::TelemetryEvent) {
+        //
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn search_stops_once_cancelled() {
+    use pet_core::cancellation::CancellationToken;
+
+    let root = std::fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_find_test_cancellation");
+    let bin = root.join("bin");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&bin).unwrap();
+    let total = 40;
+    std::fs::write(bin.join("python"), "").unwrap();
+    for i in 1..total {
+        std::fs::write(bin.join(format!("python3.{i}")), "").unwrap();
+    }
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(SlowLocator)]);
+    let token = CancellationToken::new();
+    let reporter = CancellingReporter {
+        token: token.clone(),
+        environments: AtomicUsize::new(0),
+    };
+    let environment = GlobalLocationsEnvironment {
+        home: root.clone(),
+        global_locations: vec![bin.clone()],
+    };
+
+    let start = std::time::Instant::now();
+    find_and_report_envs(
+        &reporter,
+        Configuration::default(),
+        &locators,
+        &environment,
+        None,
+        Some(token.clone()),
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+    assert!(token.is_cancelled());
+    assert_eq!(reporter.environments.load(Ordering::SeqCst), 1);
+    // Identifying all of the executables would take at least 2s.
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+}
+
+/// Locator that records whether it was used to search for environments.
+struct RecordingLocator {
+    kind: LocatorKind,
+    category: PythonEnvironmentKind,
+    searched: AtomicUsize,
+}
+
+impl RecordingLocator {
+    fn new(kind: LocatorKind, category: PythonEnvironmentKind) -> Arc<Self> {
+        Arc::new(RecordingLocator {
+            kind,
+            category,
+            searched: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl Locator for RecordingLocator {
+    fn get_kind(&self) -> LocatorKind {
+        self.kind.clone()
+    }
+    fn description(&self) -> &'static str {
+        "RecordingLocator"
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![self.category]
+    }
+    fn try_from(&self, _env: &PythonEnv) -> Option<PythonEnvironment> {
+        None
+    }
+    fn find(&self, _reporter: &dyn Reporter) {
+        self.searched.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn only_locators_matching_the_kind_filter_are_used() {
+    let poetry = RecordingLocator::new(LocatorKind::Poetry, PythonEnvironmentKind::Poetry);
+    let conda = RecordingLocator::new(LocatorKind::Conda, PythonEnvironmentKind::Conda);
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![conda.clone(), poetry.clone()]);
+    let reporter = collect::create_reporter();
+
+    let summary = find_and_report_envs(
+        &reporter,
+        Configuration {
+            kind_filter: Some(vec![PythonEnvironmentKind::Poetry]),
+            ..Default::default()
+        },
+        &locators,
+        &EmptyEnvironment,
+        None,
+        None,
+    );
+
+    assert_eq!(poetry.searched.load(Ordering::SeqCst), 1);
+    assert_eq!(conda.searched.load(Ordering::SeqCst), 0);
+    assert_eq!(
+        summary
+            .lock()
+            .unwrap()
+            .locators
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>(),
+        vec![LocatorKind::Poetry]
+    );
+}
+
+#[test]
+fn filter_locators_by_kind() {
+    use pet::locators::{create_locators, filter_locators};
+    use pet_conda::Conda;
+    use pet_poetry::Poetry;
+
+    let locators = create_locators(
+        Arc::new(Conda::from(&EmptyEnvironment)),
+        Arc::new(Poetry::from(&EmptyEnvironment)),
+        &EmptyEnvironment,
+    );
+
+    let kinds = |kind_filter: &[PythonEnvironmentKind]| {
+        filter_locators(&locators, kind_filter)
+            .iter()
+            .map(|l| l.get_kind())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        kinds(&[PythonEnvironmentKind::Poetry]),
+        vec![LocatorKind::Poetry]
+    );
+    assert!(kinds(&[]).is_empty());
+}
+
+/// Locator that identifies every environment as its own kind.
+struct PriorityLocator {
+    kind: LocatorKind,
+    category: PythonEnvironmentKind,
+    priority: u8,
+}
+
+impl Locator for PriorityLocator {
+    fn get_kind(&self) -> LocatorKind {
+        self.kind.clone()
+    }
+    fn description(&self) -> &'static str {
+        "PriorityLocator"
+    }
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+    fn supported_categories(&self) -> Vec<PythonEnvironmentKind> {
+        vec![self.category]
+    }
+    fn try_from(&self, env: &PythonEnv) -> Option<PythonEnvironment> {
+        Some(
+            PythonEnvironmentBuilder::new(Some(self.category))
+                .executable(Some(env.executable.clone()))
+                .build(),
+        )
+    }
+    fn find(&self, _reporter: &dyn Reporter) {
+        //
+    }
+}
+
+#[test]
+fn locator_with_the_lowest_priority_identifies_the_environment() {
+    use pet::locators::identify_python_environment_using_locators;
+
+    let venv: Arc<dyn Locator> = Arc::new(PriorityLocator {
+        kind: LocatorKind::Venv,
+        category: PythonEnvironmentKind::Venv,
+        priority: 200,
+    });
+    let conda: Arc<dyn Locator> = Arc::new(PriorityLocator {
+        kind: LocatorKind::Conda,
+        category: PythonEnvironmentKind::Conda,
+        priority: 10,
+    });
+    let env = PythonEnv::new(PathBuf::from("/opt/envs/ml/bin/python"), None, None);
+
+    let identified = identify_python_environment_using_locators(&env, &[venv, conda], &[]);
+
+    assert_eq!(identified.unwrap().kind, Some(PythonEnvironmentKind::Conda));
+}
+
+#[test]
+fn locators_are_created_in_the_order_of_their_priority() {
+    use pet::locators::create_locators;
+    use pet_conda::Conda;
+    use pet_poetry::Poetry;
+
+    let locators = create_locators(
+        Arc::new(Conda::from(&EmptyEnvironment)),
+        Arc::new(Poetry::from(&EmptyEnvironment)),
+        &EmptyEnvironment,
+    );
+
+    let priorities = locators.iter().map(|l| l.priority()).collect::<Vec<_>>();
+    let mut sorted = priorities.clone();
+    sorted.sort();
+    assert_eq!(priorities, sorted);
+}
+
+#[test]
+fn all_locators_have_a_description() {
+    use pet::locators::create_locators;
+    use pet_conda::Conda;
+    use pet_poetry::Poetry;
+
+    let locators = create_locators(
+        Arc::new(Conda::from(&EmptyEnvironment)),
+        Arc::new(Poetry::from(&EmptyEnvironment)),
+        &EmptyEnvironment,
+    );
+
+    for locator in locators.iter() {
+        assert!(
+            !locator.description().trim().is_empty(),
+            "{:?} does not have a description",
+            locator.get_kind()
+        );
+    }
+}
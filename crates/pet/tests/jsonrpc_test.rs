@@ -0,0 +1,406 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet::locators::create_locators;
+use pet_conda::Conda;
+use pet_core::os_environment::EnvironmentApi;
+use pet_poetry::Poetry;
+use serde_json::{json, Value};
+use std::{
+    ffi::OsStr,
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::Arc,
+};
+
+/// Minimal JSON-RPC client for the server started with `pet server`.
+struct Client {
+    server: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u32,
+}
+
+impl Client {
+    fn start() -> Self {
+        Client::start_with_env(&[])
+    }
+    /// Starts the server with additional environment variables.
+    fn start_with_env(vars: &[(&str, &OsStr)]) -> Self {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_pet"))
+            .arg("server")
+            .envs(vars.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = server.stdin.take().unwrap();
+        let stdout = BufReader::new(server.stdout.take().unwrap());
+        Client {
+            server,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+    /// Sends the request & returns the result, ignoring any notifications received in the mean time.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.send_request(method, params);
+        let message = self.read_response(id);
+        assert!(message.get("error").is_none(), "{message}");
+        message["result"].clone()
+    }
+    /// Sends the request without waiting for the response, returns the id of the request.
+    fn send_request(&mut self, method: &str, params: Value) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        id
+    }
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+    fn send(&mut self, message: Value) {
+        let message = message.to_string();
+        write!(
+            self.stdin,
+            "Content-Length: {}\r\n\r\n{}",
+            message.len(),
+            message
+        )
+        .unwrap();
+        self.stdin.flush().unwrap();
+    }
+    /// Returns the response of the request, ignoring any notifications received in the mean time.
+    fn read_response(&mut self, id: u32) -> Value {
+        loop {
+            let message = self.read_message();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+    /// Returns the params of the next notification with the given method, ignoring any other messages.
+    #[cfg(feature = "watch")]
+    fn read_notification(&mut self, method: &str) -> Value {
+        loop {
+            let message = self.read_message();
+            if message["method"] == method {
+                return message["params"].clone();
+            }
+        }
+    }
+    fn read_message(&mut self) -> Value {
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            assert!(
+                self.stdout.read_line(&mut line).unwrap() > 0,
+                "Server exited"
+            );
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(length) = line.strip_prefix("Content-Length: ") {
+                content_length = Some(length.parse::<usize>().unwrap());
+            }
+        }
+        let mut buffer = vec![0; content_length.unwrap()];
+        self.stdout.read_exact(&mut buffer).unwrap();
+        serde_json::from_slice(&buffer).unwrap()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        let _ = self.server.kill();
+        let _ = self.server.wait();
+    }
+}
+
+/// Creates a virtual env (that does not require spawning Python to be identified).
+fn create_venv(prefix: &Path) -> PathBuf {
+    let bin = prefix.join(if cfg!(windows) { "Scripts" } else { "bin" });
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        prefix.join("pyvenv.cfg"),
+        "home = /usr/bin\nversion = 3.12.1\n",
+    )
+    .unwrap();
+    let python = bin.join(if cfg!(windows) {
+        "python.exe"
+    } else {
+        "python"
+    });
+    fs::write(&python, "").unwrap();
+    python
+}
+
+#[test]
+fn initialize_returns_the_supported_environment_kinds() {
+    let mut client = Client::start();
+
+    let result = client.request("initialize", json!({}));
+
+    assert_eq!(result["version"], env!("CARGO_PKG_VERSION"));
+    let kinds = result["capabilities"]["environmentKinds"]
+        .as_array()
+        .unwrap();
+    assert!(kinds.contains(&json!("Venv")));
+    assert!(kinds.contains(&json!("Conda")));
+}
+
+#[test]
+fn find_uses_the_configuration() {
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_configure");
+    let _ = fs::remove_dir_all(&workspace);
+    let python = create_venv(&workspace.join("env"));
+    create_venv(&workspace.join("excluded"));
+
+    let mut client = Client::start();
+    let configure = client.request(
+        "configure",
+        json!({
+            "workspaceDirectories": [workspace],
+            "excludePaths": [workspace.join("excluded")],
+        }),
+    );
+    let found = client.request("find", json!({ "searchPath": workspace }));
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert_eq!(configure, Value::Null);
+    let executables = found
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|env| env["executable"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(executables, vec![python.to_str().unwrap().to_string()]);
+}
+
+#[test]
+fn resolve_returns_the_environment_of_the_executable() {
+    let prefix = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_resolve");
+    let _ = fs::remove_dir_all(&prefix);
+    let python = create_venv(&prefix);
+
+    let mut client = Client::start();
+    let env = client.request("resolve", json!({ "executable": python }));
+    let _ = fs::remove_dir_all(&prefix);
+
+    assert_eq!(env["executable"], python.to_str().unwrap());
+    assert_eq!(env["kind"], "Venv");
+}
+
+#[test]
+fn resolve_returns_null_for_an_unknown_executable() {
+    let python = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_resolve_unknown")
+        .join("python");
+
+    let mut client = Client::start();
+    let env = client.request("resolve", json!({ "executable": python }));
+
+    assert_eq!(env, Value::Null);
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_returns_null_for_a_manager_executable() {
+    let Some(python) = std::process::Command::new("which")
+        .arg("python3")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+    else {
+        return;
+    };
+    let bin = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_resolve_manager")
+        .join("bin");
+    let _ = fs::remove_dir_all(&bin);
+    fs::create_dir_all(&bin).unwrap();
+    // Named `conda`, however this is a real Python executable that could be resolved.
+    let conda = bin.join("conda");
+    std::os::unix::fs::symlink(&python, &conda).unwrap();
+
+    let mut client = Client::start();
+    let env = client.request("resolve", json!({ "executable": conda }));
+    let _ = fs::remove_dir_all(&bin);
+
+    assert_eq!(env, Value::Null);
+}
+
+#[cfg(unix)]
+#[test]
+fn configured_conda_executable_is_used_instead_of_the_one_on_path() {
+    let root = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join(format!(
+            "pet_jsonrpc_test_conda_on_path_{}",
+            std::process::id()
+        ));
+    let _ = fs::remove_dir_all(&root);
+    // Conda install (with an env) that can only be found via the conda executable on PATH.
+    let conda_dir = root.join("anaconda3");
+    fs::create_dir_all(conda_dir.join("bin")).unwrap();
+    fs::create_dir_all(conda_dir.join("conda-meta")).unwrap();
+    fs::create_dir_all(conda_dir.join("envs").join("env1").join("conda-meta")).unwrap();
+    fs::write(conda_dir.join("bin").join("conda"), "").unwrap();
+    let mut path = vec![conda_dir.join("bin")];
+    path.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    let path = std::env::join_paths(path).unwrap();
+    let mock_conda = root.join("mock").join("bin").join("conda");
+
+    let prefixes_found = |configuration: Value| {
+        let mut client = Client::start_with_env(&[("PATH", path.as_os_str())]);
+        client.request("configure", configuration);
+        let id = client.send_request("refresh", json!({}));
+        let mut prefixes = vec![];
+        loop {
+            let message = client.read_message();
+            if message["id"] == id {
+                return prefixes;
+            }
+            if message["method"] == "environment" {
+                if let Some(prefix) = message["params"]["prefix"].as_str() {
+                    prefixes.push(PathBuf::from(prefix));
+                }
+            }
+        }
+    };
+    let found_using_path = prefixes_found(json!({}));
+    let found_using_mock = prefixes_found(json!({ "condaExecutable": mock_conda }));
+    let _ = fs::remove_dir_all(&root);
+
+    assert!(
+        found_using_path.contains(&conda_dir.join("envs").join("env1")),
+        "{found_using_path:?}"
+    );
+    assert!(
+        !found_using_mock
+            .iter()
+            .any(|prefix| prefix.starts_with(&conda_dir)),
+        "{found_using_mock:?}"
+    );
+}
+
+#[test]
+fn find_can_be_cancelled() {
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_cancel_find");
+    let _ = fs::remove_dir_all(&workspace);
+    // Enough folders for the search to take a while.
+    for i in 0..500 {
+        fs::create_dir_all(workspace.join(format!("project{i}")).join("src")).unwrap();
+    }
+
+    let mut client = Client::start();
+    let id = client.send_request("find", json!({ "searchPath": workspace }));
+    client.notify("$/cancelRequest", json!({ "id": id }));
+    let response = client.read_response(id);
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert_eq!(response["error"]["code"], -32800, "{response}");
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn changes_to_environments_are_notified_when_watching_the_file_system() {
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_watch");
+    let _ = fs::remove_dir_all(&workspace);
+    fs::create_dir_all(&workspace).unwrap();
+
+    let mut client = Client::start();
+    client.request(
+        "configure",
+        json!({ "workspaceDirectories": [workspace], "watchFileSystem": true }),
+    );
+    let prefix = workspace.join(".venv");
+    let python = create_venv(&prefix);
+    let changed = client.read_notification("pet/environmentChanged");
+    fs::remove_dir_all(&prefix).unwrap();
+    let removed = client.read_notification("pet/environmentRemoved");
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert_eq!(changed["executable"], python.to_str().unwrap());
+    assert_eq!(changed["kind"], "Venv");
+    assert_eq!(removed["prefix"], prefix.to_str().unwrap());
+}
+
+#[test]
+fn summary_contains_the_time_taken_by_each_locator() {
+    let environment = EnvironmentApi::new();
+    let locators = create_locators(
+        Arc::new(Conda::from(&environment)),
+        Arc::new(Poetry::from(&environment)),
+        &environment,
+    );
+
+    let mut client = Client::start();
+    assert_eq!(client.request("getSummary", Value::Null), Value::Null);
+    client.request("refresh", json!({}));
+    let summary = client.request("getSummary", Value::Null);
+
+    let timings = summary["timings"].as_object().unwrap();
+    for locator in locators.iter() {
+        let name = format!("{:?}", locator.get_kind());
+        assert!(timings.contains_key(&name), "{name} not in {summary}");
+        assert_eq!(summary["locators"][&name], locator.description());
+    }
+}
+
+#[test]
+fn progress_is_reported_while_refreshing() {
+    let workspace = fs::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("pet_jsonrpc_test_progress");
+    let _ = fs::remove_dir_all(&workspace);
+    for i in 0..12 {
+        create_venv(&workspace.join(format!("env{i}")));
+    }
+
+    let mut client = Client::start();
+    client.request("configure", json!({ "workspaceDirectories": [workspace] }));
+    let id = client.send_request("refresh", json!({}));
+    let mut progress = vec![];
+    loop {
+        let message = client.read_message();
+        if message["id"] == id {
+            break;
+        }
+        if message["method"] == "pet/progress" {
+            progress.push(message["params"].clone());
+        }
+    }
+    let _ = fs::remove_dir_all(&workspace);
+
+    assert!(progress
+        .iter()
+        .any(|params| params["message"] == "Found 10 environments"));
+    // All of the locators have completed by the time the refresh completes.
+    let last = progress.last().unwrap();
+    assert_eq!(last["completed"], last["total"]);
+    // Progress is reported in increasing order.
+    let completed = progress
+        .iter()
+        .map(|params| params["completed"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert!(completed.windows(2).all(|pair| pair[0] <= pair[1]));
+}
@@ -0,0 +1,254 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet::resolve::{
+    find_by_version, find_env_by_executable, find_env_by_prefix, find_envs_by_prefix,
+};
+use pet_core::{
+    os_environment::Environment,
+    python_environment::{PythonEnvironment, PythonEnvironmentKind},
+    reporter::Reporter,
+    Locator,
+};
+use pet_reporter::{cache::CacheReporter, collect};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+fn resolve_crate_path(paths: &[&str]) -> PathBuf {
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    root.pop();
+    paths.iter().for_each(|p| root.push(p));
+    root
+}
+
+struct TestEnvironment {
+    vars: HashMap<String, String>,
+    home: Option<PathBuf>,
+}
+
+impl Environment for TestEnvironment {
+    fn get_env_var(&self, key: String) -> Option<String> {
+        self.vars.get(&key).cloned()
+    }
+    fn get_root(&self) -> Option<PathBuf> {
+        None
+    }
+    fn get_user_home(&self) -> Option<PathBuf> {
+        self.home.clone()
+    }
+    fn get_know_global_search_locations(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn find_conda_env_by_executable() {
+    use pet_conda::Conda;
+
+    let environment = TestEnvironment {
+        vars: HashMap::new(),
+        home: None,
+    };
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(Conda::from(&environment))]);
+    let prefix = resolve_crate_path(&[
+        "pet-conda",
+        "tests",
+        "unix",
+        "conda_env_without_manager",
+        "env_python_3",
+    ]);
+
+    let env = find_env_by_executable(&prefix.join("bin").join("python"), &locators, None).unwrap();
+
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
+    assert_eq!(env.prefix, Some(prefix.clone()));
+    assert!(find_env_by_executable(
+        &resolve_crate_path(&["pet", "tests", "does_not_exist", "python"]),
+        &locators,
+        None
+    )
+    .is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn find_poetry_env_by_executable() {
+    use pet_core::Configuration;
+    use pet_poetry::Poetry;
+
+    let root = resolve_crate_path(&["pet-poetry", "tests", "unix", "multi_project"]);
+    let project_a = root.join("project_a");
+    let project_b = root.join("project_b");
+    let environment = TestEnvironment {
+        vars: HashMap::from([("POETRY_VIRTUALENVS_IN_PROJECT".to_string(), "1".to_string())]),
+        home: Some(root.join("user_home")),
+    };
+    let poetry = Poetry::from(&environment);
+    poetry.configure(&Configuration {
+        workspace_directories: Some(vec![project_a.clone()]),
+        ..Default::default()
+    });
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(poetry)]);
+
+    let env = find_env_by_executable(
+        &project_a.join(".venv").join("bin").join("python"),
+        &locators,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Poetry));
+    assert_eq!(env.prefix, Some(project_a.join(".venv")));
+    assert_eq!(env.project, Some(project_a));
+    // Not a workspace folder, hence Poetry does not know about this environment.
+    assert!(find_env_by_executable(
+        &project_b.join(".venv").join("bin").join("python"),
+        &locators,
+        None
+    )
+    .is_none());
+}
+
+#[test]
+fn environments_in_the_cache_take_precedence() {
+    let executable = PathBuf::from("/home/user/.venv/bin/python");
+    let symlink = PathBuf::from("/home/user/.venv/bin/python3");
+    let cache = Arc::new(CacheReporter::new(Arc::new(collect::create_reporter())));
+    cache.report_environment(&PythonEnvironment {
+        executable: Some(executable.clone()),
+        kind: Some(PythonEnvironmentKind::Venv),
+        symlinks: Some(vec![executable.clone(), symlink.clone()]),
+        ..Default::default()
+    });
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![]);
+
+    for exe in [&executable, &symlink] {
+        let env = find_env_by_executable(exe, &locators, Some(cache.clone())).unwrap();
+        assert_eq!(env.executable, Some(executable.clone()));
+        assert_eq!(env.kind, Some(PythonEnvironmentKind::Venv));
+    }
+    assert!(
+        find_env_by_executable(&PathBuf::from("/usr/bin/python3"), &locators, Some(cache))
+            .is_none()
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn find_conda_env_by_prefix() {
+    use pet_conda::Conda;
+
+    let environment = TestEnvironment {
+        vars: HashMap::new(),
+        home: None,
+    };
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(Conda::from(&environment))]);
+    let prefix = resolve_crate_path(&[
+        "pet-conda",
+        "tests",
+        "unix",
+        "conda_env_without_manager",
+        "env_python_3",
+    ]);
+
+    let env = find_env_by_prefix(&prefix, &locators, None).unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Conda));
+    assert_eq!(env.prefix, Some(prefix.clone()));
+
+    // All of the executables in the prefix belong to the same environment.
+    let envs = find_envs_by_prefix(&prefix, &locators, None);
+    assert_eq!(envs.len(), 1);
+    assert_eq!(envs[0].prefix, Some(prefix));
+}
+
+#[cfg(unix)]
+#[test]
+fn find_virtualenv_by_prefix() {
+    use pet_venv::Venv;
+
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![Arc::new(Venv::new())]);
+    let prefix = resolve_crate_path(&[
+        "pet-poetry",
+        "tests",
+        "unix",
+        "multi_project",
+        "project_a",
+        ".venv",
+    ]);
+
+    let env = find_env_by_prefix(&prefix, &locators, None).unwrap();
+    assert_eq!(env.kind, Some(PythonEnvironmentKind::Venv));
+    assert_eq!(env.prefix, Some(prefix.clone()));
+    assert_eq!(find_envs_by_prefix(&prefix, &locators, None), vec![env]);
+
+    let missing = prefix.join("does_not_exist");
+    assert!(find_env_by_prefix(&missing, &locators, None).is_none());
+    assert!(find_envs_by_prefix(&missing, &locators, None).is_empty());
+}
+
+#[test]
+fn find_envs_by_prefix_in_the_cache() {
+    let prefix = PathBuf::from("/opt/conda");
+    let cache = Arc::new(CacheReporter::new(Arc::new(collect::create_reporter())));
+    for executable in ["/opt/conda/bin/python", "/opt/conda/bin/python3.12"] {
+        cache.report_environment(&PythonEnvironment {
+            executable: Some(PathBuf::from(executable)),
+            kind: Some(PythonEnvironmentKind::Conda),
+            prefix: Some(prefix.clone()),
+            ..Default::default()
+        });
+    }
+    let locators: Arc<Vec<Arc<dyn Locator>>> = Arc::new(vec![]);
+
+    let env = find_env_by_prefix(&prefix, &locators, Some(cache.clone())).unwrap();
+    assert_eq!(env.prefix, Some(prefix.clone()));
+
+    let mut executables = find_envs_by_prefix(&prefix, &locators, Some(cache.clone()))
+        .into_iter()
+        .map(|e| e.executable.unwrap())
+        .collect::<Vec<_>>();
+    executables.sort();
+    assert_eq!(
+        executables,
+        vec![
+            PathBuf::from("/opt/conda/bin/python"),
+            PathBuf::from("/opt/conda/bin/python3.12")
+        ]
+    );
+    assert!(
+        find_envs_by_prefix(&PathBuf::from("/opt/miniconda"), &locators, Some(cache)).is_empty()
+    );
+}
+
+#[test]
+fn find_environments_by_version() {
+    use pet_core::python_version::{PythonVersion, VersionTolerance};
+
+    let env = |executable: &str, version: Option<&str>| PythonEnvironment {
+        executable: Some(PathBuf::from(executable)),
+        version: version.and_then(|v| v.parse().ok()),
+        ..Default::default()
+    };
+    let environments = vec![
+        env("/usr/bin/python3", Some("3.12.1")),
+        env("/home/user/.venv/bin/python", Some("3.12.3")),
+        env("/opt/conda/bin/python", Some("3.11.9")),
+        env("/home/user/.venv2/bin/python", None),
+    ];
+    let version: PythonVersion = "3.12.3".parse().unwrap();
+
+    assert_eq!(
+        find_by_version(&version, VersionTolerance::MajorMinor, &environments),
+        vec![environments[0].clone(), environments[1].clone()]
+    );
+    assert_eq!(
+        find_by_version(&version, VersionTolerance::Exact, &environments),
+        vec![environments[1].clone()]
+    );
+    assert!(find_by_version(
+        &PythonVersion::new(3, 13, 0),
+        VersionTolerance::MajorMinor,
+        &environments
+    )
+    .is_empty());
+}
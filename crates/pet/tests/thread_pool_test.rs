@@ -0,0 +1,40 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pet::thread_pool::ThreadPool;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+/// Returns the max number of threads that were running at the same time.
+fn max_concurrent_threads(pool: &ThreadPool, total: usize) -> usize {
+    let active = AtomicUsize::new(0);
+    let max_active = AtomicUsize::new(0);
+    let completed = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..total {
+            pool.spawn(s, || {
+                let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+                max_active.fetch_max(now_active, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                active.fetch_sub(1, Ordering::SeqCst);
+                completed.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    });
+    assert_eq!(completed.load(Ordering::SeqCst), total);
+    max_active.load(Ordering::SeqCst)
+}
+
+#[test]
+fn no_more_than_max_threads_run_at_the_same_time() {
+    assert!(max_concurrent_threads(&ThreadPool::new(Some(3)), 12) <= 3);
+    assert_eq!(max_concurrent_threads(&ThreadPool::new(Some(1)), 5), 1);
+}
+
+#[test]
+fn threads_are_not_limited_without_max_threads() {
+    assert!(max_concurrent_threads(&ThreadPool::new(None), 8) > 1);
+}